@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::RequestId;
 use crate::protocol::common::AuthMode;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
@@ -10,8 +11,12 @@ use codex_protocol::items::AgentMessageContent as CoreAgentMessageContent;
 use codex_protocol::items::TurnItem as CoreTurnItem;
 use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RateLimitWindow as CoreRateLimitWindow;
+use codex_protocol::protocol::ReviewOutputEvent as CoreReviewOutputEvent;
 use codex_protocol::user_input::UserInput as CoreUserInput;
 use mcp_types::ContentBlock as McpContentBlock;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::Prompt as CorePrompt;
+use mcp_types::SamplingMessage;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -49,6 +54,18 @@ v2_enum_from_core!(
     }
 );
 
+v2_enum_from_core!(
+    pub enum McpServerElicitationAction from codex_protocol::protocol::ElicitationAction {
+        Accept, Decline, Cancel
+    }
+);
+
+v2_enum_from_core!(
+    pub enum McpServerSamplingDecision from codex_protocol::protocol::ReviewDecision {
+        Approved, ApprovedForSession, Denied, Abort
+    }
+);
+
 v2_enum_from_core!(
     pub enum SandboxMode from codex_protocol::config_types::SandboxMode {
         ReadOnly, WorkspaceWrite, DangerFullAccess
@@ -61,7 +78,12 @@ v2_enum_from_core!(
 #[ts(export_to = "v2/")]
 pub enum SandboxPolicy {
     DangerFullAccess,
-    ReadOnly,
+    ReadOnly {
+        #[serde(default)]
+        readable_roots: Vec<PathBuf>,
+        #[serde(default)]
+        deny_read: Vec<PathBuf>,
+    },
     WorkspaceWrite {
         #[serde(default)]
         writable_roots: Vec<PathBuf>,
@@ -71,6 +93,14 @@ pub enum SandboxPolicy {
         exclude_tmpdir_env_var: bool,
         #[serde(default)]
         exclude_slash_tmp: bool,
+        #[serde(default)]
+        allowed_hosts: Vec<String>,
+        #[serde(default)]
+        allowed_cidrs: Vec<String>,
+        #[serde(default)]
+        readable_roots: Vec<PathBuf>,
+        #[serde(default)]
+        deny_read: Vec<PathBuf>,
     },
 }
 
@@ -80,17 +110,31 @@ impl SandboxPolicy {
             SandboxPolicy::DangerFullAccess => {
                 codex_protocol::protocol::SandboxPolicy::DangerFullAccess
             }
-            SandboxPolicy::ReadOnly => codex_protocol::protocol::SandboxPolicy::ReadOnly,
+            SandboxPolicy::ReadOnly {
+                readable_roots,
+                deny_read,
+            } => codex_protocol::protocol::SandboxPolicy::ReadOnly {
+                readable_roots: readable_roots.clone(),
+                deny_read: deny_read.clone(),
+            },
             SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                allowed_hosts,
+                allowed_cidrs,
+                readable_roots,
+                deny_read,
             } => codex_protocol::protocol::SandboxPolicy::WorkspaceWrite {
                 writable_roots: writable_roots.clone(),
                 network_access: *network_access,
                 exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                 exclude_slash_tmp: *exclude_slash_tmp,
+                allowed_hosts: allowed_hosts.clone(),
+                allowed_cidrs: allowed_cidrs.clone(),
+                readable_roots: readable_roots.clone(),
+                deny_read: deny_read.clone(),
             },
         }
     }
@@ -102,17 +146,31 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
             codex_protocol::protocol::SandboxPolicy::DangerFullAccess => {
                 SandboxPolicy::DangerFullAccess
             }
-            codex_protocol::protocol::SandboxPolicy::ReadOnly => SandboxPolicy::ReadOnly,
+            codex_protocol::protocol::SandboxPolicy::ReadOnly {
+                readable_roots,
+                deny_read,
+            } => SandboxPolicy::ReadOnly {
+                readable_roots,
+                deny_read,
+            },
             codex_protocol::protocol::SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                allowed_hosts,
+                allowed_cidrs,
+                readable_roots,
+                deny_read,
             } => SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                allowed_hosts,
+                allowed_cidrs,
+                readable_roots,
+                deny_read,
             },
         }
     }
@@ -267,6 +325,90 @@ pub struct FeedbackUploadResponse {
     pub thread_id: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ReviewRenderFormat {
+    /// A SARIF 2.1.0 log, serialized as JSON.
+    Sarif,
+    /// GitHub Actions workflow commands, one per finding.
+    GithubAnnotations,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ReviewRenderParams {
+    pub review: CoreReviewOutputEvent,
+    pub format: ReviewRenderFormat,
+    /// Directory that finding paths are reported relative to. Defaults to the
+    /// repository root of the server's working directory.
+    pub root: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ReviewRenderResponse {
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpPromptListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpPrompt {
+    pub server_name: String,
+    pub name: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+impl McpPrompt {
+    pub fn from_core(server_name: String, prompt: CorePrompt) -> Self {
+        Self {
+            server_name,
+            name: prompt.name,
+            title: prompt.title,
+            description: prompt.description,
+            arguments: prompt
+                .arguments
+                .unwrap_or_default()
+                .into_iter()
+                .map(|argument| McpPromptArgument {
+                    name: argument.name,
+                    description: argument.description,
+                    required: argument.required.unwrap_or(false),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpPromptArgument {
+    pub name: String,
+    pub description: Option<String>,
+    pub required: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpPromptListResponse {
+    /// Prompts offered by the thread's MCP servers, sorted by server and name.
+    pub data: Vec<McpPrompt>,
+}
+
 // === Threads, Turns, and Items ===
 // Thread APIs
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -327,6 +469,29 @@ pub struct ThreadListParams {
     /// Optional provider filter; when set, only sessions recorded under these
     /// providers are returned. When present but empty, includes all providers.
     pub model_providers: Option<Vec<String>>,
+    /// Optional search filter; when set, only threads matching every field
+    /// that is set are returned.
+    pub filter: Option<ThreadListFilter>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadListFilter {
+    /// Case-insensitive, whitespace-separated terms that must all appear in
+    /// the thread's user messages, agent messages, commands run or paths of
+    /// files touched.
+    pub query: Option<String>,
+    /// Only threads started in this directory or one of its subdirectories.
+    pub cwd: Option<String>,
+    /// Only threads started on this Git branch.
+    pub git_branch: Option<String>,
+    /// Only threads with at least one turn that used this model.
+    pub model: Option<String>,
+    /// Unix timestamp (in seconds); only threads created at or after it.
+    pub created_after: Option<i64>,
+    /// Unix timestamp (in seconds); only threads created before it.
+    pub created_before: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -758,6 +923,62 @@ pub struct AccountLoginCompletedNotification {
     pub error: Option<String>,
 }
 
+/// The MCP server withdrew an `mcpServer/elicitation/request` before it was
+/// answered. Close the form; a late reply is ignored.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerElicitationCancelledNotification {
+    pub thread_id: String,
+    /// Id of the `mcpServer/elicitation/request` that was withdrawn.
+    pub request_id: RequestId,
+}
+
+// === Server Requests ===
+/// An MCP server used by the thread asks the user for input. Reply with
+/// `accept` and form `content` matching `requestedSchema`, or with
+/// `decline`/`cancel`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerElicitationRequestParams {
+    pub thread_id: String,
+    pub server_name: String,
+    pub message: String,
+    pub requested_schema: ElicitRequestParamsRequestedSchema,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerElicitationRequestResponse {
+    pub action: McpServerElicitationAction,
+    pub content: Option<JsonValue>,
+}
+
+/// An MCP server used by the thread wants to run a completion through the
+/// thread's model. `approvedForSession` approves the server's further requests
+/// until its token budget runs out.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerSamplingRequestApprovalParams {
+    pub thread_id: String,
+    pub server_name: String,
+    pub system_prompt: Option<String>,
+    pub messages: Vec<SamplingMessage>,
+    pub max_tokens: i64,
+    #[ts(type = "number")]
+    pub remaining_tokens: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerSamplingRequestApprovalResponse {
+    pub decision: McpServerSamplingDecision,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
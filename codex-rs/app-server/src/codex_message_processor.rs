@@ -58,6 +58,15 @@ use codex_app_server_protocol::LoginChatGptCompleteNotification;
 use codex_app_server_protocol::LoginChatGptResponse;
 use codex_app_server_protocol::LogoutAccountResponse;
 use codex_app_server_protocol::LogoutChatGptResponse;
+use codex_app_server_protocol::McpPrompt;
+use codex_app_server_protocol::McpPromptListParams;
+use codex_app_server_protocol::McpPromptListResponse;
+use codex_app_server_protocol::McpServerElicitationAction;
+use codex_app_server_protocol::McpServerElicitationCancelledNotification;
+use codex_app_server_protocol::McpServerElicitationRequestParams;
+use codex_app_server_protocol::McpServerElicitationRequestResponse;
+use codex_app_server_protocol::McpServerSamplingRequestApprovalParams;
+use codex_app_server_protocol::McpServerSamplingRequestApprovalResponse;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
 use codex_app_server_protocol::NewConversationParams;
@@ -68,6 +77,9 @@ use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::Result as JsonRpcResult;
 use codex_app_server_protocol::ResumeConversationParams;
 use codex_app_server_protocol::ResumeConversationResponse;
+use codex_app_server_protocol::ReviewRenderFormat;
+use codex_app_server_protocol::ReviewRenderParams;
+use codex_app_server_protocol::ReviewRenderResponse;
 use codex_app_server_protocol::SandboxMode;
 use codex_app_server_protocol::SendUserMessageParams;
 use codex_app_server_protocol::SendUserMessageResponse;
@@ -82,6 +94,7 @@ use codex_app_server_protocol::Thread;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
 use codex_app_server_protocol::ThreadItem;
+use codex_app_server_protocol::ThreadListFilter;
 use codex_app_server_protocol::ThreadListParams;
 use codex_app_server_protocol::ThreadListResponse;
 use codex_app_server_protocol::ThreadResumeParams;
@@ -103,6 +116,7 @@ use codex_backend_client::Client as BackendClient;
 use codex_core::AuthManager;
 use codex_core::CodexConversation;
 use codex_core::ConversationManager;
+use codex_core::ConversationSearch;
 use codex_core::Cursor as RolloutCursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::InitialHistory;
@@ -121,15 +135,22 @@ use codex_core::exec::ExecParams;
 use codex_core::exec_env::create_env;
 use codex_core::find_conversation_path_by_id_str;
 use codex_core::get_platform_sandbox;
+use codex_core::git_info::get_git_repo_root;
 use codex_core::git_info::git_diff_to_remote;
 use codex_core::parse_cursor;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ElicitationCancelledEvent;
+use codex_core::protocol::ElicitationRequestEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
+use codex_core::protocol::McpListPromptsResponseEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SamplingRequestEvent;
 use codex_core::read_head_for_summary;
+use codex_core::review_format::render_github_annotations;
+use codex_core::review_format::render_review_sarif;
 use codex_feedback::CodexFeedback;
 use codex_login::ServerOptions as LoginServerOptions;
 use codex_login::ShutdownHandle;
@@ -145,6 +166,7 @@ use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::USER_MESSAGE_BEGIN;
 use codex_protocol::user_input::UserInput as CoreInputItem;
 use codex_utils_json_to_toml::json_to_toml;
+use mcp_types::RequestId as McpRequestId;
 use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::io::Error as IoError;
@@ -164,6 +186,10 @@ use uuid::Uuid;
 
 type PendingInterruptQueue = Vec<(RequestId, ApiVersion)>;
 type PendingInterrupts = Arc<Mutex<HashMap<ConversationId, PendingInterruptQueue>>>;
+type PendingMcpPromptLists = Arc<Mutex<HashMap<String, RequestId>>>;
+// Ids of the `mcpServer/elicitation/request`s a listener sent, keyed by the
+// MCP server and its request id.
+type PendingElicitations = Arc<Mutex<HashMap<(String, McpRequestId), RequestId>>>;
 
 // Duration before a ChatGPT login attempt is abandoned.
 const LOGIN_CHATGPT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
@@ -189,6 +215,8 @@ pub(crate) struct CodexMessageProcessor {
     active_login: Arc<Mutex<Option<ActiveLogin>>>,
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: PendingInterrupts,
+    // Prompt listings keyed by the id of the submitted `Op::ListMcpPrompts`.
+    pending_mcp_prompt_lists: PendingMcpPromptLists,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     feedback: CodexFeedback,
 }
@@ -241,6 +269,7 @@ impl CodexMessageProcessor {
             conversation_listeners: HashMap::new(),
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
+            pending_mcp_prompt_lists: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
             feedback,
         }
@@ -388,6 +417,12 @@ impl CodexMessageProcessor {
             ClientRequest::FeedbackUpload { request_id, params } => {
                 self.upload_feedback(request_id, params).await;
             }
+            ClientRequest::ReviewRender { request_id, params } => {
+                self.render_review(request_id, params).await;
+            }
+            ClientRequest::McpPromptList { request_id, params } => {
+                self.list_mcp_prompts(request_id, params).await;
+            }
         }
     }
 
@@ -1099,6 +1134,7 @@ impl CodexMessageProcessor {
         let outgoing = self.outgoing.clone();
         let req_id = request_id;
         let sandbox_cwd = self.config.cwd.clone();
+        let sandbox_limits = self.config.sandbox_limits;
 
         tokio::spawn(async move {
             match codex_core::exec::process_exec_tool_call(
@@ -1107,6 +1143,7 @@ impl CodexMessageProcessor {
                 &effective_policy,
                 sandbox_cwd.as_path(),
                 &codex_linux_sandbox_exe,
+                &sandbox_limits,
                 None,
             )
             .await
@@ -1352,12 +1389,25 @@ impl CodexMessageProcessor {
             cursor,
             limit,
             model_providers,
+            filter,
         } = params;
 
         let page_size = limit.unwrap_or(25).max(1) as usize;
+        let search = match filter.map(conversation_search_from_filter).transpose() {
+            Ok(search) => search.unwrap_or_default(),
+            Err(message) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message,
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
 
         let (summaries, next_cursor) = match self
-            .list_conversations_common(page_size, cursor, model_providers)
+            .list_conversations_common(page_size, cursor, model_providers, &search)
             .await
         {
             Ok(r) => r,
@@ -1553,7 +1603,12 @@ impl CodexMessageProcessor {
         let page_size = page_size.unwrap_or(25).max(1);
 
         match self
-            .list_conversations_common(page_size, cursor, model_providers)
+            .list_conversations_common(
+                page_size,
+                cursor,
+                model_providers,
+                &ConversationSearch::default(),
+            )
             .await
         {
             Ok((items, next_cursor)) => {
@@ -1571,6 +1626,7 @@ impl CodexMessageProcessor {
         page_size: usize,
         cursor: Option<String>,
         model_providers: Option<Vec<String>>,
+        search: &ConversationSearch,
     ) -> Result<(Vec<ConversationSummary>, Option<String>), JSONRPCErrorError> {
         let cursor_obj: Option<RolloutCursor> = cursor.as_ref().and_then(|s| parse_cursor(s));
         let cursor_ref = cursor_obj.as_ref();
@@ -1587,13 +1643,14 @@ impl CodexMessageProcessor {
         };
         let fallback_provider = self.config.model_provider_id.clone();
 
-        let page = match RolloutRecorder::list_conversations(
+        let page = match RolloutRecorder::search_conversations(
             &self.config.codex_home,
             page_size,
             cursor_ref,
             INTERACTIVE_SESSION_SOURCES,
             model_provider_filter.as_deref(),
             fallback_provider.as_str(),
+            search,
         )
         .await
         {
@@ -2347,6 +2404,8 @@ impl CodexMessageProcessor {
 
         let outgoing_for_task = self.outgoing.clone();
         let pending_interrupts = self.pending_interrupts.clone();
+        let pending_mcp_prompt_lists = self.pending_mcp_prompt_lists.clone();
+        let pending_elicitations = PendingElicitations::default();
         tokio::spawn(async move {
             loop {
                 tokio::select! {
@@ -2402,6 +2461,8 @@ impl CodexMessageProcessor {
                             conversation.clone(),
                             outgoing_for_task.clone(),
                             pending_interrupts.clone(),
+                            pending_mcp_prompt_lists.clone(),
+                            pending_elicitations.clone(),
                         )
                         .await;
                     }
@@ -2532,6 +2593,69 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn render_review(&self, request_id: RequestId, params: ReviewRenderParams) {
+        let ReviewRenderParams {
+            review,
+            format,
+            root,
+        } = params;
+        let root = root.unwrap_or_else(|| {
+            get_git_repo_root(&self.config.cwd).unwrap_or_else(|| self.config.cwd.clone())
+        });
+
+        let content = match format {
+            ReviewRenderFormat::Sarif => {
+                match serde_json::to_string_pretty(&render_review_sarif(&review, &root)) {
+                    Ok(content) => content,
+                    Err(err) => {
+                        let error = JSONRPCErrorError {
+                            code: INTERNAL_ERROR_CODE,
+                            message: format!("failed to serialize SARIF log: {err}"),
+                            data: None,
+                        };
+                        self.outgoing.send_error(request_id, error).await;
+                        return;
+                    }
+                }
+            }
+            ReviewRenderFormat::GithubAnnotations => {
+                render_github_annotations(&review.findings, &root)
+            }
+        };
+        self.outgoing
+            .send_response(request_id, ReviewRenderResponse { content })
+            .await;
+    }
+
+    async fn list_mcp_prompts(&self, request_id: RequestId, params: McpPromptListParams) {
+        let (_, conversation) = match self.conversation_from_thread_id(&params.thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        // Hold the lock while submitting so the listener cannot observe the
+        // response before the request is recorded. We reply when
+        // McpListPromptsResponse arrives.
+        let mut pending = self.pending_mcp_prompt_lists.lock().await;
+        match conversation.submit(Op::ListMcpPrompts).await {
+            Ok(submission_id) => {
+                pending.insert(submission_id, request_id);
+            }
+            Err(err) => {
+                drop(pending);
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to list MCP prompts: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
     async fn resolve_rollout_path(&self, conversation_id: ConversationId) -> Option<PathBuf> {
         match self
             .conversation_manager
@@ -2550,6 +2674,8 @@ async fn apply_bespoke_event_handling(
     conversation: Arc<CodexConversation>,
     outgoing: Arc<OutgoingMessageSender>,
     pending_interrupts: PendingInterrupts,
+    pending_mcp_prompt_lists: PendingMcpPromptLists,
+    pending_elicitations: PendingElicitations,
 ) {
     let Event { id: event_id, msg } = event;
     match msg {
@@ -2600,6 +2726,86 @@ async fn apply_bespoke_event_handling(
                 on_exec_approval_response(event_id, rx, conversation).await;
             });
         }
+        EventMsg::ElicitationRequest(ElicitationRequestEvent {
+            server_name,
+            id: mcp_request_id,
+            message,
+            requested_schema,
+        }) => {
+            let params = McpServerElicitationRequestParams {
+                thread_id: conversation_id.to_string(),
+                server_name: server_name.clone(),
+                message,
+                requested_schema,
+            };
+            let (request_id, rx) = outgoing
+                .send_request_with_id(ServerRequestPayload::McpServerElicitationRequest(params))
+                .await;
+            let key = (server_name, mcp_request_id);
+            pending_elicitations
+                .lock()
+                .await
+                .insert(key.clone(), request_id);
+            tokio::spawn(async move {
+                let (server_name, mcp_request_id) = key;
+                let response = rx.await;
+                // A cancelled request was already removed; its late answer
+                // has nothing left to resolve.
+                if pending_elicitations
+                    .lock()
+                    .await
+                    .remove(&(server_name.clone(), mcp_request_id.clone()))
+                    .is_some()
+                {
+                    on_elicitation_response(server_name, mcp_request_id, response, conversation)
+                        .await;
+                }
+            });
+        }
+        EventMsg::ElicitationCancelled(ElicitationCancelledEvent { server_name, id }) => {
+            let request_id = pending_elicitations.lock().await.remove(&(server_name, id));
+            if let Some(request_id) = request_id {
+                outgoing
+                    .send_server_notification(ServerNotification::McpServerElicitationCancelled(
+                        McpServerElicitationCancelledNotification {
+                            thread_id: conversation_id.to_string(),
+                            request_id,
+                        },
+                    ))
+                    .await;
+            }
+        }
+        EventMsg::SamplingRequest(SamplingRequestEvent {
+            server_name,
+            id: mcp_request_id,
+            system_prompt,
+            messages,
+            max_tokens,
+            remaining_tokens,
+        }) => {
+            let params = McpServerSamplingRequestApprovalParams {
+                thread_id: conversation_id.to_string(),
+                server_name: server_name.clone(),
+                system_prompt,
+                messages,
+                max_tokens,
+                remaining_tokens,
+            };
+            let rx = outgoing
+                .send_request(ServerRequestPayload::McpServerSamplingRequestApproval(
+                    params,
+                ))
+                .await;
+            tokio::spawn(async move {
+                on_sampling_request_approval_response(
+                    server_name,
+                    mcp_request_id,
+                    rx,
+                    conversation,
+                )
+                .await;
+            });
+        }
         EventMsg::TokenCount(token_count_event) => {
             if let Some(rate_limits) = token_count_event.rate_limits {
                 outgoing
@@ -2648,6 +2854,27 @@ async fn apply_bespoke_event_handling(
                 }
             }
         }
+        EventMsg::McpListPromptsResponse(McpListPromptsResponseEvent { prompts }) => {
+            let request_id = pending_mcp_prompt_lists.lock().await.remove(&event_id);
+            if let Some(request_id) = request_id {
+                let mut data: Vec<McpPrompt> = prompts
+                    .into_iter()
+                    .flat_map(|(server_name, prompts)| {
+                        prompts
+                            .into_iter()
+                            .map(move |prompt| McpPrompt::from_core(server_name.clone(), prompt))
+                    })
+                    .collect();
+                data.sort_by(|a, b| {
+                    a.server_name
+                        .cmp(&b.server_name)
+                        .then_with(|| a.name.cmp(&b.name))
+                });
+                outgoing
+                    .send_response(request_id, McpPromptListResponse { data })
+                    .await;
+            }
+        }
 
         _ => {}
     }
@@ -2708,6 +2935,74 @@ async fn on_patch_approval_response(
     }
 }
 
+async fn on_elicitation_response(
+    server_name: String,
+    mcp_request_id: McpRequestId,
+    response: Result<JsonRpcResult, oneshot::error::RecvError>,
+    conversation: Arc<CodexConversation>,
+) {
+    let response = match response {
+        Ok(value) => serde_json::from_value::<McpServerElicitationRequestResponse>(value)
+            .unwrap_or_else(|err| {
+                error!("failed to deserialize McpServerElicitationRequestResponse: {err}");
+                McpServerElicitationRequestResponse {
+                    action: McpServerElicitationAction::Decline,
+                    content: None,
+                }
+            }),
+        Err(err) => {
+            error!("request failed: {err:?}");
+            McpServerElicitationRequestResponse {
+                action: McpServerElicitationAction::Cancel,
+                content: None,
+            }
+        }
+    };
+
+    if let Err(err) = conversation
+        .submit(Op::ResolveElicitation {
+            server_name,
+            request_id: mcp_request_id,
+            decision: response.action.to_core(),
+            content: response.content,
+        })
+        .await
+    {
+        error!("failed to submit ResolveElicitation: {err}");
+    }
+}
+
+async fn on_sampling_request_approval_response(
+    server_name: String,
+    mcp_request_id: McpRequestId,
+    receiver: oneshot::Receiver<JsonRpcResult>,
+    conversation: Arc<CodexConversation>,
+) {
+    let decision = match receiver.await {
+        Ok(value) => serde_json::from_value::<McpServerSamplingRequestApprovalResponse>(value)
+            .map(|response| response.decision.to_core())
+            .unwrap_or_else(|err| {
+                error!("failed to deserialize McpServerSamplingRequestApprovalResponse: {err}");
+                ReviewDecision::Denied
+            }),
+        Err(err) => {
+            error!("request failed: {err:?}");
+            ReviewDecision::Denied
+        }
+    };
+
+    if let Err(err) = conversation
+        .submit(Op::ResolveSamplingRequest {
+            server_name,
+            request_id: mcp_request_id,
+            decision,
+        })
+        .await
+    {
+        error!("failed to submit ResolveSamplingRequest: {err}");
+    }
+}
+
 async fn on_exec_approval_response(
     event_id: String,
     receiver: oneshot::Receiver<JsonRpcResult>,
@@ -2864,6 +3159,33 @@ fn parse_datetime(timestamp: Option<&str>) -> Option<DateTime<Utc>> {
     })
 }
 
+fn conversation_search_from_filter(filter: ThreadListFilter) -> Result<ConversationSearch, String> {
+    let ThreadListFilter {
+        query,
+        cwd,
+        git_branch,
+        model,
+        created_after,
+        created_before,
+    } = filter;
+    let timestamp = |field: &str, seconds: Option<i64>| {
+        seconds
+            .map(|seconds| {
+                chrono::DateTime::from_timestamp(seconds, 0)
+                    .ok_or_else(|| format!("invalid {field} timestamp: {seconds}"))
+            })
+            .transpose()
+    };
+    Ok(ConversationSearch {
+        query,
+        cwd: cwd.map(PathBuf::from),
+        git_branch,
+        model,
+        created_after: timestamp("createdAfter", created_after)?,
+        created_before: timestamp("createdBefore", created_before)?,
+    })
+}
+
 fn summary_to_thread(summary: ConversationSummary) -> Thread {
     let ConversationSummary {
         conversation_id,
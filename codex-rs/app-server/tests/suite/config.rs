@@ -84,6 +84,10 @@ async fn get_config_toml_parses_all_fields() -> Result<()> {
                 network_access: Some(true),
                 exclude_tmpdir_env_var: Some(true),
                 exclude_slash_tmp: Some(true),
+                allowed_hosts: vec![],
+                allowed_cidrs: vec![],
+                readable_roots: vec![],
+                deny_read: vec![],
             }),
             forced_chatgpt_workspace_id: Some("12345678-0000-0000-0000-000000000000".into()),
             forced_login_method: Some(ForcedLoginMethod::Chatgpt),
@@ -1,6 +1,7 @@
 mod account;
 mod model_list;
 mod rate_limits;
+mod review_render;
 mod thread_archive;
 mod thread_list;
 mod thread_resume;
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ReviewRenderFormat;
+use codex_app_server_protocol::ReviewRenderParams;
+use codex_app_server_protocol::ReviewRenderResponse;
+use codex_protocol::protocol::ReviewCodeLocation;
+use codex_protocol::protocol::ReviewFinding;
+use codex_protocol::protocol::ReviewLineRange;
+use codex_protocol::protocol::ReviewOutputEvent;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn review_output() -> ReviewOutputEvent {
+    ReviewOutputEvent {
+        findings: vec![ReviewFinding {
+            title: "Off by one".to_string(),
+            body: "The loop skips the last element.".to_string(),
+            confidence_score: 0.9,
+            priority: 2,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                line_range: ReviewLineRange { start: 3, end: 5 },
+            },
+        }],
+        overall_correctness: "patch is incorrect".to_string(),
+        overall_explanation: "One bug.".to_string(),
+        overall_confidence_score: 0.8,
+    }
+}
+
+async fn render(format: ReviewRenderFormat) -> Result<String> {
+    let codex_home = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_review_render_request(ReviewRenderParams {
+            review: review_output(),
+            format,
+            root: Some(PathBuf::from("/repo")),
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ReviewRenderResponse { content } = to_response::<ReviewRenderResponse>(response)?;
+    Ok(content)
+}
+
+#[tokio::test]
+async fn review_render_returns_github_annotations() -> Result<()> {
+    assert_eq!(
+        render(ReviewRenderFormat::GithubAnnotations).await?,
+        "::warning file=src/lib.rs,line=3,endLine=5,title=[P2] Off by one::The loop skips the last element."
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn review_render_returns_sarif_log() -> Result<()> {
+    let sarif: Value = serde_json::from_str(&render(ReviewRenderFormat::Sarif).await?)?;
+    assert_eq!(sarif["version"], "2.1.0");
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["level"], "warning");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "src/lib.rs"
+    );
+    Ok(())
+}
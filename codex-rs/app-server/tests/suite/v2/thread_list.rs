@@ -4,6 +4,7 @@ use app_test_support::create_fake_rollout;
 use app_test_support::to_response;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadListFilter;
 use codex_app_server_protocol::ThreadListParams;
 use codex_app_server_protocol::ThreadListResponse;
 use serde_json::json;
@@ -27,6 +28,7 @@ async fn thread_list_basic_empty() -> Result<()> {
             cursor: None,
             limit: Some(10),
             model_providers: None,
+            filter: None,
         })
         .await?;
     let list_resp: JSONRPCResponse = timeout(
@@ -90,6 +92,7 @@ async fn thread_list_pagination_next_cursor_none_on_last_page() -> Result<()> {
             cursor: None,
             limit: Some(2),
             model_providers: Some(vec!["mock_provider".to_string()]),
+            filter: None,
         })
         .await?;
     let page1_resp: JSONRPCResponse = timeout(
@@ -115,6 +118,7 @@ async fn thread_list_pagination_next_cursor_none_on_last_page() -> Result<()> {
             cursor: Some(cursor1),
             limit: Some(2),
             model_providers: Some(vec!["mock_provider".to_string()]),
+            filter: None,
         })
         .await?;
     let page2_resp: JSONRPCResponse = timeout(
@@ -200,6 +204,7 @@ async fn thread_list_respects_provider_filter() -> Result<()> {
             cursor: None,
             limit: Some(10),
             model_providers: Some(vec!["other_provider".to_string()]),
+            filter: None,
         })
         .await?;
     let resp: JSONRPCResponse = timeout(
@@ -218,3 +223,69 @@ async fn thread_list_respects_provider_filter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn thread_list_filter_matches_transcript_and_date_range() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_minimal_config(codex_home.path())?;
+
+    let migration_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "Fix the flaky migration test",
+        Some("mock_provider"),
+    )?;
+    let _docs = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-01T12-00-00",
+        "2025-01-01T12:00:00Z",
+        "Update the docs",
+        Some("mock_provider"),
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let mut list = async |filter: ThreadListFilter| -> Result<Vec<String>> {
+        let list_id = mcp
+            .send_thread_list_request(ThreadListParams {
+                cursor: None,
+                limit: Some(10),
+                model_providers: Some(vec!["mock_provider".to_string()]),
+                filter: Some(filter),
+            })
+            .await?;
+        let resp: JSONRPCResponse = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(list_id)),
+        )
+        .await??;
+        let ThreadListResponse { data, .. } = to_response::<ThreadListResponse>(resp)?;
+        Ok(data.into_iter().map(|thread| thread.id).collect())
+    };
+
+    let by_query = list(ThreadListFilter {
+        query: Some("FLAKY migration".to_string()),
+        ..Default::default()
+    })
+    .await?;
+    assert_eq!(by_query, vec![migration_id.clone()]);
+
+    let jan_2 = chrono::DateTime::parse_from_rfc3339("2025-01-02T00:00:00Z")?.timestamp();
+    let by_date = list(ThreadListFilter {
+        created_after: Some(jan_2),
+        ..Default::default()
+    })
+    .await?;
+    assert_eq!(by_date, vec![migration_id]);
+
+    let by_branch = list(ThreadListFilter {
+        git_branch: Some("main".to_string()),
+        ..Default::default()
+    })
+    .await?;
+    assert!(by_branch.is_empty());
+
+    Ok(())
+}
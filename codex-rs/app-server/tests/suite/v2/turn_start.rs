@@ -386,6 +386,10 @@ async fn turn_start_updates_sandbox_and_cwd_between_turns_v2() -> Result<()> {
                 network_access: false,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                allowed_hosts: vec![],
+                allowed_cidrs: vec![],
+                readable_roots: vec![],
+                deny_read: vec![],
             }),
             model: Some("mock-model".to_string()),
             effort: Some(ReasoningEffort::Medium),
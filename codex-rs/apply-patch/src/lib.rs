@@ -1,4 +1,5 @@
 mod parser;
+mod reverse;
 mod seek_sequence;
 mod standalone_executable;
 
@@ -10,6 +11,7 @@ use std::sync::LazyLock;
 
 use anyhow::Context;
 use anyhow::Result;
+pub use parser::FileMode;
 pub use parser::Hunk;
 pub use parser::ParseError;
 use parser::ParseError::*;
@@ -37,9 +39,21 @@ pub enum ApplyPatchError {
     ParseError(#[from] ParseError),
     #[error(transparent)]
     IoError(#[from] IoError),
-    /// Error that occurs while computing replacements when applying patch chunks
-    #[error("{0}")]
-    ComputeReplacements(String),
+    /// An update hunk whose lines could not be located in the target file.
+    #[error(transparent)]
+    ContextMismatch(#[from] ContextMismatch),
+    /// A hunk that discards a file's contents cannot be reversed without them.
+    #[error(
+        "cannot reverse the change to {}: the patch does not record its previous contents",
+        .0.display()
+    )]
+    IrreversibleChange(PathBuf),
+    /// A `*** Set Mode` hunk cannot be reversed without the mode it replaced.
+    #[error(
+        "cannot reverse the change to {}: the patch does not record its previous mode",
+        .0.display()
+    )]
+    IrreversibleModeChange(PathBuf),
     /// A raw patch body was provided without an explicit `apply_patch` invocation.
     #[error(
         "patch detected without explicit call to apply_patch. Rerun as [\"apply_patch\", \"<patch>\"]"
@@ -79,6 +93,105 @@ impl PartialEq for IoError {
     }
 }
 
+/// Number of lines shown on either side of a [`ClosestMatch`].
+const CLOSEST_MATCH_CONTEXT_LINES: usize = 2;
+
+/// Describes an update hunk that does not apply, with enough of the actual
+/// file content for the hunk to be corrected without re-reading the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMismatch {
+    pub path: PathBuf,
+    /// Zero-based index of the `@@` hunk within the file's update.
+    pub hunk_index: usize,
+    pub kind: ContextMismatchKind,
+    /// The lines the hunk expected to find.
+    pub expected_lines: Vec<String>,
+    /// The region of the file that most resembles `expected_lines`, if any.
+    pub closest_match: Option<ClosestMatch>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMismatchKind {
+    /// The `@@ <context>` line was not found.
+    ChangeContext,
+    /// The hunk's removed and context lines were not found.
+    OldLines,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosestMatch {
+    /// 1-based line number where the closest match starts.
+    pub line_number: usize,
+    /// 1-based line number of the first entry in `actual_lines`.
+    pub window_start: usize,
+    /// The file's actual content around the closest match.
+    pub actual_lines: Vec<String>,
+}
+
+impl ContextMismatch {
+    fn new(
+        path: &Path,
+        hunk_index: usize,
+        kind: ContextMismatchKind,
+        expected_lines: &[String],
+        original_lines: &[String],
+        line_index: usize,
+    ) -> Self {
+        let closest_match =
+            seek_sequence::closest_sequence(original_lines, expected_lines, line_index).map(
+                |start| {
+                    let window_start = start.saturating_sub(CLOSEST_MATCH_CONTEXT_LINES);
+                    let window_end = (start + expected_lines.len() + CLOSEST_MATCH_CONTEXT_LINES)
+                        .min(original_lines.len());
+                    ClosestMatch {
+                        line_number: start + 1,
+                        window_start: window_start + 1,
+                        actual_lines: original_lines[window_start..window_end].to_vec(),
+                    }
+                },
+            );
+        Self {
+            path: path.to_path_buf(),
+            hunk_index,
+            kind,
+            expected_lines: expected_lines.to_vec(),
+            closest_match,
+        }
+    }
+}
+
+impl std::fmt::Display for ContextMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path.display();
+        let hunk = self.hunk_index + 1;
+        match self.kind {
+            ContextMismatchKind::ChangeContext => write!(
+                f,
+                "Failed to find context '{}' in {path} (hunk {hunk})",
+                self.expected_lines.join("\n")
+            )?,
+            ContextMismatchKind::OldLines => write!(
+                f,
+                "Failed to find expected lines in {path} (hunk {hunk}):\n{}",
+                self.expected_lines.join("\n")
+            )?,
+        }
+        if let Some(closest) = &self.closest_match {
+            write!(
+                f,
+                "\nClosest match starts at line {}. Actual content of {path}:",
+                closest.line_number
+            )?;
+            for (offset, line) in closest.actual_lines.iter().enumerate() {
+                write!(f, "\n{:>5} | {line}", closest.window_start + offset)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ContextMismatch {}
+
 #[derive(Debug, PartialEq)]
 pub enum MaybeApplyPatch {
     Body(ApplyPatchArgs),
@@ -134,8 +247,19 @@ pub enum ApplyPatchFileChange {
     Update {
         unified_diff: String,
         move_path: Option<PathBuf>,
-        /// new_content that will result after the unified_diff is applied.
-        new_content: String,
+        /// new_content that will result after the unified_diff is applied, or
+        /// `None` when only the mode changes and the contents are left alone.
+        new_content: Option<String>,
+        /// Permissions the file will have afterwards, when the patch sets them.
+        new_mode: Option<FileMode>,
+        /// Permissions the file had before, recorded when the patch sets them.
+        previous_mode: Option<FileMode>,
+    },
+    /// A file is created or replaced with binary contents.
+    Binary {
+        /// The contents being replaced, or `None` when the file is created.
+        previous_content: Option<Vec<u8>>,
+        content: Vec<u8>,
     },
 }
 
@@ -179,6 +303,30 @@ impl ApplyPatchAction {
         &self.changes
     }
 
+    /// Returns the patch that undoes this one once it has been applied,
+    /// restoring deleted and replaced files and changed modes from what was
+    /// read during verification.
+    pub fn reverse_patch(&self) -> Result<String, ApplyPatchError> {
+        let hunks = parse_patch(&self.patch)?.hunks;
+        let hunks = reverse::reverse_hunks(
+            hunks,
+            |path| match self.changes.get(&self.cwd.join(path)) {
+                Some(ApplyPatchFileChange::Delete { content }) => {
+                    Some(content.clone().into_bytes())
+                }
+                Some(ApplyPatchFileChange::Binary {
+                    previous_content, ..
+                }) => previous_content.clone(),
+                _ => None,
+            },
+            |path| match self.changes.get(&self.cwd.join(path)) {
+                Some(ApplyPatchFileChange::Update { previous_mode, .. }) => *previous_mode,
+                _ => None,
+            },
+        )?;
+        Ok(reverse::format_patch(&hunks))
+    }
+
     /// Should be used exclusively for testing. (Not worth the overhead of
     /// creating a feature flag for this.)
     pub fn new_add_for_test(path: &Path, content: String) -> Self {
@@ -251,55 +399,10 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                     }
                 })
                 .unwrap_or_else(|| cwd.to_path_buf());
-            let mut changes = HashMap::new();
-            for hunk in hunks {
-                let path = hunk.resolve_path(&effective_cwd);
-                match hunk {
-                    Hunk::AddFile { contents, .. } => {
-                        changes.insert(path, ApplyPatchFileChange::Add { content: contents });
-                    }
-                    Hunk::DeleteFile { .. } => {
-                        let content = match std::fs::read_to_string(&path) {
-                            Ok(content) => content,
-                            Err(e) => {
-                                return MaybeApplyPatchVerified::CorrectnessError(
-                                    ApplyPatchError::IoError(IoError {
-                                        context: format!("Failed to read {}", path.display()),
-                                        source: e,
-                                    }),
-                                );
-                            }
-                        };
-                        changes.insert(path, ApplyPatchFileChange::Delete { content });
-                    }
-                    Hunk::UpdateFile {
-                        move_path, chunks, ..
-                    } => {
-                        let ApplyPatchFileUpdate {
-                            unified_diff,
-                            content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks) {
-                            Ok(diff) => diff,
-                            Err(e) => {
-                                return MaybeApplyPatchVerified::CorrectnessError(e);
-                            }
-                        };
-                        changes.insert(
-                            path,
-                            ApplyPatchFileChange::Update {
-                                unified_diff,
-                                move_path: move_path.map(|p| effective_cwd.join(p)),
-                                new_content: contents,
-                            },
-                        );
-                    }
-                }
+            match verify_hunks(patch, hunks, effective_cwd) {
+                Ok(action) => MaybeApplyPatchVerified::Body(action),
+                Err(e) => MaybeApplyPatchVerified::CorrectnessError(e),
             }
-            MaybeApplyPatchVerified::Body(ApplyPatchAction {
-                changes,
-                patch,
-                cwd: effective_cwd,
-            })
         }
         MaybeApplyPatch::ShellParseError(e) => MaybeApplyPatchVerified::ShellParseError(e),
         MaybeApplyPatch::PatchParseError(e) => MaybeApplyPatchVerified::CorrectnessError(e.into()),
@@ -307,6 +410,113 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
     }
 }
 
+/// Validates every hunk in `patch` against the files under `cwd` without
+/// writing anything, returning the changes that applying it would make.
+pub fn check_patch(patch: &str, cwd: &Path) -> Result<ApplyPatchAction, ApplyPatchError> {
+    let ApplyPatchArgs { patch, hunks, .. } = parse_patch(patch)?;
+    verify_hunks(patch, hunks, cwd.to_path_buf())
+}
+
+/// Returns the patch that undoes `patch` once it has been applied. Patches
+/// that delete files, replace binary files or set modes cannot be reversed
+/// this way because they do not record the previous contents or mode; use
+/// [`ApplyPatchAction::reverse_patch`] for those.
+pub fn reverse_patch(patch: &str) -> Result<String, ApplyPatchError> {
+    let hunks = reverse::reverse_hunks(parse_patch(patch)?.hunks, |_| None, |_| None)?;
+    Ok(reverse::format_patch(&hunks))
+}
+
+fn verify_hunks(
+    patch: String,
+    hunks: Vec<Hunk>,
+    effective_cwd: PathBuf,
+) -> Result<ApplyPatchAction, ApplyPatchError> {
+    let mut changes = HashMap::new();
+    for hunk in hunks {
+        let path = hunk.resolve_path(&effective_cwd);
+        match hunk {
+            Hunk::AddFile { contents, .. } => {
+                changes.insert(path, ApplyPatchFileChange::Add { content: contents });
+            }
+            Hunk::DeleteFile { .. } => {
+                let content = std::fs::read_to_string(&path).map_err(|e| {
+                    ApplyPatchError::IoError(IoError {
+                        context: format!("Failed to read {}", path.display()),
+                        source: e,
+                    })
+                })?;
+                changes.insert(path, ApplyPatchFileChange::Delete { content });
+            }
+            Hunk::UpdateFile {
+                move_path,
+                mode,
+                chunks,
+                ..
+            } => {
+                let previous_mode = match mode {
+                    Some(_) => Some(file_mode(&path).map_err(|e| {
+                        ApplyPatchError::IoError(IoError {
+                            context: format!("Failed to read file to update {}", path.display()),
+                            source: e,
+                        })
+                    })?),
+                    None => None,
+                };
+                // With no chunks only the mode changes; the file may not even
+                // be text, so leave its contents alone.
+                let (unified_diff, new_content) = if chunks.is_empty() {
+                    (String::new(), None)
+                } else {
+                    let ApplyPatchFileUpdate {
+                        unified_diff,
+                        content,
+                    } = unified_diff_from_chunks(&path, &chunks)?;
+                    (unified_diff, Some(content))
+                };
+                changes.insert(
+                    path,
+                    ApplyPatchFileChange::Update {
+                        unified_diff,
+                        move_path: move_path.map(|p| effective_cwd.join(p)),
+                        new_content,
+                        new_mode: mode,
+                        previous_mode,
+                    },
+                );
+            }
+            Hunk::AddBinaryFile { contents, .. } => {
+                changes.insert(
+                    path,
+                    ApplyPatchFileChange::Binary {
+                        previous_content: None,
+                        content: contents,
+                    },
+                );
+            }
+            Hunk::ReplaceBinaryFile { contents, .. } => {
+                let previous_content = std::fs::read(&path).map_err(|e| {
+                    ApplyPatchError::IoError(IoError {
+                        context: format!("Failed to read file to replace {}", path.display()),
+                        source: e,
+                    })
+                })?;
+                changes.insert(
+                    path,
+                    ApplyPatchFileChange::Binary {
+                        previous_content: Some(previous_content),
+                        content: contents,
+                    },
+                );
+            }
+        }
+    }
+    Ok(ApplyPatchAction {
+        changes,
+        patch,
+        cwd: effective_cwd,
+    })
+}
+
 /// Extract the heredoc body (and optional `cd` workdir) from a `bash -lc` script
 /// that invokes the apply_patch tool using a heredoc.
 ///
@@ -510,11 +720,13 @@ pub fn apply_hunks(
     let _existing_paths: Vec<&Path> = hunks
         .iter()
         .filter_map(|hunk| match hunk {
-            Hunk::AddFile { .. } => {
+            Hunk::AddFile { .. } | Hunk::AddBinaryFile { .. } => {
                 // The file is being added, so it doesn't exist yet.
                 None
             }
-            Hunk::DeleteFile { path } => Some(path.as_path()),
+            Hunk::DeleteFile { path } | Hunk::ReplaceBinaryFile { path, .. } => {
+                Some(path.as_path())
+            }
             Hunk::UpdateFile {
                 path, move_path, ..
             } => match move_path {
@@ -576,13 +788,7 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     for hunk in hunks {
         match hunk {
             Hunk::AddFile { path, contents } => {
-                if let Some(parent) = path.parent()
-                    && !parent.as_os_str().is_empty()
-                {
-                    std::fs::create_dir_all(parent).with_context(|| {
-                        format!("Failed to create parent directories for {}", path.display())
-                    })?;
-                }
+                create_parent_dirs(path)?;
                 std::fs::write(path, contents)
                     .with_context(|| format!("Failed to write file {}", path.display()))?;
                 added.push(path.clone());
@@ -595,28 +801,55 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
             Hunk::UpdateFile {
                 path,
                 move_path,
+                mode,
                 chunks,
             } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
-                if let Some(dest) = move_path {
-                    if let Some(parent) = dest.parent()
-                        && !parent.as_os_str().is_empty()
-                    {
-                        std::fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directories for {}", dest.display())
+                let dest = move_path.as_ref().unwrap_or(path);
+                if chunks.is_empty() {
+                    // Only the mode changes, so leave the contents untouched.
+                    std::fs::metadata(path).with_context(|| {
+                        format!("Failed to read file to update {}", path.display())
+                    })?;
+                    if move_path.is_some() {
+                        create_parent_dirs(dest)?;
+                        std::fs::rename(path, dest).with_context(|| {
+                            format!("Failed to move {} to {}", path.display(), dest.display())
                         })?;
                     }
-                    std::fs::write(dest, new_contents)
-                        .with_context(|| format!("Failed to write file {}", dest.display()))?;
-                    std::fs::remove_file(path)
-                        .with_context(|| format!("Failed to remove original {}", path.display()))?;
-                    modified.push(dest.clone());
                 } else {
-                    std::fs::write(path, new_contents)
-                        .with_context(|| format!("Failed to write file {}", path.display()))?;
-                    modified.push(path.clone());
+                    let AppliedPatch { new_contents, .. } =
+                        derive_new_contents_from_chunks(path, chunks)?;
+                    if move_path.is_some() {
+                        create_parent_dirs(dest)?;
+                        std::fs::write(dest, new_contents)
+                            .with_context(|| format!("Failed to write file {}", dest.display()))?;
+                        std::fs::remove_file(path).with_context(|| {
+                            format!("Failed to remove original {}", path.display())
+                        })?;
+                    } else {
+                        std::fs::write(path, new_contents)
+                            .with_context(|| format!("Failed to write file {}", path.display()))?;
+                    }
+                }
+                if let Some(mode) = mode {
+                    set_file_mode(dest, *mode)
+                        .with_context(|| format!("Failed to set mode of {}", dest.display()))?;
                 }
+                modified.push(dest.clone());
+            }
+            Hunk::AddBinaryFile { path, contents } => {
+                create_parent_dirs(path)?;
+                std::fs::write(path, contents)
+                    .with_context(|| format!("Failed to write file {}", path.display()))?;
+                added.push(path.clone());
+            }
+            Hunk::ReplaceBinaryFile { path, contents } => {
+                std::fs::metadata(path).with_context(|| {
+                    format!("Failed to read file to replace {}", path.display())
+                })?;
+                std::fs::write(path, contents)
+                    .with_context(|| format!("Failed to write file {}", path.display()))?;
+                modified.push(path.clone());
             }
         }
     }
@@ -627,6 +860,61 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     })
 }
 
+fn create_parent_dirs(path: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).with_context(|| {
+            format!("Failed to create parent directories for {}", path.display())
+        })?;
+    }
+    Ok(())
+}
+
+/// Sets or clears the executable bits, keeping the rest of the permissions.
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: FileMode) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    let bits = permissions.mode();
+    let bits = match mode {
+        // Grant execute wherever read is granted, as `chmod +x` does under the
+        // usual umask.
+        FileMode::Executable => bits | ((bits & 0o444) >> 2),
+        FileMode::Regular => bits & !0o111,
+    };
+    permissions.set_mode(bits);
+    std::fs::set_permissions(path, permissions)
+}
+
+/// Windows has no executable bit, so mode changes are a no-op there.
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, _mode: FileMode) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Returns the mode of the file at `path`: executable when any execute bit is
+/// set.
+#[cfg(unix)]
+fn file_mode(path: &Path) -> std::io::Result<FileMode> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bits = std::fs::metadata(path)?.permissions().mode();
+    Ok(if bits & 0o111 != 0 {
+        FileMode::Executable
+    } else {
+        FileMode::Regular
+    })
+}
+
+/// Windows has no executable bit, so every file is regular there.
+#[cfg(not(unix))]
+fn file_mode(path: &Path) -> std::io::Result<FileMode> {
+    std::fs::metadata(path)?;
+    Ok(FileMode::Regular)
+}
+
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
@@ -680,7 +968,7 @@ fn compute_replacements(
     let mut replacements: Vec<(usize, usize, Vec<String>)> = Vec::new();
     let mut line_index: usize = 0;
 
-    for chunk in chunks {
+    for (hunk_index, chunk) in chunks.iter().enumerate() {
         // If a chunk has a `change_context`, we use seek_sequence to find it, then
         // adjust our `line_index` to continue from there.
         if let Some(ctx_line) = &chunk.change_context {
@@ -692,11 +980,15 @@ fn compute_replacements(
             ) {
                 line_index = idx + 1;
             } else {
-                return Err(ApplyPatchError::ComputeReplacements(format!(
-                    "Failed to find context '{}' in {}",
-                    ctx_line,
-                    path.display()
-                )));
+                return Err(ContextMismatch::new(
+                    path,
+                    hunk_index,
+                    ContextMismatchKind::ChangeContext,
+                    std::slice::from_ref(ctx_line),
+                    original_lines,
+                    line_index,
+                )
+                .into());
             }
         }
 
@@ -749,11 +1041,15 @@ fn compute_replacements(
             replacements.push((start_idx, pattern.len(), new_slice.to_vec()));
             line_index = start_idx + pattern.len();
         } else {
-            return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
-                path.display(),
-                chunk.old_lines.join("\n"),
-            )));
+            return Err(ContextMismatch::new(
+                path,
+                hunk_index,
+                ContextMismatchKind::OldLines,
+                pattern,
+                original_lines,
+                line_index,
+            )
+            .into());
         }
     }
 
@@ -828,6 +1124,42 @@ pub fn print_summary(
     out: &mut impl std::io::Write,
 ) -> std::io::Result<()> {
     writeln!(out, "Success. Updated the following files:")?;
+    print_affected_paths(affected, out)
+}
+
+/// Prints the summary of a successful `--check`: the files the patch would
+/// touch, without touching them.
+pub fn print_check_summary(hunks: &[Hunk], out: &mut impl std::io::Write) -> std::io::Result<()> {
+    let mut affected = AffectedPaths {
+        added: Vec::new(),
+        modified: Vec::new(),
+        deleted: Vec::new(),
+    };
+    for hunk in hunks {
+        match hunk {
+            Hunk::AddFile { path, .. } | Hunk::AddBinaryFile { path, .. } => {
+                affected.added.push(path.clone())
+            }
+            Hunk::DeleteFile { path } => affected.deleted.push(path.clone()),
+            Hunk::ReplaceBinaryFile { path, .. } => affected.modified.push(path.clone()),
+            Hunk::UpdateFile {
+                path, move_path, ..
+            } => affected
+                .modified
+                .push(move_path.as_ref().unwrap_or(path).clone()),
+        }
+    }
+    writeln!(
+        out,
+        "Patch applies cleanly. The following files would be updated:"
+    )?;
+    print_affected_paths(&affected, out)
+}
+
+fn print_affected_paths(
+    affected: &AffectedPaths,
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
     for path in &affected.added {
         writeln!(out, "A {}", path.display())?;
     }
@@ -1144,6 +1476,223 @@ PATCH"#,
         assert_eq!(contents, "foo\nbaz\n");
     }
 
+    #[test]
+    fn test_update_file_hunk_reports_closest_match_on_mismatch() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mismatch.txt");
+        fs::write(&path, "a\nb\nfn greet() {\n    hello();\n}\nc\nd\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+ a
+-b
++B
+@@
+ fn greet() {{
+-    helo();
++    goodbye();
+ }}"#,
+            path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let err = apply_patch(&patch, &mut stdout, &mut stderr).unwrap_err();
+        let ApplyPatchError::IoError(IoError { context, .. }) = err else {
+            panic!("expected apply_hunks to wrap the error, got {err:?}");
+        };
+
+        let mismatch = derive_new_contents_from_chunks(&path, &hunk_chunks(&patch))
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(
+            mismatch,
+            ApplyPatchError::ContextMismatch(ContextMismatch {
+                path: path.clone(),
+                hunk_index: 1,
+                kind: ContextMismatchKind::OldLines,
+                expected_lines: strs_to_strings(&["fn greet() {", "    helo();", "}"]),
+                closest_match: Some(ClosestMatch {
+                    line_number: 3,
+                    window_start: 1,
+                    actual_lines: strs_to_strings(&[
+                        "a",
+                        "b",
+                        "fn greet() {",
+                        "    hello();",
+                        "}",
+                        "c",
+                        "d",
+                    ]),
+                }),
+            })
+        );
+        let expected_message = [
+            format!(
+                "Failed to find expected lines in {} (hunk 2):",
+                path.display()
+            ),
+            "fn greet() {".to_string(),
+            "    helo();".to_string(),
+            "}".to_string(),
+            format!(
+                "Closest match starts at line 3. Actual content of {}:",
+                path.display()
+            ),
+            "    1 | a".to_string(),
+            "    2 | b".to_string(),
+            "    3 | fn greet() {".to_string(),
+            "    4 |     hello();".to_string(),
+            "    5 | }".to_string(),
+            "    6 | c".to_string(),
+            "    7 | d".to_string(),
+        ]
+        .join("\n");
+        assert_eq!(context, expected_message);
+        assert_eq!(
+            String::from_utf8(stderr).unwrap(),
+            format!("{expected_message}\n")
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "a\nb\nfn greet() {\n    hello();\n}\nc\nd\n"
+        );
+    }
+
+    #[test]
+    fn test_check_patch_reverse_restores_deleted_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("keep.txt"), "old\n").unwrap();
+        fs::write(dir.path().join("gone.txt"), "precious\n").unwrap();
+        let patch =
+            wrap_patch("*** Delete File: gone.txt\n*** Update File: keep.txt\n@@\n-old\n+new");
+
+        let action = check_patch(&patch, dir.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("keep.txt")).unwrap(),
+            "old\n"
+        );
+        assert!(dir.path().join("gone.txt").exists());
+        assert_eq!(
+            action.changes().get(&dir.path().join("gone.txt")),
+            Some(&ApplyPatchFileChange::Delete {
+                content: "precious\n".to_string()
+            })
+        );
+
+        let reversed = action.reverse_patch().unwrap();
+        assert_eq!(
+            reversed,
+            "*** Begin Patch\n*** Update File: keep.txt\n@@\n-new\n+old\n*** Add File: gone.txt\n+precious\n*** End Patch"
+        );
+        assert_matches!(
+            reverse_patch(&patch),
+            Err(ApplyPatchError::IrreversibleChange(path)) if path == Path::new("gone.txt")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_mode_and_binary_hunks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("run.sh");
+        let blob = dir.path().join("blob.bin");
+        fs::write(&script, "echo hi\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+        fs::write(&blob, [0u8, 159, 146, 150]).unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n*** Set Mode: executable\n*** Replace Binary File: {}\nAAEC/w==\n*** Add Binary File: {}\nAA==",
+            script.display(),
+            blob.display(),
+            dir.path().join("nested/new.bin").display()
+        ));
+
+        let action = check_patch(&patch, dir.path()).unwrap();
+        assert_eq!(
+            action.changes().get(&blob),
+            Some(&ApplyPatchFileChange::Binary {
+                previous_content: Some(vec![0, 159, 146, 150]),
+                content: vec![0, 1, 2, 255],
+            })
+        );
+        assert_matches!(
+            action.changes().get(&script),
+            Some(ApplyPatchFileChange::Update {
+                unified_diff,
+                new_mode: Some(FileMode::Executable),
+                ..
+            }) if unified_diff.is_empty()
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(fs::read_to_string(&script).unwrap(), "echo hi\n");
+        assert_eq!(
+            fs::metadata(&script).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+        assert_eq!(fs::read(&blob).unwrap(), vec![0, 1, 2, 255]);
+        assert_eq!(
+            fs::read(dir.path().join("nested/new.bin")).unwrap(),
+            vec![0]
+        );
+
+        let mut stdout = Vec::new();
+        apply_patch(&action.reverse_patch().unwrap(), &mut stdout, &mut stderr).unwrap();
+        assert_eq!(
+            fs::metadata(&script).unwrap().permissions().mode() & 0o777,
+            0o644
+        );
+        assert_eq!(fs::read(&blob).unwrap(), vec![0, 159, 146, 150]);
+        assert!(!dir.path().join("nested/new.bin").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_mode_round_trips_previous_mode_without_reading_contents() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let tool = dir.path().join("tool");
+        fs::write(&tool, [0xff, 0xfe, 0x00, 0x80]).unwrap();
+        fs::set_permissions(&tool, fs::Permissions::from_mode(0o755)).unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n*** Set Mode: executable",
+            tool.display()
+        ));
+
+        let action = check_patch(&patch, dir.path()).unwrap();
+        assert_eq!(
+            action.changes().get(&tool),
+            Some(&ApplyPatchFileChange::Update {
+                unified_diff: String::new(),
+                move_path: None,
+                new_content: None,
+                new_mode: Some(FileMode::Executable),
+                previous_mode: Some(FileMode::Executable),
+            })
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        apply_patch(&action.reverse_patch().unwrap(), &mut stdout, &mut stderr).unwrap();
+        assert_eq!(fs::read(&tool).unwrap(), vec![0xff, 0xfe, 0x00, 0x80]);
+        assert_eq!(
+            fs::metadata(&tool).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+    }
+
+    fn hunk_chunks(patch: &str) -> Vec<UpdateFileChunk> {
+        match parse_patch(patch).unwrap().hunks.remove(0) {
+            Hunk::UpdateFile { chunks, .. } => chunks,
+            hunk => panic!("expected an update hunk, got {hunk:?}"),
+        }
+    }
+
     #[test]
     fn test_update_file_hunk_can_move_file() {
         let dir = tempdir().unwrap();
@@ -1594,7 +2143,9 @@ g
 "#
                         .to_string(),
                         move_path: None,
-                        new_content: "updated session directory content\n".to_string(),
+                        new_content: Some("updated session directory content\n".to_string()),
+                        new_mode: None,
+                        previous_mode: None,
                     },
                 )]),
                 patch: argv[1].clone(),
@@ -7,14 +7,18 @@
 //! begin_patch: "*** Begin Patch" LF
 //! end_patch: "*** End Patch" LF?
 //!
-//! hunk: add_hunk | delete_hunk | update_hunk
+//! hunk: add_hunk | delete_hunk | update_hunk | add_binary_hunk | replace_binary_hunk
 //! add_hunk: "*** Add File: " filename LF add_line+
 //! delete_hunk: "*** Delete File: " filename LF
-//! update_hunk: "*** Update File: " filename LF change_move? change?
+//! update_hunk: "*** Update File: " filename LF change_move? set_mode? change?
+//! add_binary_hunk: "*** Add Binary File: " filename LF base64_line*
+//! replace_binary_hunk: "*** Replace Binary File: " filename LF base64_line*
 //! filename: /(.+)/
 //! add_line: "+" /(.+)/ LF -> line
+//! base64_line: /[A-Za-z0-9+\/=]+/ LF
 //!
 //! change_move: "*** Move to: " filename LF
+//! set_mode: "*** Set Mode: " ("executable" | "regular") LF
 //! change: (change_context | change_line)+ eof_line?
 //! change_context: ("@@" | "@@ " /(.+)/) LF
 //! change_line: ("+" | "-" | " ") /(.+)/ LF
@@ -23,6 +27,8 @@
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
 use crate::ApplyPatchArgs;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -34,6 +40,9 @@ const ADD_FILE_MARKER: &str = "*** Add File: ";
 const DELETE_FILE_MARKER: &str = "*** Delete File: ";
 const UPDATE_FILE_MARKER: &str = "*** Update File: ";
 const MOVE_TO_MARKER: &str = "*** Move to: ";
+const SET_MODE_MARKER: &str = "*** Set Mode: ";
+const ADD_BINARY_FILE_MARKER: &str = "*** Add Binary File: ";
+const REPLACE_BINARY_FILE_MARKER: &str = "*** Replace Binary File: ";
 const EOF_MARKER: &str = "*** End of File";
 const CHANGE_CONTEXT_MARKER: &str = "@@ ";
 const EMPTY_CHANGE_CONTEXT_MARKER: &str = "@@";
@@ -68,11 +77,24 @@ pub enum Hunk {
     UpdateFile {
         path: PathBuf,
         move_path: Option<PathBuf>,
+        /// Permissions to give the file after updating it.
+        mode: Option<FileMode>,
 
         /// Chunks should be in order, i.e. the `change_context` of one chunk
-        /// should occur later in the file than the previous chunk.
+        /// should occur later in the file than the previous chunk. May be empty
+        /// when the hunk only changes the file's mode.
         chunks: Vec<UpdateFileChunk>,
     },
+    /// Creates a file with the given (decoded) binary contents.
+    AddBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+    },
+    /// Replaces the contents of an existing file with binary contents.
+    ReplaceBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+    },
 }
 
 impl Hunk {
@@ -81,6 +103,35 @@ impl Hunk {
             Hunk::AddFile { path, .. } => cwd.join(path),
             Hunk::DeleteFile { path } => cwd.join(path),
             Hunk::UpdateFile { path, .. } => cwd.join(path),
+            Hunk::AddBinaryFile { path, .. } => cwd.join(path),
+            Hunk::ReplaceBinaryFile { path, .. } => cwd.join(path),
+        }
+    }
+}
+
+/// File permissions that a patch can set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileMode {
+    /// `100644`
+    Regular,
+    /// `100755`
+    Executable,
+}
+
+impl FileMode {
+    pub(crate) fn parse(mode: &str) -> Option<Self> {
+        match mode.trim() {
+            "regular" | "100644" => Some(Self::Regular),
+            "executable" | "100755" => Some(Self::Executable),
+            _ => None,
+        }
+    }
+
+    /// The spelling used by the `*** Set Mode: ` directive.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Regular => "regular",
+            Self::Executable => "executable",
         }
     }
 }
@@ -273,6 +324,24 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             },
             1,
         ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_BINARY_FILE_MARKER) {
+        let (contents, parsed_lines) = parse_base64_lines(lines, path, line_number)?;
+        return Ok((
+            AddBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+            },
+            parsed_lines,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(REPLACE_BINARY_FILE_MARKER) {
+        let (contents, parsed_lines) = parse_base64_lines(lines, path, line_number)?;
+        return Ok((
+            ReplaceBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+            },
+            parsed_lines,
+        ));
     } else if let Some(path) = first_line.strip_prefix(UPDATE_FILE_MARKER) {
         // Update File
         let mut remaining_lines = &lines[1..];
@@ -288,6 +357,27 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             parsed_lines += 1;
         }
 
+        // Optional: set mode line
+        let mode = match remaining_lines
+            .first()
+            .and_then(|x| x.strip_prefix(SET_MODE_MARKER))
+        {
+            Some(mode) => {
+                let Some(mode) = FileMode::parse(mode) else {
+                    return Err(InvalidHunkError {
+                        message: format!(
+                            "Invalid mode '{mode}' for path '{path}'. Expected 'executable' or 'regular'"
+                        ),
+                        line_number: line_number + parsed_lines,
+                    });
+                };
+                remaining_lines = &remaining_lines[1..];
+                parsed_lines += 1;
+                Some(mode)
+            }
+            None => None,
+        };
+
         let mut chunks = Vec::new();
         // NOTE: we need to know to stop once we reach the next special marker header.
         while !remaining_lines.is_empty() {
@@ -312,7 +402,7 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             remaining_lines = &remaining_lines[chunk_lines..]
         }
 
-        if chunks.is_empty() {
+        if chunks.is_empty() && mode.is_none() {
             return Err(InvalidHunkError {
                 message: format!("Update file hunk for path '{path}' is empty"),
                 line_number,
@@ -323,6 +413,7 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             UpdateFile {
                 path: PathBuf::from(path),
                 move_path: move_path.map(PathBuf::from),
+                mode,
                 chunks,
             },
             parsed_lines,
@@ -331,12 +422,48 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
 
     Err(InvalidHunkError {
         message: format!(
-            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
+            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}', '*** Add Binary File: {{path}}', '*** Replace Binary File: {{path}}'"
         ),
         line_number,
     })
 }
 
+/// Decodes the base64 lines that follow a binary file header, stopping at the
+/// next hunk header or `*** End Patch`. Returns the contents and the number of
+/// lines consumed, including the header.
+fn parse_base64_lines(
+    lines: &[&str],
+    path: &str,
+    line_number: usize,
+) -> Result<(Vec<u8>, usize), ParseError> {
+    let body: Vec<&str> = lines[1..]
+        .iter()
+        .map(|line| line.trim())
+        .take_while(|line| !is_hunk_boundary(line))
+        .collect();
+    let contents = BASE64_STANDARD
+        .decode(body.concat())
+        .map_err(|err| InvalidHunkError {
+            message: format!("Invalid base64 contents for binary file '{path}': {err}"),
+            line_number,
+        })?;
+    Ok((contents, body.len() + 1))
+}
+
+/// Whether `line` starts the next hunk or ends the patch.
+fn is_hunk_boundary(line: &str) -> bool {
+    line == END_PATCH_MARKER
+        || [
+            ADD_FILE_MARKER,
+            DELETE_FILE_MARKER,
+            UPDATE_FILE_MARKER,
+            ADD_BINARY_FILE_MARKER,
+            REPLACE_BINARY_FILE_MARKER,
+        ]
+        .iter()
+        .any(|marker| line.starts_with(marker))
+}
+
 fn parse_update_file_chunk(
     lines: &[&str],
     line_number: usize,
@@ -494,6 +621,7 @@ fn test_parse_patch() {
             UpdateFile {
                 path: PathBuf::from("path/update.py"),
                 move_path: Some(PathBuf::from("path/update2.py")),
+                mode: None,
                 chunks: vec![UpdateFileChunk {
                     change_context: Some("def f():".to_string()),
                     old_lines: vec!["    pass".to_string()],
@@ -521,6 +649,7 @@ fn test_parse_patch() {
             UpdateFile {
                 path: PathBuf::from("file.py"),
                 move_path: None,
+                mode: None,
                 chunks: vec![UpdateFileChunk {
                     change_context: None,
                     old_lines: vec![],
@@ -551,6 +680,7 @@ fn test_parse_patch() {
         vec![UpdateFile {
             path: PathBuf::from("file2.py"),
             move_path: None,
+            mode: None,
             chunks: vec![UpdateFileChunk {
                 change_context: None,
                 old_lines: vec!["import foo".to_string()],
@@ -571,6 +701,7 @@ fn test_parse_patch_lenient() {
     let expected_patch = vec![UpdateFile {
         path: PathBuf::from("file2.py"),
         move_path: None,
+        mode: None,
         chunks: vec![UpdateFileChunk {
             change_context: None,
             old_lines: vec!["import foo".to_string()],
@@ -653,13 +784,82 @@ fn test_parse_one_hunk() {
         parse_one_hunk(&["bad"], 234),
         Err(InvalidHunkError {
             message: "'bad' is not a valid hunk header. \
-            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
+            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}', \
+            '*** Add Binary File: {path}', '*** Replace Binary File: {path}'".to_string(),
             line_number: 234
         })
     );
     // Other edge cases are already covered by tests above/below.
 }
 
+#[test]
+fn test_parse_binary_and_mode_hunks() {
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Add Binary File: img.png\n\
+             AAEC\n\
+             /w==\n\
+             *** Update File: run.sh\n\
+             *** Set Mode: executable\n\
+             *** Update File: lib.sh\n\
+             *** Set Mode: 100644\n\
+             @@\n\
+             -a\n\
+             +b\n\
+             *** End Patch",
+            ParseMode::Strict
+        )
+        .unwrap()
+        .hunks,
+        vec![
+            AddBinaryFile {
+                path: PathBuf::from("img.png"),
+                contents: vec![0, 1, 2, 255],
+            },
+            UpdateFile {
+                path: PathBuf::from("run.sh"),
+                move_path: None,
+                mode: Some(FileMode::Executable),
+                chunks: Vec::new(),
+            },
+            UpdateFile {
+                path: PathBuf::from("lib.sh"),
+                move_path: None,
+                mode: Some(FileMode::Regular),
+                chunks: vec![UpdateFileChunk {
+                    change_context: None,
+                    old_lines: vec!["a".to_string()],
+                    new_lines: vec!["b".to_string()],
+                    is_end_of_file: false,
+                }],
+            },
+        ]
+    );
+    assert_eq!(
+        parse_one_hunk(&["*** Update File: run.sh", "*** Set Mode: 777"], 3),
+        Err(InvalidHunkError {
+            message: "Invalid mode '777' for path 'run.sh'. Expected 'executable' or 'regular'"
+                .to_string(),
+            line_number: 4,
+        })
+    );
+    assert!(matches!(
+        parse_one_hunk(&["*** Replace Binary File: a.bin", "not base64!"], 7),
+        Err(InvalidHunkError { line_number: 7, .. })
+    ));
+    // Only a hunk header or `*** End Patch` ends the base64 block, so a stray
+    // `***` line is reported as part of the contents.
+    assert!(matches!(
+        parse_one_hunk(
+            &["*** Add Binary File: a.bin", "AAEC", "*** note", "/w=="],
+            7
+        ),
+        Err(InvalidHunkError { line_number: 7, message })
+            if message.starts_with("Invalid base64 contents for binary file 'a.bin'")
+    ));
+}
+
 #[test]
 fn test_update_file_chunk() {
     assert_eq!(
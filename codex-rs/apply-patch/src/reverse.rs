@@ -0,0 +1,228 @@
+//! Builds the patch that undoes a previously applied patch.
+use std::path::Path;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use similar::ChangeTag;
+use similar::TextDiff;
+
+use crate::ApplyPatchError;
+use crate::FileMode;
+use crate::Hunk;
+use crate::UpdateFileChunk;
+
+/// Line length used when writing base64 contents.
+const BASE64_LINE_LEN: usize = 76;
+
+/// Returns the hunks that undo `hunks`, in reverse order. `previous_contents`
+/// supplies the contents of files removed by `*** Delete File` hunks or
+/// overwritten by `*** Replace Binary File` hunks, and `previous_mode` the mode
+/// of files changed by `*** Set Mode`, neither of which the patch itself
+/// records.
+pub(crate) fn reverse_hunks(
+    hunks: Vec<Hunk>,
+    previous_contents: impl Fn(&Path) -> Option<Vec<u8>>,
+    previous_mode: impl Fn(&Path) -> Option<FileMode>,
+) -> Result<Vec<Hunk>, ApplyPatchError> {
+    hunks
+        .into_iter()
+        .rev()
+        .map(|hunk| match hunk {
+            Hunk::AddFile { path, .. } | Hunk::AddBinaryFile { path, .. } => {
+                Ok(Hunk::DeleteFile { path })
+            }
+            Hunk::DeleteFile { path } => match previous_contents(&path) {
+                Some(contents) => Ok(match String::from_utf8(contents) {
+                    Ok(contents) => Hunk::AddFile { path, contents },
+                    Err(err) => Hunk::AddBinaryFile {
+                        path,
+                        contents: err.into_bytes(),
+                    },
+                }),
+                None => Err(ApplyPatchError::IrreversibleChange(path)),
+            },
+            Hunk::ReplaceBinaryFile { path, .. } => match previous_contents(&path) {
+                Some(contents) => Ok(Hunk::ReplaceBinaryFile { path, contents }),
+                None => Err(ApplyPatchError::IrreversibleChange(path)),
+            },
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                mode,
+                chunks,
+            } => {
+                let mode = match mode {
+                    Some(_) => match previous_mode(&path) {
+                        Some(previous) => Some(previous),
+                        None => return Err(ApplyPatchError::IrreversibleModeChange(path)),
+                    },
+                    None => None,
+                };
+                let chunks = chunks
+                    .into_iter()
+                    .map(|chunk| UpdateFileChunk {
+                        change_context: chunk.change_context,
+                        old_lines: chunk.new_lines,
+                        new_lines: chunk.old_lines,
+                        is_end_of_file: chunk.is_end_of_file,
+                    })
+                    .collect();
+                Ok(match move_path {
+                    Some(move_path) => Hunk::UpdateFile {
+                        path: move_path,
+                        move_path: Some(path),
+                        mode,
+                        chunks,
+                    },
+                    None => Hunk::UpdateFile {
+                        path,
+                        move_path: None,
+                        mode,
+                        chunks,
+                    },
+                })
+            }
+        })
+        .collect()
+}
+
+/// Renders `hunks` in the `apply_patch` format. Each chunk is rendered as a
+/// line diff of its old and new lines, so parsing the result yields the same
+/// hunks.
+pub(crate) fn format_patch(hunks: &[Hunk]) -> String {
+    let mut out = vec!["*** Begin Patch".to_string()];
+    for hunk in hunks {
+        match hunk {
+            Hunk::AddFile { path, contents } => {
+                out.push(format!("*** Add File: {}", path.display()));
+                out.extend(contents.lines().map(|line| format!("+{line}")));
+            }
+            Hunk::DeleteFile { path } => {
+                out.push(format!("*** Delete File: {}", path.display()));
+            }
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                mode,
+                chunks,
+            } => {
+                out.push(format!("*** Update File: {}", path.display()));
+                if let Some(move_path) = move_path {
+                    out.push(format!("*** Move to: {}", move_path.display()));
+                }
+                if let Some(mode) = mode {
+                    out.push(format!("*** Set Mode: {}", mode.as_str()));
+                }
+                for chunk in chunks {
+                    match &chunk.change_context {
+                        Some(context) => out.push(format!("@@ {context}")),
+                        None => out.push("@@".to_string()),
+                    }
+                    let old: Vec<&str> = chunk.old_lines.iter().map(String::as_str).collect();
+                    let new: Vec<&str> = chunk.new_lines.iter().map(String::as_str).collect();
+                    for change in TextDiff::from_slices(&old, &new).iter_all_changes() {
+                        let sign = match change.tag() {
+                            ChangeTag::Equal => ' ',
+                            ChangeTag::Delete => '-',
+                            ChangeTag::Insert => '+',
+                        };
+                        out.push(format!("{sign}{}", change.value()));
+                    }
+                    if chunk.is_end_of_file {
+                        out.push("*** End of File".to_string());
+                    }
+                }
+            }
+            Hunk::AddBinaryFile { path, contents } => {
+                out.push(format!("*** Add Binary File: {}", path.display()));
+                push_base64_lines(&mut out, contents);
+            }
+            Hunk::ReplaceBinaryFile { path, contents } => {
+                out.push(format!("*** Replace Binary File: {}", path.display()));
+                push_base64_lines(&mut out, contents);
+            }
+        }
+    }
+    out.push("*** End Patch".to_string());
+    out.join("\n")
+}
+
+fn push_base64_lines(out: &mut Vec<String>, contents: &[u8]) {
+    let encoded = BASE64_STANDARD.encode(contents);
+    out.extend(
+        encoded
+            .as_bytes()
+            .chunks(BASE64_LINE_LEN)
+            .map(|line| String::from_utf8_lossy(line).into_owned()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_patch;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn format_patch_round_trips_through_the_parser() {
+        let patch = "*** Begin Patch\n*** Add File: new.txt\n+hello\n+\n*** Delete File: old.txt\n*** Update File: src/lib.rs\n*** Move to: src/main.rs\n@@ fn main() {\n     let a = 1;\n-    let b = 2;\n+    let b = 3;\n+\n }\n@@\n-tail\n+end\n*** End of File\n*** End Patch";
+        let hunks = parse_patch(patch).unwrap().hunks;
+        assert_eq!(format_patch(&hunks), patch);
+    }
+
+    #[test]
+    fn format_patch_round_trips_binary_and_mode_hunks() {
+        let patch = "*** Begin Patch\n*** Add Binary File: logo.png\niVBORw0KGgo=\n*** Replace Binary File: data.bin\nAAEC\n*** Update File: run.sh\n*** Set Mode: executable\n*** End Patch";
+        let hunks = parse_patch(patch).unwrap().hunks;
+        assert_eq!(format_patch(&hunks), patch);
+    }
+
+    #[test]
+    fn reverse_hunks_inverts_each_operation() {
+        let patch = "*** Begin Patch\n*** Add File: new.txt\n+hello\n*** Delete File: old.txt\n*** Update File: a.txt\n*** Move to: b.txt\n@@\n-one\n+two\n*** End Patch";
+        let hunks = parse_patch(patch).unwrap().hunks;
+        let reversed = reverse_hunks(
+            hunks,
+            |path| (path == Path::new("old.txt")).then(|| b"gone\n".to_vec()),
+            |_| None,
+        )
+        .unwrap();
+        assert_eq!(
+            format_patch(&reversed),
+            "*** Begin Patch\n*** Update File: b.txt\n*** Move to: a.txt\n@@\n-two\n+one\n*** Add File: old.txt\n+gone\n*** Delete File: new.txt\n*** End Patch"
+        );
+    }
+
+    #[test]
+    fn reverse_hunks_requires_deleted_contents() {
+        let hunks = vec![Hunk::DeleteFile {
+            path: PathBuf::from("old.txt"),
+        }];
+        assert_eq!(
+            reverse_hunks(hunks, |_| None, |_| None),
+            Err(ApplyPatchError::IrreversibleChange(PathBuf::from(
+                "old.txt"
+            )))
+        );
+    }
+
+    #[test]
+    fn reverse_hunks_restores_the_previous_mode() {
+        let patch =
+            "*** Begin Patch\n*** Update File: run.sh\n*** Set Mode: executable\n*** End Patch";
+        let hunks = parse_patch(patch).unwrap().hunks;
+
+        // Setting a mode the file already had is undone by setting it again,
+        // not by flipping it.
+        let reversed = reverse_hunks(hunks.clone(), |_| None, |_| Some(FileMode::Executable));
+        assert_eq!(format_patch(&reversed.unwrap()), patch);
+
+        assert_eq!(
+            reverse_hunks(hunks, |_| None, |_| None),
+            Err(ApplyPatchError::IrreversibleModeChange(PathBuf::from(
+                "run.sh"
+            )))
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use std::time::Duration;
+use std::time::Instant;
+
 /// Attempt to find the sequence of `pattern` lines within `lines` beginning at or after `start`.
 /// Returns the starting index of the match or `None` if not found. Matches are attempted with
 /// decreasing strictness: exact match, then ignoring trailing whitespace, then ignoring leading
@@ -109,8 +112,120 @@ pub(crate) fn seek_sequence(
     None
 }
 
+/// Minimum mean line similarity for [`closest_sequence`] to report a match.
+const MIN_SIMILARITY: f32 = 0.5;
+
+/// Most windows [`closest_sequence`] scores with a character diff.
+const MAX_SCORED_WINDOWS: usize = 64;
+
+/// Time [`closest_sequence`] may spend on character diffs. It only produces a
+/// hint, so a large or minified file must not stall the patch.
+const CLOSEST_SEQUENCE_BUDGET: Duration = Duration::from_millis(200);
+
+/// Finds the region of `lines` that most resembles `pattern`, for reporting
+/// when [`seek_sequence`] fails. Each candidate window is scored by the mean
+/// character-level similarity of its lines, ignoring surrounding whitespace;
+/// ties go to the window closest to `start`. Returns `None` when no window is
+/// similar enough to be a useful hint.
+///
+/// Windows are first ranked by a cheap upper bound on their score, and only
+/// the most promising ones are diffed, within [`CLOSEST_SEQUENCE_BUDGET`].
+pub(crate) fn closest_sequence(
+    lines: &[String],
+    pattern: &[String],
+    start: usize,
+) -> Option<usize> {
+    if pattern.is_empty() || lines.is_empty() {
+        return None;
+    }
+    let deadline = Instant::now() + CLOSEST_SEQUENCE_BUDGET;
+    let lines: Vec<(&str, usize)> = lines.iter().map(String::as_str).map(trimmed).collect();
+    let pattern: Vec<(&str, usize)> = pattern.iter().map(String::as_str).map(trimmed).collect();
+
+    let mut candidates: Vec<(usize, f32)> = (0..=lines.len().saturating_sub(pattern.len()))
+        .map(|i| (i, mean_similarity(&lines[i..], &pattern, similarity_bound)))
+        .filter(|(_, bound)| *bound >= MIN_SIMILARITY)
+        .collect();
+    candidates.sort_by(|(a, a_bound), (b, b_bound)| {
+        b_bound
+            .total_cmp(a_bound)
+            .then(a.abs_diff(start).cmp(&b.abs_diff(start)))
+    });
+
+    let mut best: Option<(usize, f32)> = None;
+    for (i, bound) in candidates.into_iter().take(MAX_SCORED_WINDOWS) {
+        if best.is_some_and(|(_, best_score)| bound < best_score) || Instant::now() >= deadline {
+            break;
+        }
+        let score = mean_similarity(&lines[i..], &pattern, |line, pat| {
+            line_similarity(line.0, pat.0, deadline)
+        });
+        let is_better = match best {
+            None => true,
+            Some((best_idx, best_score)) => {
+                score > best_score
+                    || (score == best_score && i.abs_diff(start) < best_idx.abs_diff(start))
+            }
+        };
+        if is_better {
+            best = Some((i, score));
+        }
+    }
+
+    best.filter(|(_, score)| *score >= MIN_SIMILARITY)
+        .map(|(idx, _)| idx)
+}
+
+fn trimmed(line: &str) -> (&str, usize) {
+    let line = line.trim();
+    (line, line.chars().count())
+}
+
+/// Mean of `similarity` over the lines of `pattern` and the start of `window`.
+fn mean_similarity(
+    window: &[(&str, usize)],
+    pattern: &[(&str, usize)],
+    similarity: impl Fn(&(&str, usize), &(&str, usize)) -> f32,
+) -> f32 {
+    let total: f32 = window
+        .iter()
+        .zip(pattern)
+        .map(|(line, pat)| similarity(line, pat))
+        .sum();
+    total / pattern.len() as f32
+}
+
+/// Upper bound on [`line_similarity`] from the line lengths alone: at most
+/// every character of the shorter line can match.
+fn similarity_bound(line: &(&str, usize), pattern: &(&str, usize)) -> f32 {
+    let (&(line, line_len), &(pattern, pattern_len)) = (line, pattern);
+    if line == pattern {
+        1.0
+    } else if line_len == 0 || pattern_len == 0 {
+        0.0
+    } else {
+        2.0 * line_len.min(pattern_len) as f32 / (line_len + pattern_len) as f32
+    }
+}
+
+fn line_similarity(line: &str, pattern: &str, deadline: Instant) -> f32 {
+    if line == pattern {
+        1.0
+    } else if line.is_empty() || pattern.is_empty() {
+        0.0
+    } else {
+        // Past the deadline the diff degrades to an approximation, which is
+        // good enough for a hint.
+        similar::TextDiff::configure()
+            .deadline(deadline)
+            .diff_chars(line, pattern)
+            .ratio()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::closest_sequence;
     use super::seek_sequence;
     use std::string::ToString;
 
@@ -148,4 +263,59 @@ mod tests {
         // Should not panic – must return None when pattern cannot possibly fit.
         assert_eq!(seek_sequence(&lines, &pattern, 0, false), None);
     }
+
+    #[test]
+    fn test_closest_sequence_finds_near_miss() {
+        let lines = to_vec(&[
+            "fn main() {",
+            "    let x = 1;",
+            "    println!(\"{x}\");",
+            "}",
+        ]);
+        let pattern = to_vec(&["    let x = 2;", "    println!(\"{x}\");"]);
+        assert_eq!(seek_sequence(&lines, &pattern, 0, false), None);
+        assert_eq!(closest_sequence(&lines, &pattern, 0), Some(1));
+    }
+
+    #[test]
+    fn test_closest_sequence_prefers_window_nearest_start() {
+        let lines = to_vec(&["alpha", "beta", "alpha", "beta"]);
+        let pattern = to_vec(&["alpha", "betx"]);
+        assert_eq!(closest_sequence(&lines, &pattern, 0), Some(0));
+        assert_eq!(closest_sequence(&lines, &pattern, 2), Some(2));
+    }
+
+    #[test]
+    fn test_closest_sequence_rejects_unrelated_content() {
+        let lines = to_vec(&["line1", "line2"]);
+        let pattern = to_vec(&["something completely different"]);
+        assert_eq!(closest_sequence(&lines, &pattern, 0), None);
+    }
+
+    #[test]
+    fn test_closest_sequence_stays_fast_on_large_files() {
+        // Long lines of the same length defeat the length-based pre-filter,
+        // so only the window cap and the time budget bound the work.
+        let line = |seed: u64| -> String {
+            let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+            (0..2_000)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    char::from(b'a' + (state % 26) as u8)
+                })
+                .collect()
+        };
+        let lines: Vec<String> = (0..2_000).map(line).collect();
+        let pattern: Vec<String> = (5_000..5_003).map(line).collect();
+
+        let started = std::time::Instant::now();
+        closest_sequence(&lines, &pattern, 0);
+        assert!(
+            started.elapsed() < std::time::Duration::from_secs(2),
+            "closest_sequence took {:?}",
+            started.elapsed()
+        );
+    }
 }
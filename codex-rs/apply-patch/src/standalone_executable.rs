@@ -9,11 +9,25 @@ pub fn main() -> ! {
 /// We would prefer to return `std::process::ExitCode`, but its `exit_process()`
 /// method is still a nightly API and we want main() to return !.
 pub fn run_main() -> i32 {
-    // Expect either one argument (the full apply_patch payload) or read it from stdin.
-    let mut args = std::env::args_os();
-    let _argv0 = args.next();
+    // Expect optional `--check`/`--reverse` flags followed by either one
+    // argument (the full apply_patch payload) or the payload on stdin.
+    let mut check = false;
+    let mut reverse = false;
+    let mut patch_arg = None;
+    for arg in std::env::args_os().skip(1) {
+        match arg.to_str() {
+            Some("--check") => check = true,
+            Some("--reverse") => reverse = true,
+            _ if patch_arg.is_none() => patch_arg = Some(arg),
+            _ => {
+                // Refuse extra args to avoid ambiguity.
+                eprintln!("Error: apply_patch accepts exactly one argument.");
+                return 2;
+            }
+        }
+    }
 
-    let patch_arg = match args.next() {
+    let patch_arg = match patch_arg {
         Some(arg) => match arg.into_string() {
             Ok(s) => s,
             Err(_) => {
@@ -27,7 +41,9 @@ pub fn run_main() -> i32 {
             match std::io::stdin().read_to_string(&mut buf) {
                 Ok(_) => {
                     if buf.is_empty() {
-                        eprintln!("Usage: apply_patch 'PATCH'\n       echo 'PATCH' | apply-patch");
+                        eprintln!(
+                            "Usage: apply_patch [--check] [--reverse] 'PATCH'\n       echo 'PATCH' | apply-patch [--check] [--reverse]"
+                        );
                         return 2;
                     }
                     buf
@@ -40,20 +56,51 @@ pub fn run_main() -> i32 {
         }
     };
 
-    // Refuse extra args to avoid ambiguity.
-    if args.next().is_some() {
-        eprintln!("Error: apply_patch accepts exactly one argument.");
-        return 2;
-    }
+    let patch_arg = if reverse {
+        match crate::reverse_patch(&patch_arg) {
+            Ok(reversed) => reversed,
+            Err(err) => {
+                eprintln!("{err}");
+                return 1;
+            }
+        }
+    } else {
+        patch_arg
+    };
 
     let mut stdout = std::io::stdout();
-    let mut stderr = std::io::stderr();
-    match crate::apply_patch(&patch_arg, &mut stdout, &mut stderr) {
-        Ok(()) => {
-            // Flush to ensure output ordering when used in pipelines.
-            let _ = stdout.flush();
-            0
+    let result = if check {
+        run_check(&patch_arg, &mut stdout)
+    } else {
+        let mut stderr = std::io::stderr();
+        crate::apply_patch(&patch_arg, &mut stdout, &mut stderr).is_ok()
+    };
+    if result {
+        // Flush to ensure output ordering when used in pipelines.
+        let _ = stdout.flush();
+        0
+    } else {
+        1
+    }
+}
+
+/// Validates the patch against the current directory without writing to it.
+fn run_check(patch: &str, stdout: &mut impl Write) -> bool {
+    let action = match std::env::current_dir()
+        .map_err(crate::ApplyPatchError::from)
+        .and_then(|cwd| crate::check_patch(patch, &cwd))
+    {
+        Ok(action) => action,
+        Err(err) => {
+            eprintln!("{err}");
+            return false;
+        }
+    };
+    match crate::parse_patch(&action.patch) {
+        Ok(args) => crate::print_check_summary(&args.hunks, stdout).is_ok(),
+        Err(err) => {
+            eprintln!("{err}");
+            false
         }
-        Err(_) => 1,
     }
 }
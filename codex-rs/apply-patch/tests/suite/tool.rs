@@ -104,7 +104,7 @@ fn test_apply_patch_cli_reports_missing_context() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Update File: modify.txt\n@@\n-missing\n+changed\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Failed to find expected lines in modify.txt:\nmissing\n");
+        .stderr("Failed to find expected lines in modify.txt (hunk 1):\nmissing\n");
     assert_eq!(fs::read_to_string(&target_path)?, "line1\nline2\n");
 
     Ok(())
@@ -214,7 +214,7 @@ fn test_apply_patch_cli_rejects_invalid_hunk_header() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Frobnicate File: foo\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Invalid patch hunk on line 2: '*** Frobnicate File: foo' is not a valid hunk header. Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'\n");
+        .stderr("Invalid patch hunk on line 2: '*** Frobnicate File: foo' is not a valid hunk header. Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}', '*** Add Binary File: {path}', '*** Replace Binary File: {path}'\n");
 
     Ok(())
 }
@@ -255,3 +255,70 @@ fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow
 
     Ok(())
 }
+
+#[test]
+fn test_apply_patch_cli_check_validates_without_writing() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let modify_path = tmp.path().join("modify.txt");
+    fs::write(&modify_path, "line1\nline2\n")?;
+
+    apply_patch_command(tmp.path())?
+        .arg("--check")
+        .arg("*** Begin Patch\n*** Add File: new.txt\n+hello\n*** Update File: modify.txt\n@@\n-line2\n+changed\n*** End Patch")
+        .assert()
+        .success()
+        .stdout(
+            "Patch applies cleanly. The following files would be updated:\nA new.txt\nM modify.txt\n",
+        );
+    assert_eq!(fs::read_to_string(&modify_path)?, "line1\nline2\n");
+    assert!(!tmp.path().join("new.txt").exists());
+
+    apply_patch_command(tmp.path())?
+        .arg("--check")
+        .arg("*** Begin Patch\n*** Update File: modify.txt\n@@\n-missing\n+changed\n*** End Patch")
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(format!(
+            "Failed to find expected lines in {} (hunk 1):\nmissing\n",
+            modify_path.display()
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_apply_patch_cli_reverse_undoes_patch() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let source_path = tmp.path().join("source.txt");
+    fs::write(&source_path, "line1\nline2\nline3\n")?;
+
+    let patch = "*** Begin Patch\n*** Add File: new.txt\n+hello\n*** Update File: source.txt\n*** Move to: renamed.txt\n@@\n line1\n-line2\n+changed\n*** End Patch";
+    run_apply_patch_in_dir(tmp.path(), patch)?.success();
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("renamed.txt"))?,
+        "line1\nchanged\nline3\n"
+    );
+
+    apply_patch_command(tmp.path())?
+        .args(["--reverse", patch])
+        .assert()
+        .success()
+        .stdout("Success. Updated the following files:\nM source.txt\nD new.txt\n");
+    assert_eq!(fs::read_to_string(&source_path)?, "line1\nline2\nline3\n");
+    assert!(!tmp.path().join("renamed.txt").exists());
+    assert!(!tmp.path().join("new.txt").exists());
+
+    apply_patch_command(tmp.path())?
+        .args([
+            "--reverse",
+            "*** Begin Patch\n*** Delete File: source.txt\n*** End Patch",
+        ])
+        .assert()
+        .failure()
+        .stderr(
+            "cannot reverse the change to source.txt: the patch does not record its previous contents\n",
+        );
+
+    Ok(())
+}
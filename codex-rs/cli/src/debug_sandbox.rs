@@ -140,7 +140,7 @@ async fn run_command_under_sandbox(
 
             let policy_str = match &config.sandbox_policy {
                 codex_core::protocol::SandboxPolicy::DangerFullAccess => "workspace-write",
-                codex_core::protocol::SandboxPolicy::ReadOnly => "read-only",
+                codex_core::protocol::SandboxPolicy::ReadOnly { .. } => "read-only",
                 codex_core::protocol::SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
             };
 
@@ -222,6 +222,7 @@ async fn run_command_under_sandbox(
                 cwd,
                 &config.sandbox_policy,
                 sandbox_policy_cwd.as_path(),
+                &config.sandbox_limits,
                 stdio_policy,
                 env,
             )
@@ -0,0 +1,94 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::ValueEnum;
+use codex_core::RolloutRecorder;
+use codex_core::TranscriptFormat;
+use codex_core::TranscriptOptions;
+use codex_core::config::find_codex_home;
+use codex_core::find_conversation_path_by_id_str;
+use codex_core::render_transcript;
+
+/// Export a recorded session as a Markdown, HTML or JSON transcript.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCommand {
+    /// Session id (UUID) or path to a rollout `.jsonl` file.
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+
+    /// Format of the transcript.
+    #[arg(long = "format", value_enum, default_value_t = ExportFormat::Md)]
+    pub format: ExportFormat,
+
+    /// Write the transcript to FILE instead of stdout.
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Replace the output of commands, patches and tool calls with a
+    /// placeholder. The calls themselves are still listed.
+    #[arg(long = "redact-output", default_value_t = false)]
+    pub redact_output: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Markdown.
+    #[value(alias = "markdown")]
+    Md,
+    /// A standalone HTML page.
+    Html,
+    /// Structured JSON.
+    Json,
+}
+
+impl From<ExportFormat> for TranscriptFormat {
+    fn from(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Md => TranscriptFormat::Markdown,
+            ExportFormat::Html => TranscriptFormat::Html,
+            ExportFormat::Json => TranscriptFormat::Json,
+        }
+    }
+}
+
+pub async fn run_export_command(cmd: ExportCommand) -> Result<()> {
+    let ExportCommand {
+        session,
+        format,
+        output,
+        redact_output,
+    } = cmd;
+
+    let path = resolve_session_path(&session).await?;
+    let history = RolloutRecorder::get_rollout_history(&path)
+        .await
+        .with_context(|| format!("failed to read session {}", path.display()))?;
+    let options = TranscriptOptions {
+        redact_tool_output: redact_output,
+    };
+    let transcript = render_transcript(&history.get_rollout_items(), format.into(), &options);
+
+    match output {
+        Some(output) => std::fs::write(&output, transcript)
+            .with_context(|| format!("failed to write {}", output.display()))?,
+        None => print!("{transcript}"),
+    }
+    Ok(())
+}
+
+async fn resolve_session_path(session: &str) -> Result<PathBuf> {
+    let path = Path::new(session);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    match find_conversation_path_by_id_str(&codex_home, session).await? {
+        Some(path) => Ok(path),
+        None => bail!("No saved session found with ID {session}."),
+    }
+}
@@ -21,16 +21,22 @@ use codex_exec::Cli as ExecCli;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_tui::AppExitInfo;
 use codex_tui::Cli as TuiCli;
+use codex_tui::ResumeSearchArgs;
 use codex_tui::update_action::UpdateAction;
 use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod export_cmd;
 mod mcp_cmd;
+mod sessions_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::export_cmd::ExportCommand;
+use crate::export_cmd::run_export_command;
 use crate::mcp_cmd::McpCli;
+use crate::sessions_cmd::SessionsCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -100,6 +106,12 @@ enum Subcommand {
     /// Resume a previous interactive session (picker by default; use --last to continue the most recent).
     Resume(ResumeCommand),
 
+    /// Export a recorded session as a Markdown, HTML or JSON transcript.
+    Export(ExportCommand),
+
+    /// Manage recorded sessions.
+    Sessions(SessionsCli),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -134,6 +146,9 @@ struct ResumeCommand {
     #[arg(long = "last", default_value_t = false, conflicts_with = "session_id")]
     last: bool,
 
+    #[clap(flatten)]
+    search: ResumeSearchArgs,
+
     #[clap(flatten)]
     config_overrides: TuiCli,
 }
@@ -448,6 +463,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         Some(Subcommand::Resume(ResumeCommand {
             session_id,
             last,
+            search,
             config_overrides,
         })) => {
             interactive = finalize_resume_interactive(
@@ -455,11 +471,22 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 root_config_overrides.clone(),
                 session_id,
                 last,
+                search,
                 config_overrides,
             );
             let exit_info = codex_tui::run_main(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Export(export_cli)) => {
+            run_export_command(export_cli).await?;
+        }
+        Some(Subcommand::Sessions(mut sessions_cli)) => {
+            prepend_config_flags(
+                &mut sessions_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            sessions_cli.run().await?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -611,6 +638,7 @@ fn finalize_resume_interactive(
     root_config_overrides: CliConfigOverrides,
     session_id: Option<String>,
     last: bool,
+    search: ResumeSearchArgs,
     resume_cli: TuiCli,
 ) -> TuiCli {
     // Start with the parsed interactive CLI so resume shares the same
@@ -619,6 +647,7 @@ fn finalize_resume_interactive(
     interactive.resume_picker = resume_session_id.is_none() && !last;
     interactive.resume_last = last;
     interactive.resume_session_id = resume_session_id;
+    interactive.resume_search = search.into_search();
 
     // Merge resume-scoped flags and overrides with highest precedence.
     merge_resume_cli_flags(&mut interactive, resume_cli);
@@ -702,13 +731,21 @@ mod tests {
         let Subcommand::Resume(ResumeCommand {
             session_id,
             last,
+            search,
             config_overrides: resume_cli,
         }) = subcommand.expect("resume present")
         else {
             unreachable!()
         };
 
-        finalize_resume_interactive(interactive, root_overrides, session_id, last, resume_cli)
+        finalize_resume_interactive(
+            interactive,
+            root_overrides,
+            session_id,
+            last,
+            search,
+            resume_cli,
+        )
     }
 
     fn sample_exit_info(conversation: Option<&str>) -> AppExitInfo {
@@ -793,6 +830,35 @@ mod tests {
         assert_eq!(interactive.resume_session_id.as_deref(), Some("1234"));
     }
 
+    #[test]
+    fn resume_find_flags_populate_filter() {
+        let interactive = finalize_from_args(
+            [
+                "codex",
+                "resume",
+                "--find",
+                "flaky migration",
+                "--find-branch",
+                "main",
+                "--find-model",
+                "gpt-5-codex",
+                "--since",
+                "2025-01-02",
+            ]
+            .as_ref(),
+        );
+        assert!(interactive.resume_picker);
+        let search = interactive.resume_search;
+        assert_eq!(search.query.as_deref(), Some("flaky migration"));
+        assert_eq!(search.git_branch.as_deref(), Some("main"));
+        assert_eq!(search.model.as_deref(), Some("gpt-5-codex"));
+        assert_eq!(
+            search.created_after.map(|ts| ts.to_rfc3339()),
+            Some("2025-01-02T00:00:00+00:00".to_string())
+        );
+        assert_eq!(search.created_before, None);
+    }
+
     #[test]
     fn resume_merges_option_flags_and_full_auto() {
         let interactive = finalize_from_args(
@@ -261,6 +261,7 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        sampling: None,
     };
 
     servers.insert(name.clone(), new_entry);
@@ -726,6 +727,7 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
             "tool_timeout_sec": server
                 .tool_timeout_sec
                 .map(|timeout| timeout.as_secs_f64()),
+            "sampling_token_budget": server.sampling.map(|sampling| sampling.token_budget),
         }))?;
         println!("{output}");
         return Ok(());
@@ -753,6 +755,9 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
         let disabled_tools_display = format_tool_list(&server.disabled_tools);
         println!("  disabled_tools: {disabled_tools_display}");
     }
+    if let Some(sampling) = server.sampling {
+        println!("  sampling_token_budget: {}", sampling.token_budget);
+    }
     match &server.transport {
         McpServerTransportConfig::Stdio {
             command,
@@ -0,0 +1,135 @@
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::prune_sessions;
+
+/// Manage recorded sessions.
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    /// Remove sessions and history entries that exceed the `[retention]`
+    /// limits in config.toml.
+    Prune(PruneArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct PruneArgs {
+    /// List what would be removed without deleting anything.
+    #[arg(long = "dry-run", default_value_t = false)]
+    pub dry_run: bool,
+}
+
+impl SessionsCli {
+    pub async fn run(self) -> Result<()> {
+        let SessionsCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            SessionsSubcommand::Prune(args) => run_prune(&config_overrides, args).await,
+        }
+    }
+}
+
+async fn run_prune(config_overrides: &CliConfigOverrides, args: PruneArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+        .await
+        .context("failed to load configuration")?;
+
+    let retention = &config.retention;
+    if retention.max_age_days.is_none()
+        && retention.max_total_bytes.is_none()
+        && config.history.max_bytes.is_none()
+    {
+        if retention.keep_last_per_cwd.is_some() {
+            println!(
+                "Nothing to prune: `keep_last_per_cwd` only exempts sessions from the other limits. Set `max_age_days` or `max_total_bytes` under [retention] in config.toml."
+            );
+        } else {
+            println!(
+                "No retention limits configured. Set `max_age_days` or `max_total_bytes` under [retention] in config.toml."
+            );
+        }
+        return Ok(());
+    }
+
+    let report = prune_sessions(&config, args.dry_run)
+        .await
+        .context("failed to prune sessions")?;
+
+    let verb = if args.dry_run {
+        "Would remove"
+    } else {
+        "Removed"
+    };
+    if args.dry_run {
+        for session in &report.removed_sessions {
+            println!(
+                "{}\t{}",
+                format_bytes(session.bytes),
+                session.path.display()
+            );
+        }
+    }
+    println!(
+        "{verb} {} session(s) ({}); {} session(s) ({}) kept.",
+        report.removed_sessions.len(),
+        format_bytes(report.removed_session_bytes()),
+        report.kept_sessions,
+        format_bytes(report.kept_bytes),
+    );
+    if report.removed_history_entries > 0 {
+        println!(
+            "{verb} {} history entr{} ({}).",
+            report.removed_history_entries,
+            if report.removed_history_entries == 1 {
+                "y"
+            } else {
+                "ies"
+            },
+            format_bytes(report.removed_history_bytes),
+        );
+    }
+    Ok(())
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+}
@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use serde_json::json;
+use tempfile::TempDir;
+
+const SESSION_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_rollout(codex_home: &Path) -> Result<()> {
+    let dir = codex_home.join("sessions/2025/01/02");
+    std::fs::create_dir_all(&dir)?;
+    let lines = [
+        json!({
+            "timestamp": "2025-01-02T03:04:05.000Z",
+            "type": "session_meta",
+            "payload": {
+                "id": SESSION_ID,
+                "timestamp": "2025-01-02T03:04:05.000Z",
+                "cwd": "/work/repo",
+                "originator": "codex_cli_rs",
+                "cli_version": "1.2.3",
+                "instructions": null,
+                "source": "cli",
+                "model_provider": "openai"
+            }
+        }),
+        json!({
+            "timestamp": "2025-01-02T03:04:06.000Z",
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": "list the files" }
+        }),
+        json!({
+            "timestamp": "2025-01-02T03:04:07.000Z",
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": "{\"command\":[\"ls\"]}",
+                "call_id": "call-1"
+            }
+        }),
+        json!({
+            "timestamp": "2025-01-02T03:04:08.000Z",
+            "type": "response_item",
+            "payload": {
+                "type": "function_call_output",
+                "call_id": "call-1",
+                "output": "{\"output\":\"Cargo.toml\\n\",\"metadata\":{\"exit_code\":0,\"duration_seconds\":0.1}}"
+            }
+        }),
+        json!({
+            "timestamp": "2025-01-02T03:04:09.000Z",
+            "type": "event_msg",
+            "payload": { "type": "agent_message", "message": "There is one file." }
+        }),
+    ];
+    let contents: Vec<String> = lines.iter().map(ToString::to_string).collect();
+    std::fs::write(
+        dir.join(format!("rollout-2025-01-02T03-04-05-{SESSION_ID}.jsonl")),
+        contents.join("\n") + "\n",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn export_by_session_id_renders_markdown() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    codex_command(codex_home.path())?
+        .args(["export", SESSION_ID])
+        .assert()
+        .success()
+        .stdout(contains(format!("# Codex session {SESSION_ID}")))
+        .stdout(contains("## User\n\nlist the files"))
+        .stdout(contains("**Command** (exit code 0)"))
+        .stdout(contains("Cargo.toml"))
+        .stdout(contains("## Codex\n\nThere is one file."));
+
+    Ok(())
+}
+
+#[test]
+fn export_redacts_output_and_writes_file() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+    let output = codex_home.path().join("transcript.html");
+
+    codex_command(codex_home.path())?
+        .args([
+            "export",
+            SESSION_ID,
+            "--format",
+            "html",
+            "--redact-output",
+            "-o",
+        ])
+        .arg(&output)
+        .assert()
+        .success();
+
+    let html = std::fs::read_to_string(&output)?;
+    assert!(html.contains("$ ls"));
+    assert!(html.contains("[output redacted]"));
+    assert!(!html.contains("Cargo.toml"));
+
+    Ok(())
+}
+
+#[test]
+fn export_unknown_session_fails() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .args(["export", SESSION_ID])
+        .assert()
+        .failure()
+        .stderr(contains("No saved session found"));
+
+    Ok(())
+}
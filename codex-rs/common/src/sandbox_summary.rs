@@ -1,14 +1,25 @@
 use codex_core::protocol::SandboxPolicy;
 
 pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
+    let mut summary = summarize_access(sandbox_policy);
+    if !sandbox_policy.has_full_disk_read_access() {
+        summary.push_str(" (read access restricted)");
+    }
+    summary
+}
+
+fn summarize_access(sandbox_policy: &SandboxPolicy) -> String {
     match sandbox_policy {
         SandboxPolicy::DangerFullAccess => "danger-full-access".to_string(),
-        SandboxPolicy::ReadOnly => "read-only".to_string(),
+        SandboxPolicy::ReadOnly { .. } => "read-only".to_string(),
         SandboxPolicy::WorkspaceWrite {
             writable_roots,
             network_access,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
+            allowed_hosts,
+            allowed_cidrs,
+            ..
         } => {
             let mut summary = "workspace-write".to_string();
 
@@ -29,6 +40,13 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             summary.push_str(&format!(" [{}]", writable_entries.join(", ")));
             if *network_access {
                 summary.push_str(" (network access enabled)");
+            } else if !allowed_hosts.is_empty() || !allowed_cidrs.is_empty() {
+                let allowed: Vec<&str> = allowed_hosts
+                    .iter()
+                    .chain(allowed_cidrs)
+                    .map(String::as_str)
+                    .collect();
+                summary.push_str(&format!(" (network allowed to {})", allowed.join(", ")));
             }
             summary
         }
@@ -2,6 +2,7 @@ use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::protocol::FileChange;
+use crate::protocol::FileMode;
 use crate::protocol::ReviewDecision;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
@@ -39,10 +40,27 @@ pub(crate) async fn apply_patch(
     call_id: &str,
     action: ApplyPatchAction,
 ) -> InternalApplyPatchInvocation {
+    let changes = convert_apply_patch_to_protocol(&action);
+    if let Err(reason) = sess
+        .hooks()
+        .pre_patch(
+            sess.hook_scope(turn_context),
+            &turn_context.sub_id,
+            call_id,
+            &changes,
+        )
+        .await
+    {
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            format!("patch rejected by hook: {reason}"),
+        )));
+    }
+
     match assess_patch_safety(
         &action,
         turn_context.approval_policy,
         &turn_context.sandbox_policy,
+        &turn_context.protected_paths,
         &turn_context.cwd,
     ) {
         SafetyCheck::AutoApprove {
@@ -52,7 +70,7 @@ pub(crate) async fn apply_patch(
             action,
             user_explicitly_approved_this_action: user_explicitly_approved,
         }),
-        SafetyCheck::AskUser => {
+        SafetyCheck::AskUser { reason } => {
             // Compute a readable summary of path changes to include in the
             // approval request so the user can make an informed decision.
             //
@@ -61,13 +79,7 @@ pub(crate) async fn apply_patch(
             // that similar patches can be auto-approved in the future during
             // this session.
             let rx_approve = sess
-                .request_patch_approval(
-                    turn_context,
-                    call_id.to_owned(),
-                    convert_apply_patch_to_protocol(&action),
-                    None,
-                    None,
-                )
+                .request_patch_approval(turn_context, call_id.to_owned(), changes, reason, None)
                 .await;
             match rx_approve.await.unwrap_or_default() {
                 ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
@@ -105,10 +117,22 @@ pub(crate) fn convert_apply_patch_to_protocol(
             ApplyPatchFileChange::Update {
                 unified_diff,
                 move_path,
-                new_content: _new_content,
+                new_mode,
+                ..
             } => FileChange::Update {
                 unified_diff: unified_diff.clone(),
                 move_path: move_path.clone(),
+                new_mode: new_mode.map(|mode| match mode {
+                    codex_apply_patch::FileMode::Regular => FileMode::Regular,
+                    codex_apply_patch::FileMode::Executable => FileMode::Executable,
+                }),
+            },
+            ApplyPatchFileChange::Binary {
+                previous_content,
+                content,
+            } => FileChange::Binary {
+                old_size: previous_content.as_ref().map(|bytes| bytes.len() as u64),
+                new_size: content.len() as u64,
             },
         };
         result.insert(path.clone(), protocol_change);
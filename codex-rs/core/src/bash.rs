@@ -1,3 +1,4 @@
+use codex_execpolicy::Redirection;
 use tree_sitter::Node;
 use tree_sitter::Parser;
 use tree_sitter::Tree;
@@ -22,6 +23,32 @@ pub fn try_parse_shell(shell_lc_arg: &str) -> Option<Tree> {
 /// (parentheses, redirections, substitutions, control flow, etc.). Otherwise
 /// returns `None`.
 pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<Vec<Vec<String>>> {
+    let commands = parse_commands_sequence(tree, src, false)?;
+    Some(commands.into_iter().map(|command| command.argv).collect())
+}
+
+/// Like [`try_parse_word_only_commands_sequence`], but additionally accepts
+/// redirections to and from plain file names (`> out.txt`, `2>> log`,
+/// `< in.txt`, `2>&1`, ...). Each command is returned together with the file
+/// redirections that apply to it. Heredocs, here-strings and redirections to
+/// expanded paths are still rejected.
+pub fn try_parse_commands_with_redirections(tree: &Tree, src: &str) -> Option<Vec<ShellCommand>> {
+    parse_commands_sequence(tree, src, true)
+}
+
+/// A plain command from a shell script and the file redirections that apply
+/// to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellCommand {
+    pub argv: Vec<String>,
+    pub redirections: Vec<Redirection>,
+}
+
+fn parse_commands_sequence(
+    tree: &Tree,
+    src: &str,
+    allow_redirections: bool,
+) -> Option<Vec<ShellCommand>> {
     if tree.root_node().has_error() {
         return None;
     }
@@ -42,8 +69,19 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
         "raw_string",
         "number",
     ];
+    const REDIRECTION_KINDS: &[&str] =
+        &["redirected_statement", "file_redirect", "file_descriptor"];
     // Allow only safe punctuation / operator tokens; anything else causes reject.
     const ALLOWED_PUNCT_TOKENS: &[&str] = &["&&", "||", ";", "|", "\"", "'"];
+    const REDIRECTION_TOKENS: &[&str] = &["<", ">", ">>", ">|", "&>", "&>>", ">&", "<&"];
+
+    let is_allowed_kind = |kind: &str| {
+        ALLOWED_KINDS.contains(&kind) || (allow_redirections && REDIRECTION_KINDS.contains(&kind))
+    };
+    let is_allowed_token = |kind: &str| {
+        ALLOWED_PUNCT_TOKENS.contains(&kind)
+            || (allow_redirections && REDIRECTION_TOKENS.contains(&kind))
+    };
 
     let root = tree.root_node();
     let mut cursor = root.walk();
@@ -52,18 +90,23 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
     while let Some(node) = stack.pop() {
         let kind = node.kind();
         if node.is_named() {
-            if !ALLOWED_KINDS.contains(&kind) {
+            if !is_allowed_kind(kind) {
                 return None;
             }
             if kind == "command" {
                 command_nodes.push(node);
             }
+            // A bare redirection such as `> out.txt` truncates a file without
+            // running any command; reject it rather than silently dropping it.
+            if kind == "redirected_statement" && node.child_by_field_name("body").is_none() {
+                return None;
+            }
         } else {
             // Reject any punctuation / operator tokens that are not explicitly allowed.
-            if kind.chars().any(|c| "&;|".contains(c)) && !ALLOWED_PUNCT_TOKENS.contains(&kind) {
+            if kind.chars().any(|c| "&;|".contains(c)) && !is_allowed_token(kind) {
                 return None;
             }
-            if !(ALLOWED_PUNCT_TOKENS.contains(&kind) || kind.trim().is_empty()) {
+            if !(is_allowed_token(kind) || kind.trim().is_empty()) {
                 // If it's a quote token or operator it's allowed above; we also allow whitespace tokens.
                 // Any other punctuation like parentheses, braces, redirects, backticks, etc are rejected.
                 return None;
@@ -79,11 +122,9 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
 
     let mut commands = Vec::new();
     for node in command_nodes {
-        if let Some(words) = parse_plain_command_from_node(node, src) {
-            commands.push(words);
-        } else {
-            return None;
-        }
+        let argv = parse_plain_command_from_node(node, src)?;
+        let redirections = parse_redirections_for_command(node, src)?;
+        commands.push(ShellCommand { argv, redirections });
     }
     Some(commands)
 }
@@ -120,6 +161,61 @@ pub fn parse_shell_lc_plain_commands(command: &[String]) -> Option<Vec<Vec<Strin
     try_parse_word_only_commands_sequence(&tree, script)
 }
 
+/// Returns the sequence of commands, with their file redirections, within a
+/// `bash -lc "..."` or `zsh -lc "..."` invocation when the script only
+/// contains word-only commands joined by safe operators.
+pub fn parse_shell_lc_commands_with_redirections(command: &[String]) -> Option<Vec<ShellCommand>> {
+    let (_, script) = extract_bash_command(command)?;
+
+    let tree = try_parse_shell(script)?;
+    try_parse_commands_with_redirections(&tree, script)
+}
+
+/// Collects the file redirections of every `redirected_statement` enclosing
+/// `cmd`. tree-sitter-bash may attach a trailing redirection to a whole
+/// pipeline or list (`a | b > out`), so it is conservatively applied to every
+/// command in the statement's body.
+fn parse_redirections_for_command(cmd: Node, src: &str) -> Option<Vec<Redirection>> {
+    let mut redirections = Vec::new();
+    let mut ancestor = cmd.parent();
+    while let Some(node) = ancestor {
+        if node.kind() == "redirected_statement" {
+            let mut cursor = node.walk();
+            for redirect in node.children_by_field_name("redirect", &mut cursor) {
+                if let Some(redirection) = parse_file_redirect(redirect, src)? {
+                    redirections.push(redirection);
+                }
+            }
+        }
+        ancestor = node.parent();
+    }
+    Some(redirections)
+}
+
+/// Returns `Some(None)` for redirections between file descriptors (`2>&1`,
+/// `>&-`), which do not touch the filesystem, and `None` if the redirection
+/// is not understood.
+fn parse_file_redirect(redirect: Node, src: &str) -> Option<Option<Redirection>> {
+    if redirect.kind() != "file_redirect" {
+        return None;
+    }
+    let mut cursor = redirect.walk();
+    let operator = redirect
+        .children(&mut cursor)
+        .find(|child| !child.is_named())?
+        .kind();
+    let destination = redirect.child_by_field_name("destination")?;
+    let target = parse_word_from_node(destination, src)?;
+    match operator {
+        "<" => Some(Some(Redirection::read(&target))),
+        ">" | ">>" | ">|" | "&>" | "&>>" => Some(Some(Redirection::write(&target))),
+        ">&" | "<&" if destination.kind() == "number" || target == "-" => Some(None),
+        // `>& file` is an alias for `&> file`.
+        ">&" => Some(Some(Redirection::write(&target))),
+        _ => None,
+    }
+}
+
 fn parse_plain_command_from_node(cmd: tree_sitter::Node, src: &str) -> Option<Vec<String>> {
     if cmd.kind() != "command" {
         return None;
@@ -135,37 +231,39 @@ fn parse_plain_command_from_node(cmd: tree_sitter::Node, src: &str) -> Option<Ve
                 }
                 words.push(word_node.utf8_text(src.as_bytes()).ok()?.to_owned());
             }
-            "word" | "number" => {
-                words.push(child.utf8_text(src.as_bytes()).ok()?.to_owned());
-            }
-            "string" => {
-                if child.child_count() == 3
-                    && child.child(0)?.kind() == "\""
-                    && child.child(1)?.kind() == "string_content"
-                    && child.child(2)?.kind() == "\""
-                {
-                    words.push(child.child(1)?.utf8_text(src.as_bytes()).ok()?.to_owned());
-                } else {
-                    return None;
-                }
-            }
-            "raw_string" => {
-                let raw_string = child.utf8_text(src.as_bytes()).ok()?;
-                let stripped = raw_string
-                    .strip_prefix('\'')
-                    .and_then(|s| s.strip_suffix('\''));
-                if let Some(s) = stripped {
-                    words.push(s.to_owned());
-                } else {
-                    return None;
-                }
-            }
-            _ => return None,
+            _ => words.push(parse_word_from_node(child, src)?),
         }
     }
     Some(words)
 }
 
+/// Returns the literal value of a word, number or quoted string without
+/// expansions.
+fn parse_word_from_node(node: Node, src: &str) -> Option<String> {
+    match node.kind() {
+        "word" | "number" => Some(node.utf8_text(src.as_bytes()).ok()?.to_owned()),
+        "string" => {
+            if node.child_count() == 3
+                && node.child(0)?.kind() == "\""
+                && node.child(1)?.kind() == "string_content"
+                && node.child(2)?.kind() == "\""
+            {
+                Some(node.child(1)?.utf8_text(src.as_bytes()).ok()?.to_owned())
+            } else {
+                None
+            }
+        }
+        "raw_string" => {
+            let raw_string = node.utf8_text(src.as_bytes()).ok()?;
+            raw_string
+                .strip_prefix('\'')
+                .and_then(|s| s.strip_suffix('\''))
+                .map(str::to_owned)
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,6 +350,61 @@ mod tests {
         assert!(parse_seq("ls &&").is_none());
     }
 
+    fn parse_with_redirections(src: &str) -> Option<Vec<ShellCommand>> {
+        let tree = try_parse_shell(src)?;
+        try_parse_commands_with_redirections(&tree, src)
+    }
+
+    fn shell_command(argv: &[&str], redirections: Vec<Redirection>) -> ShellCommand {
+        ShellCommand {
+            argv: argv.iter().map(ToString::to_string).collect(),
+            redirections,
+        }
+    }
+
+    #[test]
+    fn accepts_file_redirections() {
+        // The trailing `< in.txt` is attached to the whole `&&` list by the
+        // grammar, so every command in it is treated as reading the file.
+        let cmds =
+            parse_with_redirections("rg foo 2>&1 | head -n 3 >> log && wc -l < in.txt").unwrap();
+        assert_eq!(
+            cmds,
+            vec![
+                shell_command(
+                    &["rg", "foo"],
+                    vec![Redirection::write("log"), Redirection::read("in.txt")]
+                ),
+                shell_command(
+                    &["head", "-n", "3"],
+                    vec![Redirection::write("log"), Redirection::read("in.txt")]
+                ),
+                shell_command(&["wc", "-l"], vec![Redirection::read("in.txt")]),
+            ]
+        );
+
+        let cmds = parse_with_redirections("ls 2>/dev/null &> 'all out' >& -").unwrap();
+        assert_eq!(
+            cmds,
+            vec![shell_command(
+                &["ls"],
+                vec![
+                    Redirection::write("/dev/null"),
+                    Redirection::write("all out"),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_redirections() {
+        assert!(parse_with_redirections("> out.txt").is_none());
+        assert!(parse_with_redirections("ls > $HOME/out.txt").is_none());
+        assert!(parse_with_redirections("cat <<EOF\nhi\nEOF").is_none());
+        assert!(parse_with_redirections("cat <<< hi").is_none());
+        assert!(parse_with_redirections("(ls) > out.txt").is_none());
+    }
+
     #[test]
     fn parse_zsh_lc_plain_commands() {
         let command = vec!["zsh".to_string(), "-lc".to_string(), "ls".to_string()];
@@ -251,6 +251,8 @@ impl ModelClient {
             include,
             prompt_cache_key: Some(self.conversation_id.to_string()),
             text,
+            max_output_tokens: prompt.max_output_tokens,
+            temperature: prompt.temperature,
         };
 
         let mut payload_json = serde_json::to_value(&payload)?;
@@ -46,6 +46,16 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Optional cap on the number of tokens the model may generate.
+    pub(crate) max_output_tokens: Option<u64>,
+
+    /// Optional sampling temperature, for models that accept one.
+    pub(crate) temperature: Option<f64>,
+
+    /// Sequences that end the completion. Only the Chat Completions API
+    /// accepts them; the Responses API ignores this field.
+    pub(crate) stop_sequences: Vec<String>,
 }
 
 impl Prompt {
@@ -281,6 +291,10 @@ pub(crate) struct ResponsesApiRequest<'a> {
     pub(crate) prompt_cache_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) text: Option<TextControls>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) max_output_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) temperature: Option<f64>,
 }
 
 pub(crate) mod tools {
@@ -471,6 +485,8 @@ mod tests {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
             }),
+            max_output_tokens: None,
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -509,6 +525,8 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: Some(text_controls),
+            max_output_tokens: None,
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -545,6 +563,8 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: None,
+            max_output_tokens: None,
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
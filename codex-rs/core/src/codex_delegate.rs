@@ -5,10 +5,12 @@ use async_channel::Receiver;
 use async_channel::Sender;
 use codex_async_utils::OrCancelExt;
 use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
+use codex_protocol::protocol::ElicitationAction;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExecApprovalRequestEvent;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::Submission;
@@ -196,6 +198,41 @@ async fn forward_events(
                 )
                 .await;
             }
+            Event {
+                id: _,
+                msg: EventMsg::ElicitationRequest(event),
+            } => {
+                // Answers go to the sub-agent's own MCP connections, which the
+                // parent cannot reach; decline so the server does not hang.
+                let _ = codex
+                    .submit(Op::ResolveElicitation {
+                        server_name: event.server_name,
+                        request_id: event.id,
+                        decision: ElicitationAction::Decline,
+                        content: None,
+                    })
+                    .await;
+            }
+            // The request was declined above and never surfaced, so there is
+            // no form to close.
+            Event {
+                id: _,
+                msg: EventMsg::ElicitationCancelled(_),
+            } => continue,
+            Event {
+                id: _,
+                msg: EventMsg::SamplingRequest(event),
+            } => {
+                // Same as elicitations: the parent cannot answer for the
+                // sub-agent's MCP connections.
+                let _ = codex
+                    .submit(Op::ResolveSamplingRequest {
+                        server_name: event.server_name,
+                        request_id: event.id,
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await;
+            }
             other => {
                 let _ = tx_sub.send(other).await;
             }
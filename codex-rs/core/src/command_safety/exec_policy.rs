@@ -0,0 +1,469 @@
+use std::ffi::OsString;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_execpolicy::ArgType;
+use codex_execpolicy::ExecCall;
+use codex_execpolicy::ExecvChecker;
+use codex_execpolicy::MatchedExec;
+use codex_execpolicy::PolicyParser;
+use codex_execpolicy::Redirection;
+use codex_execpolicy::ValidExec;
+use codex_protocol::protocol::SandboxPolicy;
+
+use crate::bash::ShellCommand;
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_commands_with_redirections;
+
+/// Outcome of checking a proposed command against the user-configured
+/// `.policy` files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ExecPolicyDecision {
+    /// Every command matched a rule and only touches files the sandbox policy
+    /// allows, so no initial approval is required.
+    Allowed,
+    /// At least one command was explicitly forbidden by a policy.
+    Forbidden { reason: String },
+    /// No policy had an opinion; defer to the regular approval flow.
+    Unmatched,
+}
+
+/// The set of execpolicy files loaded for a session. An empty set never
+/// matches, which preserves the pre-existing approval behavior.
+#[derive(Default)]
+pub(crate) struct ExecPolicy {
+    checkers: Vec<ExecvChecker>,
+}
+
+impl ExecPolicy {
+    /// Parses every file in `paths`. Fails on the first unreadable or
+    /// malformed policy so a typo never silently disables a `forbidden` rule.
+    pub(crate) fn load(paths: &[PathBuf]) -> anyhow::Result<Self> {
+        let mut checkers = Vec::with_capacity(paths.len());
+        for path in paths {
+            let source = std::fs::read_to_string(path).map_err(|e| {
+                anyhow::anyhow!("failed to read exec policy {}: {e}", path.display())
+            })?;
+            let policy = PolicyParser::new(&path.to_string_lossy(), &source)
+                .parse()
+                .map_err(|e| {
+                    anyhow::anyhow!("failed to parse exec policy {}: {e}", path.display())
+                })?;
+            checkers.push(ExecvChecker::new(policy));
+        }
+        Ok(Self { checkers })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.checkers.is_empty()
+    }
+
+    /// Checks `command` (either a plain argv or a `bash -lc "..."` script made
+    /// of plain commands, pipelines, `&&`/`||`/`;` lists and file
+    /// redirections) against every loaded policy. A script is allowed only if
+    /// every command in it is allowed and every redirection stays within the
+    /// folders the sandbox policy can read or write. A script that cannot be
+    /// parsed is forbidden whenever a loaded policy has forbidden rules,
+    /// since it may hide a forbidden command behind `$(...)`, a heredoc or
+    /// similar.
+    pub(crate) fn evaluate(
+        &self,
+        command: &[String],
+        cwd: &Path,
+        sandbox_policy: &SandboxPolicy,
+    ) -> ExecPolicyDecision {
+        if self.is_empty() {
+            return ExecPolicyDecision::Unmatched;
+        }
+
+        let commands = match parse_shell_lc_commands_with_redirections(command) {
+            Some(commands) => commands,
+            None if extract_bash_command(command).is_some() => {
+                return if self.checkers.iter().any(ExecvChecker::has_forbidden_rules) {
+                    ExecPolicyDecision::Forbidden {
+                        reason: "the shell script is too complex to check against the forbidden \
+                                 rules in the exec policy; run the commands separately"
+                            .to_string(),
+                    }
+                } else {
+                    ExecPolicyDecision::Unmatched
+                };
+            }
+            None => vec![ShellCommand {
+                argv: command.to_vec(),
+                redirections: Vec::new(),
+            }],
+        };
+        let mut all_allowed = true;
+        for ShellCommand { argv, redirections } in &commands {
+            match self.evaluate_argv(argv, redirections, cwd, sandbox_policy) {
+                ExecPolicyDecision::Allowed => {}
+                forbidden @ ExecPolicyDecision::Forbidden { .. } => return forbidden,
+                ExecPolicyDecision::Unmatched => all_allowed = false,
+            }
+        }
+
+        if all_allowed {
+            ExecPolicyDecision::Allowed
+        } else {
+            ExecPolicyDecision::Unmatched
+        }
+    }
+
+    fn evaluate_argv(
+        &self,
+        argv: &[String],
+        redirections: &[Redirection],
+        cwd: &Path,
+        sandbox_policy: &SandboxPolicy,
+    ) -> ExecPolicyDecision {
+        let Some((program, args)) = argv.split_first() else {
+            return ExecPolicyDecision::Unmatched;
+        };
+        let exec_call = ExecCall {
+            program: program.clone(),
+            args: args.to_vec(),
+        };
+
+        let mut allowed = false;
+        for checker in &self.checkers {
+            match checker.r#match(&exec_call) {
+                Ok(MatchedExec::Forbidden { reason, .. }) => {
+                    return ExecPolicyDecision::Forbidden { reason };
+                }
+                Ok(MatchedExec::Match { exec }) => {
+                    if touches_denied_path(&exec, redirections, cwd, sandbox_policy) {
+                        continue;
+                    }
+                    let (readable_folders, writeable_folders) =
+                        accessible_folders(cwd, sandbox_policy);
+                    let cwd: Option<OsString> = Some(cwd.as_os_str().to_owned());
+                    if checker
+                        .check(exec, &cwd, &readable_folders, &writeable_folders)
+                        .is_ok()
+                        && redirections.iter().all(|redirection| {
+                            checker
+                                .check_redirection(
+                                    redirection,
+                                    &cwd,
+                                    &readable_folders,
+                                    &writeable_folders,
+                                )
+                                .is_ok()
+                        })
+                    {
+                        allowed = true;
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+
+        if allowed {
+            ExecPolicyDecision::Allowed
+        } else {
+            ExecPolicyDecision::Unmatched
+        }
+    }
+}
+
+/// Maps the sandbox policy onto the folder lists expected by
+/// [`ExecvChecker::check`]: reads are limited to the policy's readable roots
+/// (the whole filesystem when it sets none) and writes to its writable roots.
+/// `deny_read` cannot be expressed as folders; see [`touches_denied_path`].
+fn accessible_folders(cwd: &Path, sandbox_policy: &SandboxPolicy) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let filesystem_root = cwd
+        .ancestors()
+        .last()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| cwd.to_path_buf());
+    let readable_folders = sandbox_policy
+        .get_readable_roots_with_cwd(cwd)
+        .unwrap_or_else(|| vec![filesystem_root.clone()]);
+    let writeable_folders = if sandbox_policy.has_full_disk_write_access() {
+        vec![filesystem_root]
+    } else {
+        sandbox_policy
+            .get_writable_roots_with_cwd(cwd)
+            .into_iter()
+            .map(|writable_root| writable_root.root)
+            .collect()
+    };
+    (readable_folders, writeable_folders)
+}
+
+/// Whether any file argument or redirection of `exec` points into one of the
+/// sandbox policy's `deny_read` paths. Such commands are never pre-approved.
+fn touches_denied_path(
+    exec: &ValidExec,
+    redirections: &[Redirection],
+    cwd: &Path,
+    sandbox_policy: &SandboxPolicy,
+) -> bool {
+    let file_args = exec
+        .args
+        .iter()
+        .map(|arg| (&arg.r#type, &arg.value))
+        .chain(exec.opts.iter().map(|opt| (&opt.r#type, &opt.value)))
+        .filter(|(arg_type, _)| matches!(arg_type, ArgType::ReadableFile | ArgType::WriteableFile))
+        .map(|(_, value)| value);
+    file_args
+        .chain(redirections.iter().map(|redirection| &redirection.target))
+        .any(|path| sandbox_policy.is_read_denied(&absolute_path(cwd, path)))
+}
+
+/// Resolves `path` against `cwd` and drops `.`/`..` components without
+/// touching the filesystem.
+fn absolute_path(cwd: &Path, path: &str) -> PathBuf {
+    let mut resolved = PathBuf::new();
+    for component in cwd.join(path).components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            other => resolved.push(other.as_os_str()),
+        }
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    const TEST_POLICY: &str = r#"
+define_program(
+    program="ls",
+    options=[flag("-l")],
+    args=[ARG_RFILES_OR_CWD],
+)
+
+define_program(
+    program="cp",
+    args=[ARG_RFILE, ARG_WFILE],
+)
+
+define_program(
+    program="deploy",
+    forbidden="deploys must go through CI",
+    args=[ARG_UNVERIFIED_VARARGS],
+)
+"#;
+
+    fn vec_str(items: &[&str]) -> Vec<String> {
+        items.iter().map(std::string::ToString::to_string).collect()
+    }
+
+    fn load_test_policy(dir: &TempDir) -> ExecPolicy {
+        let path = dir.path().join("test.policy");
+        std::fs::write(&path, TEST_POLICY).expect("write policy");
+        ExecPolicy::load(&[path]).expect("load policy")
+    }
+
+    #[test]
+    fn empty_policy_never_matches() {
+        let policy = ExecPolicy::default();
+        assert_eq!(
+            policy.evaluate(
+                &vec_str(&["ls"]),
+                Path::new("/"),
+                &SandboxPolicy::DangerFullAccess
+            ),
+            ExecPolicyDecision::Unmatched
+        );
+    }
+
+    #[test]
+    fn matched_command_is_allowed() {
+        let dir = TempDir::new().expect("tempdir");
+        let policy = load_test_policy(&dir);
+        assert_eq!(
+            policy.evaluate(
+                &vec_str(&["ls", "-l", "src"]),
+                dir.path(),
+                &SandboxPolicy::new_read_only_policy()
+            ),
+            ExecPolicyDecision::Allowed
+        );
+        assert_eq!(
+            policy.evaluate(
+                &vec_str(&["bash", "-lc", "ls -l && ls src"]),
+                dir.path(),
+                &SandboxPolicy::new_read_only_policy()
+            ),
+            ExecPolicyDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn forbidden_command_reports_reason() {
+        let dir = TempDir::new().expect("tempdir");
+        let policy = load_test_policy(&dir);
+        assert_eq!(
+            policy.evaluate(
+                &vec_str(&["bash", "-lc", "ls && deploy prod"]),
+                dir.path(),
+                &SandboxPolicy::DangerFullAccess
+            ),
+            ExecPolicyDecision::Forbidden {
+                reason: "deploys must go through CI".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn unparseable_script_fails_closed_when_policy_forbids_commands() {
+        let dir = TempDir::new().expect("tempdir");
+        let policy = load_test_policy(&dir);
+        let command = vec_str(&["bash", "-lc", "deploy prod $(true)"]);
+        assert!(matches!(
+            policy.evaluate(&command, dir.path(), &SandboxPolicy::DangerFullAccess),
+            ExecPolicyDecision::Forbidden { .. }
+        ));
+
+        let path = dir.path().join("allow_only.policy");
+        std::fs::write(
+            &path,
+            "define_program(program=\"ls\", args=[ARG_RFILES_OR_CWD])\n",
+        )
+        .expect("write policy");
+        let allow_only = ExecPolicy::load(&[path]).expect("load policy");
+        assert_eq!(
+            allow_only.evaluate(&command, dir.path(), &SandboxPolicy::DangerFullAccess),
+            ExecPolicyDecision::Unmatched
+        );
+    }
+
+    #[test]
+    fn unmatched_when_any_command_is_unknown() {
+        let dir = TempDir::new().expect("tempdir");
+        let policy = load_test_policy(&dir);
+        assert_eq!(
+            policy.evaluate(
+                &vec_str(&["bash", "-lc", "ls && rm -rf src"]),
+                dir.path(),
+                &SandboxPolicy::DangerFullAccess
+            ),
+            ExecPolicyDecision::Unmatched
+        );
+        assert_eq!(
+            policy.evaluate(
+                &vec_str(&["ls", "-a"]),
+                dir.path(),
+                &SandboxPolicy::DangerFullAccess
+            ),
+            ExecPolicyDecision::Unmatched
+        );
+    }
+
+    #[test]
+    fn writes_outside_writable_roots_are_unmatched() {
+        let dir = TempDir::new().expect("tempdir");
+        let policy = load_test_policy(&dir);
+        let command = vec_str(&["cp", "a.txt", "b.txt"]);
+        assert_eq!(
+            policy.evaluate(&command, dir.path(), &SandboxPolicy::new_read_only_policy()),
+            ExecPolicyDecision::Unmatched
+        );
+        assert_eq!(
+            policy.evaluate(
+                &command,
+                dir.path(),
+                &SandboxPolicy::new_workspace_write_policy()
+            ),
+            ExecPolicyDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn redirections_are_checked_as_writes() {
+        let dir = TempDir::new().expect("tempdir");
+        let policy = load_test_policy(&dir);
+        let command = vec_str(&["bash", "-lc", "ls -l src 2>/dev/null > listing.txt"]);
+        assert_eq!(
+            policy.evaluate(&command, dir.path(), &SandboxPolicy::new_read_only_policy()),
+            ExecPolicyDecision::Unmatched
+        );
+        assert_eq!(
+            policy.evaluate(
+                &command,
+                dir.path(),
+                &SandboxPolicy::new_workspace_write_policy()
+            ),
+            ExecPolicyDecision::Allowed
+        );
+        assert_eq!(
+            policy.evaluate(
+                &vec_str(&["bash", "-lc", "ls src | ls 2>&1 > /dev/null"]),
+                dir.path(),
+                &SandboxPolicy::new_read_only_policy()
+            ),
+            ExecPolicyDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn reads_outside_readable_roots_or_in_deny_read_are_unmatched() {
+        let dir = TempDir::new().expect("tempdir");
+        let policy = load_test_policy(&dir);
+        let outside = TempDir::new().expect("tempdir");
+        let outside_file = outside.path().join("notes.txt");
+        let command = vec_str(&["ls", "-l", &outside_file.to_string_lossy()]);
+        assert_eq!(
+            policy.evaluate(&command, dir.path(), &SandboxPolicy::new_read_only_policy()),
+            ExecPolicyDecision::Allowed
+        );
+
+        let narrowed = SandboxPolicy::ReadOnly {
+            readable_roots: vec![dir.path().join("src")],
+            deny_read: Vec::new(),
+        };
+        assert_eq!(
+            policy.evaluate(&command, dir.path(), &narrowed),
+            ExecPolicyDecision::Unmatched
+        );
+        assert_eq!(
+            policy.evaluate(&vec_str(&["ls", "src"]), dir.path(), &narrowed),
+            ExecPolicyDecision::Allowed
+        );
+
+        let denied = SandboxPolicy::ReadOnly {
+            readable_roots: Vec::new(),
+            deny_read: vec![dir.path().join("secrets")],
+        };
+        assert_eq!(
+            policy.evaluate(&vec_str(&["ls", "src/../secrets/key"]), dir.path(), &denied),
+            ExecPolicyDecision::Unmatched
+        );
+        assert_eq!(
+            policy.evaluate(
+                &vec_str(&["bash", "-lc", "ls src < secrets/key"]),
+                dir.path(),
+                &denied
+            ),
+            ExecPolicyDecision::Unmatched
+        );
+        assert_eq!(
+            policy.evaluate(&vec_str(&["ls", "src"]), dir.path(), &denied),
+            ExecPolicyDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn load_reports_malformed_policy() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("broken.policy");
+        std::fs::write(&path, "define_program(").expect("write policy");
+        let err = ExecPolicy::load(&[path])
+            .err()
+            .expect("malformed policy should fail to load");
+        assert!(
+            err.to_string().contains("failed to parse exec policy"),
+            "unexpected error: {err}"
+        );
+    }
+}
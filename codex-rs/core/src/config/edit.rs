@@ -155,6 +155,15 @@ mod document_helpers {
         {
             entry["disabled_tools"] = array_from_iter(disabled_tools.iter().cloned());
         }
+        if let Some(sampling) = &config.sampling {
+            let mut table = TomlTable::new();
+            table.set_implicit(false);
+            table.insert(
+                "token_budget",
+                value(i64::try_from(sampling.token_budget).unwrap_or(i64::MAX)),
+            );
+            entry["sampling"] = TomlItem::Table(table);
+        }
 
         TomlItem::Table(entry)
     }
@@ -802,6 +811,7 @@ hide_rate_limit_model_nudge = true
                 tool_timeout_sec: None,
                 enabled_tools: Some(vec!["one".to_string(), "two".to_string()]),
                 disabled_tools: None,
+                sampling: None,
             },
         );
 
@@ -823,6 +833,7 @@ hide_rate_limit_model_nudge = true
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: Some(vec!["forbidden".to_string()]),
+                sampling: None,
             },
         );
 
@@ -1,13 +1,20 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::CustomTools;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::HooksConfig;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::ProtectedPath;
 use crate::config::types::ReasoningSummaryFormat;
+use crate::config::types::Redaction;
+use crate::config::types::Retention;
+use crate::config::types::SandboxLimits;
+use crate::config::types::SandboxReadOnly;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
@@ -103,6 +110,18 @@ pub struct Config {
 
     pub sandbox_policy: SandboxPolicy,
 
+    /// Starlark `.policy` files consulted before the approval policy. A
+    /// command matched by a policy runs without an approval prompt, and a
+    /// forbidden command is rejected outright.
+    pub exec_policy_files: Vec<PathBuf>,
+
+    /// Resource limits applied to commands run by the model.
+    pub sandbox_limits: SandboxLimits,
+
+    /// Globs for files that `apply_patch` may only touch with explicit
+    /// approval, or not at all.
+    pub protected_paths: Vec<ProtectedPath>,
+
     /// True if the user passed in an override or set a value in config.toml
     /// for either of approval_policy or sandbox_mode.
     pub did_user_set_custom_approval_policy_or_sandbox_mode: bool,
@@ -199,6 +218,23 @@ pub struct Config {
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
+    /// Limits enforced by `codex sessions prune`.
+    pub retention: Retention,
+
+    /// When true, session rollouts and `history.jsonl` are encrypted at rest
+    /// with a key kept in the OS keyring.
+    pub encrypt_session_data: bool,
+
+    /// Secret redaction applied to tool output before it reaches the model.
+    pub redaction: Redaction,
+
+    /// Command-backed tools declared under `[tools.custom.<name>]`.
+    pub custom_tools: CustomTools,
+
+    /// Hook scripts run before and after tool calls, before patches, at
+    /// session start and at turn completion.
+    pub hooks: HooksConfig,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: UriBasedFileOpener,
@@ -361,6 +397,27 @@ pub async fn load_global_mcp_servers(
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
 }
 
+fn validate_custom_tools(tools: &CustomTools) -> std::io::Result<()> {
+    for (name, tool) in tools {
+        let valid_name = !name.is_empty()
+            && name.len() <= 64
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_name {
+            let message = format!(
+                "tools.custom.{name}: tool names may only contain letters, digits, `_` and `-` (at most 64 characters)"
+            );
+            return Err(std::io::Error::new(ErrorKind::InvalidData, message));
+        }
+        if tool.command.is_empty() {
+            let message = format!("tools.custom.{name}: `command` must not be empty");
+            return Err(std::io::Error::new(ErrorKind::InvalidData, message));
+        }
+    }
+    Ok(())
+}
+
 /// We briefly allowed plain text bearer_token fields in MCP server configs.
 /// We want to warn people who recently added these fields but can remove this after a few months.
 fn ensure_no_inline_bearer_tokens(value: &TomlValue) -> std::io::Result<()> {
@@ -535,6 +592,22 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Sandbox configuration to apply if `sandbox` is `ReadOnly`.
+    pub sandbox_read_only: Option<SandboxReadOnly>,
+
+    /// Resource limits for commands run by the model.
+    pub sandbox_limits: Option<SandboxLimits>,
+
+    /// Files that patches may only touch with explicit approval.
+    #[serde(default)]
+    pub protected_paths: Option<Vec<ProtectedPath>>,
+
+    /// Paths to execpolicy `.policy` files. Relative paths are resolved
+    /// against `CODEX_HOME`, so a checked-out repository cannot approve its
+    /// own commands.
+    #[serde(default)]
+    pub exec_policy_files: Option<Vec<PathBuf>>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
@@ -597,6 +670,22 @@ pub struct ConfigToml {
     #[serde(default)]
     pub history: Option<History>,
 
+    /// Limits enforced by `codex sessions prune` on recorded sessions.
+    #[serde(default)]
+    pub retention: Option<Retention>,
+
+    /// Encrypt session rollouts and `history.jsonl` at rest, with the key held
+    /// in the OS keyring. Defaults to false.
+    pub encrypt_session_data: Option<bool>,
+
+    /// Redact secrets from tool output before it is sent to the model.
+    #[serde(default)]
+    pub redaction: Option<Redaction>,
+
+    /// Hook scripts keyed by lifecycle event.
+    #[serde(default)]
+    pub hooks: Option<HooksConfig>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
@@ -706,6 +795,10 @@ pub struct ToolsToml {
     /// Enable the `view_image` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Command-backed tools, keyed by tool name.
+    #[serde(default)]
+    pub custom: CustomTools,
 }
 
 impl From<ToolsToml> for Tools {
@@ -746,18 +839,35 @@ impl ConfigToml {
             })
             .unwrap_or_default();
         let mut sandbox_policy = match resolved_sandbox_mode {
-            SandboxMode::ReadOnly => SandboxPolicy::new_read_only_policy(),
+            SandboxMode::ReadOnly => match self.sandbox_read_only.as_ref() {
+                Some(SandboxReadOnly {
+                    readable_roots,
+                    deny_read,
+                }) => SandboxPolicy::ReadOnly {
+                    readable_roots: readable_roots.clone(),
+                    deny_read: deny_read.clone(),
+                },
+                None => SandboxPolicy::new_read_only_policy(),
+            },
             SandboxMode::WorkspaceWrite => match self.sandbox_workspace_write.as_ref() {
                 Some(SandboxWorkspaceWrite {
                     writable_roots,
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    allowed_hosts,
+                    allowed_cidrs,
+                    readable_roots,
+                    deny_read,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
+                    allowed_hosts: allowed_hosts.clone(),
+                    allowed_cidrs: allowed_cidrs.clone(),
+                    readable_roots: readable_roots.clone(),
+                    deny_read: deny_read.clone(),
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
@@ -923,18 +1033,8 @@ impl Config {
             }
         };
         let additional_writable_roots: Vec<PathBuf> = additional_writable_roots
-            .into_iter()
-            .map(|path| {
-                let absolute = if path.is_absolute() {
-                    path
-                } else {
-                    resolved_cwd.join(path)
-                };
-                match canonicalize(&absolute) {
-                    Ok(canonical) => canonical,
-                    Err(_) => absolute,
-                }
-            })
+            .iter()
+            .map(|path| resolve_sandbox_path(path, &resolved_cwd))
             .collect();
         let active_project = cfg
             .get_active_project(&resolved_cwd)
@@ -944,6 +1044,20 @@ impl Config {
             policy: mut sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
         } = cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
+        if let SandboxPolicy::ReadOnly {
+            readable_roots,
+            deny_read,
+        }
+        | SandboxPolicy::WorkspaceWrite {
+            readable_roots,
+            deny_read,
+            ..
+        } = &mut sandbox_policy
+        {
+            for path in readable_roots.iter_mut().chain(deny_read.iter_mut()) {
+                *path = resolve_sandbox_path(path, &resolved_cwd);
+            }
+        }
         if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
             for path in additional_writable_roots {
                 if !writable_roots.iter().any(|existing| existing == &path) {
@@ -951,6 +1065,46 @@ impl Config {
                 }
             }
         }
+        // Only the Linux sandbox can enforce a network allowlist; elsewhere it
+        // would silently block everything, so refuse it up front.
+        if cfg!(not(target_os = "linux")) && sandbox_policy.network_allowlist().is_some() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "sandbox_workspace_write.allowed_hosts and allowed_cidrs are only supported on Linux",
+            ));
+        }
+        // Likewise only Landlock can limit reads to `readable_roots`; Seatbelt
+        // enforces `deny_read`, but nothing does on Windows.
+        if cfg!(not(target_os = "linux"))
+            && sandbox_policy
+                .get_readable_roots_with_cwd(&resolved_cwd)
+                .is_some()
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "sandbox readable_roots is only supported on Linux",
+            ));
+        }
+        if cfg!(not(any(target_os = "linux", target_os = "macos")))
+            && !sandbox_policy.get_deny_read_paths().is_empty()
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "sandbox deny_read is only supported on Linux and macOS",
+            ));
+        }
+        let exec_policy_files = cfg
+            .exec_policy_files
+            .unwrap_or_default()
+            .into_iter()
+            .map(|path| {
+                if path.is_relative() {
+                    codex_home.join(path)
+                } else {
+                    path
+                }
+            })
+            .collect();
         let approval_policy = approval_policy_override
             .or(config_profile.approval_policy)
             .or(cfg.approval_policy)
@@ -994,6 +1148,13 @@ impl Config {
 
         let history = cfg.history.unwrap_or_default();
 
+        let custom_tools = cfg
+            .tools
+            .as_ref()
+            .map(|tools| tools.custom.clone())
+            .unwrap_or_default();
+        validate_custom_tools(&custom_tools)?;
+
         let include_apply_patch_tool_flag = features.enabled(Feature::ApplyPatchFreeform);
         let tools_web_search_request = features.enabled(Feature::WebSearchRequest);
         let use_experimental_unified_exec_tool = features.enabled(Feature::UnifiedExec);
@@ -1095,6 +1256,9 @@ impl Config {
             cwd: resolved_cwd,
             approval_policy,
             sandbox_policy,
+            exec_policy_files,
+            sandbox_limits: cfg.sandbox_limits.unwrap_or_default(),
+            protected_paths: cfg.protected_paths.unwrap_or_default(),
             did_user_set_custom_approval_policy_or_sandbox_mode,
             forced_auto_mode_downgraded_on_windows,
             shell_environment_policy,
@@ -1127,6 +1291,11 @@ impl Config {
                 .collect(),
             codex_home,
             history,
+            retention: cfg.retention.unwrap_or_default(),
+            encrypt_session_data: cfg.encrypt_session_data.unwrap_or(false),
+            redaction: cfg.redaction.unwrap_or_default(),
+            custom_tools,
+            hooks: cfg.hooks.unwrap_or_default(),
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
 
@@ -1266,6 +1435,31 @@ pub fn find_codex_home() -> std::io::Result<PathBuf> {
     Ok(p)
 }
 
+/// Resolves a path that bounds the sandbox: a leading `~` stands for the home
+/// directory, relative paths are resolved against `cwd`, `.` and `..` are
+/// dropped, and paths that exist are canonicalized so they match what the
+/// sandbox sees after following symlinks.
+fn resolve_sandbox_path(path: &Path, cwd: &Path) -> PathBuf {
+    let expanded = match path.strip_prefix("~") {
+        Ok(rest) => match home_dir() {
+            Some(home) => home.join(rest),
+            None => path.to_path_buf(),
+        },
+        Err(_) => path.to_path_buf(),
+    };
+    let mut absolute = PathBuf::new();
+    for component in cwd.join(expanded).components() {
+        match component {
+            std::path::Component::ParentDir => {
+                absolute.pop();
+            }
+            std::path::Component::CurDir => {}
+            other => absolute.push(other.as_os_str()),
+        }
+    }
+    canonicalize(&absolute).unwrap_or(absolute)
+}
+
 /// Returns the path to the folder where Codex logs are stored. Does not verify
 /// that the directory exists.
 pub fn log_dir(cfg: &Config) -> std::io::Result<PathBuf> {
@@ -1282,6 +1476,7 @@ mod tests {
     use crate::config::types::HistoryPersistence;
     use crate::config::types::McpServerTransportConfig;
     use crate::config::types::Notifications;
+    use crate::config::types::ProtectedPathAction;
     use crate::features::Feature;
 
     use super::*;
@@ -1377,7 +1572,7 @@ network_access = true  # This should be ignored.
         assert_eq!(
             resolution,
             SandboxPolicyResolution {
-                policy: SandboxPolicy::ReadOnly,
+                policy: SandboxPolicy::new_read_only_policy(),
                 forced_auto_mode_downgraded_on_windows: false,
             }
         );
@@ -1405,7 +1600,7 @@ exclude_slash_tmp = true
             assert_eq!(
                 resolution,
                 SandboxPolicyResolution {
-                    policy: SandboxPolicy::ReadOnly,
+                    policy: SandboxPolicy::new_read_only_policy(),
                     forced_auto_mode_downgraded_on_windows: true,
                 }
             );
@@ -1418,6 +1613,10 @@ exclude_slash_tmp = true
                         network_access: false,
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        allowed_hosts: vec![],
+                        allowed_cidrs: vec![],
+                        readable_roots: vec![],
+                        deny_read: vec![],
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -1433,6 +1632,8 @@ writable_roots = [
 ]
 exclude_tmpdir_env_var = true
 exclude_slash_tmp = true
+allowed_hosts = ["crates.io", "*.registry.example.com"]
+allowed_cidrs = ["10.20.0.0/16"]
 
 [projects."/tmp/test"]
 trust_level = "trusted"
@@ -1450,7 +1651,7 @@ trust_level = "trusted"
             assert_eq!(
                 resolution,
                 SandboxPolicyResolution {
-                    policy: SandboxPolicy::ReadOnly,
+                    policy: SandboxPolicy::new_read_only_policy(),
                     forced_auto_mode_downgraded_on_windows: true,
                 }
             );
@@ -1463,6 +1664,13 @@ trust_level = "trusted"
                         network_access: false,
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        allowed_hosts: vec![
+                            "crates.io".to_string(),
+                            "*.registry.example.com".to_string(),
+                        ],
+                        allowed_cidrs: vec!["10.20.0.0/16".to_string()],
+                        readable_roots: vec![],
+                        deny_read: vec![],
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -1498,7 +1706,7 @@ trust_level = "trusted"
                 "expected workspace-write request to be downgraded on Windows"
             );
             match config.sandbox_policy {
-                SandboxPolicy::ReadOnly => {}
+                SandboxPolicy::ReadOnly { .. } => {}
                 other => panic!("expected read-only policy on Windows, got {other:?}"),
             }
         } else {
@@ -1521,6 +1729,230 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn readable_roots_and_deny_read_are_resolved() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        std::fs::create_dir(cwd.path().join("secrets"))?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+sandbox_mode = "read-only"
+
+[sandbox_read_only]
+readable_roots = ["~/shared", "vendor"]
+deny_read = ["secrets", "./vendor/../.env"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(cwd.path().to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let home = home_dir().expect("home directory");
+        assert_eq!(
+            config.sandbox_policy,
+            SandboxPolicy::ReadOnly {
+                readable_roots: vec![home.join("shared"), cwd.path().join("vendor")],
+                deny_read: vec![
+                    canonicalize(cwd.path().join("secrets"))?,
+                    cwd.path().join(".env"),
+                ],
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn network_allowlist_is_rejected_where_it_cannot_be_enforced() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+allowed_hosts = ["crates.io"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let result = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(codex_home.path().to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        );
+        if cfg!(target_os = "linux") {
+            assert!(result?.sandbox_policy.network_allowlist().is_some());
+        } else if cfg!(target_os = "macos") {
+            let err = result.expect_err("allowlist should be rejected on macOS");
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_restrictions_are_rejected_where_they_cannot_be_enforced() -> std::io::Result<()> {
+        let load = |toml: &str| {
+            let codex_home = TempDir::new()?;
+            let cfg =
+                toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed");
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides {
+                    cwd: Some(codex_home.path().to_path_buf()),
+                    ..Default::default()
+                },
+                codex_home.path().to_path_buf(),
+            )
+        };
+        let readable_roots = load(
+            r#"
+sandbox_mode = "read-only"
+
+[sandbox_read_only]
+readable_roots = ["/srv"]
+"#,
+        );
+        let deny_read = load(
+            r#"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+deny_read = ["/srv/secrets"]
+"#,
+        );
+        if cfg!(target_os = "linux") {
+            readable_roots?;
+            deny_read?;
+        } else if cfg!(target_os = "macos") {
+            let err = readable_roots.expect_err("readable_roots should be rejected on macOS");
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+            deny_read?;
+        } else {
+            let err = readable_roots.expect_err("readable_roots should be rejected");
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+            let err = deny_read.expect_err("deny_read should be rejected");
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn exec_policy_files_resolve_relative_to_codex_home() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let absolute = cwd.path().join("team.policy");
+        let cfg = toml::from_str::<ConfigToml>(&format!(
+            r#"exec_policy_files = ["policies/repo.policy", {absolute:?}]"#
+        ))
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(cwd.path().to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.exec_policy_files,
+            vec![codex_home.path().join("policies/repo.policy"), absolute],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn protected_paths_accept_patterns_and_rules() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+protected_paths = ["migrations/**", { pattern = ".github/**", action = "reject" }]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        assert_eq!(
+            cfg.protected_paths,
+            Some(vec![
+                ProtectedPath {
+                    pattern: "migrations/**".to_string(),
+                    action: ProtectedPathAction::Ask,
+                },
+                ProtectedPath {
+                    pattern: ".github/**".to_string(),
+                    action: ProtectedPathAction::Reject,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn custom_tools_deserialize_and_validate_names() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[tools.custom.lint_file]
+description = "Lint one file."
+command = ["make", "lint-file", "FILE={path}"]
+timeout_ms = 60000
+parameters = { type = "object", properties = { path = { type = "string" } }, required = ["path"] }
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        let tool = config
+            .custom_tools
+            .get("lint_file")
+            .expect("lint_file tool");
+        assert_eq!(tool.command, vec!["make", "lint-file", "FILE={path}"]);
+        assert_eq!(tool.timeout_ms, Some(60_000));
+        assert_eq!(
+            tool.parameters,
+            Some(serde_json::json!({
+                "type": "object",
+                "properties": { "path": { "type": "string" } },
+                "required": ["path"],
+            }))
+        );
+
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[tools.custom."lint file"]
+description = "Lint one file."
+command = ["make", "lint"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("invalid tool name");
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        Ok(())
+    }
+
     #[test]
     fn config_defaults_to_file_cli_auth_store_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -1671,7 +2103,10 @@ trust_level = "trusted"
         )?;
 
         if cfg!(target_os = "windows") {
-            assert!(matches!(config.sandbox_policy, SandboxPolicy::ReadOnly));
+            assert!(matches!(
+                config.sandbox_policy,
+                SandboxPolicy::ReadOnly { .. }
+            ));
             assert!(config.forced_auto_mode_downgraded_on_windows);
         } else {
             assert!(matches!(
@@ -1824,6 +2259,7 @@ trust_level = "trusted"
                 tool_timeout_sec: Some(Duration::from_secs(5)),
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         );
 
@@ -1970,6 +2406,7 @@ bearer_token = "secret"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         )]);
 
@@ -2038,6 +2475,7 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         )]);
 
@@ -2086,6 +2524,7 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         )]);
 
@@ -2132,6 +2571,7 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         )]);
 
@@ -2194,6 +2634,7 @@ startup_timeout_sec = 2.0
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         )]);
         apply_blocking(
@@ -2268,6 +2709,7 @@ X-Auth = "DOCS_AUTH"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         )]);
 
@@ -2295,6 +2737,7 @@ X-Auth = "DOCS_AUTH"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         );
         apply_blocking(
@@ -2360,6 +2803,7 @@ url = "https://example.com/mcp"
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    sampling: None,
                 },
             ),
             (
@@ -2377,6 +2821,7 @@ url = "https://example.com/mcp"
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    sampling: None,
                 },
             ),
         ]);
@@ -2457,6 +2902,7 @@ url = "https://example.com/mcp"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                sampling: None,
             },
         )]);
 
@@ -2499,6 +2945,7 @@ url = "https://example.com/mcp"
                 tool_timeout_sec: None,
                 enabled_tools: Some(vec!["allowed".to_string()]),
                 disabled_tools: Some(vec!["blocked".to_string()]),
+                sampling: None,
             },
         )]);
 
@@ -2865,6 +3312,9 @@ model_verbosity = "high"
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: SandboxPolicy::new_read_only_policy(),
+                exec_policy_files: Vec::new(),
+                sandbox_limits: SandboxLimits::default(),
+                protected_paths: Vec::new(),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 forced_auto_mode_downgraded_on_windows: false,
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
@@ -2879,6 +3329,11 @@ model_verbosity = "high"
                 project_doc_fallback_filenames: Vec::new(),
                 codex_home: fixture.codex_home(),
                 history: History::default(),
+                retention: Retention::default(),
+                encrypt_session_data: false,
+                redaction: Redaction::default(),
+                custom_tools: CustomTools::default(),
+                hooks: HooksConfig::default(),
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
                 hide_agent_reasoning: false,
@@ -2936,6 +3391,9 @@ model_verbosity = "high"
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: AskForApproval::UnlessTrusted,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            exec_policy_files: Vec::new(),
+            sandbox_limits: SandboxLimits::default(),
+            protected_paths: Vec::new(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
@@ -2950,6 +3408,11 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            retention: Retention::default(),
+            encrypt_session_data: false,
+            redaction: Redaction::default(),
+            custom_tools: CustomTools::default(),
+            hooks: HooksConfig::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3022,6 +3485,9 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: AskForApproval::OnFailure,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            exec_policy_files: Vec::new(),
+            sandbox_limits: SandboxLimits::default(),
+            protected_paths: Vec::new(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
@@ -3036,6 +3502,11 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            retention: Retention::default(),
+            encrypt_session_data: false,
+            redaction: Redaction::default(),
+            custom_tools: CustomTools::default(),
+            hooks: HooksConfig::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3094,6 +3565,9 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: AskForApproval::OnFailure,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            exec_policy_files: Vec::new(),
+            sandbox_limits: SandboxLimits::default(),
+            protected_paths: Vec::new(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
@@ -3108,6 +3582,11 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            retention: Retention::default(),
+            encrypt_session_data: false,
+            redaction: Redaction::default(),
+            custom_tools: CustomTools::default(),
+            hooks: HooksConfig::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -4,6 +4,7 @@
 // definitions that do not contain business logic.
 
 use serde::Deserializer;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -43,6 +44,19 @@ pub struct McpServerConfig {
     /// Explicit deny-list of tools. These tools will be removed after applying `enabled_tools`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disabled_tools: Option<Vec<String>>,
+
+    /// When set, the server may ask Codex to sample the model through
+    /// `sampling/createMessage`. Each request still needs the user's approval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<McpSamplingConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct McpSamplingConfig {
+    /// Total tokens (prompt and completion) the server may spend through
+    /// sampling in one session.
+    pub token_budget: u64,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -84,6 +98,8 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             enabled_tools: Option<Vec<String>>,
             #[serde(default)]
             disabled_tools: Option<Vec<String>>,
+            #[serde(default)]
+            sampling: Option<McpSamplingConfig>,
         }
 
         let mut raw = RawMcpServerConfig::deserialize(deserializer)?;
@@ -100,6 +116,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         let enabled = raw.enabled.unwrap_or_else(default_enabled);
         let enabled_tools = raw.enabled_tools.clone();
         let disabled_tools = raw.disabled_tools.clone();
+        let sampling = raw.sampling;
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -153,6 +170,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             enabled,
             enabled_tools,
             disabled_tools,
+            sampling,
         })
     }
 }
@@ -256,11 +274,117 @@ pub struct History {
     /// If true, history entries will not be written to disk.
     pub persistence: HistoryPersistence,
 
-    /// If set, the maximum size of the history file in bytes.
-    /// TODO(mbolin): Not currently honored.
+    /// If set, the maximum size of the history file in bytes. The oldest
+    /// entries are dropped by `codex sessions prune` to stay under it.
     pub max_bytes: Option<usize>,
 }
 
+/// Limits applied by `codex sessions prune` to the recorded session rollouts
+/// in `~/.codex/sessions` and `~/.codex/archived_sessions`. Unset limits are
+/// not enforced.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Retention {
+    /// Remove sessions (and history entries) that were last updated more than
+    /// this many days ago.
+    pub max_age_days: Option<u64>,
+
+    /// Remove the oldest sessions until the rollouts use at most this many
+    /// bytes in total.
+    pub max_total_bytes: Option<u64>,
+
+    /// Always keep the most recent N sessions of each working directory,
+    /// regardless of the other limits.
+    pub keep_last_per_cwd: Option<usize>,
+}
+
+/// A tool declared under `[tools.custom.<name>]` that runs a command through
+/// the same approval and sandbox pipeline as `shell`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomToolConfig {
+    /// Description shown to the model.
+    pub description: String,
+
+    /// Command to run, one element per argument. `{param}` placeholders are
+    /// replaced with the values of the tool arguments.
+    pub command: Vec<String>,
+
+    /// JSON Schema for the tool arguments. Defaults to an object without
+    /// properties.
+    #[serde(default)]
+    pub parameters: Option<serde_json::Value>,
+
+    /// Working directory for the command, relative to the session cwd.
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+
+    /// Timeout for the command in milliseconds.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+
+    /// Let arguments that start with `-` begin a command element. Off by
+    /// default so the model cannot turn a value into an option.
+    #[serde(default)]
+    pub allow_leading_dash: bool,
+}
+
+/// Tools declared in config, keyed by tool name.
+pub type CustomTools = BTreeMap<String, CustomToolConfig>;
+
+/// Hook scripts run at points in the session lifecycle, keyed by event.
+/// Each script receives a JSON payload on stdin; see `docs/config.md#hooks`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct HooksConfig {
+    /// Run before a tool call. May allow, deny or rewrite the arguments.
+    #[serde(default)]
+    pub pre_tool_call: Vec<HookConfig>,
+
+    /// Run after a tool call. May append context to the tool output.
+    #[serde(default)]
+    pub post_tool_call: Vec<HookConfig>,
+
+    /// Run before a patch is applied. May deny the patch.
+    #[serde(default)]
+    pub pre_patch: Vec<HookConfig>,
+
+    /// Run when a session starts.
+    #[serde(default)]
+    pub session_start: Vec<HookConfig>,
+
+    /// Run when a turn completes.
+    #[serde(default)]
+    pub turn_complete: Vec<HookConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct HookConfig {
+    /// Program and arguments to run.
+    pub command: Vec<String>,
+
+    /// Tool names (`*` and `?` wildcards allowed) the hook applies to. Only
+    /// used by tool-call hooks; unset matches every tool.
+    #[serde(default)]
+    pub tools: Option<Vec<String>>,
+
+    /// How long to wait for the hook before giving up, in milliseconds.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Secret redaction applied to tool output (command output, file reads, MCP
+/// results) before it is sent to the model or written to the session rollout.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Redaction {
+    /// Turn the redaction pass on. The built-in detectors cover AWS keys,
+    /// GitHub tokens, PEM private keys and JWTs.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Additional regular expressions whose matches are redacted. If a pattern
+    /// has a capture group named `secret`, only that group is replaced.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
 #[derive(Deserialize, Debug, Copy, Clone, PartialEq, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum HistoryPersistence {
@@ -379,6 +503,19 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Hosts reachable from the sandbox when `network_access` is false.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+    /// CIDR ranges reachable from the sandbox when `network_access` is false.
+    #[serde(default)]
+    pub allowed_cidrs: Vec<String>,
+    /// When non-empty, limits reads to these folders (plus the cwd and the
+    /// writable roots).
+    #[serde(default)]
+    pub readable_roots: Vec<PathBuf>,
+    /// Paths that must not be readable from within the sandbox.
+    #[serde(default)]
+    pub deny_read: Vec<PathBuf>,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -388,6 +525,87 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
             network_access: Some(sandbox_workspace_write.network_access),
             exclude_tmpdir_env_var: Some(sandbox_workspace_write.exclude_tmpdir_env_var),
             exclude_slash_tmp: Some(sandbox_workspace_write.exclude_slash_tmp),
+            allowed_hosts: sandbox_workspace_write.allowed_hosts,
+            allowed_cidrs: sandbox_workspace_write.allowed_cidrs,
+            readable_roots: sandbox_workspace_write.readable_roots,
+            deny_read: sandbox_workspace_write.deny_read,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SandboxReadOnly {
+    /// When non-empty, limits reads to these folders (plus the cwd).
+    #[serde(default)]
+    pub readable_roots: Vec<PathBuf>,
+    /// Paths that must not be readable from within the sandbox.
+    #[serde(default)]
+    pub deny_read: Vec<PathBuf>,
+}
+
+/// Resource limits applied to commands run by the model, configured via
+/// `[sandbox_limits]`. Unset fields are unlimited.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct SandboxLimits {
+    /// Maximum memory for the command's whole process tree, in MiB,
+    /// enforced with a cgroup v2 `memory.max`. Enforced by the Linux sandbox
+    /// only.
+    pub max_memory_mb: Option<u64>,
+    /// Maximum CPU time per process, in seconds. Enforced by the Linux
+    /// sandbox only.
+    pub max_cpu_seconds: Option<u64>,
+    /// Maximum number of processes and threads the command's tree may have
+    /// running at once, enforced with a cgroup v2 `pids.max`. Enforced by the
+    /// Linux sandbox only.
+    pub max_processes: Option<u64>,
+    /// Maximum combined stdout and stderr, in bytes, before the command is
+    /// killed.
+    pub max_output_bytes: Option<u64>,
+}
+
+/// What happens when a patch touches a path listed in `protected_paths`.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProtectedPathAction {
+    /// Always ask the user, regardless of the approval policy. Under
+    /// `approval_policy = "never"` the patch is rejected instead.
+    #[default]
+    Ask,
+    /// Reject the patch without asking.
+    Reject,
+}
+
+/// A glob that write-protects matching files from `apply_patch`. Accepts a
+/// bare pattern (`"migrations/**"`) or a table with an explicit action
+/// (`{ pattern = ".github/**", action = "reject" }`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "ProtectedPathToml")]
+pub struct ProtectedPath {
+    /// Patterns containing `/` match the path relative to the session cwd;
+    /// other patterns match the file name at any depth.
+    pub pattern: String,
+    pub action: ProtectedPathAction,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ProtectedPathToml {
+    Pattern(String),
+    Rule {
+        pattern: String,
+        #[serde(default)]
+        action: ProtectedPathAction,
+    },
+}
+
+impl From<ProtectedPathToml> for ProtectedPath {
+    fn from(toml: ProtectedPathToml) -> Self {
+        match toml {
+            ProtectedPathToml::Pattern(pattern) => Self {
+                pattern,
+                action: ProtectedPathAction::default(),
+            },
+            ProtectedPathToml::Rule { pattern, action } => Self { pattern, action },
         }
     }
 }
@@ -712,6 +930,24 @@ mod tests {
         assert_eq!(cfg.disabled_tools, Some(vec!["blocked".to_string()]));
     }
 
+    #[test]
+    fn deserialize_server_config_with_sampling() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "echo"
+            sampling = { token_budget = 20000 }
+        "#,
+        )
+        .expect("should deserialize sampling config");
+
+        assert_eq!(
+            cfg.sampling,
+            Some(McpSamplingConfig {
+                token_budget: 20_000
+            })
+        );
+    }
+
     #[test]
     fn deserialize_rejects_command_and_url() {
         toml::from_str::<McpServerConfig>(
@@ -1,6 +1,9 @@
 mod history;
 mod normalize;
+mod redact;
 mod truncate;
 
 pub(crate) use history::ContextManager;
+pub(crate) use redact::RedactionReport;
+pub(crate) use redact::SecretRedactor;
 pub(crate) use truncate::format_output_for_model_body;
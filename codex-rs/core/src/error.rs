@@ -52,6 +52,16 @@ pub enum SandboxErr {
     /// Error from linux landlock
     #[error("Landlock was not able to fully enforce all sandbox rules")]
     LandlockRestrict,
+
+    /// Outbound connections rejected by the sandbox's network allowlist
+    #[error(
+        "sandbox blocked network access to {}, exit code: {}",
+        .destinations.join(", "), .output.exit_code
+    )]
+    NetworkBlocked {
+        destinations: Vec<String>,
+        output: Box<ExecToolCallOutput>,
+    },
 }
 
 #[derive(Error, Debug)]
@@ -451,6 +461,12 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
                 }
             }
         }
+        CodexErr::Sandbox(SandboxErr::NetworkBlocked { destinations, .. }) => {
+            format!(
+                "network access blocked by sandbox allowlist: {}",
+                destinations.join(", ")
+            )
+        }
         // Timeouts are not sandbox errors from a UX perspective; present them plainly
         CodexErr::Sandbox(SandboxErr::Timeout { output }) => {
             format!(
@@ -529,6 +545,8 @@ mod tests {
             aggregated_output: StreamOutput::new("aggregate detail".to_string()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -545,6 +563,8 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -561,6 +581,8 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(8),
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -577,6 +599,8 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(5),
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
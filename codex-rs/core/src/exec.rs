@@ -9,12 +9,18 @@ use std::process::ExitStatus;
 use std::time::Duration;
 use std::time::Instant;
 
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
 use async_channel::Sender;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
+use tokio::sync::Notify;
 
+use crate::config::types::SandboxLimits;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
@@ -22,6 +28,7 @@ use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecOutputStream;
+use crate::protocol::ExecTerminationReason;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecEnv;
@@ -34,6 +41,7 @@ const DEFAULT_TIMEOUT_MS: u64 = 10_000;
 // Hardcode these since it does not seem worth including the libc crate just
 // for these.
 const SIGKILL_CODE: i32 = 9;
+const SIGXCPU_CODE: i32 = 24;
 const TIMEOUT_CODE: i32 = 64;
 const EXIT_CODE_SIGNAL_BASE: i32 = 128; // conventional shell: 128 + signal
 const EXEC_TIMEOUT_EXIT_CODE: i32 = 124; // conventional timeout exit code
@@ -90,6 +98,7 @@ pub async fn process_exec_tool_call(
     sandbox_policy: &SandboxPolicy,
     sandbox_cwd: &Path,
     codex_linux_sandbox_exe: &Option<PathBuf>,
+    limits: &SandboxLimits,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<ExecToolCallOutput> {
     let ExecParams {
@@ -127,6 +136,7 @@ pub async fn process_exec_tool_call(
             sandbox_type,
             sandbox_cwd,
             codex_linux_sandbox_exe.as_ref(),
+            limits,
         )
         .map_err(CodexErr::from)?;
 
@@ -148,6 +158,7 @@ pub(crate) async fn execute_exec_env(
         with_escalated_permissions,
         justification,
         arg0,
+        limits,
     } = env;
 
     let params = ExecParams {
@@ -161,9 +172,16 @@ pub(crate) async fn execute_exec_env(
     };
 
     let start = Instant::now();
-    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
+    let raw_output_result = exec(
+        params,
+        sandbox,
+        sandbox_policy,
+        limits.max_output_bytes,
+        stdout_stream,
+    )
+    .await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    finalize_exec_result(raw_output_result, sandbox, &limits, duration)
 }
 
 #[cfg(target_os = "windows")]
@@ -184,7 +202,7 @@ async fn exec_windows_sandbox(
 
     let policy_str = match sandbox_policy {
         SandboxPolicy::DangerFullAccess => "workspace-write",
-        SandboxPolicy::ReadOnly => "read-only",
+        SandboxPolicy::ReadOnly { .. } => "read-only",
         SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
     };
 
@@ -241,18 +259,24 @@ async fn exec_windows_sandbox(
         stderr,
         aggregated_output,
         timed_out: capture.timed_out,
+        termination_reason: None,
+        blocked_network_destinations: Vec::new(),
     })
 }
 
 fn finalize_exec_result(
     raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr>,
     sandbox_type: SandboxType,
+    limits: &SandboxLimits,
     duration: Duration,
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
+            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
+            #[allow(unused_mut)]
+            let mut termination_signal = None;
 
             #[cfg(target_family = "unix")]
             {
@@ -260,12 +284,12 @@ fn finalize_exec_result(
                     if signal == TIMEOUT_CODE {
                         timed_out = true;
                     } else {
-                        return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
+                        exit_code = EXIT_CODE_SIGNAL_BASE + signal;
+                        termination_signal = Some(signal);
                     }
                 }
             }
 
-            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
             if timed_out {
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
@@ -273,13 +297,15 @@ fn finalize_exec_result(
             let stdout = raw_output.stdout.from_utf8_lossy();
             let stderr = raw_output.stderr.from_utf8_lossy();
             let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
-            let exec_output = ExecToolCallOutput {
+            let mut exec_output = ExecToolCallOutput {
                 exit_code,
                 stdout,
                 stderr,
                 aggregated_output,
                 duration,
                 timed_out,
+                termination_reason: raw_output.termination_reason,
+                blocked_network_destinations: raw_output.blocked_network_destinations,
             };
 
             if timed_out {
@@ -288,6 +314,33 @@ fn finalize_exec_result(
                 }));
             }
 
+            if sandbox_type == SandboxType::LinuxSeccomp && exec_output.termination_reason.is_none()
+            {
+                exec_output.termination_reason =
+                    exceeded_sandbox_limit(limits, exec_output.exit_code);
+            }
+            // Report limit kills as a regular (failed) result so the model
+            // sees the reason rather than a bare signal. Only kills we can
+            // attribute from the exit status get here; every other failure
+            // goes through the usual sandbox-denial checks below.
+            if exec_output.termination_reason.is_some() {
+                return Ok(exec_output);
+            }
+
+            if let Some(signal) = termination_signal {
+                return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
+            }
+
+            if sandbox_type == SandboxType::LinuxSeccomp
+                && exec_output.exit_code != 0
+                && !exec_output.blocked_network_destinations.is_empty()
+            {
+                return Err(CodexErr::Sandbox(SandboxErr::NetworkBlocked {
+                    destinations: exec_output.blocked_network_destinations.clone(),
+                    output: Box::new(exec_output),
+                }));
+            }
+
             if is_likely_sandbox_denied(sandbox_type, &exec_output) {
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
@@ -322,6 +375,20 @@ pub(crate) mod errors {
     }
 }
 
+/// Attributes a command's exit status to the CPU rlimit the Linux sandbox
+/// applies for `limits`, which announces itself by killing the process with
+/// `SIGXCPU`. Memory and process limits are enforced with a cgroup instead,
+/// and the helper reports those on its report pipe.
+pub(crate) fn exceeded_sandbox_limit(
+    limits: &SandboxLimits,
+    exit_code: i32,
+) -> Option<ExecTerminationReason> {
+    if limits.max_cpu_seconds.is_some() && exit_code == EXIT_CODE_SIGNAL_BASE + SIGXCPU_CODE {
+        return Some(ExecTerminationReason::CpuTimeLimit);
+    }
+    None
+}
+
 /// We don't have a fully deterministic way to tell if our command failed
 /// because of the sandbox - a command in the user's zshrc file might hit an
 /// error, but the command itself might fail or succeed for other reasons.
@@ -397,6 +464,10 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    pub termination_reason: Option<ExecTerminationReason>,
+    /// Destinations the Linux sandbox's network allowlist rejected, as
+    /// reported by the sandbox helper rather than by the command itself.
+    pub blocked_network_destinations: Vec<String>,
 }
 
 impl StreamOutput<String> {
@@ -430,6 +501,11 @@ pub struct ExecToolCallOutput {
     pub aggregated_output: StreamOutput<String>,
     pub duration: Duration,
     pub timed_out: bool,
+    /// Set when the command was killed for exceeding a [`SandboxLimits`].
+    pub termination_reason: Option<ExecTerminationReason>,
+    /// Destinations the Linux sandbox's network allowlist rejected while the
+    /// command ran.
+    pub blocked_network_destinations: Vec<String>,
 }
 
 #[cfg_attr(not(target_os = "windows"), allow(unused_variables))]
@@ -437,6 +513,7 @@ async fn exec(
     params: ExecParams,
     sandbox: SandboxType,
     sandbox_policy: &SandboxPolicy,
+    max_output_bytes: Option<u64>,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     #[cfg(target_os = "windows")]
@@ -447,7 +524,8 @@ async fn exec(
     let ExecParams {
         command,
         cwd,
-        env,
+        #[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
+        mut env,
         arg0,
         ..
     } = params;
@@ -458,6 +536,31 @@ async fn exec(
             "command args are empty",
         ))
     })?;
+
+    // The sandbox helper reports connections blocked by the network allowlist
+    // and resource limits the command ran into on a pipe of its own; the
+    // command's stderr could claim anything.
+    #[cfg(target_os = "linux")]
+    let report_pipe = if sandbox == SandboxType::LinuxSeccomp {
+        use std::os::fd::AsRawFd;
+
+        let (reader, writer) = io::pipe()?;
+        env.insert(
+            crate::landlock::SANDBOX_REPORT_FD_ENV_VAR.to_string(),
+            writer.as_raw_fd().to_string(),
+        );
+        Some((reader, writer))
+    } else {
+        None
+    };
+    #[cfg(target_os = "linux")]
+    let inherited_fd = report_pipe.as_ref().map(|(_, writer)| {
+        use std::os::fd::AsRawFd;
+        writer.as_raw_fd()
+    });
+    #[cfg(not(target_os = "linux"))]
+    let inherited_fd = None;
+
     let arg0_ref = arg0.as_deref();
     let child = spawn_child_async(
         PathBuf::from(program),
@@ -467,16 +570,44 @@ async fn exec(
         sandbox_policy,
         StdioPolicy::RedirectForShellTool,
         env,
+        inherited_fd,
     )
     .await?;
-    consume_truncated_output(child, timeout, stdout_stream).await
+
+    // Drop our write end so the read below ends once the helper exits.
+    #[cfg(target_os = "linux")]
+    let report_log = report_pipe.map(|(mut reader, writer)| {
+        drop(writer);
+        tokio::task::spawn_blocking(move || {
+            use std::io::Read;
+
+            let mut log = String::new();
+            let _ = reader.read_to_string(&mut log);
+            log
+        })
+    });
+
+    #[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
+    let mut output =
+        consume_truncated_output(child, timeout, max_output_bytes, stdout_stream).await?;
+    #[cfg(target_os = "linux")]
+    if let Some(log) = report_log {
+        let report = crate::landlock::parse_sandbox_report(&log.await.unwrap_or_default());
+        output.blocked_network_destinations = report.blocked_network_destinations;
+        if output.termination_reason.is_none() {
+            output.termination_reason = report.termination_reason;
+        }
+    }
+    Ok(output)
 }
 
 /// Consumes the output of a child process, truncating it so it is suitable for
-/// use as the output of a `shell` tool call. Also enforces specified timeout.
+/// use as the output of a `shell` tool call. Also enforces specified timeout
+/// and, when set, kills the child once it writes more than `max_output_bytes`.
 async fn consume_truncated_output(
     mut child: Child,
     timeout: Duration,
+    max_output_bytes: Option<u64>,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     // Both stdout and stderr were configured with `Stdio::piped()`
@@ -495,20 +626,30 @@ async fn consume_truncated_output(
     })?;
 
     let (agg_tx, agg_rx) = async_channel::unbounded::<Vec<u8>>();
+    let output_budget = max_output_bytes.map(|max_bytes| Arc::new(OutputBudget::new(max_bytes)));
 
     let stdout_handle = tokio::spawn(read_capped(
         BufReader::new(stdout_reader),
         stdout_stream.clone(),
         false,
         Some(agg_tx.clone()),
+        output_budget.clone(),
     ));
     let stderr_handle = tokio::spawn(read_capped(
         BufReader::new(stderr_reader),
         stdout_stream.clone(),
         true,
         Some(agg_tx.clone()),
+        output_budget.clone(),
     ));
 
+    let output_exceeded = async {
+        match &output_budget {
+            Some(budget) => budget.exceeded.notified().await,
+            None => std::future::pending().await,
+        }
+    };
+
     let (exit_status, timed_out) = tokio::select! {
         result = tokio::time::timeout(timeout, child.wait()) => {
             match result {
@@ -525,6 +666,11 @@ async fn consume_truncated_output(
                 }
             }
         }
+        _ = output_exceeded => {
+            kill_child_process_group(&mut child)?;
+            child.start_kill()?;
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false)
+        }
         _ = tokio::signal::ctrl_c() => {
             kill_child_process_group(&mut child)?;
             child.start_kill()?;
@@ -534,6 +680,9 @@ async fn consume_truncated_output(
 
     let stdout = stdout_handle.await??;
     let stderr = stderr_handle.await??;
+    let termination_reason = output_budget
+        .filter(|budget| budget.is_exceeded())
+        .map(|_| ExecTerminationReason::OutputLimit);
 
     drop(agg_tx);
 
@@ -552,14 +701,49 @@ async fn consume_truncated_output(
         stderr,
         aggregated_output,
         timed_out,
+        termination_reason,
+        blocked_network_destinations: Vec::new(),
     })
 }
 
+/// Output shared by stdout and stderr under `max_output_bytes`.
+struct OutputBudget {
+    max_bytes: u64,
+    used: AtomicU64,
+    exceeded: Notify,
+}
+
+impl OutputBudget {
+    fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            used: AtomicU64::new(0),
+            exceeded: Notify::new(),
+        }
+    }
+
+    /// Records `n` more bytes and returns how many of them fit in the budget.
+    fn consume(&self, n: usize) -> usize {
+        let n = n as u64;
+        let before = self.used.fetch_add(n, Ordering::Relaxed);
+        let remaining = self.max_bytes.saturating_sub(before);
+        if n > remaining {
+            self.exceeded.notify_one();
+        }
+        n.min(remaining) as usize
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.used.load(Ordering::Relaxed) > self.max_bytes
+    }
+}
+
 async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
     stream: Option<StdoutStream>,
     is_stderr: bool,
     aggregate_tx: Option<Sender<Vec<u8>>>,
+    output_budget: Option<Arc<OutputBudget>>,
 ) -> io::Result<StreamOutput<Vec<u8>>> {
     let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY);
     let mut tmp = [0u8; READ_CHUNK_SIZE];
@@ -568,10 +752,18 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     // No caps: append all bytes
 
     loop {
-        let n = reader.read(&mut tmp).await?;
+        let mut n = reader.read(&mut tmp).await?;
         if n == 0 {
             break;
         }
+        if let Some(budget) = &output_budget {
+            // Keep draining once over budget so the child never blocks on a
+            // full pipe before it is killed.
+            n = budget.consume(n);
+            if n == 0 {
+                continue;
+            }
+        }
 
         if let Some(stream) = &stream
             && emitted_deltas < MAX_EXEC_OUTPUT_DELTAS_PER_CALL
@@ -673,6 +865,8 @@ mod tests {
             aggregated_output: StreamOutput::new(aggregated.to_string()),
             duration: Duration::from_millis(1),
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         }
     }
 
@@ -747,7 +941,14 @@ mod tests {
             arg0: None,
         };
 
-        let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
+        let output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::new_read_only_policy(),
+            None,
+            None,
+        )
+        .await?;
         assert!(output.timed_out);
 
         let stdout = output.stdout.from_utf8_lossy().text;
@@ -774,4 +975,115 @@ mod tests {
         assert!(killed, "grandchild process with pid {pid} is still alive");
         Ok(())
     }
+
+    #[test]
+    fn sandbox_limit_detection_only_trusts_sigxcpu_exit_status() {
+        assert_eq!(
+            exceeded_sandbox_limit(
+                &SandboxLimits::default(),
+                EXIT_CODE_SIGNAL_BASE + SIGXCPU_CODE
+            ),
+            None
+        );
+
+        let limits = SandboxLimits {
+            max_memory_mb: Some(512),
+            max_cpu_seconds: Some(60),
+            max_processes: Some(64),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded_sandbox_limit(&limits, EXIT_CODE_SIGNAL_BASE + SIGXCPU_CODE),
+            Some(ExecTerminationReason::CpuTimeLimit)
+        );
+        assert_eq!(
+            exceeded_sandbox_limit(&limits, EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE),
+            None
+        );
+        assert_eq!(exceeded_sandbox_limit(&limits, 1), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn limit_keywords_in_output_do_not_hide_sandbox_denials() {
+        let limits = SandboxLimits {
+            max_memory_mb: Some(512),
+            max_processes: Some(64),
+            ..Default::default()
+        };
+        let stderr =
+            b"bash: fork: retry: Resource temporarily unavailable\nOperation not permitted";
+        let raw = RawExecToolCallOutput {
+            // Wait status for a normal exit with code 1.
+            exit_status: synthetic_exit_status(1 << 8),
+            stdout: StreamOutput {
+                text: Vec::new(),
+                truncated_after_lines: None,
+            },
+            stderr: StreamOutput {
+                text: stderr.to_vec(),
+                truncated_after_lines: None,
+            },
+            aggregated_output: StreamOutput {
+                text: stderr.to_vec(),
+                truncated_after_lines: None,
+            },
+            timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
+        };
+        let result = finalize_exec_result(
+            Ok(raw),
+            SandboxType::LinuxSeccomp,
+            &limits,
+            Duration::from_millis(1),
+        );
+        assert!(matches!(
+            result,
+            Err(CodexErr::Sandbox(SandboxErr::Denied { .. }))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn output_limit_kills_chatty_command() -> Result<()> {
+        let env: HashMap<String, String> = std::env::vars().collect();
+        let params = ExecParams {
+            command: vec!["yes".to_string()],
+            cwd: std::env::current_dir()?,
+            timeout_ms: Some(10_000),
+            env,
+            with_escalated_permissions: None,
+            justification: None,
+            arg0: None,
+        };
+
+        let output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::new_read_only_policy(),
+            Some(1_000),
+            None,
+        )
+        .await?;
+        assert!(!output.timed_out);
+        assert_eq!(
+            output.termination_reason,
+            Some(ExecTerminationReason::OutputLimit)
+        );
+        assert_eq!(output.aggregated_output.text.len(), 1_000);
+
+        let output = finalize_exec_result(
+            Ok(output),
+            SandboxType::None,
+            &SandboxLimits::default(),
+            Duration::from_millis(1),
+        )?;
+        assert_eq!(output.exit_code, EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE);
+        assert_eq!(
+            output.termination_reason,
+            Some(ExecTerminationReason::OutputLimit)
+        );
+        Ok(())
+    }
 }
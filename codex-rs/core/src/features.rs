@@ -43,6 +43,9 @@ pub enum Feature {
     GhostCommit,
     /// Enable Windows sandbox (restricted token) on Windows.
     WindowsSandbox,
+    /// Include the memory tools and summarize saved memories into the
+    /// initial context.
+    Memory,
 }
 
 impl Feature {
@@ -292,4 +295,10 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::Memory,
+        key: "memory",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
 ];
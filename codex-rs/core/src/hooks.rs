@@ -0,0 +1,512 @@
+//! Lifecycle hooks configured under `[hooks]` in config.toml.
+//!
+//! Each hook is a program that receives a JSON payload describing the event
+//! on stdin. Hooks that run before an action (`pre_tool_call`, `pre_patch`)
+//! can deny it, and `pre_tool_call` hooks can also rewrite the tool
+//! arguments; `post_tool_call` hooks can append context to the tool output.
+//! Their decision is read from a JSON object on stdout:
+//!
+//! ```json
+//! {"decision": "deny", "reason": "generated files are read-only"}
+//! {"arguments": {"command": ["cargo", "fmt"]}}
+//! {"context": "rustfmt reformatted 2 files"}
+//! ```
+//!
+//! Empty stdout means "no opinion". A pre-action hook that fails, times out
+//! or prints something other than a JSON object denies the action, so a
+//! broken guard never silently lets a call through. `session_start` and
+//! `turn_complete` hooks are fire-and-forget.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use codex_protocol::ConversationId;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tracing::warn;
+use wildmatch::WildMatchPattern;
+
+use crate::config::types::HookConfig;
+use crate::config::types::HooksConfig;
+use crate::protocol::FileChange;
+
+const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+pub(crate) struct Hooks {
+    config: HooksConfig,
+}
+
+/// Where and for which session a hook runs.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HookScope<'a> {
+    pub(crate) session_id: ConversationId,
+    pub(crate) cwd: &'a Path,
+}
+
+/// The tool call a `pre_tool_call` or `post_tool_call` hook runs for.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ToolCallInfo<'a> {
+    pub(crate) turn_id: &'a str,
+    pub(crate) tool_name: &'a str,
+    pub(crate) call_id: &'a str,
+    pub(crate) arguments: &'a str,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum PreToolCallOutcome {
+    /// Run the tool, with rewritten arguments if a hook provided them.
+    /// `approved` is set when a hook answered `allow`, in which case the
+    /// tool runs without asking the user first.
+    Continue {
+        arguments: Option<String>,
+        approved: bool,
+    },
+    Deny {
+        reason: String,
+    },
+}
+
+#[derive(Serialize)]
+struct HookPayload<'a> {
+    session_id: String,
+    cwd: &'a Path,
+    #[serde(flatten)]
+    event: HookEvent<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum HookEvent<'a> {
+    PreToolCall {
+        turn_id: &'a str,
+        tool_name: &'a str,
+        call_id: &'a str,
+        arguments: &'a str,
+    },
+    PostToolCall {
+        turn_id: &'a str,
+        tool_name: &'a str,
+        call_id: &'a str,
+        arguments: &'a str,
+        output: &'a str,
+        success: Option<bool>,
+    },
+    PrePatch {
+        turn_id: &'a str,
+        call_id: &'a str,
+        changes: &'a HashMap<PathBuf, FileChange>,
+    },
+    SessionStart {
+        model: &'a str,
+    },
+    TurnComplete {
+        turn_id: &'a str,
+        input_messages: &'a [String],
+        last_assistant_message: Option<&'a str>,
+    },
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HookResponse {
+    decision: Option<HookDecision>,
+    reason: Option<String>,
+    arguments: Option<Value>,
+    context: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum HookDecision {
+    Allow,
+    Deny,
+}
+
+impl Hooks {
+    pub(crate) fn new(config: HooksConfig) -> Self {
+        Self { config }
+    }
+
+    pub(crate) async fn pre_tool_call(
+        &self,
+        scope: HookScope<'_>,
+        call: ToolCallInfo<'_>,
+    ) -> PreToolCallOutcome {
+        let mut rewritten: Option<String> = None;
+        let mut approved = false;
+        for hook in matching(&self.config.pre_tool_call, call.tool_name) {
+            let payload = payload(
+                scope,
+                HookEvent::PreToolCall {
+                    turn_id: call.turn_id,
+                    tool_name: call.tool_name,
+                    call_id: call.call_id,
+                    arguments: rewritten.as_deref().unwrap_or(call.arguments),
+                },
+            );
+            let response = match run_hook(hook, scope.cwd, payload).await {
+                Ok(response) => response,
+                Err(reason) => return PreToolCallOutcome::Deny { reason },
+            };
+            if response.decision == Some(HookDecision::Deny) {
+                return PreToolCallOutcome::Deny {
+                    reason: deny_reason(hook, response.reason),
+                };
+            }
+            approved |= response.decision == Some(HookDecision::Allow);
+            match response.arguments {
+                Some(Value::String(arguments)) => rewritten = Some(arguments),
+                Some(arguments) => rewritten = Some(arguments.to_string()),
+                None => {}
+            }
+        }
+        PreToolCallOutcome::Continue {
+            arguments: rewritten,
+            approved,
+        }
+    }
+
+    /// Returns the context appended by hooks, if any.
+    pub(crate) async fn post_tool_call(
+        &self,
+        scope: HookScope<'_>,
+        call: ToolCallInfo<'_>,
+        output: &str,
+        success: Option<bool>,
+    ) -> Option<String> {
+        let mut contexts = Vec::new();
+        for hook in matching(&self.config.post_tool_call, call.tool_name) {
+            let payload = payload(
+                scope,
+                HookEvent::PostToolCall {
+                    turn_id: call.turn_id,
+                    tool_name: call.tool_name,
+                    call_id: call.call_id,
+                    arguments: call.arguments,
+                    output,
+                    success,
+                },
+            );
+            match run_hook(hook, scope.cwd, payload).await {
+                Ok(HookResponse {
+                    context: Some(context),
+                    ..
+                }) if !context.trim().is_empty() => contexts.push(context),
+                Ok(_) => {}
+                Err(err) => warn!("{err}"),
+            }
+        }
+        (!contexts.is_empty()).then(|| contexts.join("\n"))
+    }
+
+    /// Returns the reason when a hook denies the patch.
+    pub(crate) async fn pre_patch(
+        &self,
+        scope: HookScope<'_>,
+        turn_id: &str,
+        call_id: &str,
+        changes: &HashMap<PathBuf, FileChange>,
+    ) -> Result<(), String> {
+        for hook in &self.config.pre_patch {
+            let payload = payload(
+                scope,
+                HookEvent::PrePatch {
+                    turn_id,
+                    call_id,
+                    changes,
+                },
+            );
+            let response = run_hook(hook, scope.cwd, payload).await?;
+            if response.decision == Some(HookDecision::Deny) {
+                return Err(deny_reason(hook, response.reason));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn session_start(&self, scope: HookScope<'_>, model: &str) {
+        self.spawn_all(
+            &self.config.session_start,
+            scope,
+            HookEvent::SessionStart { model },
+        );
+    }
+
+    pub(crate) fn turn_complete(
+        &self,
+        scope: HookScope<'_>,
+        turn_id: &str,
+        input_messages: &[String],
+        last_assistant_message: Option<&str>,
+    ) {
+        self.spawn_all(
+            &self.config.turn_complete,
+            scope,
+            HookEvent::TurnComplete {
+                turn_id,
+                input_messages,
+                last_assistant_message,
+            },
+        );
+    }
+
+    fn spawn_all(&self, hooks: &[HookConfig], scope: HookScope<'_>, event: HookEvent<'_>) {
+        if hooks.is_empty() {
+            return;
+        }
+        let payload = payload(scope, event);
+        for hook in hooks {
+            let hook = hook.clone();
+            let cwd = scope.cwd.to_path_buf();
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                if let Err(err) = run_hook(&hook, &cwd, payload).await {
+                    warn!("{err}");
+                }
+            });
+        }
+    }
+}
+
+fn matching<'a>(
+    hooks: &'a [HookConfig],
+    tool_name: &'a str,
+) -> impl Iterator<Item = &'a HookConfig> {
+    hooks.iter().filter(move |hook| match &hook.tools {
+        None => true,
+        Some(patterns) => patterns
+            .iter()
+            .any(|pattern| WildMatchPattern::<'*', '?'>::new(pattern).matches(tool_name)),
+    })
+}
+
+fn payload(scope: HookScope<'_>, event: HookEvent<'_>) -> String {
+    let payload = HookPayload {
+        session_id: scope.session_id.to_string(),
+        cwd: scope.cwd,
+        event,
+    };
+    // Serializing plain structs of strings and paths cannot fail.
+    serde_json::to_string(&payload).unwrap_or_default()
+}
+
+fn deny_reason(hook: &HookConfig, reason: Option<String>) -> String {
+    reason.unwrap_or_else(|| format!("denied by hook `{}`", hook.command.join(" ")))
+}
+
+async fn run_hook(hook: &HookConfig, cwd: &Path, payload: String) -> Result<HookResponse, String> {
+    let name = hook.command.join(" ");
+    let Some((program, args)) = hook.command.split_first() else {
+        return Err("hook has an empty command".to_string());
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("failed to start hook `{name}`: {e}"))?;
+
+    // Feed stdin while waiting for the hook so that one which never reads a
+    // large payload is still bound by the timeout.
+    let stdin = child.stdin.take();
+    let write_payload = async move {
+        if let Some(mut stdin) = stdin {
+            // A hook may exit without reading its input; that is not an error.
+            let _ = stdin.write_all(payload.as_bytes()).await;
+        }
+    };
+
+    let timeout = hook
+        .timeout_ms
+        .map_or(DEFAULT_HOOK_TIMEOUT, Duration::from_millis);
+    let output = tokio::time::timeout(timeout, async {
+        let ((), output) = tokio::join!(write_payload, child.wait_with_output());
+        output
+    })
+    .await
+    .map_err(|_| format!("hook `{name}` timed out after {}ms", timeout.as_millis()))?
+    .map_err(|e| format!("hook `{name}` failed: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = stderr.trim();
+        return Err(if stderr.is_empty() {
+            format!("hook `{name}` exited with {}", output.status)
+        } else {
+            stderr.to_string()
+        });
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Ok(HookResponse::default());
+    }
+    serde_json::from_str(&stdout).map_err(|e| format!("hook `{name}` printed invalid JSON: {e}"))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn hook(script: &str, tools: Option<&[&str]>) -> HookConfig {
+        HookConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            tools: tools.map(|tools| tools.iter().map(ToString::to_string).collect()),
+            timeout_ms: Some(5_000),
+        }
+    }
+
+    fn scope(cwd: &Path) -> HookScope<'_> {
+        HookScope {
+            session_id: ConversationId::default(),
+            cwd,
+        }
+    }
+
+    fn call(tool_name: &str) -> ToolCallInfo<'_> {
+        ToolCallInfo {
+            turn_id: "turn-1",
+            tool_name,
+            call_id: "call-1",
+            arguments: "{}",
+        }
+    }
+
+    #[tokio::test]
+    async fn pre_tool_call_hooks_rewrite_and_deny() {
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let hooks = Hooks::new(HooksConfig {
+            pre_tool_call: vec![
+                hook(
+                    r#"cat > payload.json; echo '{"arguments": {"command": ["cargo", "fmt"]}}'"#,
+                    Some(&["shell"]),
+                ),
+                hook(
+                    r#"echo '{"decision": "deny", "reason": "no MCP today"}'"#,
+                    Some(&["mcp__*"]),
+                ),
+            ],
+            ..Default::default()
+        });
+
+        let outcome = hooks.pre_tool_call(scope(cwd.path()), call("shell")).await;
+        assert_eq!(
+            outcome,
+            PreToolCallOutcome::Continue {
+                arguments: Some(r#"{"command":["cargo","fmt"]}"#.to_string()),
+                approved: false,
+            }
+        );
+        let payload: Value = serde_json::from_str(
+            &std::fs::read_to_string(cwd.path().join("payload.json")).expect("payload"),
+        )
+        .expect("json payload");
+        assert_eq!(payload["event"], "pre_tool_call");
+        assert_eq!(payload["tool_name"], "shell");
+        assert_eq!(payload["arguments"], "{}");
+
+        let outcome = hooks
+            .pre_tool_call(scope(cwd.path()), call("mcp__docs__search"))
+            .await;
+        assert_eq!(
+            outcome,
+            PreToolCallOutcome::Deny {
+                reason: "no MCP today".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn pre_tool_call_allow_approves_the_call() {
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let hooks = Hooks::new(HooksConfig {
+            pre_tool_call: vec![
+                hook(r#"echo '{"decision": "allow"}'"#, Some(&["shell"])),
+                hook("exit 0", None),
+            ],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            hooks.pre_tool_call(scope(cwd.path()), call("shell")).await,
+            PreToolCallOutcome::Continue {
+                arguments: None,
+                approved: true,
+            }
+        );
+        assert_eq!(
+            hooks
+                .pre_tool_call(scope(cwd.path()), call("read_file"))
+                .await,
+            PreToolCallOutcome::Continue {
+                arguments: None,
+                approved: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn hooks_that_never_read_their_input_still_time_out() {
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let hook = HookConfig {
+            timeout_ms: Some(200),
+            ..hook("sleep 30", None)
+        };
+        // Larger than any pipe buffer, so writing it blocks until the hook
+        // reads or exits.
+        let payload = "x".repeat(1 << 20);
+
+        let started = std::time::Instant::now();
+        let err = run_hook(&hook, cwd.path(), payload)
+            .await
+            .expect_err("hook should time out");
+
+        assert_eq!(err, "hook `sh -c sleep 30` timed out after 200ms");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn failing_pre_hooks_deny_and_post_hooks_add_context() {
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let hooks = Hooks::new(HooksConfig {
+            pre_patch: vec![hook(
+                "echo 'generated files are read-only' >&2; exit 2",
+                None,
+            )],
+            post_tool_call: vec![
+                hook(r#"echo '{"context": "rustfmt reformatted 1 file"}'"#, None),
+                hook("exit 1", None),
+            ],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            hooks
+                .pre_patch(scope(cwd.path()), "turn-1", "call-1", &HashMap::new())
+                .await,
+            Err("generated files are read-only".to_string())
+        );
+        assert_eq!(
+            hooks
+                .post_tool_call(
+                    scope(cwd.path()),
+                    call("apply_patch"),
+                    "Success",
+                    Some(true)
+                )
+                .await,
+            Some("rustfmt reformatted 1 file".to_string())
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::types::SandboxLimits;
+use crate::protocol::ExecTerminationReason;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
@@ -6,26 +8,51 @@ use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
 
+/// Prefix of the line `codex-linux-sandbox` writes to stderr for every
+/// outbound connection rejected by the policy's network allowlist, so the
+/// command's output explains the failure.
+pub const NETWORK_BLOCKED_PREFIX: &str = "codex-linux-sandbox: blocked network connection to ";
+
+/// Environment variable through which Codex hands `codex-linux-sandbox` the
+/// write end of a pipe. The helper reports there, one per line, each
+/// connection rejected by the network allowlist and the resource limit that
+/// stopped the command, and does not pass the descriptor on to the command,
+/// so unlike stderr the command cannot forge these reports.
+pub const SANDBOX_REPORT_FD_ENV_VAR: &str = "CODEX_SANDBOX_REPORT_FD";
+
+/// Prefix of a report line naming a `host:port` the network allowlist
+/// rejected.
+pub const REPORT_BLOCKED_NETWORK_PREFIX: &str = "blocked-network ";
+
+/// Report line for a command killed by the `max_memory_mb` cgroup limit.
+pub const REPORT_MEMORY_LIMIT: &str = "limit memory";
+
+/// Report line for a command that ran into the `max_processes` cgroup limit.
+pub const REPORT_PROCESS_LIMIT: &str = "limit processes";
+
 /// Spawn a shell tool command under the Linux Landlock+seccomp sandbox helper
 /// (codex-linux-sandbox).
 ///
 /// Unlike macOS Seatbelt where we directly embed the policy text, the Linux
 /// helper accepts a list of `--sandbox-permission`/`-s` flags mirroring the
 /// public CLI. We convert the internal [`SandboxPolicy`] representation into
-/// the equivalent CLI options.
+/// the equivalent CLI options, together with the configured `limits`.
+#[allow(clippy::too_many_arguments)]
 pub async fn spawn_command_under_linux_sandbox<P>(
     codex_linux_sandbox_exe: P,
     command: Vec<String>,
     command_cwd: PathBuf,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    limits: &SandboxLimits,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
 ) -> std::io::Result<Child>
 where
     P: AsRef<Path>,
 {
-    let args = create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args =
+        create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd, limits);
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(
         codex_linux_sandbox_exe.as_ref().to_path_buf(),
@@ -35,6 +62,7 @@ where
         sandbox_policy,
         stdio_policy,
         env,
+        None,
     )
     .await
 }
@@ -44,6 +72,7 @@ pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    limits: &SandboxLimits,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -60,13 +89,117 @@ pub(crate) fn create_linux_sandbox_command_args(
         sandbox_policy_cwd,
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
-        // Separator so that command arguments starting with `-` are not parsed as
-        // options of the helper itself.
-        "--".to_string(),
     ];
 
+    let limit_flags = [
+        ("--max-memory-mb", limits.max_memory_mb),
+        ("--max-cpu-seconds", limits.max_cpu_seconds),
+        ("--max-processes", limits.max_processes),
+    ];
+    for (flag, value) in limit_flags {
+        if let Some(value) = value {
+            linux_cmd.push(flag.to_string());
+            linux_cmd.push(value.to_string());
+        }
+    }
+
+    // Separator so that command arguments starting with `-` are not parsed as
+    // options of the helper itself.
+    linux_cmd.push("--".to_string());
+
     // Append the original tool command.
     linux_cmd.extend(command);
 
     linux_cmd
 }
+
+/// What `codex-linux-sandbox` reported on the [`SANDBOX_REPORT_FD_ENV_VAR`]
+/// pipe.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct SandboxReport {
+    /// Rejected destinations, without duplicates and in the order they were
+    /// first reported.
+    pub blocked_network_destinations: Vec<String>,
+    pub termination_reason: Option<ExecTerminationReason>,
+}
+
+pub(crate) fn parse_sandbox_report(log: &str) -> SandboxReport {
+    let mut report = SandboxReport::default();
+    for line in log.lines().map(str::trim) {
+        if let Some(destination) = line.strip_prefix(REPORT_BLOCKED_NETWORK_PREFIX) {
+            let destination = destination.trim();
+            if !destination.is_empty()
+                && !report
+                    .blocked_network_destinations
+                    .iter()
+                    .any(|d| d == destination)
+            {
+                report
+                    .blocked_network_destinations
+                    .push(destination.to_string());
+            }
+        } else if line == REPORT_MEMORY_LIMIT {
+            report.termination_reason = Some(ExecTerminationReason::MemoryLimit);
+        } else if line == REPORT_PROCESS_LIMIT {
+            report.termination_reason = Some(ExecTerminationReason::ProcessLimit);
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_sandbox_report() {
+        assert_eq!(
+            parse_sandbox_report(
+                "blocked-network example.com:443\nblocked-network 10.1.2.3:80\nblocked-network example.com:443\nlimit memory\n"
+            ),
+            SandboxReport {
+                blocked_network_destinations: vec![
+                    "example.com:443".to_string(),
+                    "10.1.2.3:80".to_string()
+                ],
+                termination_reason: Some(ExecTerminationReason::MemoryLimit),
+            }
+        );
+        assert_eq!(
+            parse_sandbox_report("limit processes\n").termination_reason,
+            Some(ExecTerminationReason::ProcessLimit)
+        );
+        assert_eq!(parse_sandbox_report(""), SandboxReport::default());
+    }
+
+    #[test]
+    fn passes_configured_limits_to_helper() {
+        let limits = SandboxLimits {
+            max_memory_mb: Some(2048),
+            max_processes: Some(256),
+            ..Default::default()
+        };
+        let args = create_linux_sandbox_command_args(
+            vec!["-x".to_string()],
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/work"),
+            &limits,
+        );
+        assert_eq!(
+            args,
+            vec![
+                "--sandbox-policy-cwd".to_string(),
+                "/work".to_string(),
+                "--sandbox-policy".to_string(),
+                r#"{"mode":"read-only"}"#.to_string(),
+                "--max-memory-mb".to_string(),
+                "2048".to_string(),
+                "--max-processes".to_string(),
+                "256".to_string(),
+                "--".to_string(),
+                "-x".to_string(),
+            ]
+        );
+    }
+}
@@ -27,10 +27,12 @@ pub mod exec_env;
 pub mod features;
 mod flags;
 pub mod git_info;
+mod hooks;
 pub mod landlock;
 pub mod mcp;
 mod mcp_connection_manager;
 mod mcp_tool_call;
+pub mod memory;
 mod message_history;
 mod model_provider_info;
 pub mod parse_command;
@@ -48,6 +50,7 @@ pub use model_provider_info::create_oss_provider_with_base_url;
 mod conversation_manager;
 mod event_mapping;
 pub mod review_format;
+mod review_prompts;
 pub use codex_protocol::protocol::InitialHistory;
 pub use conversation_manager::ConversationManager;
 pub use conversation_manager::NewConversation;
@@ -61,6 +64,7 @@ pub mod project_doc;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
+mod session_crypto;
 pub mod shell;
 pub mod spawn;
 pub mod terminal;
@@ -71,12 +75,20 @@ pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
+pub use rollout::export::REDACTED_TOOL_OUTPUT;
+pub use rollout::export::TranscriptFormat;
+pub use rollout::export::TranscriptOptions;
+pub use rollout::export::render_transcript;
 pub use rollout::find_conversation_path_by_id_str;
 pub use rollout::list::ConversationItem;
+pub use rollout::list::ConversationSearch;
 pub use rollout::list::ConversationsPage;
 pub use rollout::list::Cursor;
 pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
+pub use rollout::retention::PruneReport;
+pub use rollout::retention::PrunedSession;
+pub use rollout::retention::prune_sessions;
 mod function_tool;
 mod state;
 mod tasks;
@@ -1 +1,3 @@
 pub mod auth;
+pub(crate) mod roots;
+pub(crate) mod sampling;
@@ -0,0 +1,96 @@
+//! Roots reported to MCP servers through `roots/list`.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use mcp_types::Root;
+use url::Url;
+
+use crate::protocol::SandboxPolicy;
+
+/// Roots for a session running in `cwd` under `sandbox_policy`: the working
+/// directory first, then the extra `writable_roots` of a workspace-write
+/// sandbox. `/tmp` and `$TMPDIR` are left out because they are scratch space
+/// rather than part of the workspace.
+pub(crate) fn workspace_roots(cwd: &Path, sandbox_policy: &SandboxPolicy) -> Vec<Root> {
+    let mut paths: Vec<PathBuf> = vec![cwd.to_path_buf()];
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = sandbox_policy {
+        for root in writable_roots {
+            if !paths.contains(root) {
+                paths.push(root.clone());
+            }
+        }
+    }
+
+    paths
+        .into_iter()
+        // Relative paths have no `file://` URI.
+        .filter_map(|path| {
+            let uri = Url::from_file_path(&path).ok()?;
+            Some(Root {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned()),
+                uri: uri.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn uri(path: &Path) -> String {
+        Url::from_file_path(path)
+            .expect("absolute path")
+            .to_string()
+    }
+
+    #[test]
+    fn cwd_comes_first_followed_by_writable_roots() {
+        let tmp = TempDir::new().expect("tempdir");
+        let cwd = tmp.path().join("repo");
+        let extra = tmp.path().join("cache");
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![extra.clone(), cwd.clone()],
+            network_access: false,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+            allowed_hosts: Vec::new(),
+            allowed_cidrs: Vec::new(),
+            readable_roots: Vec::new(),
+            deny_read: Vec::new(),
+        };
+
+        let roots = workspace_roots(&cwd, &policy);
+
+        assert_eq!(
+            roots,
+            vec![
+                Root {
+                    name: Some("repo".to_string()),
+                    uri: uri(&cwd),
+                },
+                Root {
+                    name: Some("cache".to_string()),
+                    uri: uri(&extra),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn read_only_sessions_only_expose_cwd() {
+        let tmp = TempDir::new().expect("tempdir");
+
+        let roots = workspace_roots(tmp.path(), &SandboxPolicy::new_read_only_policy());
+
+        assert_eq!(
+            roots.into_iter().map(|root| root.uri).collect::<Vec<_>>(),
+            vec![uri(tmp.path())]
+        );
+    }
+}
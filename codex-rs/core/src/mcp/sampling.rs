@@ -0,0 +1,650 @@
+//! `sampling/createMessage` support for MCP servers.
+//!
+//! Servers that opt in through `[mcp_servers.<name>.sampling]` may run
+//! completions through the session's model client. Every request is shown to
+//! the user as `EventMsg::SamplingRequest` and only runs once approved, and
+//! each server has a token budget for the whole session that bounds both the
+//! prompt and the `maxTokens` sent to the model.
+//!
+//! `stopSequences` are sent to providers that use the Chat Completions API.
+//! The Responses API has no equivalent, so there they are applied to the
+//! finished reply instead: the model may keep generating past a stop sequence
+//! until it ends its turn or reaches `maxTokens`, and those tokens are charged
+//! to the budget even though they are cut from the result.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use async_channel::Sender;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_rmcp_client::SendCreateMessage;
+use futures::FutureExt;
+use futures::StreamExt;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::CreateMessageResultContent;
+use mcp_types::RequestId;
+use mcp_types::Role;
+use mcp_types::SamplingMessage;
+use mcp_types::SamplingMessageContent;
+use mcp_types::TextContent;
+use tokio::sync::Mutex;
+use tokio::sync::oneshot;
+use tracing::warn;
+
+use crate::client::ModelClient;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::config::types::McpSamplingConfig;
+use crate::model_family::ModelFamily;
+use crate::model_provider_info::WireApi;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::protocol::ReviewDecision;
+use crate::protocol::SamplingRequestEvent;
+
+/// Event id used for `EventMsg::SamplingRequest`. Like elicitations, sampling
+/// requests are not tied to a submission.
+const SAMPLING_EVENT_ID: &str = "mcp_sampling_request";
+
+/// Rough bytes-per-token ratio used when the provider does not report usage.
+const APPROX_BYTES_PER_TOKEN: usize = 4;
+
+/// Most stop sequences the Chat Completions API accepts in one request; any
+/// further ones are only applied to the reply.
+const MAX_PROVIDER_STOP_SEQUENCES: usize = 4;
+
+#[derive(Default)]
+struct SamplingState {
+    pending: HashMap<(String, RequestId), oneshot::Sender<ReviewDecision>>,
+    /// Servers the user approved with `ReviewDecision::ApprovedForSession`.
+    approved_for_session: HashSet<String>,
+    /// Server name -> tokens spent on sampling so far, including what is
+    /// reserved for requests that are still running.
+    tokens_used: HashMap<String, u64>,
+}
+
+impl SamplingState {
+    /// Reserves the prompt plus the allowed output in `server_name`'s budget
+    /// and returns how many output tokens the request may use.
+    fn reserve(
+        &mut self,
+        server_name: &str,
+        token_budget: u64,
+        prompt_tokens: u64,
+        max_tokens: u64,
+    ) -> Result<u64> {
+        let used = self.tokens_used.entry(server_name.to_string()).or_default();
+        let max_output_tokens =
+            output_allowance(server_name, token_budget, *used, prompt_tokens, max_tokens)?;
+        *used += prompt_tokens + max_output_tokens;
+        Ok(max_output_tokens)
+    }
+
+    /// Replaces a reservation with what the request actually cost; `spent`
+    /// is `None` when it failed.
+    fn settle(&mut self, server_name: &str, reserved: u64, spent: Option<u64>) {
+        let used = self.tokens_used.entry(server_name.to_string()).or_default();
+        *used = used.saturating_sub(reserved) + spent.unwrap_or(0);
+    }
+}
+
+/// Asks the user to approve sampling requests from MCP servers and runs the
+/// approved ones against the session's current model.
+#[derive(Clone, Default)]
+pub(crate) struct SamplingRequestManager {
+    state: Arc<Mutex<SamplingState>>,
+    /// Set by the session whenever it builds a turn context, so sampling
+    /// follows model changes.
+    client: Arc<RwLock<Option<ModelClient>>>,
+}
+
+impl SamplingRequestManager {
+    pub(crate) fn set_client(&self, client: ModelClient) {
+        *self.client.write().unwrap_or_else(PoisonError::into_inner) = Some(client);
+    }
+
+    /// `config` is `None` when the server did not opt in; its requests are
+    /// rejected without asking the user.
+    pub(crate) fn make_sender(
+        &self,
+        server_name: String,
+        config: Option<McpSamplingConfig>,
+        tx_event: Sender<Event>,
+    ) -> SendCreateMessage {
+        let manager = self.clone();
+        Arc::new(move |id, params| {
+            let manager = manager.clone();
+            let server_name = server_name.clone();
+            let tx_event = tx_event.clone();
+            async move {
+                let Some(config) = config else {
+                    bail!("sampling is not enabled for MCP server '{server_name}'");
+                };
+                manager
+                    .handle_request(server_name, config, tx_event, id, params)
+                    .await
+            }
+            .boxed()
+        })
+    }
+
+    pub(crate) async fn resolve(
+        &self,
+        server_name: String,
+        id: RequestId,
+        decision: ReviewDecision,
+    ) -> Result<()> {
+        let tx = self
+            .state
+            .lock()
+            .await
+            .pending
+            .remove(&(server_name.clone(), id.clone()))
+            .ok_or_else(|| {
+                anyhow!("no pending sampling request {id:?} from MCP server '{server_name}'")
+            })?;
+        tx.send(decision)
+            .map_err(|_| anyhow!("MCP server '{server_name}' stopped waiting for request {id:?}"))
+    }
+
+    async fn handle_request(
+        &self,
+        server_name: String,
+        config: McpSamplingConfig,
+        tx_event: Sender<Event>,
+        id: RequestId,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult> {
+        // Reject anything that cannot run before asking the user about it.
+        let client = self
+            .client
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .ok_or_else(|| anyhow!("no model client is available for sampling yet"))?;
+        let max_tokens = u64::try_from(params.max_tokens)
+            .ok()
+            .filter(|max_tokens| *max_tokens > 0)
+            .ok_or_else(|| anyhow!("maxTokens must be positive, got {}", params.max_tokens))?;
+        let input = params
+            .messages
+            .iter()
+            .cloned()
+            .map(sampling_message_to_response_item)
+            .collect::<Result<Vec<_>>>()?;
+        let system_prompt = params.system_prompt.clone().unwrap_or_default();
+        let prompt_bytes =
+            system_prompt.len() + input.iter().map(response_item_text_len).sum::<usize>();
+        let prompt_tokens = approx_tokens(prompt_bytes);
+
+        let (remaining_tokens, approved_for_session) = {
+            let state = self.state.lock().await;
+            let used = state.tokens_used.get(&server_name).copied().unwrap_or(0);
+            output_allowance(
+                &server_name,
+                config.token_budget,
+                used,
+                prompt_tokens,
+                max_tokens,
+            )?;
+            (
+                config.token_budget.saturating_sub(used),
+                state.approved_for_session.contains(&server_name),
+            )
+        };
+
+        if !approved_for_session {
+            let decision = self
+                .request_approval(&server_name, id, &params, remaining_tokens, tx_event)
+                .await?;
+            match decision {
+                ReviewDecision::Approved => {}
+                ReviewDecision::ApprovedForSession => {
+                    self.state
+                        .lock()
+                        .await
+                        .approved_for_session
+                        .insert(server_name.clone());
+                }
+                ReviewDecision::Denied | ReviewDecision::Abort => {
+                    bail!("the user declined the sampling request");
+                }
+            }
+        }
+
+        // Other requests from the same server may have run while the user was
+        // deciding, so the budget is checked again and reserved before the
+        // completion starts.
+        let max_output_tokens = self.state.lock().await.reserve(
+            &server_name,
+            config.token_budget,
+            prompt_tokens,
+            max_tokens,
+        )?;
+        let reserved = prompt_tokens + max_output_tokens;
+        let prompt = Prompt {
+            input,
+            tools: Vec::new(),
+            parallel_tool_calls: false,
+            // Never send Codex's own instructions to a completion the server
+            // asked for.
+            base_instructions_override: Some(system_prompt),
+            output_schema: None,
+            max_output_tokens: Some(max_output_tokens),
+            temperature: sampling_temperature(
+                &server_name,
+                &client.get_model_family(),
+                params.temperature,
+            ),
+            stop_sequences: Vec::new(),
+        };
+        let stop_sequences: Vec<String> = params
+            .stop_sequences
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|stop| !stop.is_empty())
+            .collect();
+        let prompt = match client.get_provider().wire_api {
+            WireApi::Chat => Prompt {
+                stop_sequences: stop_sequences
+                    .iter()
+                    .take(MAX_PROVIDER_STOP_SEQUENCES)
+                    .cloned()
+                    .collect(),
+                ..prompt
+            },
+            WireApi::Responses => prompt,
+        };
+        let result = create_message(&client, &prompt, prompt_bytes, &stop_sequences).await;
+
+        let spent = result.as_ref().ok().map(|(_, tokens)| *tokens);
+        self.state
+            .lock()
+            .await
+            .settle(&server_name, reserved, spent);
+        result.map(|(result, _)| result)
+    }
+
+    async fn request_approval(
+        &self,
+        server_name: &str,
+        id: RequestId,
+        params: &CreateMessageRequestParams,
+        remaining_tokens: u64,
+        tx_event: Sender<Event>,
+    ) -> Result<ReviewDecision> {
+        let key = (server_name.to_string(), id.clone());
+        let (tx, rx) = oneshot::channel();
+        self.state.lock().await.pending.insert(key.clone(), tx);
+
+        let event = Event {
+            id: SAMPLING_EVENT_ID.to_string(),
+            msg: EventMsg::SamplingRequest(SamplingRequestEvent {
+                server_name: server_name.to_string(),
+                id,
+                system_prompt: params.system_prompt.clone(),
+                messages: params.messages.clone(),
+                max_tokens: params.max_tokens,
+                remaining_tokens,
+            }),
+        };
+        if tx_event.send(event).await.is_err() {
+            self.state.lock().await.pending.remove(&key);
+            bail!("session is shutting down");
+        }
+        rx.await
+            .context("sampling request was dropped before it was answered")
+    }
+}
+
+/// Runs a single completion for `prompt` and returns it together with the
+/// number of tokens it cost. The reply is cut at the first of
+/// `stop_sequences`, which covers providers that do not take them.
+async fn create_message(
+    client: &ModelClient,
+    prompt: &Prompt,
+    prompt_bytes: usize,
+    stop_sequences: &[String],
+) -> Result<(CreateMessageResult, u64)> {
+    let mut stream = client.stream(prompt).await?;
+    let mut text = String::new();
+    let mut usage = None;
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                for item in content {
+                    if let ContentItem::OutputText { text: delta } = item {
+                        text.push_str(&delta);
+                    }
+                }
+            }
+            ResponseEvent::Completed { token_usage, .. } => {
+                usage = token_usage;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let tokens = match &usage {
+        Some(usage) => usage.total_tokens.max(0) as u64,
+        None => approx_tokens(prompt_bytes + text.len()),
+    };
+    let stop_at = stop_sequences
+        .iter()
+        .filter_map(|stop| text.find(stop.as_str()))
+        .min();
+    let reached_max_tokens = usage.is_some_and(|usage| {
+        prompt
+            .max_output_tokens
+            .is_some_and(|max| usage.output_tokens.max(0) as u64 >= max)
+    });
+    let stop_reason = if let Some(end) = stop_at {
+        text.truncate(end);
+        "stopSequence"
+    } else if reached_max_tokens {
+        "maxTokens"
+    } else {
+        "endTurn"
+    };
+    let result = CreateMessageResult {
+        content: CreateMessageResultContent::TextContent(TextContent {
+            annotations: None,
+            text,
+            r#type: "text".to_string(),
+        }),
+        model: client.get_model(),
+        role: Role::Assistant,
+        stop_reason: Some(stop_reason.to_string()),
+    };
+    Ok((result, tokens))
+}
+
+/// Returns the temperature to send for a request from `server_name`. Models
+/// that reject a temperature still run the request, without one, since the
+/// sampling parameters are only preferences of the server.
+fn sampling_temperature(
+    server_name: &str,
+    model_family: &ModelFamily,
+    temperature: Option<f64>,
+) -> Option<f64> {
+    if temperature.is_some() && !model_family.supports_temperature {
+        warn!(
+            "ignoring the temperature in a sampling request from MCP server '{server_name}': model '{}' does not accept one",
+            model_family.slug
+        );
+        return None;
+    }
+    temperature
+}
+
+fn approx_tokens(bytes: usize) -> u64 {
+    bytes.div_ceil(APPROX_BYTES_PER_TOKEN) as u64
+}
+
+/// How many output tokens a request may use: `max_tokens`, capped by what is
+/// left of the budget once the prompt is paid for.
+fn output_allowance(
+    server_name: &str,
+    token_budget: u64,
+    used: u64,
+    prompt_tokens: u64,
+    max_tokens: u64,
+) -> Result<u64> {
+    let remaining = token_budget.saturating_sub(used);
+    if remaining == 0 {
+        bail!("MCP server '{server_name}' used up its sampling budget of {token_budget} tokens");
+    }
+    match remaining.checked_sub(prompt_tokens) {
+        Some(left) if left > 0 => Ok(max_tokens.min(left)),
+        _ => bail!(
+            "MCP server '{server_name}' has {remaining} sampling tokens left, which does not cover a prompt of about {prompt_tokens} tokens"
+        ),
+    }
+}
+
+fn sampling_message_to_response_item(message: SamplingMessage) -> Result<ResponseItem> {
+    let SamplingMessage { content, role } = message;
+    let content = match (content, &role) {
+        (SamplingMessageContent::TextContent(text), Role::User) => {
+            ContentItem::InputText { text: text.text }
+        }
+        (SamplingMessageContent::TextContent(text), Role::Assistant) => {
+            ContentItem::OutputText { text: text.text }
+        }
+        (SamplingMessageContent::ImageContent(image), _) => ContentItem::InputImage {
+            image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+        },
+        (SamplingMessageContent::AudioContent(_), _) => {
+            bail!("audio content is not supported for sampling");
+        }
+    };
+    let role = match role {
+        Role::User => "user",
+        Role::Assistant => "assistant",
+    };
+    Ok(ResponseItem::Message {
+        id: None,
+        role: role.to_string(),
+        content: vec![content],
+    })
+}
+
+fn response_item_text_len(item: &ResponseItem) -> usize {
+    match item {
+        ResponseItem::Message { content, .. } => content
+            .iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => text.len(),
+                ContentItem::InputImage { .. } => 0,
+            })
+            .sum(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::model_family::find_family_for_model;
+    use pretty_assertions::assert_eq;
+
+    fn text(text: &str) -> SamplingMessageContent {
+        SamplingMessageContent::TextContent(TextContent {
+            annotations: None,
+            text: text.to_string(),
+            r#type: "text".to_string(),
+        })
+    }
+
+    #[test]
+    fn sampling_messages_map_to_response_items() {
+        let user = sampling_message_to_response_item(SamplingMessage {
+            content: text("hello"),
+            role: Role::User,
+        })
+        .expect("user message");
+        let assistant = sampling_message_to_response_item(SamplingMessage {
+            content: text("hi"),
+            role: Role::Assistant,
+        })
+        .expect("assistant message");
+
+        assert_eq!(
+            vec![user, assistant],
+            vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "hello".to_string()
+                    }],
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "hi".to_string()
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_from_servers_without_opt_in_are_rejected() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let manager = SamplingRequestManager::default();
+        let send = manager.make_sender("docs".to_string(), None, tx_event);
+
+        let err = send(
+            RequestId::Integer(1),
+            CreateMessageRequestParams {
+                include_context: None,
+                max_tokens: 100,
+                messages: Vec::new(),
+                metadata: None,
+                model_preferences: None,
+                stop_sequences: None,
+                system_prompt: None,
+                temperature: None,
+            },
+        )
+        .await
+        .expect_err("sampling should be rejected");
+
+        assert_eq!(
+            err.to_string(),
+            "sampling is not enabled for MCP server 'docs'"
+        );
+        assert!(rx_event.is_empty());
+    }
+
+    fn request(max_tokens: i64, temperature: Option<f64>) -> CreateMessageRequestParams {
+        CreateMessageRequestParams {
+            include_context: None,
+            max_tokens,
+            messages: vec![SamplingMessage {
+                content: text("hello"),
+                role: Role::User,
+            }],
+            metadata: None,
+            model_preferences: None,
+            stop_sequences: None,
+            system_prompt: Some("be brief".to_string()),
+            temperature,
+        }
+    }
+
+    #[test]
+    fn reservations_keep_concurrent_requests_within_the_budget() {
+        let mut state = SamplingState::default();
+
+        assert_eq!(state.reserve("docs", 1000, 100, 600).expect("first"), 600);
+        // Only 300 tokens are left, 100 of which pay for the prompt.
+        assert_eq!(state.reserve("docs", 1000, 100, 600).expect("second"), 200);
+        let err = state
+            .reserve("docs", 1000, 100, 600)
+            .expect_err("budget is fully reserved");
+        assert_eq!(
+            err.to_string(),
+            "MCP server 'docs' used up its sampling budget of 1000 tokens"
+        );
+
+        // The first request only used 250 of the 700 tokens it reserved, and
+        // the second one failed.
+        state.settle("docs", 700, Some(250));
+        state.settle("docs", 300, None);
+        assert_eq!(state.tokens_used.get("docs"), Some(&250));
+    }
+
+    #[test]
+    fn prompts_larger_than_the_remaining_budget_are_rejected() {
+        let err = output_allowance("docs", 1000, 950, 80, 10).expect_err("prompt too large");
+        assert_eq!(
+            err.to_string(),
+            "MCP server 'docs' has 50 sampling tokens left, which does not cover a prompt of about 80 tokens"
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_are_rejected_before_prompting_when_they_cannot_run() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let manager = SamplingRequestManager::default();
+        let send = manager.make_sender(
+            "docs".to_string(),
+            Some(McpSamplingConfig { token_budget: 1000 }),
+            tx_event,
+        );
+
+        let err = send(RequestId::Integer(1), request(100, None))
+            .await
+            .expect_err("no client yet");
+        assert_eq!(
+            err.to_string(),
+            "no model client is available for sampling yet"
+        );
+
+        let (_session, turn) = make_session_and_context();
+        manager.set_client(turn.client.clone());
+        let err = send(RequestId::Integer(2), request(0, None))
+            .await
+            .expect_err("maxTokens must be positive");
+        assert_eq!(err.to_string(), "maxTokens must be positive, got 0");
+
+        assert!(rx_event.is_empty());
+    }
+
+    #[test]
+    fn temperature_is_dropped_for_models_that_reject_it() {
+        let reasoning = find_family_for_model("gpt-5").expect("gpt-5 family");
+        let chat = find_family_for_model("gpt-4.1").expect("gpt-4.1 family");
+
+        assert_eq!(sampling_temperature("docs", &reasoning, Some(0.2)), None);
+        assert_eq!(sampling_temperature("docs", &chat, Some(0.2)), Some(0.2));
+        assert_eq!(sampling_temperature("docs", &chat, None), None);
+    }
+
+    #[tokio::test]
+    async fn denied_requests_report_an_error() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let manager = SamplingRequestManager::default();
+        let (_session, turn) = make_session_and_context();
+        manager.set_client(turn.client);
+        let send = manager.make_sender(
+            "docs".to_string(),
+            Some(McpSamplingConfig { token_budget: 1000 }),
+            tx_event,
+        );
+
+        let request = tokio::spawn(send(RequestId::Integer(7), request(100, None)));
+
+        let event = rx_event.recv().await.expect("sampling request event");
+        let EventMsg::SamplingRequest(event) = event.msg else {
+            panic!("expected SamplingRequest, got {:?}", event.msg);
+        };
+        assert_eq!(event.server_name, "docs");
+        assert_eq!(event.system_prompt.as_deref(), Some("be brief"));
+        assert_eq!(event.remaining_tokens, 1000);
+
+        manager
+            .resolve("docs".to_string(), event.id, ReviewDecision::Denied)
+            .await
+            .expect("resolve pending request");
+        let err = request
+            .await
+            .expect("join request")
+            .expect_err("denied request should fail");
+        assert_eq!(err.to_string(), "the user declined the sampling request");
+    }
+}
@@ -11,41 +11,71 @@ use std::collections::HashSet;
 use std::env;
 use std::ffi::OsString;
 use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::sync::Weak;
 use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
+use async_channel::Sender;
+use codex_rmcp_client::CancelElicitation;
+use codex_rmcp_client::ClientCallbacks;
+use codex_rmcp_client::ListRoots;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_rmcp_client::OnListChanged;
 use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::SendElicitation;
+use futures::FutureExt;
 use mcp_types::ClientCapabilities;
+use mcp_types::ClientCapabilitiesRoots;
+use mcp_types::ElicitResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::Implementation;
+use mcp_types::ListPromptsRequestParams;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
 use mcp_types::ListResourcesResult;
+use mcp_types::Prompt;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
+use mcp_types::RequestId;
 use mcp_types::Resource;
 use mcp_types::ResourceTemplate;
+use mcp_types::Root;
 use mcp_types::Tool;
 
 use serde_json::json;
 use sha1::Digest;
 use sha1::Sha1;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 use tracing::info;
 use tracing::warn;
 
+use crate::client::ModelClient;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
+use crate::mcp::sampling::SamplingRequestManager;
+use crate::protocol::ElicitationCancelledEvent;
+use crate::protocol::ElicitationRequestEvent;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::protocol::McpListChangedEvent;
+use crate::protocol::McpListChangedKind;
+use crate::protocol::ReviewDecision;
 
 /// Delimiter used to separate the server name from the tool name in a fully
 /// qualified tool name.
 ///
 /// OpenAI requires tool names to conform to `^[a-zA-Z0-9_-]+$`, so we must
 /// choose a delimiter from this character set.
-const MCP_TOOL_NAME_DELIMITER: &str = "__";
+pub(crate) const MCP_TOOL_NAME_DELIMITER: &str = "__";
 const MAX_TOOL_NAME_LENGTH: usize = 64;
 
 /// Default timeout for initializing MCP server & initially listing tools.
@@ -58,7 +88,186 @@ const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(60);
 /// spawned successfully.
 pub type ClientStartErrors = HashMap<String, anyhow::Error>;
 
-fn qualify_tools(tools: Vec<ToolInfo>) -> HashMap<String, ToolInfo> {
+/// Event id used for `EventMsg::ElicitationRequest`. Elicitations are not
+/// tied to a submission: a server may ask at any time.
+const ELICITATION_EVENT_ID: &str = "mcp_elicitation_request";
+
+/// Event id used for `EventMsg::McpListChanged`.
+const LIST_CHANGED_EVENT_ID: &str = "mcp_list_changed";
+
+type PendingElicitations = HashMap<(String, RequestId), oneshot::Sender<ElicitResult>>;
+
+/// Routes elicitation requests from MCP servers to the user as
+/// `EventMsg::ElicitationRequest` and waits for the matching
+/// `Op::ResolveElicitation`.
+#[derive(Clone, Default)]
+struct ElicitationRequestManager {
+    pending: Arc<Mutex<PendingElicitations>>,
+}
+
+impl ElicitationRequestManager {
+    fn make_sender(&self, server_name: String, tx_event: Sender<Event>) -> SendElicitation {
+        let pending = Arc::clone(&self.pending);
+        Arc::new(move |id, params| {
+            let pending = Arc::clone(&pending);
+            let server_name = server_name.clone();
+            let tx_event = tx_event.clone();
+            async move {
+                let key = (server_name.clone(), id.clone());
+                let (tx, rx) = oneshot::channel();
+                pending.lock().await.insert(key.clone(), tx);
+
+                let event = Event {
+                    id: ELICITATION_EVENT_ID.to_string(),
+                    msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                        server_name,
+                        id,
+                        message: params.message,
+                        requested_schema: params.requested_schema,
+                    }),
+                };
+                if tx_event.send(event).await.is_err() {
+                    pending.lock().await.remove(&key);
+                    return Err(anyhow!("session is shutting down"));
+                }
+                rx.await
+                    .context("elicitation request was dropped before it was answered")
+            }
+            .boxed()
+        })
+    }
+
+    /// Builds the callback for requests the server cancels: the request is
+    /// dropped and the client is told to close its form.
+    fn make_canceller(&self, server_name: String, tx_event: Sender<Event>) -> CancelElicitation {
+        let pending = Arc::clone(&self.pending);
+        Arc::new(move |id| {
+            let pending = Arc::clone(&pending);
+            let server_name = server_name.clone();
+            let tx_event = tx_event.clone();
+            tokio::spawn(async move {
+                let key = (server_name, id);
+                if pending.lock().await.remove(&key).is_none() {
+                    return;
+                }
+                let (server_name, id) = key;
+                let event = Event {
+                    id: ELICITATION_EVENT_ID.to_string(),
+                    msg: EventMsg::ElicitationCancelled(ElicitationCancelledEvent {
+                        server_name,
+                        id,
+                    }),
+                };
+                let _ = tx_event.send(event).await;
+            });
+        })
+    }
+
+    async fn resolve(
+        &self,
+        server_name: String,
+        id: RequestId,
+        response: ElicitResult,
+    ) -> Result<()> {
+        let tx = self
+            .pending
+            .lock()
+            .await
+            .remove(&(server_name.clone(), id.clone()))
+            .ok_or_else(|| {
+                anyhow!("no pending elicitation request {id:?} from MCP server '{server_name}'")
+            })?;
+        tx.send(response)
+            .map_err(|_| anyhow!("MCP server '{server_name}' stopped waiting for request {id:?}"))
+    }
+}
+
+/// Builds the `on_list_changed` callback for one server. Notifications are
+/// queued on `tx` and handled by [`refresh_on_list_changed`].
+fn list_changed_sender(
+    server_name: String,
+    tx: mpsc::UnboundedSender<(String, McpListChangedKind)>,
+) -> OnListChanged {
+    Arc::new(move |kind| {
+        // The receiver only goes away when the session shuts down.
+        let _ = tx.send((server_name.clone(), kind));
+    })
+}
+
+/// Re-fetches a server's tools whenever it reports that they changed and
+/// tells the client about every change. Holds only weak references to the
+/// clients so it ends together with the connection manager.
+async fn refresh_on_list_changed(
+    mut rx: mpsc::UnboundedReceiver<(String, McpListChangedKind)>,
+    clients: HashMap<String, (Weak<RmcpClient>, Duration)>,
+    tool_filters: HashMap<String, ToolFilter>,
+    tools: Arc<RwLock<HashMap<String, ToolInfo>>>,
+    tx_event: Sender<Event>,
+) {
+    while let Some((server_name, kind)) = rx.recv().await {
+        if kind == McpListChangedKind::Tools {
+            let Some((client, timeout)) = clients.get(&server_name) else {
+                continue;
+            };
+            let Some(client) = client.upgrade() else {
+                break;
+            };
+            let listed = match client.list_tools(None, Some(*timeout)).await {
+                Ok(result) => result.tools,
+                Err(err) => {
+                    warn!("Failed to refresh tools for MCP server '{server_name}': {err:#}");
+                    continue;
+                }
+            };
+            let server_tools = listed
+                .into_iter()
+                .map(|tool| ToolInfo {
+                    server_name: server_name.clone(),
+                    tool_name: tool.name.clone(),
+                    tool,
+                })
+                .collect();
+            let server_tools = filter_tools(server_tools, &tool_filters);
+
+            let mut tools = tools.write().unwrap_or_else(PoisonError::into_inner);
+            replace_server_tools(&mut tools, &server_name, server_tools);
+            info!("refreshed tools for MCP server '{server_name}'");
+        }
+
+        let event = Event {
+            id: LIST_CHANGED_EVENT_ID.to_string(),
+            msg: EventMsg::McpListChanged(McpListChangedEvent { server_name, kind }),
+        };
+        if tx_event.send(event).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Replaces the tools of `server_name` in `tools` with `server_tools` and
+/// qualifies the combined list again.
+fn replace_server_tools(
+    tools: &mut HashMap<String, ToolInfo>,
+    server_name: &str,
+    server_tools: Vec<ToolInfo>,
+) {
+    let mut all_tools: Vec<ToolInfo> = tools
+        .drain()
+        .map(|(_, tool)| tool)
+        .filter(|tool| tool.server_name != server_name)
+        .collect();
+    all_tools.extend(server_tools);
+    *tools = qualify_tools(all_tools);
+}
+
+/// Assigns each tool its model-facing name. Tools are ordered by server and
+/// tool name first, so when two of them end up with the same name the same one
+/// wins every time, no matter how `tools` was collected.
+fn qualify_tools(mut tools: Vec<ToolInfo>) -> HashMap<String, ToolInfo> {
+    tools.sort_by(|a, b| {
+        (a.server_name.as_str(), a.tool_name.as_str())
+            .cmp(&(b.server_name.as_str(), b.tool_name.as_str()))
+    });
     let mut used_names = HashSet::new();
     let mut qualified_tools = HashMap::new();
     for tool in tools {
@@ -111,11 +320,21 @@ pub(crate) struct McpConnectionManager {
     /// the user configuration.
     clients: HashMap<String, ManagedClient>,
 
-    /// Fully qualified tool name -> tool instance.
-    tools: HashMap<String, ToolInfo>,
+    /// Fully qualified tool name -> tool instance. Refreshed in the
+    /// background when a server reports that its tools changed.
+    tools: Arc<RwLock<HashMap<String, ToolInfo>>>,
 
     /// Server-name -> configured tool filters.
     tool_filters: HashMap<String, ToolFilter>,
+
+    /// Elicitation requests waiting for the user's answer.
+    elicitation_requests: ElicitationRequestManager,
+
+    /// Sampling requests from servers that opted in.
+    sampling_requests: SamplingRequestManager,
+
+    /// Roots returned to servers that call `roots/list`.
+    roots: Arc<RwLock<Vec<Root>>>,
 }
 
 impl McpConnectionManager {
@@ -127,19 +346,40 @@ impl McpConnectionManager {
     ///
     /// Servers that fail to start are reported in `ClientStartErrors`: the
     /// user should be informed about these errors.
+    ///
+    /// Elicitation and sampling requests and list change notifications from
+    /// the servers are sent on `tx_event`. `roots` answers their `roots/list`
+    /// requests until [`Self::set_roots`] replaces it.
     pub async fn new(
         mcp_servers: HashMap<String, McpServerConfig>,
         store_mode: OAuthCredentialsStoreMode,
+        roots: Vec<Root>,
+        tx_event: Sender<Event>,
     ) -> Result<(Self, ClientStartErrors)> {
+        let roots = Arc::new(RwLock::new(roots));
+
         // Early exit if no servers are configured.
         if mcp_servers.is_empty() {
-            return Ok((Self::default(), ClientStartErrors::default()));
+            return Ok((
+                Self {
+                    roots,
+                    ..Self::default()
+                },
+                ClientStartErrors::default(),
+            ));
         }
 
         // Launch all configured servers concurrently.
         let mut join_set = JoinSet::new();
         let mut errors = ClientStartErrors::new();
         let mut tool_filters: HashMap<String, ToolFilter> = HashMap::new();
+        let elicitation_requests = ElicitationRequestManager::default();
+        let sampling_requests = SamplingRequestManager::default();
+        let (list_changed_tx, list_changed_rx) = mpsc::unbounded_channel();
+        let list_roots: ListRoots = {
+            let roots = Arc::clone(&roots);
+            Arc::new(move || roots.read().unwrap_or_else(PoisonError::into_inner).clone())
+        };
 
         for (server_name, cfg) in mcp_servers {
             // Validate server name before spawning
@@ -168,13 +408,34 @@ impl McpConnectionManager {
                 _ => Ok(None),
             };
 
+            let callbacks = ClientCallbacks {
+                send_elicitation: elicitation_requests
+                    .make_sender(server_name.clone(), tx_event.clone()),
+                cancel_elicitation: elicitation_requests
+                    .make_canceller(server_name.clone(), tx_event.clone()),
+                send_create_message: sampling_requests.make_sender(
+                    server_name.clone(),
+                    cfg.sampling,
+                    tx_event.clone(),
+                ),
+                on_list_changed: list_changed_sender(server_name.clone(), list_changed_tx.clone()),
+                list_roots: Arc::clone(&list_roots),
+            };
+
             join_set.spawn(async move {
-                let McpServerConfig { transport, .. } = cfg;
+                let McpServerConfig {
+                    transport,
+                    sampling,
+                    ..
+                } = cfg;
                 let params = mcp_types::InitializeRequestParams {
                     capabilities: ClientCapabilities {
                         experimental: None,
-                        roots: None,
-                        sampling: None,
+                        roots: Some(ClientCapabilitiesRoots {
+                            list_changed: Some(true),
+                        }),
+                        // Only advertised to servers that opted in.
+                        sampling: sampling.map(|_| json!({})),
                         // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
                         // indicates this should be an empty object.
                         elicitation: Some(json!({})),
@@ -208,7 +469,11 @@ impl McpConnectionManager {
                             Ok(client) => {
                                 let client = Arc::new(client);
                                 client
-                                    .initialize(params.clone(), Some(startup_timeout))
+                                    .initialize(
+                                        params.clone(),
+                                        Some(startup_timeout),
+                                        callbacks.clone(),
+                                    )
                                     .await
                                     .map(|_| client)
                             }
@@ -234,7 +499,11 @@ impl McpConnectionManager {
                             Ok(client) => {
                                 let client = Arc::new(client);
                                 client
-                                    .initialize(params.clone(), Some(startup_timeout))
+                                    .initialize(
+                                        params.clone(),
+                                        Some(startup_timeout),
+                                        callbacks.clone(),
+                                    )
                                     .await
                                     .map(|_| client)
                             }
@@ -287,13 +556,33 @@ impl McpConnectionManager {
         };
 
         let filtered_tools = filter_tools(all_tools, &tool_filters);
-        let tools = qualify_tools(filtered_tools);
+        let tools = Arc::new(RwLock::new(qualify_tools(filtered_tools)));
+
+        let weak_clients = clients
+            .iter()
+            .map(|(server_name, managed)| {
+                (
+                    server_name.clone(),
+                    (Arc::downgrade(&managed.client), managed.startup_timeout),
+                )
+            })
+            .collect();
+        tokio::spawn(refresh_on_list_changed(
+            list_changed_rx,
+            weak_clients,
+            tool_filters.clone(),
+            Arc::clone(&tools),
+            tx_event,
+        ));
 
         Ok((
             Self {
                 clients,
                 tools,
                 tool_filters,
+                elicitation_requests,
+                sampling_requests,
+                roots,
             },
             errors,
         ))
@@ -303,6 +592,8 @@ impl McpConnectionManager {
     /// fully-qualified name for the tool.
     pub fn list_all_tools(&self) -> HashMap<String, Tool> {
         self.tools
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
             .iter()
             .map(|(name, tool)| (name.clone(), tool.tool.clone()))
             .collect()
@@ -435,6 +726,117 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns a single map that contains all prompts. Each key is the
+    /// server name and the value is a vector of prompts.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut join_set = JoinSet::new();
+
+        for (server_name, managed_client) in &self.clients {
+            let server_name_cloned = server_name.clone();
+            let client_clone = managed_client.client.clone();
+            let timeout = managed_client.tool_timeout;
+
+            join_set.spawn(async move {
+                let mut collected: Vec<Prompt> = Vec::new();
+                let mut cursor: Option<String> = None;
+
+                loop {
+                    let params = cursor.as_ref().map(|next| ListPromptsRequestParams {
+                        cursor: Some(next.clone()),
+                    });
+                    let response = match client_clone.list_prompts(params, timeout).await {
+                        Ok(result) => result,
+                        Err(err) => return (server_name_cloned, Err(err)),
+                    };
+
+                    collected.extend(response.prompts);
+
+                    match response.next_cursor {
+                        Some(next) => {
+                            if cursor.as_ref() == Some(&next) {
+                                return (
+                                    server_name_cloned,
+                                    Err(anyhow!("prompts/list returned duplicate cursor")),
+                                );
+                            }
+                            cursor = Some(next);
+                        }
+                        None => return (server_name_cloned, Ok(collected)),
+                    }
+                }
+            });
+        }
+
+        let mut aggregated: HashMap<String, Vec<Prompt>> = HashMap::new();
+
+        while let Some(join_res) = join_set.join_next().await {
+            match join_res {
+                Ok((server_name, Ok(prompts))) => {
+                    aggregated.insert(server_name, prompts);
+                }
+                Ok((server_name, Err(err))) => {
+                    warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when listing prompts for MCP server: {err:#}");
+                }
+            }
+        }
+
+        aggregated
+    }
+
+    /// Deliver the user's answer to a pending elicitation request.
+    pub async fn resolve_elicitation(
+        &self,
+        server_name: String,
+        id: RequestId,
+        response: ElicitResult,
+    ) -> Result<()> {
+        self.elicitation_requests
+            .resolve(server_name, id, response)
+            .await
+    }
+
+    /// Deliver the user's decision on a pending sampling request.
+    pub async fn resolve_sampling_request(
+        &self,
+        server_name: String,
+        id: RequestId,
+        decision: ReviewDecision,
+    ) -> Result<()> {
+        self.sampling_requests
+            .resolve(server_name, id, decision)
+            .await
+    }
+
+    /// Use `client` for sampling requests from now on.
+    pub(crate) fn set_sampling_client(&self, client: ModelClient) {
+        self.sampling_requests.set_client(client);
+    }
+
+    /// Replace the roots returned by `roots/list`, sending
+    /// `notifications/roots/list_changed` to every server if they differ.
+    pub(crate) fn set_roots(&self, roots: Vec<Root>) {
+        {
+            let mut current = self.roots.write().unwrap_or_else(PoisonError::into_inner);
+            if *current == roots {
+                return;
+            }
+            *current = roots;
+        }
+
+        for (server_name, managed) in &self.clients {
+            let client = Arc::clone(&managed.client);
+            let server_name = server_name.clone();
+            tokio::spawn(async move {
+                if let Err(err) = client.notify_roots_list_changed().await {
+                    warn!("Failed to notify MCP server '{server_name}' of new roots: {err:#}");
+                }
+            });
+        }
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -520,8 +922,30 @@ impl McpConnectionManager {
             .with_context(|| format!("resources/read failed for `{server}` ({uri})"))
     }
 
+    /// Fetch a prompt from the specified server.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        params: GetPromptRequestParams,
+    ) -> Result<GetPromptResult> {
+        let managed = self
+            .clients
+            .get(server)
+            .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
+        let client = managed.client.clone();
+        let timeout = managed.tool_timeout;
+        let name = params.name.clone();
+
+        client
+            .get_prompt(params, timeout)
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}/{name}`"))
+    }
+
     pub fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.tools
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
             .get(tool_name)
             .map(|tool| (tool.server_name.clone(), tool.tool_name.clone()))
     }
@@ -749,6 +1173,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_qualify_tools_collisions_survive_refresh() {
+        // Both qualify to `mcp__a__b__c`.
+        let mut tools = qualify_tools(vec![
+            create_test_tool("a__b", "c"),
+            create_test_tool("a", "b__c"),
+        ]);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools["mcp__a__b__c"].server_name, "a");
+
+        for _ in 0..16 {
+            replace_server_tools(
+                &mut tools,
+                "a__b",
+                vec![create_test_tool("a__b", "c"), create_test_tool("a__b", "d")],
+            );
+            assert_eq!(tools["mcp__a__b__c"].server_name, "a");
+            assert_eq!(tools["mcp__a__b__d"].server_name, "a__b");
+        }
+    }
+
     #[test]
     fn tool_filter_allows_by_default() {
         let filter = ToolFilter::default();
@@ -819,4 +1264,82 @@ mod tests {
         assert_eq!(filtered[0].server_name, "server1");
         assert_eq!(filtered[0].tool_name, "tool_a");
     }
+
+    #[tokio::test]
+    async fn elicitation_requests_round_trip_through_events() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let manager = ElicitationRequestManager::default();
+        let send = manager.make_sender("docs".to_string(), tx_event);
+
+        let pending = tokio::spawn(send(
+            RequestId::Integer(7),
+            mcp_types::ElicitRequestParams {
+                message: "Which branch?".to_string(),
+                requested_schema: mcp_types::ElicitRequestParamsRequestedSchema {
+                    r#type: "object".to_string(),
+                    properties: json!({ "branch": { "type": "string" } }),
+                    required: Some(vec!["branch".to_string()]),
+                },
+            },
+        ));
+
+        let event = rx_event.recv().await.expect("elicitation event");
+        let EventMsg::ElicitationRequest(request) = event.msg else {
+            panic!("unexpected event: {:?}", event.msg);
+        };
+        assert_eq!(request.server_name, "docs");
+        assert_eq!(request.id, RequestId::Integer(7));
+        assert_eq!(request.message, "Which branch?");
+
+        let answer = ElicitResult {
+            action: "accept".to_string(),
+            content: Some(json!({ "branch": "main" })),
+        };
+        manager
+            .resolve("docs".to_string(), RequestId::Integer(7), answer.clone())
+            .await
+            .expect("pending request");
+        assert_eq!(pending.await.expect("task").expect("answered"), answer);
+
+        assert!(
+            manager
+                .resolve("docs".to_string(), RequestId::Integer(7), answer)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_elicitations_are_dropped_and_announced() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let manager = ElicitationRequestManager::default();
+        let send = manager.make_sender("docs".to_string(), tx_event.clone());
+        let cancel = manager.make_canceller("docs".to_string(), tx_event);
+
+        let pending = tokio::spawn(send(
+            RequestId::Integer(7),
+            mcp_types::ElicitRequestParams {
+                message: "Which branch?".to_string(),
+                requested_schema: mcp_types::ElicitRequestParamsRequestedSchema {
+                    r#type: "object".to_string(),
+                    properties: json!({}),
+                    required: None,
+                },
+            },
+        ));
+        let event = rx_event.recv().await.expect("elicitation event");
+        assert!(matches!(event.msg, EventMsg::ElicitationRequest(_)));
+
+        // The connection drops the request future once the server cancels.
+        pending.abort();
+        cancel(RequestId::Integer(7));
+
+        let event = rx_event.recv().await.expect("cancellation event");
+        let EventMsg::ElicitationCancelled(cancelled) = event.msg else {
+            panic!("unexpected event: {:?}", event.msg);
+        };
+        assert_eq!(cancelled.server_name, "docs");
+        assert_eq!(cancelled.id, RequestId::Integer(7));
+        assert!(manager.pending.lock().await.is_empty());
+    }
 }
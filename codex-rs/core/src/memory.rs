@@ -0,0 +1,405 @@
+//! Per-project memory that persists across sessions.
+//!
+//! Memories are short notes the agent saves with the `memory_write` tool so
+//! that later sessions in the same project can recall them. Each project gets
+//! its own JSON-Lines file under `~/.codex/memories`, keyed by the Git
+//! repository root (or the working directory outside a repository):
+//!
+//! ````text
+//! {"id":"3f9c2a1b","created_at":"2025-01-01T00:00:00Z","content":"...","tags":["build"]}
+//! ````
+//!
+//! When the memory feature is enabled, the most recent entries are summarized
+//! into the user instructions at the start of every session.
+
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
+use tokio::fs;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::git_info::get_git_repo_root;
+
+/// Directory inside `~/.codex` that holds one memory file per project.
+pub const MEMORIES_SUBDIR: &str = "memories";
+
+/// Maximum number of entries included in the session's initial context.
+const SUMMARY_MAX_ENTRIES: usize = 20;
+
+/// Entries longer than this are cut short in the initial context; the full
+/// text remains available through `memory_search`.
+const SUMMARY_MAX_ENTRY_CHARS: usize = 300;
+
+const MAX_LOCK_RETRIES: usize = 10;
+const LOCK_RETRY_SLEEP: Duration = Duration::from_millis(100);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MemoryEntry {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// The memories saved for a single project.
+#[derive(Debug, Clone)]
+pub struct MemoryStore {
+    path: PathBuf,
+}
+
+impl MemoryStore {
+    /// Returns the store for the project that contains `cwd`.
+    pub fn for_project(codex_home: &Path, cwd: &Path) -> Self {
+        let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        let root = dunce::canonicalize(&root).unwrap_or(root);
+        let mut hasher = Sha1::new();
+        hasher.update(root.to_string_lossy().as_bytes());
+        let digest = format!("{:x}", hasher.finalize());
+        let name = root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "root".to_string());
+        let path = codex_home
+            .join(MEMORIES_SUBDIR)
+            .join(format!("{name}-{}.jsonl", &digest[..12]));
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns every entry, oldest first.
+    pub async fn list(&self) -> io::Result<Vec<MemoryEntry>> {
+        let contents = match fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        Ok(parse_entries(&self.path, &contents))
+    }
+
+    /// Saves a new entry and returns it.
+    pub async fn write(&self, content: String, tags: Vec<String>) -> io::Result<MemoryEntry> {
+        let entry = MemoryEntry {
+            id: Uuid::new_v4().simple().to_string()[..8].to_string(),
+            created_at: Utc::now(),
+            content,
+            tags,
+        };
+        let mut line = serde_json::to_string(&entry).map_err(io::Error::other)?;
+        line.push('\n');
+
+        let path = self.path.clone();
+        self.with_lock(move || {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?;
+            file.write_all(line.as_bytes())?;
+            file.flush()
+        })
+        .await?;
+        Ok(entry)
+    }
+
+    /// Returns up to `limit` entries matching `query`, best match first. Every
+    /// whitespace-separated term that appears in an entry's content or tags
+    /// counts towards its score; ties go to the newer entry. An empty query
+    /// returns the most recent entries.
+    pub async fn search(&self, query: &str, limit: usize) -> io::Result<Vec<MemoryEntry>> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let mut scored: Vec<(usize, MemoryEntry)> = self
+            .list()
+            .await?
+            .into_iter()
+            .map(|entry| (match_score(&entry, &terms), entry))
+            .filter(|(score, _)| terms.is_empty() || *score > 0)
+            .collect();
+        scored.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| b.created_at.cmp(&a.created_at))
+        });
+        Ok(scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, entry)| entry)
+            .collect())
+    }
+
+    /// Removes the entries whose ids are in `ids` and returns the ones that
+    /// were removed.
+    pub async fn delete(&self, ids: &[String]) -> io::Result<Vec<MemoryEntry>> {
+        let path = self.path.clone();
+        let ids = ids.to_vec();
+        self.with_lock(move || {
+            let contents = match std::fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(e) => return Err(e),
+            };
+            let (removed, kept): (Vec<MemoryEntry>, Vec<MemoryEntry>) =
+                parse_entries(&path, &contents)
+                    .into_iter()
+                    .partition(|entry| ids.contains(&entry.id));
+            if removed.is_empty() {
+                return Ok(removed);
+            }
+
+            let mut contents = String::new();
+            for entry in &kept {
+                contents.push_str(&serde_json::to_string(entry).map_err(io::Error::other)?);
+                contents.push('\n');
+            }
+            // Write to a sibling file and rename so a crash never leaves a
+            // truncated store behind.
+            let tmp_path = path.with_extension("jsonl.tmp");
+            std::fs::write(&tmp_path, contents)?;
+            std::fs::rename(&tmp_path, &path)?;
+            Ok(removed)
+        })
+        .await
+    }
+
+    /// Runs `op` on a blocking thread while holding an exclusive lock that
+    /// `write` and `delete` share, so an append never lands in a file that
+    /// `delete` is about to replace. The lock is taken on a sibling file
+    /// because `delete` swaps the store itself out with a rename.
+    async fn with_lock<T: Send + 'static>(
+        &self,
+        op: impl FnOnce() -> io::Result<T> + Send + 'static,
+    ) -> io::Result<T> {
+        let lock_path = self.path.with_extension("jsonl.lock");
+        tokio::task::spawn_blocking(move || {
+            if let Some(parent) = lock_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let lock_file = std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&lock_path)?;
+            // Retry a few times to avoid indefinite blocking when contended.
+            for _ in 0..MAX_LOCK_RETRIES {
+                match lock_file.try_lock() {
+                    Ok(()) => return op(),
+                    Err(std::fs::TryLockError::WouldBlock) => std::thread::sleep(LOCK_RETRY_SLEEP),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "could not acquire exclusive lock on memory store after multiple attempts",
+            ))
+        })
+        .await?
+    }
+}
+
+fn parse_entries(path: &Path, contents: &str) -> Vec<MemoryEntry> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                tracing::warn!("skipping malformed memory in {}: {e}", path.display());
+                None
+            }
+        })
+        .collect()
+}
+
+fn match_score(entry: &MemoryEntry, terms: &[String]) -> usize {
+    let content = entry.content.to_lowercase();
+    terms
+        .iter()
+        .filter(|term| {
+            content.contains(term.as_str())
+                || entry
+                    .tags
+                    .iter()
+                    .any(|tag| tag.to_lowercase().contains(term.as_str()))
+        })
+        .count()
+}
+
+/// Formats `entry` as a single line for tool output and the initial context.
+pub fn format_memory_entry(entry: &MemoryEntry, max_chars: Option<usize>) -> String {
+    let content = entry
+        .content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let content = match max_chars {
+        Some(max_chars) if content.chars().count() > max_chars => {
+            let truncated: String = content.chars().take(max_chars).collect();
+            format!("{truncated}…")
+        }
+        _ => content,
+    };
+    if entry.tags.is_empty() {
+        format!("[{}] {content}", entry.id)
+    } else {
+        format!("[{}] {content} (tags: {})", entry.id, entry.tags.join(", "))
+    }
+}
+
+/// Summarizes the project's most recent memories for the initial context, or
+/// returns `None` when there are none.
+pub(crate) async fn summarize_memories(config: &Config) -> Option<String> {
+    let store = MemoryStore::for_project(&config.codex_home, &config.cwd);
+    let entries = match store.search("", SUMMARY_MAX_ENTRIES).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("error reading memories: {e:#}");
+            return None;
+        }
+    };
+    if entries.is_empty() {
+        return None;
+    }
+    let mut summary = String::from(
+        "Memories saved in earlier sessions for this project, most recent first. Use memory_search to look up more and memory_delete to remove entries that are no longer accurate.\n",
+    );
+    for entry in &entries {
+        summary.push_str("\n- ");
+        summary.push_str(&format_memory_entry(entry, Some(SUMMARY_MAX_ENTRY_CHARS)));
+    }
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn store(codex_home: &TempDir, project: &TempDir) -> MemoryStore {
+        MemoryStore::for_project(codex_home.path(), project.path())
+    }
+
+    #[tokio::test]
+    async fn write_search_and_delete_round_trip() {
+        let codex_home = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        let store = store(&codex_home, &project);
+        assert_eq!(store.list().await.unwrap(), Vec::new());
+
+        let build = store
+            .write(
+                "Run `just fmt` before committing".to_string(),
+                vec!["build".to_string()],
+            )
+            .await
+            .unwrap();
+        let tests = store
+            .write("Integration tests need Docker".to_string(), Vec::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.list().await.unwrap(),
+            vec![build.clone(), tests.clone()]
+        );
+        assert_eq!(
+            store.search("BUILD fmt", 10).await.unwrap(),
+            vec![build.clone()]
+        );
+        assert_eq!(
+            store.search("docker", 10).await.unwrap(),
+            vec![tests.clone()]
+        );
+        assert_eq!(store.search("", 1).await.unwrap().len(), 1);
+
+        let removed = store.delete(std::slice::from_ref(&build.id)).await.unwrap();
+        assert_eq!(removed, vec![build]);
+        assert_eq!(store.list().await.unwrap(), vec![tests]);
+        assert_eq!(
+            store.delete(&["missing".to_string()]).await.unwrap(),
+            Vec::new()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_writes_survive_a_delete() {
+        let codex_home = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        let store = store(&codex_home, &project);
+        let stale = store
+            .write("no longer true".to_string(), Vec::new())
+            .await
+            .unwrap();
+
+        let writes: Vec<_> = (0..10)
+            .map(|i| {
+                let store = store.clone();
+                tokio::spawn(async move { store.write(format!("note {i}"), Vec::new()).await })
+            })
+            .collect();
+        let removed = store.delete(std::slice::from_ref(&stale.id)).await.unwrap();
+        for write in writes {
+            write.await.unwrap().unwrap();
+        }
+
+        assert_eq!(removed, vec![stale]);
+        let mut contents: Vec<String> = store
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.content)
+            .collect();
+        contents.sort();
+        let mut expected: Vec<String> = (0..10).map(|i| format!("note {i}")).collect();
+        expected.sort();
+        assert_eq!(contents, expected);
+    }
+
+    #[tokio::test]
+    async fn projects_do_not_share_memories() {
+        let codex_home = TempDir::new().unwrap();
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        store(&codex_home, &first)
+            .write("only for the first project".to_string(), Vec::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store(&codex_home, &second).list().await.unwrap(),
+            Vec::new()
+        );
+        assert_eq!(store(&codex_home, &first).list().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn format_truncates_long_entries() {
+        let entry = MemoryEntry {
+            id: "abcd1234".to_string(),
+            created_at: Utc::now(),
+            content: "line one\nline two".to_string(),
+            tags: vec!["notes".to_string()],
+        };
+        assert_eq!(
+            format_memory_entry(&entry, None),
+            "[abcd1234] line one line two (tags: notes)"
+        );
+        assert_eq!(
+            format_memory_entry(&entry, Some(4)),
+            "[abcd1234] line… (tags: notes)"
+        );
+    }
+}
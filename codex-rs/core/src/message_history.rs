@@ -16,8 +16,10 @@
 
 use std::fs::File;
 use std::fs::OpenOptions;
+use std::io::Read;
 use std::io::Result;
 use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 
 use serde::Deserialize;
@@ -29,6 +31,7 @@ use tokio::io::AsyncReadExt;
 
 use crate::config::Config;
 use crate::config::types::HistoryPersistence;
+use crate::session_crypto;
 
 use codex_protocol::ConversationId;
 #[cfg(unix)]
@@ -55,6 +58,105 @@ fn history_filepath(config: &Config) -> PathBuf {
     path
 }
 
+/// Parse one line of the history file, decrypting it if needed.
+fn parse_entry(line: &str) -> Option<HistoryEntry> {
+    let line = session_crypto::decode_line(line.trim_end()).ok()?;
+    serde_json::from_str(&line).ok()
+}
+
+/// Entries dropped from the history file by [`prune_history`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryPruneStats {
+    pub removed_entries: usize,
+    pub removed_bytes: u64,
+}
+
+/// Drop the history entries recorded before `min_ts` (seconds since the Unix
+/// epoch), then the oldest remaining entries until the file is at most
+/// `max_bytes` long. Unless `dry_run` is set, the kept entries are written to
+/// a sibling file that is renamed over the original while holding the same
+/// exclusive lock used by [`append_entry`].
+pub(crate) fn prune_history(
+    codex_home: &Path,
+    min_ts: Option<u64>,
+    max_bytes: Option<usize>,
+    dry_run: bool,
+) -> Result<HistoryPruneStats> {
+    let path = codex_home.join(HISTORY_FILENAME);
+    let mut history_file = match OpenOptions::new().read(true).write(true).open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(HistoryPruneStats::default());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut locked = false;
+    for _ in 0..MAX_RETRIES {
+        match history_file.try_lock() {
+            Ok(()) => {
+                locked = true;
+                break;
+            }
+            Err(std::fs::TryLockError::WouldBlock) => std::thread::sleep(RETRY_SLEEP),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    if !locked {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::WouldBlock,
+            "could not acquire exclusive lock on history file after multiple attempts",
+        ));
+    }
+
+    let mut contents = String::new();
+    history_file.read_to_string(&mut contents)?;
+    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
+
+    // Lines that are not valid entries are kept: they are not ours to judge.
+    let mut kept: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| match (min_ts, parse_entry(line)) {
+            (Some(min_ts), Some(entry)) => entry.ts >= min_ts,
+            _ => true,
+        })
+        .collect();
+    if let Some(max_bytes) = max_bytes {
+        let mut total: usize = kept.iter().map(|line| line.len()).sum();
+        let mut drop = 0;
+        while total > max_bytes && drop < kept.len() {
+            total -= kept[drop].len();
+            drop += 1;
+        }
+        kept.drain(..drop);
+    }
+
+    let kept_bytes: usize = kept.iter().map(|line| line.len()).sum();
+    let stats = HistoryPruneStats {
+        removed_entries: lines.len() - kept.len(),
+        removed_bytes: (contents.len() - kept_bytes) as u64,
+    };
+    if dry_run || stats.removed_entries == 0 {
+        return Ok(stats);
+    }
+
+    // Write to a sibling file and rename so a crash never leaves a truncated
+    // history behind. `append_entry` notices the swap and reopens the file.
+    let tmp_path = path.with_extension("jsonl.tmp");
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        options.mode(0o600);
+    }
+    let mut tmp_file = options.open(&tmp_path)?;
+    tmp_file.write_all(kept.concat().as_bytes())?;
+    tmp_file.flush()?;
+    std::fs::rename(&tmp_path, &path)?;
+    Ok(stats)
+}
+
 /// Append a `text` entry associated with `conversation_id` to the history file. Uses
 /// advisory file locking to ensure that concurrent writes do not interleave,
 /// which entails a small amount of blocking I/O internally.
@@ -93,8 +195,14 @@ pub(crate) async fn append_entry(
         ts,
         text: text.to_string(),
     };
-    let mut line = serde_json::to_string(&entry)
+    let line = serde_json::to_string(&entry)
         .map_err(|e| std::io::Error::other(format!("failed to serialise history entry: {e}")))?;
+    let cipher = if config.encrypt_session_data {
+        Some(session_crypto::writer_cipher(&config.codex_home)?)
+    } else {
+        None
+    };
+    let mut line = session_crypto::encode_line(cipher.as_deref(), line)?;
     line.push('\n');
 
     // Open in append-only mode.
@@ -116,6 +224,12 @@ pub(crate) async fn append_entry(
         for _ in 0..MAX_RETRIES {
             match history_file.try_lock() {
                 Ok(()) => {
+                    // `prune_history` may have renamed a new file over the
+                    // one we opened while we waited for the lock.
+                    if !is_current_file(&history_file, &path)? {
+                        history_file = options.open(&path)?;
+                        continue;
+                    }
                     // While holding the exclusive lock, write the full line.
                     history_file.write_all(line.as_bytes())?;
                     history_file.flush()?;
@@ -231,6 +345,13 @@ pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<Hist
                     };
 
                     if idx == offset {
+                        let line = match session_crypto::decode_line(&line) {
+                            Ok(line) => line,
+                            Err(e) => {
+                                tracing::warn!(error = %e, "failed to decrypt history entry");
+                                return None;
+                            }
+                        };
                         match serde_json::from_str::<HistoryEntry>(&line) {
                             Ok(entry) => return Some(entry),
                             Err(e) => {
@@ -263,6 +384,23 @@ pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<Hist
     None
 }
 
+/// Whether `file` is still the file at `path`.
+#[cfg(unix)]
+fn is_current_file(file: &File, path: &Path) -> Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    let open = file.metadata()?;
+    match std::fs::metadata(path) {
+        Ok(current) => Ok(open.dev() == current.dev() && open.ino() == current.ino()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(not(unix))]
+fn is_current_file(_file: &File, _path: &Path) -> Result<bool> {
+    Ok(true)
+}
+
 /// On Unix systems ensure the file permissions are `0o600` (rw-------). If the
 /// permissions cannot be changed the error is propagated to the caller.
 #[cfg(unix)]
@@ -14,6 +14,8 @@
 //! 3.  We do **not** walk past the Git root.
 
 use crate::config::Config;
+use crate::features::Feature;
+use crate::memory::summarize_memories;
 use dunce::canonicalize as normalize_path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
@@ -28,9 +30,26 @@ pub const LOCAL_PROJECT_DOC_FILENAME: &str = "AGENTS.override.md";
 /// be concatenated with the following separator.
 const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
 
-/// Combines `Config::instructions` and `AGENTS.md` (if present) into a single
-/// string of instructions.
+/// Separates the saved memories from the rest of the user instructions.
+const MEMORIES_SEPARATOR: &str = "\n\n--- memories ---\n\n";
+
+/// Combines `Config::instructions`, `AGENTS.md` (if present) and, when the
+/// memory feature is enabled, a summary of the project's saved memories into a
+/// single string of instructions.
 pub(crate) async fn get_user_instructions(config: &Config) -> Option<String> {
+    let instructions = instructions_with_project_doc(config).await;
+    if !config.features.enabled(Feature::Memory) {
+        return instructions;
+    }
+    match (instructions, summarize_memories(config).await) {
+        (Some(instructions), Some(memories)) => {
+            Some(format!("{instructions}{MEMORIES_SEPARATOR}{memories}"))
+        }
+        (instructions, memories) => instructions.or(memories),
+    }
+}
+
+async fn instructions_with_project_doc(config: &Config) -> Option<String> {
     match read_project_docs(config).await {
         Ok(Some(project_doc)) => match &config.user_instructions {
             Some(original_instructions) => Some(format!(
@@ -200,6 +219,7 @@ mod tests {
     use super::*;
     use crate::config::ConfigOverrides;
     use crate::config::ConfigToml;
+    use crate::memory::MemoryStore;
     use std::fs;
     use tempfile::TempDir;
 
@@ -447,4 +467,28 @@ mod tests {
                 .eq(DEFAULT_PROJECT_DOC_FILENAME)
         );
     }
+
+    /// Saved memories are appended after the project doc when the memory
+    /// feature is enabled, and ignored otherwise.
+    #[tokio::test]
+    async fn memories_follow_project_doc_when_enabled() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::write(tmp.path().join("AGENTS.md"), "project doc").unwrap();
+        let codex_home = tempfile::tempdir().expect("tempdir");
+
+        let mut cfg = make_config(&tmp, 4096, None);
+        cfg.codex_home = codex_home.path().to_path_buf();
+        let entry = MemoryStore::for_project(&cfg.codex_home, &cfg.cwd)
+            .write("tests need Docker".to_string(), Vec::new())
+            .await
+            .expect("write memory");
+
+        assert_eq!(get_user_instructions(&cfg).await.unwrap(), "project doc");
+
+        cfg.features.enable(Feature::Memory);
+        let res = get_user_instructions(&cfg).await.expect("instructions");
+        let (doc, memories) = res.split_once(MEMORIES_SEPARATOR).expect("separator");
+        assert_eq!(doc, "project doc");
+        assert!(memories.ends_with(&format!("\n- [{}] tests need Docker", entry.id)));
+    }
 }
@@ -1,4 +1,11 @@
+use std::path::Path;
+
+use serde_json::Value;
+use serde_json::json;
+use url::Url;
+
 use crate::protocol::ReviewFinding;
+use crate::protocol::ReviewOutputEvent;
 
 // Note: We keep this module UI-agnostic. It returns plain strings that
 // higher layers (e.g., TUI) may style as needed.
@@ -53,3 +60,277 @@ pub fn format_review_findings_block(
 
     lines.join("\n")
 }
+
+/// URI base id that SARIF consumers resolve to the repository root.
+const SARIF_SRCROOT: &str = "%SRCROOT%";
+
+/// Percent-encodes each `/`-separated segment of `path` so that it is a valid
+/// relative URI reference.
+fn relative_uri(path: &str) -> String {
+    let Ok(mut url) = Url::parse("file:///") else {
+        return path.to_string();
+    };
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments.clear().extend(path.split('/'));
+    }
+    url.path()
+        .strip_prefix('/')
+        .unwrap_or(url.path())
+        .to_string()
+}
+
+/// Returns the path of `item` relative to `root` using `/` separators, or the
+/// absolute path when it lies outside `root`.
+fn relative_path(item: &ReviewFinding, root: &Path) -> (String, bool) {
+    let path = &item.code_location.absolute_file_path;
+    match path.strip_prefix(root) {
+        Ok(relative) => {
+            let parts: Vec<String> = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            (parts.join("/"), true)
+        }
+        Err(_) => (path.to_string_lossy().replace('\\', "/"), false),
+    }
+}
+
+/// Review priorities run from 0 (most severe) to 3; anything outside that
+/// range is clamped.
+fn clamped_priority(item: &ReviewFinding) -> i32 {
+    item.priority.clamp(0, 3)
+}
+
+/// Returns the line range of `item`, clamped to the 1-based, ordered range
+/// that annotation formats require.
+fn line_range(item: &ReviewFinding) -> (u32, u32) {
+    let start = item.code_location.line_range.start.max(1);
+    let end = item.code_location.line_range.end.max(start);
+    (start, end)
+}
+
+fn sarif_level(priority: i32) -> &'static str {
+    match priority {
+        0 | 1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+fn sarif_rule_id(priority: i32) -> String {
+    format!("codex-review/p{priority}")
+}
+
+/// Render a review as a SARIF 2.1.0 log with one result per finding.
+///
+/// Findings under `root` are reported relative to the `%SRCROOT%` base id so
+/// that code scanning tools can map them onto the repository; findings
+/// outside it keep their absolute path. Priorities P0 and P1 map to `error`,
+/// P2 to `warning` and P3 to `note`.
+pub fn render_review_sarif(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let rules: Vec<Value> = (0..=3)
+        .map(|priority| {
+            json!({
+                "id": sarif_rule_id(priority),
+                "name": format!("ReviewFindingP{priority}"),
+                "shortDescription": { "text": format!("P{priority} code review finding") },
+                "defaultConfiguration": { "level": sarif_level(priority) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|item| {
+            let priority = clamped_priority(item);
+            let (path, is_relative) = relative_path(item, root);
+            let artifact_location = if is_relative {
+                json!({ "uri": relative_uri(&path), "uriBaseId": SARIF_SRCROOT })
+            } else {
+                // `from_file_path` percent-encodes the path; it fails only for
+                // relative paths, which have no `file:` URI.
+                match Url::from_file_path(&item.code_location.absolute_file_path) {
+                    Ok(url) => json!({ "uri": url.as_str() }),
+                    Err(()) => json!({ "uri": path }),
+                }
+            };
+            let (start, end) = line_range(item);
+            let message = if item.body.trim().is_empty() {
+                item.title.clone()
+            } else {
+                format!("{}\n\n{}", item.title, item.body)
+            };
+            json!({
+                "ruleId": sarif_rule_id(priority),
+                "ruleIndex": priority,
+                "level": sarif_level(priority),
+                "message": { "text": message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": artifact_location,
+                        "region": { "startLine": start, "endLine": end },
+                    },
+                }],
+                "properties": {
+                    "title": item.title,
+                    "priority": item.priority,
+                    "confidenceScore": item.confidence_score,
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "codex",
+                    "informationUri": "https://github.com/openai/codex",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+            "properties": {
+                "overallCorrectness": output.overall_correctness,
+                "overallExplanation": output.overall_explanation,
+                "overallConfidenceScore": output.overall_confidence_score,
+            },
+        }],
+    })
+}
+
+fn github_command(priority: i32) -> &'static str {
+    match priority {
+        0 | 1 => "error",
+        2 => "warning",
+        _ => "notice",
+    }
+}
+
+/// Escapes a workflow command message.
+fn escape_github_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a workflow command property value.
+fn escape_github_property(value: &str) -> String {
+    escape_github_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Render findings as GitHub Actions workflow commands, one per line, e.g.
+/// `::warning file=src/lib.rs,line=10,endLine=12,title=[P2] Title::Body`.
+///
+/// File paths are made relative to `root`, which should be the repository
+/// root so that GitHub can place the annotations on the diff. Priorities P0
+/// and P1 map to `error`, P2 to `warning` and P3 to `notice`.
+pub fn render_github_annotations(findings: &[ReviewFinding], root: &Path) -> String {
+    findings
+        .iter()
+        .map(|item| {
+            let priority = clamped_priority(item);
+            let (path, _) = relative_path(item, root);
+            let (start, end) = line_range(item);
+            let title = format!("[P{priority}] {}", item.title);
+            let message = if item.body.trim().is_empty() {
+                &item.title
+            } else {
+                &item.body
+            };
+            format!(
+                "::{} file={},line={start},endLine={end},title={}::{}",
+                github_command(priority),
+                escape_github_property(&path),
+                escape_github_property(&title),
+                escape_github_data(message),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ReviewCodeLocation;
+    use crate::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(path: &str, priority: i32, start: u32, end: u32) -> ReviewFinding {
+        ReviewFinding {
+            title: "Off by one".to_string(),
+            body: "The loop skips\nthe last element: 100%".to_string(),
+            confidence_score: 0.5,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from(path),
+                line_range: ReviewLineRange { start, end },
+            },
+        }
+    }
+
+    #[test]
+    fn github_annotations_escape_and_relativize() {
+        let findings = vec![
+            finding("/repo/src/lib.rs", 1, 10, 12),
+            finding("/elsewhere/a,b.rs", 3, 0, 0),
+        ];
+        assert_eq!(
+            render_github_annotations(&findings, Path::new("/repo")),
+            "::error file=src/lib.rs,line=10,endLine=12,title=[P1] Off by one::The loop skips%0Athe last element: 100%25\n\
+             ::notice file=/elsewhere/a%2Cb.rs,line=1,endLine=1,title=[P3] Off by one::The loop skips%0Athe last element: 100%25"
+        );
+    }
+
+    #[test]
+    fn sarif_reports_each_finding_with_its_location() {
+        let output = ReviewOutputEvent {
+            findings: vec![
+                finding("/repo/src/lib.rs", 2, 3, 4),
+                finding("/elsewhere/my file.rs", 7, 5, 5),
+                finding("/repo/docs/a b#1?.md", 1, 1, 1),
+            ],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "One bug.".to_string(),
+            overall_confidence_score: 0.8,
+        };
+        let sarif = render_review_sarif(&output, Path::new("/repo"));
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 4);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "src/lib.rs", "uriBaseId": "%SRCROOT%" },
+                "region": { "startLine": 3, "endLine": 4 },
+            })
+        );
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[0]["ruleId"], "codex-review/p2");
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["artifactLocation"],
+            json!({ "uri": "file:///elsewhere/my%20file.rs" })
+        );
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"]["artifactLocation"],
+            json!({ "uri": "docs/a%20b%231%3F.md", "uriBaseId": "%SRCROOT%" })
+        );
+        assert_eq!(results[1]["level"], "note");
+        assert_eq!(results[1]["properties"]["priority"], 7);
+        assert_eq!(
+            run["properties"]["overallCorrectness"],
+            "patch is incorrect"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::path::Path;
+
+use crate::protocol::ReviewRequest;
+use crate::protocol::ReviewTarget;
+
+/// Patch files larger than this are rejected instead of being inlined into the
+/// review prompt.
+const MAX_PATCH_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Returns the prompt that seeds the review conversation for `request`.
+///
+/// Requests without a target use `request.prompt` verbatim. Otherwise the
+/// prompt is derived from the target and `request.prompt`, when non-empty, is
+/// appended as additional instructions.
+pub(crate) async fn resolve_review_prompt(
+    request: &ReviewRequest,
+    cwd: &Path,
+) -> Result<String, String> {
+    let Some(target) = &request.target else {
+        return Ok(request.prompt.clone());
+    };
+    let prompt = target_prompt(target, cwd).await?;
+    let extra = request.prompt.trim();
+    if extra.is_empty() {
+        Ok(prompt)
+    } else {
+        Ok(format!("{prompt}\n\nAdditional instructions:\n{extra}"))
+    }
+}
+
+async fn target_prompt(target: &ReviewTarget, cwd: &Path) -> Result<String, String> {
+    Ok(match target {
+        ReviewTarget::UncommittedChanges => "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.".to_string(),
+        ReviewTarget::BaseBranch { branch } => format!(
+            "Review the code changes against the base branch '{branch}'. Start by finding the merge diff between the current branch and {branch}'s upstream e.g. (`git merge-base HEAD \"$(git rev-parse --abbrev-ref \"{branch}@{{upstream}}\")\"`), then run `git diff` against that SHA to see what changes we would merge into the {branch} branch. Provide prioritized, actionable findings."
+        ),
+        ReviewTarget::Commit {
+            sha,
+            title: Some(title),
+        } => format!(
+            "Review the code changes introduced by commit {sha} (\"{title}\"). Provide prioritized, actionable findings."
+        ),
+        ReviewTarget::Commit { sha, title: None } => format!(
+            "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings."
+        ),
+        ReviewTarget::CommitRange { base, head } => format!(
+            "Review the code changes introduced by the commits in {base}..{head}. Run `git log {base}..{head}` to list the commits and `git diff {base}...{head}` to see their combined changes. Provide prioritized, actionable findings."
+        ),
+        ReviewTarget::PatchFile { path } => {
+            let path = cwd.join(path);
+            let contents = read_patch_file(&path).await?;
+            format!(
+                "Review the code changes in the patch file {}. The patch may not be applied to the working tree, so use the repository only for surrounding context. Provide prioritized, actionable findings.\n\n```diff\n{}\n```",
+                path.display(),
+                contents.trim_end()
+            )
+        }
+    })
+}
+
+async fn read_patch_file(path: &Path) -> Result<String, String> {
+    let read_error =
+        |err: std::io::Error| format!("failed to read patch file {}: {err}", path.display());
+    let size = tokio::fs::metadata(path).await.map_err(read_error)?.len();
+    if size > MAX_PATCH_FILE_BYTES {
+        return Err(format!(
+            "patch file {} is {size} bytes; reviews support patch files up to {MAX_PATCH_FILE_BYTES} bytes",
+            path.display()
+        ));
+    }
+    let contents = tokio::fs::read_to_string(path).await.map_err(read_error)?;
+    if contents.trim().is_empty() {
+        return Err(format!("patch file {} is empty", path.display()));
+    }
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn untargeted_requests_use_the_prompt_verbatim() {
+        let request = ReviewRequest {
+            prompt: "Check the error handling".to_string(),
+            user_facing_hint: "custom".to_string(),
+            target: None,
+        };
+        assert_eq!(
+            resolve_review_prompt(&request, Path::new("/")).await,
+            Ok("Check the error handling".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn targeted_requests_append_extra_instructions() {
+        let mut request = ReviewRequest::for_target(ReviewTarget::CommitRange {
+            base: "main".to_string(),
+            head: "HEAD".to_string(),
+        });
+        request.prompt = "Focus on concurrency.".to_string();
+        let prompt = resolve_review_prompt(&request, Path::new("/"))
+            .await
+            .unwrap();
+        assert!(prompt.contains("`git diff main...HEAD`"));
+        assert!(prompt.ends_with("\n\nAdditional instructions:\nFocus on concurrency."));
+        assert_eq!(request.user_facing_hint, "commits main..HEAD");
+    }
+
+    #[tokio::test]
+    async fn patch_files_are_inlined_relative_to_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("fix.patch"),
+            "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n",
+        )
+        .unwrap();
+        let request = ReviewRequest::for_target(ReviewTarget::PatchFile {
+            path: PathBuf::from("fix.patch"),
+        });
+        let prompt = resolve_review_prompt(&request, dir.path()).await.unwrap();
+        assert!(prompt.ends_with("```diff\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n```"));
+
+        let missing = ReviewRequest::for_target(ReviewTarget::PatchFile {
+            path: PathBuf::from("missing.diff"),
+        });
+        let err = resolve_review_prompt(&missing, dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("failed to read patch file"));
+    }
+}
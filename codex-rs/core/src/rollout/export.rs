@@ -0,0 +1,768 @@
+//! Render a recorded session rollout as a transcript that can be shared
+//! outside of Codex (Markdown, a standalone HTML page, or JSON).
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::WebSearchAction;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::mcp_connection_manager::MCP_TOOL_NAME_DELIMITER;
+use crate::protocol::EventMsg;
+use crate::protocol::RolloutItem;
+
+/// Placeholder used in place of tool output when
+/// [`TranscriptOptions::redact_tool_output`] is set.
+pub const REDACTED_TOOL_OUTPUT: &str = "[output redacted]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptOptions {
+    /// Replace the output of commands, patches and tool calls with
+    /// [`REDACTED_TOOL_OUTPUT`]. The calls themselves are kept.
+    pub redact_tool_output: bool,
+}
+
+/// Render the items of a rollout file as a transcript in `format`.
+pub fn render_transcript(
+    items: &[RolloutItem],
+    format: TranscriptFormat,
+    options: &TranscriptOptions,
+) -> String {
+    let transcript = Transcript::from_rollout(items, options);
+    match format {
+        TranscriptFormat::Markdown => transcript.to_markdown(),
+        TranscriptFormat::Html => transcript.to_html(),
+        TranscriptFormat::Json => serde_json::to_string_pretty(&transcript).unwrap_or_default(),
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Transcript {
+    session: Option<TranscriptSession>,
+    entries: Vec<TranscriptEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct TranscriptSession {
+    id: String,
+    timestamp: String,
+    cwd: PathBuf,
+    cli_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TranscriptEntry {
+    UserMessage {
+        text: String,
+    },
+    AgentMessage {
+        text: String,
+    },
+    Reasoning {
+        text: String,
+    },
+    Command {
+        command: String,
+        exit_code: Option<i32>,
+        output: Option<String>,
+    },
+    Patch {
+        patch: String,
+        output: Option<String>,
+    },
+    McpToolCall {
+        server: String,
+        tool: String,
+        arguments: String,
+        output: Option<String>,
+    },
+    ToolCall {
+        name: String,
+        arguments: String,
+        output: Option<String>,
+    },
+    WebSearch {
+        query: String,
+    },
+    Compacted {
+        summary: String,
+    },
+}
+
+/// Shape of the output the shell tools send back to the model.
+#[derive(Deserialize)]
+struct ExecOutput {
+    output: String,
+    metadata: ExecMetadata,
+}
+
+#[derive(Deserialize)]
+struct ExecMetadata {
+    exit_code: i32,
+}
+
+impl Transcript {
+    fn from_rollout(items: &[RolloutItem], options: &TranscriptOptions) -> Self {
+        let mut transcript = Transcript::default();
+        // Index of the entry each pending call id belongs to.
+        let mut calls: HashMap<String, usize> = HashMap::new();
+
+        for item in items {
+            match item {
+                RolloutItem::SessionMeta(meta_line) => {
+                    if transcript.session.is_none() {
+                        let git = meta_line.git.as_ref();
+                        transcript.session = Some(TranscriptSession {
+                            id: meta_line.meta.id.to_string(),
+                            timestamp: meta_line.meta.timestamp.clone(),
+                            cwd: meta_line.meta.cwd.clone(),
+                            cli_version: meta_line.meta.cli_version.clone(),
+                            git_branch: git.and_then(|git| git.branch.clone()),
+                            git_commit: git.and_then(|git| git.commit_hash.clone()),
+                            model: None,
+                        });
+                    }
+                }
+                RolloutItem::TurnContext(context) => {
+                    if let Some(session) = transcript.session.as_mut()
+                        && session.model.is_none()
+                    {
+                        session.model = Some(context.model.clone());
+                    }
+                }
+                RolloutItem::Compacted(compacted) => {
+                    transcript.entries.push(TranscriptEntry::Compacted {
+                        summary: compacted.message.clone(),
+                    });
+                }
+                RolloutItem::EventMsg(EventMsg::UserMessage(ev)) => {
+                    transcript.entries.push(TranscriptEntry::UserMessage {
+                        text: ev.message.clone(),
+                    });
+                }
+                RolloutItem::EventMsg(EventMsg::AgentMessage(ev)) => {
+                    transcript.entries.push(TranscriptEntry::AgentMessage {
+                        text: ev.message.clone(),
+                    });
+                }
+                RolloutItem::EventMsg(EventMsg::AgentReasoning(ev)) => {
+                    transcript.entries.push(TranscriptEntry::Reasoning {
+                        text: ev.text.clone(),
+                    });
+                }
+                RolloutItem::EventMsg(_) => {}
+                RolloutItem::ResponseItem(item) => {
+                    transcript.push_response_item(item, &mut calls, options);
+                }
+            }
+        }
+
+        transcript
+    }
+
+    fn push_response_item(
+        &mut self,
+        item: &ResponseItem,
+        calls: &mut HashMap<String, usize>,
+        options: &TranscriptOptions,
+    ) {
+        // Messages and reasoning are taken from the matching events, which
+        // do not include the injected instructions and environment context.
+        let (call_id, entry) = match item {
+            ResponseItem::LocalShellCall {
+                call_id,
+                id,
+                action: LocalShellAction::Exec(exec),
+                ..
+            } => (
+                call_id.clone().or_else(|| id.clone()),
+                TranscriptEntry::Command {
+                    command: exec.command.join(" "),
+                    exit_code: None,
+                    output: None,
+                },
+            ),
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => (Some(call_id.clone()), function_call_entry(name, arguments)),
+            ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            } => {
+                let entry = if name == "apply_patch" {
+                    TranscriptEntry::Patch {
+                        patch: input.clone(),
+                        output: None,
+                    }
+                } else {
+                    TranscriptEntry::ToolCall {
+                        name: name.clone(),
+                        arguments: input.clone(),
+                        output: None,
+                    }
+                };
+                (Some(call_id.clone()), entry)
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                self.attach_output(calls.remove(call_id), &output.content, options);
+                return;
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.attach_output(calls.remove(call_id), output, options);
+                return;
+            }
+            ResponseItem::WebSearchCall {
+                action: WebSearchAction::Search { query },
+                ..
+            } => (
+                None,
+                TranscriptEntry::WebSearch {
+                    query: query.clone(),
+                },
+            ),
+            _ => return,
+        };
+
+        if let Some(call_id) = call_id {
+            calls.insert(call_id, self.entries.len());
+        }
+        self.entries.push(entry);
+    }
+
+    fn attach_output(&mut self, index: Option<usize>, raw: &str, options: &TranscriptOptions) {
+        let Some(entry) = index.and_then(|index| self.entries.get_mut(index)) else {
+            return;
+        };
+        let redact = |text: String| {
+            if options.redact_tool_output {
+                REDACTED_TOOL_OUTPUT.to_string()
+            } else {
+                text
+            }
+        };
+        match entry {
+            TranscriptEntry::Command {
+                exit_code, output, ..
+            } => match serde_json::from_str::<ExecOutput>(raw) {
+                Ok(exec) => {
+                    *exit_code = Some(exec.metadata.exit_code);
+                    *output = Some(redact(exec.output));
+                }
+                Err(_) => *output = Some(redact(raw.to_string())),
+            },
+            TranscriptEntry::Patch { output, .. }
+            | TranscriptEntry::McpToolCall { output, .. }
+            | TranscriptEntry::ToolCall { output, .. } => *output = Some(redact(raw.to_string())),
+            _ => {}
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        match &self.session {
+            Some(session) => {
+                out.push_str(&format!("# Codex session {}\n\n", session.id));
+                out.push_str(&format!("- Started: {}\n", session.timestamp));
+                out.push_str(&format!("- Directory: `{}`\n", session.cwd.display()));
+                if let Some(git) = session.git_label() {
+                    out.push_str(&format!("- Git: `{git}`\n"));
+                }
+                if let Some(model) = &session.model {
+                    out.push_str(&format!("- Model: `{model}`\n"));
+                }
+                out.push_str(&format!("- Codex version: {}\n", session.cli_version));
+            }
+            None => out.push_str("# Codex session\n"),
+        }
+
+        for entry in &self.entries {
+            out.push('\n');
+            match entry {
+                TranscriptEntry::UserMessage { text } => {
+                    out.push_str(&format!("## User\n\n{}\n", text.trim_end()));
+                }
+                TranscriptEntry::AgentMessage { text } => {
+                    out.push_str(&format!("## Codex\n\n{}\n", text.trim_end()));
+                }
+                TranscriptEntry::Reasoning { text } => {
+                    out.push_str("> **Reasoning**\n>\n");
+                    for line in text.trim_end().lines() {
+                        if line.is_empty() {
+                            out.push_str(">\n");
+                        } else {
+                            out.push_str(&format!("> {line}\n"));
+                        }
+                    }
+                }
+                TranscriptEntry::Command {
+                    command,
+                    exit_code,
+                    output,
+                } => {
+                    match exit_code {
+                        Some(code) => out.push_str(&format!("**Command** (exit code {code})\n\n")),
+                        None => out.push_str("**Command**\n\n"),
+                    }
+                    out.push_str(&fenced_block("sh", &format!("$ {command}")));
+                    if let Some(output) = output {
+                        out.push('\n');
+                        out.push_str(&fenced_block("text", output));
+                    }
+                }
+                TranscriptEntry::Patch { patch, output } => {
+                    out.push_str("**Patch**\n\n");
+                    out.push_str(&fenced_block("diff", patch));
+                    if let Some(output) = output {
+                        out.push('\n');
+                        out.push_str(&fenced_block("text", output));
+                    }
+                }
+                TranscriptEntry::McpToolCall {
+                    server,
+                    tool,
+                    arguments,
+                    output,
+                } => {
+                    out.push_str(&format!("**MCP tool call** `{server}/{tool}`\n\n"));
+                    out.push_str(&fenced_block("json", arguments));
+                    if let Some(output) = output {
+                        out.push('\n');
+                        out.push_str(&fenced_block("text", output));
+                    }
+                }
+                TranscriptEntry::ToolCall {
+                    name,
+                    arguments,
+                    output,
+                } => {
+                    out.push_str(&format!("**Tool call** `{name}`\n\n"));
+                    out.push_str(&fenced_block("json", arguments));
+                    if let Some(output) = output {
+                        out.push('\n');
+                        out.push_str(&fenced_block("text", output));
+                    }
+                }
+                TranscriptEntry::WebSearch { query } => {
+                    out.push_str(&format!("**Web search:** {query}\n"));
+                }
+                TranscriptEntry::Compacted { summary } => {
+                    out.push_str("_Earlier conversation was compacted:_\n\n");
+                    out.push_str(&fenced_block("text", summary));
+                }
+            }
+        }
+
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let title = match &self.session {
+            Some(session) => format!("Codex session {}", session.id),
+            None => "Codex session".to_string(),
+        };
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n", escape_html(&title)));
+        out.push_str(HTML_STYLE);
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!("<h1>{}</h1>\n", escape_html(&title)));
+
+        if let Some(session) = &self.session {
+            out.push_str("<dl class=\"meta\">\n");
+            let mut field = |name: &str, value: &str| {
+                out.push_str(&format!("<dt>{name}</dt><dd>{}</dd>\n", escape_html(value)));
+            };
+            field("Started", &session.timestamp);
+            field("Directory", &session.cwd.display().to_string());
+            if let Some(git) = session.git_label() {
+                field("Git", &git);
+            }
+            if let Some(model) = &session.model {
+                field("Model", model);
+            }
+            field("Codex version", &session.cli_version);
+            out.push_str("</dl>\n");
+        }
+
+        for entry in &self.entries {
+            match entry {
+                TranscriptEntry::UserMessage { text } => {
+                    out.push_str(&html_section("user", "User", &html_text(text)));
+                }
+                TranscriptEntry::AgentMessage { text } => {
+                    out.push_str(&html_section("agent", "Codex", &html_text(text)));
+                }
+                TranscriptEntry::Reasoning { text } => {
+                    out.push_str(&html_section("reasoning", "Reasoning", &html_text(text)));
+                }
+                TranscriptEntry::Command {
+                    command,
+                    exit_code,
+                    output,
+                } => {
+                    let heading = match exit_code {
+                        Some(code) => format!("Command (exit code {code})"),
+                        None => "Command".to_string(),
+                    };
+                    let mut body = html_pre(&format!("$ {command}"));
+                    body.push_str(&html_output(output.as_deref()));
+                    out.push_str(&html_section("tool", &heading, &body));
+                }
+                TranscriptEntry::Patch { patch, output } => {
+                    let mut body = html_diff(patch);
+                    body.push_str(&html_output(output.as_deref()));
+                    out.push_str(&html_section("tool", "Patch", &body));
+                }
+                TranscriptEntry::McpToolCall {
+                    server,
+                    tool,
+                    arguments,
+                    output,
+                } => {
+                    let mut body = html_pre(arguments);
+                    body.push_str(&html_output(output.as_deref()));
+                    out.push_str(&html_section(
+                        "tool",
+                        &format!("MCP tool call {server}/{tool}"),
+                        &body,
+                    ));
+                }
+                TranscriptEntry::ToolCall {
+                    name,
+                    arguments,
+                    output,
+                } => {
+                    let mut body = html_pre(arguments);
+                    body.push_str(&html_output(output.as_deref()));
+                    out.push_str(&html_section("tool", &format!("Tool call {name}"), &body));
+                }
+                TranscriptEntry::WebSearch { query } => {
+                    out.push_str(&html_section("tool", "Web search", &html_text(query)));
+                }
+                TranscriptEntry::Compacted { summary } => {
+                    out.push_str(&html_section(
+                        "reasoning",
+                        "Earlier conversation was compacted",
+                        &html_text(summary),
+                    ));
+                }
+            }
+        }
+
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+impl TranscriptSession {
+    fn git_label(&self) -> Option<String> {
+        match (&self.git_branch, &self.git_commit) {
+            (Some(branch), Some(commit)) => Some(format!("{branch} @ {commit}")),
+            (Some(branch), None) => Some(branch.clone()),
+            (None, Some(commit)) => Some(commit.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+fn function_call_entry(name: &str, arguments: &str) -> TranscriptEntry {
+    let parsed: Option<serde_json::Value> = serde_json::from_str(arguments).ok();
+    let argument = |key: &str| parsed.as_ref().and_then(|args| args.get(key));
+
+    if name == "apply_patch"
+        && let Some(patch) = argument("input").and_then(serde_json::Value::as_str)
+    {
+        return TranscriptEntry::Patch {
+            patch: patch.to_string(),
+            output: None,
+        };
+    }
+
+    let command = match argument("command").or_else(|| argument("cmd")) {
+        Some(serde_json::Value::String(command)) => Some(command.clone()),
+        Some(serde_json::Value::Array(parts)) => Some(
+            parts
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    };
+    if let Some(command) = command
+        && matches!(name, "shell" | "container.exec" | "exec_command")
+    {
+        return TranscriptEntry::Command {
+            command,
+            exit_code: None,
+            output: None,
+        };
+    }
+
+    let arguments = parsed
+        .as_ref()
+        .and_then(|args| serde_json::to_string_pretty(args).ok())
+        .unwrap_or_else(|| arguments.to_string());
+    let mcp_prefix = format!("mcp{MCP_TOOL_NAME_DELIMITER}");
+    if let Some((server, tool)) = name
+        .strip_prefix(&mcp_prefix)
+        .and_then(|qualified| qualified.split_once(MCP_TOOL_NAME_DELIMITER))
+    {
+        return TranscriptEntry::McpToolCall {
+            server: server.to_string(),
+            tool: tool.to_string(),
+            arguments,
+            output: None,
+        };
+    }
+
+    TranscriptEntry::ToolCall {
+        name: name.to_string(),
+        arguments,
+        output: None,
+    }
+}
+
+/// Wrap `content` in a fenced code block whose fence is longer than any run
+/// of backticks inside it.
+fn fenced_block(lang: &str, content: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in content.chars() {
+        if ch == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "{fence}{lang}\n{}\n{fence}\n",
+        content.trim_end_matches('\n')
+    )
+}
+
+const HTML_STYLE: &str = r#"<style>
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1f2328; }
+.meta dt { font-weight: 600; float: left; clear: left; width: 8rem; }
+.meta dd { margin-left: 8rem; }
+section { border-left: 4px solid #d0d7de; margin: 1rem 0; padding: 0.25rem 1rem; }
+section.user { border-color: #0969da; }
+section.agent { border-color: #1a7f37; }
+section.reasoning { border-color: #8250df; color: #57606a; }
+h2 { font-size: 1rem; margin: 0.5rem 0; }
+pre { background: #f6f8fa; padding: 0.75rem; overflow-x: auto; }
+.text { white-space: pre-wrap; }
+.add { color: #1a7f37; }
+.del { color: #cf222e; }
+</style>
+"#;
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn html_section(class: &str, heading: &str, body: &str) -> String {
+    format!(
+        "<section class=\"{class}\">\n<h2>{}</h2>\n{body}</section>\n",
+        escape_html(heading)
+    )
+}
+
+fn html_text(text: &str) -> String {
+    format!(
+        "<div class=\"text\">{}</div>\n",
+        escape_html(text.trim_end())
+    )
+}
+
+fn html_pre(text: &str) -> String {
+    format!("<pre>{}</pre>\n", escape_html(text.trim_end_matches('\n')))
+}
+
+fn html_output(output: Option<&str>) -> String {
+    output.map(html_pre).unwrap_or_default()
+}
+
+fn html_diff(patch: &str) -> String {
+    let lines: Vec<String> = patch
+        .trim_end_matches('\n')
+        .lines()
+        .map(|line| {
+            let escaped = escape_html(line);
+            if line.starts_with('+') {
+                format!("<span class=\"add\">{escaped}</span>")
+            } else if line.starts_with('-') {
+                format!("<span class=\"del\">{escaped}</span>")
+            } else {
+                escaped
+            }
+        })
+        .collect();
+    format!("<pre>{}</pre>\n", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::AgentMessageEvent;
+    use crate::protocol::AgentReasoningEvent;
+    use crate::protocol::SessionMeta;
+    use crate::protocol::SessionMetaLine;
+    use crate::protocol::UserMessageEvent;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn sample_rollout() -> Vec<RolloutItem> {
+        vec![
+            RolloutItem::SessionMeta(SessionMetaLine {
+                meta: SessionMeta {
+                    timestamp: "2025-01-02T03:04:05.000Z".to_string(),
+                    cwd: PathBuf::from("/work/repo"),
+                    cli_version: "1.2.3".to_string(),
+                    ..Default::default()
+                },
+                git: None,
+            }),
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "Why does <main> fail?".to_string(),
+                images: None,
+            })),
+            RolloutItem::EventMsg(EventMsg::AgentReasoning(AgentReasoningEvent {
+                text: "Checking the tests".to_string(),
+            })),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["cargo","test"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: r#"{"output":"secret token ```x```","metadata":{"exit_code":101,"duration_seconds":1.5}}"#
+                        .to_string(),
+                    ..Default::default()
+                },
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Update File: src/main.rs\n-old\n+new\n*** End Patch"
+                    .to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput {
+                call_id: "call-2".to_string(),
+                output: "Success. Updated the following files:\nM src/main.rs".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "mcp__docs__search".to_string(),
+                arguments: r#"{"q":"panic"}"#.to_string(),
+                call_id: "call-3".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: "call-3".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "no results".to_string(),
+                    ..Default::default()
+                },
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Fixed the test.".to_string(),
+            })),
+        ]
+    }
+
+    #[test]
+    fn markdown_transcript_renders_messages_and_tool_calls() {
+        let markdown = render_transcript(
+            &sample_rollout(),
+            TranscriptFormat::Markdown,
+            &TranscriptOptions::default(),
+        );
+
+        assert!(markdown.contains("- Directory: `/work/repo`"));
+        assert!(markdown.contains("## User\n\nWhy does <main> fail?\n"));
+        assert!(markdown.contains("> **Reasoning**\n>\n> Checking the tests\n"));
+        assert!(markdown.contains("**Command** (exit code 101)\n\n```sh\n$ cargo test\n```\n"));
+        // Output containing a fence is wrapped in a longer fence.
+        assert!(markdown.contains("````text\nsecret token ```x```\n````\n"));
+        assert!(
+            markdown
+                .contains("```diff\n*** Begin Patch\n*** Update File: src/main.rs\n-old\n+new\n")
+        );
+        assert!(markdown.contains("**MCP tool call** `docs/search`"));
+        assert!(markdown.contains("## Codex\n\nFixed the test.\n"));
+    }
+
+    #[test]
+    fn redaction_replaces_tool_output_in_every_format() {
+        let options = TranscriptOptions {
+            redact_tool_output: true,
+        };
+        let items = sample_rollout();
+
+        let markdown = render_transcript(&items, TranscriptFormat::Markdown, &options);
+        assert!(!markdown.contains("secret token"));
+        assert!(!markdown.contains("no results"));
+        assert!(markdown.contains(REDACTED_TOOL_OUTPUT));
+        assert!(markdown.contains("$ cargo test"));
+
+        let html = render_transcript(&items, TranscriptFormat::Html, &options);
+        assert!(!html.contains("secret token"));
+        assert!(html.contains("Why does &lt;main&gt; fail?"));
+        assert!(html.contains("<span class=\"add\">+new</span>"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_transcript(&items, TranscriptFormat::Json, &options))
+                .expect("transcript is valid JSON");
+        assert_eq!(
+            json["entries"][2],
+            serde_json::json!({
+                "type": "command",
+                "command": "cargo test",
+                "exit_code": 101,
+                "output": REDACTED_TOOL_OUTPUT,
+            })
+        );
+        assert_eq!(json["entries"][4]["type"], "mcp_tool_call");
+        assert_eq!(json["session"]["cwd"], "/work/repo");
+    }
+}
@@ -6,6 +6,8 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
+use chrono::DateTime;
+use chrono::Utc;
 use time::OffsetDateTime;
 use time::PrimitiveDateTime;
 use time::format_description::FormatItem;
@@ -14,7 +16,10 @@ use uuid::Uuid;
 
 use super::SESSIONS_SUBDIR;
 use crate::protocol::EventMsg;
+use crate::session_crypto;
 use codex_file_search as file_search;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
 use codex_protocol::protocol::SessionSource;
@@ -47,6 +52,73 @@ pub struct ConversationItem {
     pub updated_at: Option<String>,
 }
 
+/// Filters that narrow down which conversations are returned. The default
+/// value matches every conversation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConversationSearch {
+    /// Case-insensitive, whitespace-separated terms that must all appear in
+    /// the conversation's user messages, agent messages, commands run or
+    /// paths of files touched by patches.
+    pub query: Option<String>,
+    /// Only conversations started in this directory or one of its
+    /// subdirectories.
+    pub cwd: Option<PathBuf>,
+    /// Only conversations started on this Git branch.
+    pub git_branch: Option<String>,
+    /// Only conversations with at least one turn that used this model.
+    pub model: Option<String>,
+    /// Only conversations created at or after this time.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only conversations created before this time.
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl ConversationSearch {
+    fn query_terms(&self) -> Vec<String> {
+        self.query
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// Checks the filters that only need the session metadata.
+    fn matches_summary(&self, summary: &HeadTailSummary) -> bool {
+        if let Some(cwd) = &self.cwd
+            && !summary
+                .cwd
+                .as_ref()
+                .is_some_and(|session_cwd| session_cwd.starts_with(cwd))
+        {
+            return false;
+        }
+        if let Some(branch) = &self.git_branch
+            && summary.git_branch.as_ref() != Some(branch)
+        {
+            return false;
+        }
+        if self.created_after.is_some() || self.created_before.is_some() {
+            let Some(created_at) = summary
+                .created_at
+                .as_deref()
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                .map(|ts| ts.with_timezone(&Utc))
+            else {
+                return false;
+            };
+            if self.created_after.is_some_and(|after| created_at < after)
+                || self
+                    .created_before
+                    .is_some_and(|before| created_at >= before)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 #[derive(Default)]
 struct HeadTailSummary {
     head: Vec<serde_json::Value>,
@@ -55,6 +127,8 @@ struct HeadTailSummary {
     saw_user_event: bool,
     source: Option<SessionSource>,
     model_provider: Option<String>,
+    cwd: Option<PathBuf>,
+    git_branch: Option<String>,
     created_at: Option<String>,
     updated_at: Option<String>,
 }
@@ -112,6 +186,31 @@ pub(crate) async fn get_conversations(
     allowed_sources: &[SessionSource],
     model_providers: Option<&[String]>,
     default_provider: &str,
+) -> io::Result<ConversationsPage> {
+    search_conversations(
+        codex_home,
+        page_size,
+        cursor,
+        allowed_sources,
+        model_providers,
+        default_provider,
+        &ConversationSearch::default(),
+    )
+    .await
+}
+
+/// Like [`get_conversations`], but only returns conversations matching
+/// `search`. Filters that need the full transcript (the query and the model)
+/// read each candidate rollout in full, so they count against the same scan
+/// cap as a plain listing.
+pub(crate) async fn search_conversations(
+    codex_home: &Path,
+    page_size: usize,
+    cursor: Option<&Cursor>,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    default_provider: &str,
+    search: &ConversationSearch,
 ) -> io::Result<ConversationsPage> {
     let mut root = codex_home.to_path_buf();
     root.push(SESSIONS_SUBDIR);
@@ -136,6 +235,7 @@ pub(crate) async fn get_conversations(
         anchor,
         allowed_sources,
         provider_matcher.as_ref(),
+        search,
     )
     .await?;
     Ok(result)
@@ -158,7 +258,9 @@ async fn traverse_directories_for_paths(
     anchor: Option<Cursor>,
     allowed_sources: &[SessionSource],
     provider_matcher: Option<&ProviderMatcher<'_>>,
+    search: &ConversationSearch,
 ) -> io::Result<ConversationsPage> {
+    let query_terms = search.query_terms();
     let mut items: Vec<ConversationItem> = Vec::with_capacity(page_size);
     let mut scanned_files = 0usize;
     let mut anchor_passed = anchor.is_none();
@@ -220,7 +322,8 @@ async fn traverse_directories_for_paths(
                     if !allowed_sources.is_empty()
                         && !summary
                             .source
-                            .is_some_and(|source| allowed_sources.iter().any(|s| s == &source))
+                            .as_ref()
+                            .is_some_and(|source| allowed_sources.iter().any(|s| s == source))
                     {
                         continue;
                     }
@@ -230,23 +333,34 @@ async fn traverse_directories_for_paths(
                         continue;
                     }
                     // Apply filters: must have session meta and at least one user message event
-                    if summary.saw_session_meta && summary.saw_user_event {
-                        let HeadTailSummary {
-                            head,
-                            tail,
-                            created_at,
-                            mut updated_at,
-                            ..
-                        } = summary;
-                        updated_at = updated_at.or_else(|| created_at.clone());
-                        items.push(ConversationItem {
-                            path,
-                            head,
-                            tail,
-                            created_at,
-                            updated_at,
-                        });
+                    if !summary.saw_session_meta || !summary.saw_user_event {
+                        continue;
                     }
+                    if !search.matches_summary(&summary) {
+                        continue;
+                    }
+                    if (!query_terms.is_empty() || search.model.is_some())
+                        && !rollout_matches(&path, &query_terms, search.model.as_deref())
+                            .await
+                            .unwrap_or(false)
+                    {
+                        continue;
+                    }
+                    let HeadTailSummary {
+                        head,
+                        tail,
+                        created_at,
+                        mut updated_at,
+                        ..
+                    } = summary;
+                    updated_at = updated_at.or_else(|| created_at.clone());
+                    items.push(ConversationItem {
+                        path,
+                        head,
+                        tail,
+                        created_at,
+                        updated_at,
+                    });
                 }
             }
         }
@@ -404,13 +518,21 @@ async fn read_head_and_tail(
             continue;
         }
 
-        let parsed: Result<RolloutLine, _> = serde_json::from_str(trimmed);
+        let Ok(decoded) = session_crypto::decode_line(trimmed) else {
+            continue;
+        };
+        let parsed: Result<RolloutLine, _> = serde_json::from_str(&decoded);
         let Ok(rollout_line) = parsed else { continue };
 
         match rollout_line.item {
             RolloutItem::SessionMeta(session_meta_line) => {
                 summary.source = Some(session_meta_line.meta.source.clone());
                 summary.model_provider = session_meta_line.meta.model_provider.clone();
+                summary.cwd = Some(session_meta_line.meta.cwd.clone());
+                summary.git_branch = session_meta_line
+                    .git
+                    .as_ref()
+                    .and_then(|git| git.branch.clone());
                 summary.created_at = summary
                     .created_at
                     .clone()
@@ -451,6 +573,102 @@ async fn read_head_and_tail(
     Ok(summary)
 }
 
+/// Reads the whole rollout at `path` and reports whether every term in
+/// `terms` appears in its searchable text and, when `model` is set, whether
+/// any turn used that model.
+async fn rollout_matches(path: &Path, terms: &[String], model: Option<&str>) -> io::Result<bool> {
+    use tokio::io::AsyncBufReadExt;
+
+    let file = tokio::fs::File::open(path).await?;
+    let mut lines = tokio::io::BufReader::new(file).lines();
+    let mut unmatched: Vec<&str> = terms.iter().map(String::as_str).collect();
+    let mut model_matched = model.is_none();
+
+    while let Some(line) = lines.next_line().await? {
+        if unmatched.is_empty() && model_matched {
+            break;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let Ok(decoded) = session_crypto::decode_line(trimmed) else {
+            continue;
+        };
+        let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(&decoded) else {
+            continue;
+        };
+        if let (RolloutItem::TurnContext(turn_context), Some(model)) = (&rollout_line.item, model)
+            && turn_context.model.eq_ignore_ascii_case(model)
+        {
+            model_matched = true;
+        }
+        if unmatched.is_empty() {
+            continue;
+        }
+        if let Some(text) = searchable_text(&rollout_line.item) {
+            let text = text.to_lowercase();
+            unmatched.retain(|term| !text.contains(term));
+        }
+    }
+
+    Ok(unmatched.is_empty() && model_matched)
+}
+
+/// Returns the text of `item` that full-text search matches against: user
+/// and agent messages, commands run and paths of files touched by patches.
+fn searchable_text(item: &RolloutItem) -> Option<String> {
+    match item {
+        RolloutItem::EventMsg(EventMsg::UserMessage(ev)) => Some(ev.message.clone()),
+        RolloutItem::EventMsg(EventMsg::AgentMessage(ev)) => Some(ev.message.clone()),
+        RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+            action: LocalShellAction::Exec(exec),
+            ..
+        }) => Some(exec.command.join(" ")),
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+            name, arguments, ..
+        }) => {
+            let arguments: serde_json::Value = serde_json::from_str(arguments).ok()?;
+            if name == "apply_patch" {
+                return arguments
+                    .get("input")
+                    .and_then(serde_json::Value::as_str)
+                    .map(patch_paths);
+            }
+            match arguments.get("command")? {
+                serde_json::Value::String(command) => Some(command.clone()),
+                serde_json::Value::Array(parts) => Some(
+                    parts
+                        .iter()
+                        .filter_map(serde_json::Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                _ => None,
+            }
+        }
+        RolloutItem::ResponseItem(ResponseItem::CustomToolCall { name, input, .. })
+            if name == "apply_patch" =>
+        {
+            Some(patch_paths(input))
+        }
+        _ => None,
+    }
+}
+
+/// Extracts the paths named in the file headers of an `apply_patch` patch.
+fn patch_paths(patch: &str) -> String {
+    patch
+        .lines()
+        .filter_map(|line| {
+            let header = line.trim().strip_prefix("*** ")?;
+            let (kind, path) = header.split_once(": ")?;
+            (kind.ends_with(" File") || kind == "Move to").then_some(path)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Read up to `HEAD_RECORD_LIMIT` records from the start of the rollout file at `path`.
 /// This should be enough to produce a summary including the session meta line.
 pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Value>> {
@@ -525,7 +743,10 @@ fn collect_last_response_values(
         if trimmed.is_empty() {
             continue;
         }
-        let parsed: serde_json::Result<RolloutLine> = serde_json::from_str(trimmed);
+        let Ok(decoded) = session_crypto::decode_line(trimmed) else {
+            continue;
+        };
+        let parsed: serde_json::Result<RolloutLine> = serde_json::from_str(&decoded);
         let Ok(rollout_line) = parsed else { continue };
         let RolloutLine { timestamp, item } = rollout_line;
         if let RolloutItem::ResponseItem(item) = item
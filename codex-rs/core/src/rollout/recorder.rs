@@ -5,6 +5,7 @@ use std::fs::{self};
 use std::io::Error as IoError;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use codex_protocol::ConversationId;
 use serde_json::Value;
@@ -19,13 +20,19 @@ use tracing::info;
 use tracing::warn;
 
 use super::SESSIONS_SUBDIR;
+use super::list::ConversationSearch;
 use super::list::ConversationsPage;
 use super::list::Cursor;
 use super::list::get_conversations;
+use super::list::search_conversations;
 use super::policy::is_persisted_response_item;
 use crate::config::Config;
+use crate::context_manager::RedactionReport;
+use crate::context_manager::SecretRedactor;
 use crate::default_client::originator;
 use crate::git_info::collect_git_info;
+use crate::session_crypto;
+use crate::session_crypto::SessionCipher;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::ResumedHistory;
 use codex_protocol::protocol::RolloutItem;
@@ -47,6 +54,7 @@ use codex_protocol::protocol::SessionSource;
 pub struct RolloutRecorder {
     tx: Sender<RolloutCmd>,
     pub(crate) rollout_path: PathBuf,
+    redactor: Option<Arc<SecretRedactor>>,
 }
 
 #[derive(Clone)]
@@ -111,10 +119,41 @@ impl RolloutRecorder {
         .await
     }
 
+    /// List conversations (rollout files) that match `search`, newest first.
+    pub async fn search_conversations(
+        codex_home: &Path,
+        page_size: usize,
+        cursor: Option<&Cursor>,
+        allowed_sources: &[SessionSource],
+        model_providers: Option<&[String]>,
+        default_provider: &str,
+        search: &ConversationSearch,
+    ) -> std::io::Result<ConversationsPage> {
+        search_conversations(
+            codex_home,
+            page_size,
+            cursor,
+            allowed_sources,
+            model_providers,
+            default_provider,
+            search,
+        )
+        .await
+    }
+
     /// Attempt to create a new [`RolloutRecorder`]. If the sessions directory
     /// cannot be created or the rollout file cannot be opened we return the
     /// error so the caller can decide whether to disable persistence.
     pub async fn new(config: &Config, params: RolloutRecorderParams) -> std::io::Result<Self> {
+        // Resolve the key before touching the file so that a session never
+        // falls back to writing plaintext when encryption is requested.
+        let cipher = if config.encrypt_session_data {
+            Some(session_crypto::writer_cipher(&config.codex_home)?)
+        } else {
+            None
+        };
+        let redactor = SecretRedactor::from_config(&config.redaction)?.map(Arc::new);
+
         let (file, rollout_path, meta) = match params {
             RolloutRecorderParams::Create {
                 conversation_id,
@@ -172,9 +211,13 @@ impl RolloutRecorder {
         // Spawn a Tokio task that owns the file handle and performs async
         // writes. Using `tokio::fs::File` keeps everything on the async I/O
         // driver instead of blocking the runtime.
-        tokio::task::spawn(rollout_writer(file, rx, meta, cwd));
+        tokio::task::spawn(rollout_writer(file, cipher, rx, meta, cwd));
 
-        Ok(Self { tx, rollout_path })
+        Ok(Self {
+            tx,
+            rollout_path,
+            redactor,
+        })
     }
 
     pub(crate) async fn record_items(&self, items: &[RolloutItem]) -> std::io::Result<()> {
@@ -183,9 +226,18 @@ impl RolloutRecorder {
             // Note that function calls may look a bit strange if they are
             // "fully qualified MCP tool calls," so we could consider
             // reformatting them in that case.
-            if is_persisted_response_item(item) {
-                filtered.push(item.clone());
+            if !is_persisted_response_item(item) {
+                continue;
             }
+            // Tool output recorded through the session is already redacted;
+            // this catches anything persisted through other paths.
+            let redacted = match (&self.redactor, item) {
+                (Some(redactor), RolloutItem::ResponseItem(response_item)) => redactor
+                    .redact_response_item(response_item, &mut RedactionReport::default())
+                    .map(RolloutItem::ResponseItem),
+                _ => None,
+            };
+            filtered.push(redacted.unwrap_or_else(|| item.clone()));
         }
         if filtered.is_empty() {
             return Ok(());
@@ -220,7 +272,9 @@ impl RolloutRecorder {
             if line.trim().is_empty() {
                 continue;
             }
-            let v: Value = match serde_json::from_str(line) {
+            // Fail loudly rather than resume a session with missing history.
+            let line = session_crypto::decode_line(line)?;
+            let v: Value = match serde_json::from_str(&line) {
                 Ok(v) => v,
                 Err(e) => {
                     warn!("failed to parse line as JSON: {line:?}, error: {e}");
@@ -348,11 +402,12 @@ fn create_log_file(
 
 async fn rollout_writer(
     file: tokio::fs::File,
+    cipher: Option<Arc<SessionCipher>>,
     mut rx: mpsc::Receiver<RolloutCmd>,
     mut meta: Option<SessionMeta>,
     cwd: std::path::PathBuf,
 ) -> std::io::Result<()> {
-    let mut writer = JsonlWriter { file };
+    let mut writer = JsonlWriter { file, cipher };
 
     // If we have a meta, collect git info asynchronously and write meta first
     if let Some(session_meta) = meta.take() {
@@ -397,6 +452,8 @@ async fn rollout_writer(
 
 struct JsonlWriter {
     file: tokio::fs::File,
+    /// Encrypts every line when session data encryption is enabled.
+    cipher: Option<Arc<SessionCipher>>,
 }
 
 impl JsonlWriter {
@@ -415,7 +472,8 @@ impl JsonlWriter {
         self.write_line(&line).await
     }
     async fn write_line(&mut self, item: &impl serde::Serialize) -> std::io::Result<()> {
-        let mut json = serde_json::to_string(item)?;
+        let mut json =
+            session_crypto::encode_line(self.cipher.as_deref(), serde_json::to_string(item)?)?;
         json.push('\n');
         self.file.write_all(json.as_bytes()).await?;
         self.file.flush().await?;
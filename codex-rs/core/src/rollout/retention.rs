@@ -0,0 +1,411 @@
+//! Enforce the `[retention]` limits on recorded session rollouts and the
+//! message history file.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use tokio::io::AsyncBufReadExt;
+use tokio::io::BufReader;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use crate::config::Config;
+use crate::config::types::Retention;
+use crate::message_history;
+use crate::protocol::RolloutItem;
+use crate::protocol::RolloutLine;
+use crate::session_crypto;
+
+/// Rollouts written to this recently are never removed, so a running session
+/// does not lose its log.
+const ACTIVE_SESSION_GRACE: Duration = Duration::from_secs(10 * 60);
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A rollout file removed (or, for a dry run, selected for removal).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunedSession {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PruneReport {
+    pub removed_sessions: Vec<PrunedSession>,
+    pub kept_sessions: usize,
+    pub kept_bytes: u64,
+    pub removed_history_entries: usize,
+    pub removed_history_bytes: u64,
+}
+
+impl PruneReport {
+    pub fn removed_session_bytes(&self) -> u64 {
+        self.removed_sessions
+            .iter()
+            .map(|session| session.bytes)
+            .sum()
+    }
+}
+
+struct SessionFile {
+    path: PathBuf,
+    bytes: u64,
+    modified: SystemTime,
+    cwd: Option<PathBuf>,
+}
+
+/// Apply `config.retention` (and `config.history.max_bytes`) to the sessions,
+/// archived sessions and message history under `config.codex_home`. With
+/// `dry_run`, nothing is deleted and the report lists what would be.
+pub async fn prune_sessions(config: &Config, dry_run: bool) -> io::Result<PruneReport> {
+    prune_codex_home(
+        &config.codex_home,
+        &config.retention,
+        config.history.max_bytes,
+        SystemTime::now(),
+        dry_run,
+    )
+    .await
+}
+
+async fn prune_codex_home(
+    codex_home: &Path,
+    retention: &Retention,
+    history_max_bytes: Option<usize>,
+    now: SystemTime,
+    dry_run: bool,
+) -> io::Result<PruneReport> {
+    let max_age = retention
+        .max_age_days
+        .map(|days| Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY)));
+
+    let mut sessions = Vec::new();
+    for subdir in [SESSIONS_SUBDIR, ARCHIVED_SESSIONS_SUBDIR] {
+        collect_rollouts(&codex_home.join(subdir), &mut sessions).await?;
+    }
+    // Newest first.
+    sessions.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+    let mut protected = vec![false; sessions.len()];
+    let mut per_cwd: HashMap<Option<&Path>, usize> = HashMap::new();
+    for (idx, session) in sessions.iter().enumerate() {
+        let recent = now
+            .duration_since(session.modified)
+            .is_ok_and(|age| age < ACTIVE_SESSION_GRACE)
+            || session.modified > now;
+        let kept_for_cwd = retention.keep_last_per_cwd.is_some_and(|keep| {
+            let seen = per_cwd.entry(session.cwd.as_deref()).or_default();
+            *seen += 1;
+            *seen <= keep
+        });
+        protected[idx] = recent || kept_for_cwd;
+    }
+
+    let mut remove = vec![false; sessions.len()];
+    if let Some(max_age) = max_age {
+        for (idx, session) in sessions.iter().enumerate() {
+            let expired = now
+                .duration_since(session.modified)
+                .is_ok_and(|age| age > max_age);
+            remove[idx] = expired && !protected[idx];
+        }
+    }
+    if let Some(max_total_bytes) = retention.max_total_bytes {
+        let mut total: u64 = sessions
+            .iter()
+            .zip(&remove)
+            .filter(|(_, removed)| !**removed)
+            .map(|(session, _)| session.bytes)
+            .sum();
+        for idx in (0..sessions.len()).rev() {
+            if total <= max_total_bytes {
+                break;
+            }
+            if !remove[idx] && !protected[idx] {
+                remove[idx] = true;
+                total -= sessions[idx].bytes;
+            }
+        }
+    }
+
+    let mut report = PruneReport::default();
+    for (session, removed) in sessions.into_iter().zip(remove) {
+        if !removed {
+            report.kept_sessions += 1;
+            report.kept_bytes += session.bytes;
+            continue;
+        }
+        if !dry_run {
+            tokio::fs::remove_file(&session.path).await?;
+            remove_empty_parents(&session.path, codex_home).await;
+        }
+        report.removed_sessions.push(PrunedSession {
+            path: session.path,
+            bytes: session.bytes,
+        });
+    }
+
+    let min_ts = max_age
+        .and_then(|max_age| now.checked_sub(max_age))
+        .and_then(|cutoff| cutoff.duration_since(UNIX_EPOCH).ok())
+        .map(|cutoff| cutoff.as_secs());
+    if min_ts.is_some() || history_max_bytes.is_some() {
+        let codex_home = codex_home.to_path_buf();
+        let stats = tokio::task::spawn_blocking(move || {
+            message_history::prune_history(&codex_home, min_ts, history_max_bytes, dry_run)
+        })
+        .await
+        .map_err(io::Error::other)??;
+        report.removed_history_entries = stats.removed_entries;
+        report.removed_history_bytes = stats.removed_bytes;
+    }
+
+    Ok(report)
+}
+
+/// Recursively collect the `rollout-*.jsonl` files under `root`.
+async fn collect_rollouts(root: &Path, sessions: &mut Vec<SessionFile>) -> io::Result<()> {
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let is_rollout = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(".jsonl"));
+            if !file_type.is_file() || !is_rollout {
+                continue;
+            }
+            let metadata = entry.metadata().await?;
+            sessions.push(SessionFile {
+                cwd: read_session_cwd(&path).await,
+                bytes: metadata.len(),
+                modified: metadata.modified()?,
+                path,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The working directory recorded in the session meta line of a rollout.
+async fn read_session_cwd(path: &Path) -> Option<PathBuf> {
+    let file = tokio::fs::File::open(path).await.ok()?;
+    let mut lines = BufReader::new(file).lines();
+    let first = lines.next_line().await.ok()??;
+    let first = session_crypto::decode_line(&first).ok()?;
+    match serde_json::from_str::<RolloutLine>(&first).ok()?.item {
+        RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta.cwd),
+        _ => None,
+    }
+}
+
+/// Remove the date directories left empty by a removed rollout, stopping at
+/// the sessions root.
+async fn remove_empty_parents(path: &Path, codex_home: &Path) {
+    let stop = [
+        codex_home.join(SESSIONS_SUBDIR),
+        codex_home.join(ARCHIVED_SESSIONS_SUBDIR),
+    ];
+    let mut dir = path.parent();
+    while let Some(current) = dir {
+        if stop.iter().any(|root| root == current) || !current.starts_with(codex_home) {
+            break;
+        }
+        if tokio::fs::remove_dir(current).await.is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::fs::File;
+    use std::fs::FileTimes;
+    use tempfile::TempDir;
+
+    const DAY: Duration = Duration::from_secs(SECONDS_PER_DAY);
+
+    fn write_rollout(
+        codex_home: &Path,
+        relative: &str,
+        cwd: &str,
+        bytes: usize,
+        modified: SystemTime,
+    ) -> PathBuf {
+        let path = codex_home.join(relative);
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        let meta = json!({
+            "timestamp": "2025-01-01T00:00:00.000Z",
+            "type": "session_meta",
+            "payload": {
+                "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "timestamp": "2025-01-01T00:00:00.000Z",
+                "cwd": cwd,
+                "originator": "test",
+                "cli_version": "0.0.0",
+                "instructions": null
+            }
+        })
+        .to_string();
+        let padding = "x".repeat(bytes.saturating_sub(meta.len() + 1));
+        std::fs::write(&path, format!("{meta}\n{padding}")).expect("write rollout");
+        File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_times(FileTimes::new().set_modified(modified)))
+            .expect("set mtime");
+        path
+    }
+
+    fn history_line(ts: u64, text: &str) -> String {
+        format!("{{\"session_id\":\"s\",\"ts\":{ts},\"text\":\"{text}\"}}\n")
+    }
+
+    #[tokio::test]
+    async fn prune_applies_age_size_and_keep_last_limits() {
+        let home = TempDir::new().expect("tempdir");
+        let codex_home = home.path();
+        let now = SystemTime::now();
+
+        let old_a = write_rollout(
+            codex_home,
+            "sessions/2025/01/01/rollout-a.jsonl",
+            "/repo/a",
+            1000,
+            now - DAY * 40,
+        );
+        let old_b = write_rollout(
+            codex_home,
+            "sessions/2025/01/02/rollout-b.jsonl",
+            "/repo/b",
+            1000,
+            now - DAY * 41,
+        );
+        let mid_b = write_rollout(
+            codex_home,
+            "sessions/2025/02/01/rollout-c.jsonl",
+            "/repo/b",
+            1000,
+            now - DAY * 10,
+        );
+        let new_b = write_rollout(
+            codex_home,
+            "archived_sessions/rollout-d.jsonl",
+            "/repo/b",
+            1000,
+            now - DAY * 2,
+        );
+        let active = write_rollout(
+            codex_home,
+            "sessions/2025/03/01/rollout-e.jsonl",
+            "/repo/b",
+            1000,
+            now,
+        );
+        std::fs::write(
+            codex_home.join("history.jsonl"),
+            history_line(1, "ancient") + &history_line(u64::MAX / 2, "fresh"),
+        )
+        .expect("write history");
+
+        let retention = Retention {
+            max_age_days: Some(30),
+            max_total_bytes: Some(2500),
+            keep_last_per_cwd: Some(1),
+        };
+
+        let dry_run = prune_codex_home(codex_home, &retention, None, now, true)
+            .await
+            .expect("dry run");
+        let mut removed: Vec<PathBuf> = dry_run
+            .removed_sessions
+            .iter()
+            .map(|session| session.path.clone())
+            .collect();
+        removed.sort();
+        // `old_b` is too old. `old_a` is just as old but is the last session
+        // of /repo/a. The size limit then removes the oldest unprotected
+        // sessions until 2500 bytes remain, keeping the active one.
+        let mut expected = vec![old_b.clone(), mid_b.clone(), new_b.clone()];
+        expected.sort();
+        assert_eq!(removed, expected);
+        assert_eq!(dry_run.kept_sessions, 2);
+        assert_eq!(dry_run.removed_history_entries, 1);
+        assert!(old_b.exists() && mid_b.exists() && new_b.exists());
+
+        let report = prune_codex_home(codex_home, &retention, None, now, false)
+            .await
+            .expect("prune");
+        assert_eq!(report, dry_run);
+        assert!(old_a.exists());
+        assert!(active.exists());
+        assert!(!old_b.exists() && !mid_b.exists() && !new_b.exists());
+        assert!(!codex_home.join("sessions/2025/01/02").exists());
+        assert!(!codex_home.join("sessions/2025/02").exists());
+        assert!(codex_home.join("archived_sessions").exists());
+        assert_eq!(
+            std::fs::read_to_string(codex_home.join("history.jsonl")).expect("history"),
+            history_line(u64::MAX / 2, "fresh")
+        );
+    }
+
+    #[tokio::test]
+    async fn history_max_bytes_drops_oldest_entries() {
+        let home = TempDir::new().expect("tempdir");
+        let codex_home = home.path();
+        let lines = [
+            history_line(1, "one"),
+            history_line(2, "two"),
+            history_line(3, "three"),
+        ];
+        std::fs::write(codex_home.join("history.jsonl"), lines.concat()).expect("write");
+
+        let max_bytes = lines[1].len() + lines[2].len();
+        let report = prune_codex_home(
+            codex_home,
+            &Retention::default(),
+            Some(max_bytes),
+            SystemTime::now(),
+            false,
+        )
+        .await
+        .expect("prune");
+
+        assert_eq!(report.removed_history_entries, 1);
+        assert_eq!(report.removed_history_bytes, lines[0].len() as u64);
+        assert_eq!(
+            std::fs::read_to_string(codex_home.join("history.jsonl")).expect("history"),
+            lines[1..].concat()
+        );
+        assert!(!codex_home.join("history.jsonl.tmp").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(codex_home.join("history.jsonl"))
+                .expect("metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -14,21 +14,29 @@ use uuid::Uuid;
 
 use crate::rollout::INTERACTIVE_SESSION_SOURCES;
 use crate::rollout::list::ConversationItem;
+use crate::rollout::list::ConversationSearch;
 use crate::rollout::list::ConversationsPage;
 use crate::rollout::list::Cursor;
 use crate::rollout::list::get_conversation;
 use crate::rollout::list::get_conversations;
+use crate::rollout::list::search_conversations;
 use anyhow::Result;
 use codex_protocol::ConversationId;
+use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AgentMessageEvent;
+use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::CompactedItem;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::GitInfo;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionMeta;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TurnContextItem;
 use codex_protocol::protocol::UserMessageEvent;
 
 const NO_SOURCE_FILTER: &[SessionSource] = &[];
@@ -1129,3 +1137,257 @@ async fn test_model_provider_filter_selects_only_matching_sessions() -> Result<(
 
     Ok(())
 }
+
+/// Writes a session whose transcript exercises every searchable field.
+fn write_searchable_session(
+    root: &Path,
+    ts_str: &str,
+    uuid: Uuid,
+    cwd: &str,
+    branch: &str,
+    model: &str,
+    items: Vec<RolloutItem>,
+) -> std::io::Result<()> {
+    let day_dir = root.join("sessions/2025/03").join(&ts_str[8..10]);
+    fs::create_dir_all(&day_dir)?;
+    let mut file = File::create(day_dir.join(format!("rollout-{ts_str}-{uuid}.jsonl")))?;
+    let timestamp = format!("{}T10:00:00.000Z", &ts_str[..10]);
+    let meta = RolloutItem::SessionMeta(SessionMetaLine {
+        meta: SessionMeta {
+            id: ConversationId::from_string(&uuid.to_string()).unwrap(),
+            timestamp: timestamp.clone(),
+            cwd: cwd.into(),
+            originator: "test_originator".into(),
+            cli_version: "test_version".into(),
+            instructions: None,
+            source: SessionSource::Cli,
+            model_provider: Some(TEST_PROVIDER.into()),
+        },
+        git: Some(GitInfo {
+            commit_hash: None,
+            branch: Some(branch.into()),
+            repository_url: None,
+        }),
+    });
+    let turn_context = RolloutItem::TurnContext(TurnContextItem {
+        cwd: cwd.into(),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        model: model.into(),
+        effort: None,
+        summary: ReasoningSummary::Auto,
+    });
+    for item in [meta, turn_context].into_iter().chain(items) {
+        let line = RolloutLine {
+            timestamp: timestamp.clone(),
+            item,
+        };
+        writeln!(file, "{}", serde_json::to_string(&line)?)?;
+    }
+    Ok(())
+}
+
+fn user_message(message: &str) -> RolloutItem {
+    RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+        message: message.into(),
+        images: None,
+    }))
+}
+
+#[tokio::test]
+async fn test_search_conversations_matches_transcript_and_metadata() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+    let migration_id = Uuid::from_u128(1);
+    let docs_id = Uuid::from_u128(2);
+
+    write_searchable_session(
+        home,
+        "2025-03-01T10-00-00",
+        migration_id,
+        "/work/app",
+        "main",
+        "gpt-5-codex",
+        vec![
+            user_message("Fix the flaky migration test"),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".into(),
+                arguments: r#"{"command":["bash","-lc","cargo test -p migrations"]}"#.into(),
+                call_id: "call-1".into(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-2".into(),
+                name: "apply_patch".into(),
+                input: "*** Begin Patch\n*** Update File: src/migrate.rs\n@@\n-sleep(1)\n+wait()\n*** End Patch".into(),
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "The retry loop now waits for the lock.".into(),
+            })),
+        ],
+    )?;
+    write_searchable_session(
+        home,
+        "2025-03-02T10-00-00",
+        docs_id,
+        "/work/docs",
+        "feature",
+        "gpt-5",
+        vec![user_message("Update the docs")],
+    )?;
+
+    let search = |search: ConversationSearch| async move {
+        let page = search_conversations(
+            home,
+            10,
+            None,
+            NO_SOURCE_FILTER,
+            None,
+            TEST_PROVIDER,
+            &search,
+        )
+        .await
+        .unwrap();
+        page.items
+            .iter()
+            .map(|item| {
+                let name = item.path.file_name().unwrap().to_string_lossy().to_string();
+                if name.contains(&migration_id.to_string()) {
+                    "migration"
+                } else {
+                    "docs"
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let query = |query: &str| ConversationSearch {
+        query: Some(query.into()),
+        ..Default::default()
+    };
+
+    assert_eq!(search(query("FLAKY migration")).await, vec!["migration"]);
+    assert_eq!(search(query("cargo test")).await, vec!["migration"]);
+    assert_eq!(search(query("src/migrate.rs")).await, vec!["migration"]);
+    assert_eq!(search(query("lock retry")).await, vec!["migration"]);
+    assert_eq!(search(query("docs")).await, vec!["docs"]);
+    // Patch contents other than the file headers are not searchable.
+    assert!(search(query("sleep")).await.is_empty());
+    assert!(search(query("migration docs")).await.is_empty());
+
+    assert_eq!(
+        search(ConversationSearch {
+            cwd: Some("/work".into()),
+            ..Default::default()
+        })
+        .await,
+        vec!["docs", "migration"]
+    );
+    assert_eq!(
+        search(ConversationSearch {
+            cwd: Some("/work/app".into()),
+            git_branch: Some("main".into()),
+            ..Default::default()
+        })
+        .await,
+        vec!["migration"]
+    );
+    assert_eq!(
+        search(ConversationSearch {
+            model: Some("gpt-5".into()),
+            ..Default::default()
+        })
+        .await,
+        vec!["docs"]
+    );
+    let march_2 = chrono::DateTime::parse_from_rfc3339("2025-03-02T00:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    assert_eq!(
+        search(ConversationSearch {
+            created_after: Some(march_2),
+            ..Default::default()
+        })
+        .await,
+        vec!["docs"]
+    );
+    assert_eq!(
+        search(ConversationSearch {
+            created_before: Some(march_2),
+            ..Default::default()
+        })
+        .await,
+        vec!["migration"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_encrypted_rollout_round_trips() -> Result<()> {
+    use crate::config::Config;
+    use crate::config::ConfigOverrides;
+    use crate::config::ConfigToml;
+    use crate::rollout::RolloutRecorder;
+    use crate::rollout::RolloutRecorderParams;
+    use crate::session_crypto;
+
+    session_crypto::use_mock_keyring();
+    let temp = TempDir::new()?;
+    let config = Config::load_from_base_config_with_overrides(
+        ConfigToml {
+            encrypt_session_data: Some(true),
+            ..Default::default()
+        },
+        ConfigOverrides::default(),
+        temp.path().to_path_buf(),
+    )?;
+
+    let recorder = RolloutRecorder::new(
+        &config,
+        RolloutRecorderParams::Create {
+            conversation_id: ConversationId::new(),
+            instructions: None,
+            source: SessionSource::Cli,
+        },
+    )
+    .await?;
+    recorder
+        .record_items(&[RolloutItem::EventMsg(EventMsg::UserMessage(
+            UserMessageEvent {
+                message: "deploy with token hunter2".to_string(),
+                images: None,
+            },
+        ))])
+        .await?;
+    recorder.flush().await?;
+
+    let raw = fs::read_to_string(&recorder.rollout_path)?;
+    assert!(!raw.contains("hunter2"));
+    assert!(raw.lines().count() >= 2);
+    assert!(
+        raw.lines()
+            .all(|line| line.starts_with(session_crypto::ENCRYPTED_LINE_PREFIX))
+    );
+
+    let history = RolloutRecorder::get_rollout_history(&recorder.rollout_path).await?;
+    assert!(history.get_rollout_items().iter().any(|item| matches!(
+        item,
+        RolloutItem::EventMsg(EventMsg::UserMessage(ev)) if ev.message.contains("hunter2")
+    )));
+
+    let page = get_conversations(
+        temp.path(),
+        10,
+        None,
+        INTERACTIVE_SESSION_SOURCES,
+        None,
+        TEST_PROVIDER,
+    )
+    .await?;
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].path, recorder.rollout_path);
+
+    Ok(())
+}
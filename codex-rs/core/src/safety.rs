@@ -4,7 +4,10 @@ use std::path::PathBuf;
 
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use wildmatch::WildMatchPattern;
 
+use crate::config::types::ProtectedPath;
+use crate::config::types::ProtectedPathAction;
 use crate::exec::SandboxType;
 
 use crate::protocol::AskForApproval;
@@ -33,7 +36,10 @@ pub enum SafetyCheck {
         sandbox_type: SandboxType,
         user_explicitly_approved: bool,
     },
-    AskUser,
+    AskUser {
+        /// Shown in the approval prompt when set.
+        reason: Option<String>,
+    },
     Reject {
         reason: String,
     },
@@ -43,6 +49,7 @@ pub fn assess_patch_safety(
     action: &ApplyPatchAction,
     policy: AskForApproval,
     sandbox_policy: &SandboxPolicy,
+    protected_paths: &[ProtectedPath],
     cwd: &Path,
 ) -> SafetyCheck {
     if action.is_empty() {
@@ -51,6 +58,23 @@ pub fn assess_patch_safety(
         };
     }
 
+    // Protected paths are checked first: they apply regardless of the
+    // approval policy and the sandbox.
+    if let Some((path, rule)) = find_protected_path(action, protected_paths, cwd) {
+        let reason = format!(
+            "{} is protected by the `{}` rule in protected_paths",
+            path.display(),
+            rule.pattern
+        );
+        return match (rule.action, policy) {
+            (ProtectedPathAction::Reject, _)
+            | (ProtectedPathAction::Ask, AskForApproval::Never) => SafetyCheck::Reject { reason },
+            (ProtectedPathAction::Ask, _) => SafetyCheck::AskUser {
+                reason: Some(reason),
+            },
+        };
+    }
+
     match policy {
         AskForApproval::OnFailure | AskForApproval::Never | AskForApproval::OnRequest => {
             // Continue to see if this can be auto-approved.
@@ -58,7 +82,7 @@ pub fn assess_patch_safety(
         // TODO(ragona): I'm not sure this is actually correct? I believe in this case
         // we want to continue to the writable paths check before asking the user.
         AskForApproval::UnlessTrusted => {
-            return SafetyCheck::AskUser;
+            return SafetyCheck::AskUser { reason: None };
         }
     }
 
@@ -83,7 +107,7 @@ pub fn assess_patch_safety(
                     sandbox_type,
                     user_explicitly_approved: false,
                 },
-                None => SafetyCheck::AskUser,
+                None => SafetyCheck::AskUser { reason: None },
             }
         }
     } else if policy == AskForApproval::Never {
@@ -92,7 +116,7 @@ pub fn assess_patch_safety(
                 .to_string(),
         }
     } else {
-        SafetyCheck::AskUser
+        SafetyCheck::AskUser { reason: None }
     }
 }
 
@@ -121,7 +145,7 @@ fn is_write_patch_constrained_to_writable_paths(
 ) -> bool {
     // Early‑exit if there are no declared writable roots.
     let writable_roots = match sandbox_policy {
-        SandboxPolicy::ReadOnly => {
+        SandboxPolicy::ReadOnly { .. } => {
             return false;
         }
         SandboxPolicy::DangerFullAccess => {
@@ -130,22 +154,6 @@ fn is_write_patch_constrained_to_writable_paths(
         SandboxPolicy::WorkspaceWrite { .. } => sandbox_policy.get_writable_roots_with_cwd(cwd),
     };
 
-    // Normalize a path by removing `.` and resolving `..` without touching the
-    // filesystem (works even if the file does not exist).
-    fn normalize(path: &Path) -> Option<PathBuf> {
-        let mut out = PathBuf::new();
-        for comp in path.components() {
-            match comp {
-                Component::ParentDir => {
-                    out.pop();
-                }
-                Component::CurDir => { /* skip */ }
-                other => out.push(other.as_os_str()),
-            }
-        }
-        Some(out)
-    }
-
     // Determine whether `path` is inside **any** writable root. Both `path`
     // and roots are converted to absolute, normalized forms before the
     // prefix check.
@@ -167,7 +175,9 @@ fn is_write_patch_constrained_to_writable_paths(
 
     for (path, change) in action.changes() {
         match change {
-            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => {
+            ApplyPatchFileChange::Add { .. }
+            | ApplyPatchFileChange::Delete { .. }
+            | ApplyPatchFileChange::Binary { .. } => {
                 if !is_path_writable(path) {
                     return false;
                 }
@@ -188,6 +198,100 @@ fn is_write_patch_constrained_to_writable_paths(
     true
 }
 
+/// Returns the first path in the patch matched by a protected-path rule,
+/// preferring `reject` rules over `ask` rules.
+fn find_protected_path<'a>(
+    action: &'a ApplyPatchAction,
+    protected_paths: &'a [ProtectedPath],
+    cwd: &Path,
+) -> Option<(&'a Path, &'a ProtectedPath)> {
+    if protected_paths.is_empty() {
+        return None;
+    }
+
+    let mut touched: Vec<&Path> = Vec::new();
+    for (path, change) in action.changes() {
+        touched.push(path);
+        if let ApplyPatchFileChange::Update {
+            move_path: Some(dest),
+            ..
+        } = change
+        {
+            touched.push(dest);
+        }
+    }
+
+    let mut first_match = None;
+    for path in touched {
+        for rule in protected_paths {
+            if !is_protected(path, &rule.pattern, cwd) {
+                continue;
+            }
+            if rule.action == ProtectedPathAction::Reject {
+                return Some((path, rule));
+            }
+            first_match.get_or_insert((path, rule));
+        }
+    }
+    first_match
+}
+
+/// Patterns starting with `/` match the absolute path, other patterns
+/// containing `/` match the path relative to `cwd`, and the rest match the
+/// file name.
+fn is_protected(path: &Path, pattern: &str, cwd: &Path) -> bool {
+    let matches = |pattern: &str, candidate: &str| {
+        WildMatchPattern::<'*', '?'>::new(pattern).matches(candidate)
+    };
+    let Some(abs) = normalize(&cwd.join(path)) else {
+        return false;
+    };
+
+    if pattern.starts_with('/') {
+        return matches(pattern, &slash_path(&abs));
+    }
+    if !pattern.contains('/') {
+        return abs
+            .file_name()
+            .is_some_and(|name| matches(pattern, &name.to_string_lossy()));
+    }
+
+    let Some(relative) = normalize(cwd).and_then(|cwd| abs.strip_prefix(cwd).ok().map(slash_path))
+    else {
+        return false;
+    };
+    // A leading `**/` also matches zero directories.
+    matches(pattern, &relative)
+        || pattern
+            .strip_prefix("**/")
+            .is_some_and(|rest| matches(rest, &relative))
+}
+
+fn slash_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.into_owned()
+    }
+}
+
+// Normalize a path by removing `.` and resolving `..` without touching the
+// filesystem (works even if the file does not exist).
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut out = PathBuf::new();
+    for comp in path.components() {
+        match comp {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => { /* skip */ }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    Some(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,6 +318,10 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            allowed_hosts: vec![],
+            allowed_cidrs: vec![],
+            readable_roots: vec![],
+            deny_read: vec![],
         };
 
         assert!(is_write_patch_constrained_to_writable_paths(
@@ -235,6 +343,10 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            allowed_hosts: vec![],
+            allowed_cidrs: vec![],
+            readable_roots: vec![],
+            deny_read: vec![],
         };
         assert!(is_write_patch_constrained_to_writable_paths(
             &add_outside,
@@ -242,4 +354,70 @@ mod tests {
             &cwd,
         ));
     }
+
+    #[test]
+    fn protected_paths_override_the_approval_policy() {
+        let tmp = TempDir::new().unwrap();
+        let cwd = tmp.path().to_path_buf();
+        let rules = vec![
+            ProtectedPath {
+                pattern: "**/migrations/**".to_string(),
+                action: ProtectedPathAction::Ask,
+            },
+            ProtectedPath {
+                pattern: "*.lock".to_string(),
+                action: ProtectedPathAction::Reject,
+            },
+        ];
+        let add = |p: &str| ApplyPatchAction::new_add_for_test(&cwd.join(p), "".to_string());
+
+        assert_eq!(
+            assess_patch_safety(
+                &add("migrations/0001_init.sql"),
+                AskForApproval::OnRequest,
+                &SandboxPolicy::DangerFullAccess,
+                &rules,
+                &cwd,
+            ),
+            SafetyCheck::AskUser {
+                reason: Some(format!(
+                    "{} is protected by the `**/migrations/**` rule in protected_paths",
+                    cwd.join("migrations/0001_init.sql").display()
+                )),
+            }
+        );
+        assert!(matches!(
+            assess_patch_safety(
+                &add("migrations/0001_init.sql"),
+                AskForApproval::Never,
+                &SandboxPolicy::DangerFullAccess,
+                &rules,
+                &cwd,
+            ),
+            SafetyCheck::Reject { .. }
+        ));
+        assert!(matches!(
+            assess_patch_safety(
+                &add("vendor/Cargo.lock"),
+                AskForApproval::OnRequest,
+                &SandboxPolicy::DangerFullAccess,
+                &rules,
+                &cwd,
+            ),
+            SafetyCheck::Reject { .. }
+        ));
+        assert_eq!(
+            assess_patch_safety(
+                &add("src/lock.rs"),
+                AskForApproval::OnRequest,
+                &SandboxPolicy::DangerFullAccess,
+                &rules,
+                &cwd,
+            ),
+            SafetyCheck::AutoApprove {
+                sandbox_type: SandboxType::None,
+                user_explicitly_approved: false,
+            }
+        );
+    }
 }
@@ -39,6 +39,7 @@ pub async fn spawn_command_under_seatbelt(
         sandbox_policy,
         stdio_policy,
         env,
+        None,
     )
     .await
 }
@@ -98,11 +99,18 @@ pub(crate) fn create_seatbelt_command_args(
         }
     };
 
-    let file_read_policy = if sandbox_policy.has_full_disk_read_access() {
-        "; allow read-only file operations\n(allow file-read*)"
-    } else {
-        ""
-    };
+    // `readable_roots` is only enforced by the Linux sandbox, but `deny_read`
+    // is honored here by denying reads after the blanket allow.
+    let mut file_read_policy = "; allow read-only file operations\n(allow file-read*)".to_string();
+    let mut file_read_params = Vec::new();
+    for (index, denied) in sandbox_policy.get_deny_read_paths().iter().enumerate() {
+        let canonical_denied = denied.canonicalize().unwrap_or_else(|_| denied.clone());
+        let denied_param = format!("DENY_READ_{index}");
+        file_read_policy.push_str(&format!(
+            "\n(deny file-read* (subpath (param \"{denied_param}\")))"
+        ));
+        file_read_params.push((denied_param, canonical_denied));
+    }
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
@@ -115,7 +123,7 @@ pub(crate) fn create_seatbelt_command_args(
         "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
     );
 
-    let dir_params = [file_write_dir_params, macos_dir_params()].concat();
+    let dir_params = [file_write_dir_params, file_read_params, macos_dir_params()].concat();
 
     let mut seatbelt_args: Vec<String> = vec!["-p".to_string(), full_policy];
     let definition_args = dir_params
@@ -186,6 +194,10 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            allowed_hosts: vec![],
+            allowed_cidrs: vec![],
+            readable_roots: vec![],
+            deny_read: vec![],
         };
 
         let args = create_seatbelt_command_args(
@@ -262,6 +274,10 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            allowed_hosts: vec![],
+            allowed_cidrs: vec![],
+            readable_roots: vec![],
+            deny_read: vec![],
         };
 
         let args = create_seatbelt_command_args(
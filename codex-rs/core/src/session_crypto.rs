@@ -0,0 +1,288 @@
+//! Optional encryption at rest for session rollouts and the message history.
+//!
+//! Both files are JSON Lines, appended to one record at a time, so encryption
+//! is applied per line: an encrypted line is [`ENCRYPTED_LINE_PREFIX`]
+//! followed by the base64 encoding of a random nonce and the AES-256-GCM
+//! ciphertext of the original JSON. Plaintext and encrypted lines may appear
+//! in the same file (e.g. after encryption is turned on mid-session), and
+//! readers go through [`decode_line`] so both kinds are handled
+//! transparently.
+//!
+//! A single key per user is stored in the OS keyring and created the first
+//! time a session is written with encryption enabled. Creation happens under
+//! an exclusive lock on a file in `CODEX_HOME` so that Codex processes started
+//! together agree on one key.
+
+use std::borrow::Cow;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_keyring_store::KeyringStore;
+use ring::aead::AES_256_GCM;
+use ring::aead::Aad;
+use ring::aead::LessSafeKey;
+use ring::aead::NONCE_LEN;
+use ring::aead::Nonce;
+use ring::aead::UnboundKey;
+use ring::rand::SecureRandom;
+use ring::rand::SystemRandom;
+
+/// Marks a line written by [`SessionCipher::encrypt_line`].
+pub(crate) const ENCRYPTED_LINE_PREFIX: &str = "codex-enc-v1:";
+
+const KEYRING_SERVICE: &str = "Codex Session Encryption";
+const KEYRING_ACCOUNT: &str = "session-key";
+const KEY_LEN: usize = 32;
+/// Lock file in `CODEX_HOME` held while the key is created.
+const KEY_LOCK_FILENAME: &str = "session-key.lock";
+
+static KEYRING_STORE: OnceLock<Arc<dyn KeyringStore>> = OnceLock::new();
+static CIPHER: Mutex<Option<Arc<SessionCipher>>> = Mutex::new(None);
+
+pub(crate) struct SessionCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl SessionCipher {
+    fn new(key_bytes: &[u8]) -> io::Result<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key_bytes)
+            .map_err(|_| io::Error::other("invalid session encryption key"))?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Encrypt one line of JSON (without its trailing newline).
+    pub(crate) fn encrypt_line(&self, plaintext: &str) -> io::Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| io::Error::other("failed to generate nonce"))?;
+        let mut in_out = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| io::Error::other("failed to encrypt session data"))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&in_out);
+        Ok(format!(
+            "{ENCRYPTED_LINE_PREFIX}{}",
+            BASE64_STANDARD.encode(payload)
+        ))
+    }
+
+    fn decrypt_line(&self, encoded: &str) -> io::Result<String> {
+        let payload = BASE64_STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if payload.len() < NONCE_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "encrypted line is too short",
+            ));
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid nonce"))?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "failed to decrypt session data; was it written with a different key?",
+                )
+            })?;
+        String::from_utf8(plaintext.to_vec())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+fn keyring_store() -> Arc<dyn KeyringStore> {
+    KEYRING_STORE
+        .get_or_init(|| Arc::new(DefaultKeyringStore))
+        .clone()
+}
+
+/// The cipher used to write encrypted lines. Creates and stores a new key in
+/// the OS keyring if none exists yet.
+pub(crate) fn writer_cipher(codex_home: &Path) -> io::Result<Arc<SessionCipher>> {
+    load_cipher(|store| load_or_create_key(store, codex_home))
+}
+
+fn reader_cipher() -> io::Result<Arc<SessionCipher>> {
+    load_cipher(|store| {
+        load_key(store)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "session data is encrypted but no encryption key was found in the keyring",
+            )
+        })
+    })
+}
+
+fn load_cipher(
+    key: impl FnOnce(&dyn KeyringStore) -> io::Result<Vec<u8>>,
+) -> io::Result<Arc<SessionCipher>> {
+    let mut cached = CIPHER.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(cipher) = cached.as_ref() {
+        return Ok(cipher.clone());
+    }
+
+    let key = key(keyring_store().as_ref())?;
+    let cipher = Arc::new(SessionCipher::new(&key)?);
+    *cached = Some(cipher.clone());
+    Ok(cipher)
+}
+
+fn load_key(store: &dyn KeyringStore) -> io::Result<Option<Vec<u8>>> {
+    let encoded = store.load(KEYRING_SERVICE, KEYRING_ACCOUNT).map_err(|e| {
+        io::Error::other(format!(
+            "failed to load the session encryption key from the keyring: {}",
+            e.message()
+        ))
+    })?;
+    encoded
+        .map(|encoded| {
+            BASE64_STANDARD.decode(encoded.trim()).map_err(|e| {
+                io::Error::other(format!("invalid session encryption key in keyring: {e}"))
+            })
+        })
+        .transpose()
+}
+
+fn load_or_create_key(store: &dyn KeyringStore, codex_home: &Path) -> io::Result<Vec<u8>> {
+    if let Some(key) = load_key(store)? {
+        return Ok(key);
+    }
+
+    // Another process may be creating the key right now. Check again once we
+    // hold the lock so that only one of them generates it.
+    std::fs::create_dir_all(codex_home)?;
+    let lock_file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(codex_home.join(KEY_LOCK_FILENAME))?;
+    lock_file.lock()?;
+    if let Some(key) = load_key(store)? {
+        return Ok(key);
+    }
+
+    let mut key = vec![0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| io::Error::other("failed to generate session encryption key"))?;
+    store
+        .save(
+            KEYRING_SERVICE,
+            KEYRING_ACCOUNT,
+            &BASE64_STANDARD.encode(&key),
+        )
+        .map_err(|e| {
+            io::Error::other(format!(
+                "failed to save the session encryption key to the keyring: {}",
+                e.message()
+            ))
+        })?;
+    // Use the key the keyring actually holds, not the one we generated.
+    load_key(store)?.ok_or_else(|| {
+        io::Error::other("the session encryption key was missing from the keyring after saving it")
+    })
+}
+
+/// Encrypt `line` when `cipher` is set; otherwise return it unchanged.
+pub(crate) fn encode_line(cipher: Option<&SessionCipher>, line: String) -> io::Result<String> {
+    match cipher {
+        Some(cipher) => cipher.encrypt_line(&line),
+        None => Ok(line),
+    }
+}
+
+/// Return the plaintext of a line read from a rollout or history file,
+/// decrypting it with the key from the OS keyring if needed.
+pub(crate) fn decode_line(line: &str) -> io::Result<Cow<'_, str>> {
+    match line.trim_start().strip_prefix(ENCRYPTED_LINE_PREFIX) {
+        Some(encoded) => reader_cipher()?.decrypt_line(encoded).map(Cow::Owned),
+        None => Ok(Cow::Borrowed(line)),
+    }
+}
+
+/// Use an in-memory keyring for the rest of the test process.
+#[cfg(test)]
+pub(crate) fn use_mock_keyring() {
+    let _ = KEYRING_STORE
+        .get_or_init(|| Arc::new(codex_keyring_store::tests::MockKeyringStore::default()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn encrypted_lines_round_trip_and_plaintext_passes_through() {
+        use_mock_keyring();
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let cipher = writer_cipher(codex_home.path()).expect("cipher");
+
+        let line = r#"{"type":"event_msg","payload":{"message":"secret"}}"#;
+        let encrypted = encode_line(Some(&cipher), line.to_string()).expect("encrypt");
+        assert!(encrypted.starts_with(ENCRYPTED_LINE_PREFIX));
+        assert!(!encrypted.contains("secret"));
+        // Each line uses a fresh nonce.
+        assert_ne!(encrypted, cipher.encrypt_line(line).expect("encrypt"));
+
+        assert_eq!(decode_line(&encrypted).expect("decrypt"), line);
+        assert_eq!(decode_line(line).expect("plaintext"), line);
+
+        let mut payload = BASE64_STANDARD
+            .decode(&encrypted[ENCRYPTED_LINE_PREFIX.len()..])
+            .expect("base64");
+        let last = payload.len() - 1;
+        payload[last] ^= 1;
+        let tampered = format!("{ENCRYPTED_LINE_PREFIX}{}", BASE64_STANDARD.encode(payload));
+        assert!(decode_line(&tampered).is_err());
+    }
+
+    #[test]
+    fn racing_creators_agree_on_one_key() {
+        let store = codex_keyring_store::tests::MockKeyringStore::default();
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let barrier = std::sync::Barrier::new(8);
+        let (store_ref, home, barrier) = (&store, codex_home.path(), &barrier);
+
+        let keys: Vec<Vec<u8>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    scope.spawn(move || {
+                        barrier.wait();
+                        load_or_create_key(store_ref, home).expect("key")
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("thread"))
+                .collect()
+        });
+
+        let stored = load_key(&store).expect("load").expect("stored key");
+        assert!(keys.iter().all(|key| key == &stored));
+    }
+}
@@ -2,6 +2,9 @@ use std::sync::Arc;
 
 use crate::AuthManager;
 use crate::RolloutRecorder;
+use crate::command_safety::exec_policy::ExecPolicy;
+use crate::context_manager::SecretRedactor;
+use crate::hooks::Hooks;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecSessionManager;
@@ -19,4 +22,7 @@ pub(crate) struct SessionServices {
     pub(crate) auth_manager: Arc<AuthManager>,
     pub(crate) otel_event_manager: OtelEventManager,
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
+    pub(crate) exec_policy: ExecPolicy,
+    pub(crate) secret_redactor: Option<SecretRedactor>,
+    pub(crate) hooks: Hooks,
 }
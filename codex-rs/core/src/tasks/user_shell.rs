@@ -111,6 +111,7 @@ impl SessionTask for UserShellCommandTask {
             with_escalated_permissions: None,
             justification: None,
             arg0: None,
+            limits: turn_context.sandbox_limits,
         };
 
         let stdout_stream = Some(StdoutStream {
@@ -134,6 +135,8 @@ impl SessionTask for UserShellCommandTask {
                     aggregated_output: StreamOutput::new(aborted_message.clone()),
                     duration: Duration::ZERO,
                     timed_out: false,
+                    termination_reason: None,
+                    blocked_network_destinations: Vec::new(),
                 };
                 let output_items = [user_shell_command_record_item(&raw_command, &exec_output)];
                 session
@@ -150,6 +153,8 @@ impl SessionTask for UserShellCommandTask {
                             exit_code: -1,
                             duration: Duration::ZERO,
                             formatted_output: aborted_message,
+                            termination_reason: None,
+                            blocked_network_destinations: Vec::new(),
                         }),
                     )
                     .await;
@@ -166,6 +171,10 @@ impl SessionTask for UserShellCommandTask {
                             exit_code: output.exit_code,
                             duration: output.duration,
                             formatted_output: format_exec_output_str(&output),
+                            termination_reason: output.termination_reason,
+                            blocked_network_destinations: output
+                                .blocked_network_destinations
+                                .clone(),
                         }),
                     )
                     .await;
@@ -185,6 +194,8 @@ impl SessionTask for UserShellCommandTask {
                     aggregated_output: StreamOutput::new(message.clone()),
                     duration: Duration::ZERO,
                     timed_out: false,
+                    termination_reason: None,
+                    blocked_network_destinations: Vec::new(),
                 };
                 session
                     .send_event(
@@ -197,6 +208,8 @@ impl SessionTask for UserShellCommandTask {
                             exit_code: exec_output.exit_code,
                             duration: exec_output.duration,
                             formatted_output: format_exec_output_str(&exec_output),
+                            termination_reason: None,
+                            blocked_network_destinations: Vec::new(),
                         }),
                     )
                     .await;
@@ -28,6 +28,9 @@ pub struct ToolInvocation {
     pub call_id: String,
     pub tool_name: String,
     pub payload: ToolPayload,
+    /// Set when a `pre_tool_call` hook answered `allow`, so the call runs
+    /// without an initial approval prompt.
+    pub approved_by_hook: bool,
 }
 
 #[derive(Clone)]
@@ -61,6 +64,39 @@ impl ToolPayload {
             ToolPayload::Mcp { raw_arguments, .. } => Cow::Borrowed(raw_arguments),
         }
     }
+
+    /// The raw arguments of the call, as shown to lifecycle hooks. Local
+    /// shell calls are rendered in the `shell` tool's JSON shape.
+    pub fn hook_arguments(&self) -> Cow<'_, str> {
+        match self {
+            ToolPayload::LocalShell { params } => Cow::Owned(
+                serde_json::json!({
+                    "command": params.command,
+                    "workdir": params.workdir,
+                    "timeout_ms": params.timeout_ms,
+                })
+                .to_string(),
+            ),
+            _ => self.log_payload(),
+        }
+    }
+
+    /// Replace the arguments of the call with ones rewritten by a hook.
+    pub fn with_hook_arguments(self, arguments: String) -> Result<Self, serde_json::Error> {
+        Ok(match self {
+            ToolPayload::Function { .. } => ToolPayload::Function { arguments },
+            ToolPayload::Custom { .. } => ToolPayload::Custom { input: arguments },
+            ToolPayload::LocalShell { .. } => ToolPayload::LocalShell {
+                params: serde_json::from_str(&arguments)?,
+            },
+            ToolPayload::UnifiedExec { .. } => ToolPayload::UnifiedExec { arguments },
+            ToolPayload::Mcp { server, tool, .. } => ToolPayload::Mcp {
+                server,
+                tool,
+                raw_arguments: arguments,
+            },
+        })
+    }
 }
 
 #[derive(Clone)]
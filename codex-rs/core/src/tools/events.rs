@@ -8,6 +8,7 @@ use crate::parse_command::parse_command;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandBeginEvent;
 use crate::protocol::ExecCommandEndEvent;
+use crate::protocol::ExecTerminationReason;
 use crate::protocol::FileChange;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
@@ -140,6 +141,8 @@ impl ToolEmitter {
                     output.exit_code,
                     output.duration,
                     format_exec_output_str(&output),
+                    output.termination_reason,
+                    output.blocked_network_destinations.clone(),
                 )
                 .await;
             }
@@ -152,6 +155,8 @@ impl ToolEmitter {
                     output.exit_code,
                     output.duration,
                     format_exec_output_str(&output),
+                    output.termination_reason,
+                    output.blocked_network_destinations.clone(),
                 )
                 .await;
             }
@@ -164,6 +169,8 @@ impl ToolEmitter {
                     -1,
                     Duration::ZERO,
                     message.clone(),
+                    None,
+                    Vec::new(),
                 )
                 .await;
             }
@@ -229,6 +236,8 @@ impl ToolEmitter {
                     output.exit_code,
                     output.duration,
                     format_exec_output_str(&output),
+                    output.termination_reason,
+                    output.blocked_network_destinations.clone(),
                 )
                 .await;
             }
@@ -244,6 +253,8 @@ impl ToolEmitter {
                     output.exit_code,
                     output.duration,
                     format_exec_output_str(&output),
+                    output.termination_reason,
+                    output.blocked_network_destinations.clone(),
                 )
                 .await;
             }
@@ -259,6 +270,8 @@ impl ToolEmitter {
                     -1,
                     Duration::ZERO,
                     message.clone(),
+                    None,
+                    Vec::new(),
                 )
                 .await;
             }
@@ -287,7 +300,10 @@ impl ToolEmitter {
                 (event, result)
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
-            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output })))
+            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::NetworkBlocked {
+                output, ..
+            }))) => {
                 let response = super::format_exec_output_for_model(&output);
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
@@ -317,6 +333,7 @@ impl ToolEmitter {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn emit_exec_end(
     ctx: ToolEventCtx<'_>,
     stdout: String,
@@ -325,6 +342,8 @@ async fn emit_exec_end(
     exit_code: i32,
     duration: Duration,
     formatted_output: String,
+    termination_reason: Option<ExecTerminationReason>,
+    blocked_network_destinations: Vec<String>,
 ) {
     ctx.session
         .send_event(
@@ -337,6 +356,8 @@ async fn emit_exec_end(
                 exit_code,
                 duration,
                 formatted_output,
+                termination_reason,
+                blocked_network_destinations,
             }),
         )
         .await;
@@ -22,6 +22,7 @@ use crate::tools::sandboxing::ToolCtx;
 use crate::tools::spec::ApplyPatchToolArgs;
 use crate::tools::spec::JsonSchema;
 use async_trait::async_trait;
+use codex_apply_patch::ApplyPatchError;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -50,6 +51,7 @@ impl ToolHandler for ApplyPatchHandler {
             call_id,
             tool_name,
             payload,
+            approved_by_hook,
         } = invocation;
 
         let patch_input = match payload {
@@ -114,6 +116,7 @@ impl ToolHandler for ApplyPatchHandler {
                             turn: turn.as_ref(),
                             call_id: call_id.clone(),
                             tool_name: tool_name.to_string(),
+                            approved_by_hook,
                         };
                         let out = orchestrator
                             .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
@@ -133,11 +136,9 @@ impl ToolHandler for ApplyPatchHandler {
                     }
                 }
             }
-            codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
-                Err(FunctionCallError::RespondToModel(format!(
-                    "apply_patch verification failed: {parse_error}"
-                )))
-            }
+            codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => Err(
+                FunctionCallError::RespondToModel(verification_error_message(&parse_error)),
+            ),
             codex_apply_patch::MaybeApplyPatchVerified::ShellParseError(error) => {
                 tracing::trace!("Failed to parse apply_patch input, {error:?}");
                 Err(FunctionCallError::RespondToModel(
@@ -153,6 +154,20 @@ impl ToolHandler for ApplyPatchHandler {
     }
 }
 
+/// Formats an `apply_patch` verification failure for the model. A context
+/// mismatch already carries the file's actual content around the closest
+/// match, so steer the model toward fixing the hunk instead of re-reading the
+/// whole file.
+pub(crate) fn verification_error_message(err: &ApplyPatchError) -> String {
+    match err {
+        ApplyPatchError::ContextMismatch(mismatch) if mismatch.closest_match.is_some() => format!(
+            "apply_patch verification failed: {mismatch}\nNo changes were applied. Rewrite hunk {} against the actual content above and retry.",
+            mismatch.hunk_index + 1
+        ),
+        _ => format!("apply_patch verification failed: {err}"),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum ApplyPatchToolType {
@@ -195,13 +210,16 @@ Your patch language is a stripped‑down, file‑oriented diff format designed t
 
 Within that envelope, you get a sequence of file operations.
 You MUST include a header to specify the action you are taking.
-Each operation starts with one of three headers:
+Each operation starts with one of five headers:
 
 *** Add File: <path> - create a new file. Every following line is a + line (the initial contents).
 *** Delete File: <path> - remove an existing file. Nothing follows.
 *** Update File: <path> - patch an existing file in place (optionally with a rename).
+*** Add Binary File: <path> - create a new binary file. Every following line is base64 (the initial contents).
+*** Replace Binary File: <path> - overwrite an existing binary file. Every following line is base64 (the new contents).
 
 May be immediately followed by *** Move to: <new path> if you want to rename the file.
+May then be followed by *** Set Mode: executable (or regular) to change whether the file is executable; the hunks are optional when only the mode changes.
 Then one or more “hunks”, each introduced by @@ (optionally followed by a hunk header).
 Within a hunk each line starts with:
 
@@ -227,11 +245,14 @@ The full grammar definition is below:
 Patch := Begin { FileOp } End
 Begin := "*** Begin Patch" NEWLINE
 End := "*** End Patch" NEWLINE
-FileOp := AddFile | DeleteFile | UpdateFile
+FileOp := AddFile | DeleteFile | UpdateFile | AddBinaryFile | ReplaceBinaryFile
 AddFile := "*** Add File: " path NEWLINE { "+" line NEWLINE }
 DeleteFile := "*** Delete File: " path NEWLINE
-UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] { Hunk }
+UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] [ SetMode ] { Hunk }
 MoveTo := "*** Move to: " newPath NEWLINE
+SetMode := "*** Set Mode: " ("executable" | "regular") NEWLINE
+AddBinaryFile := "*** Add Binary File: " path NEWLINE { base64 NEWLINE }
+ReplaceBinaryFile := "*** Replace Binary File: " path NEWLINE { base64 NEWLINE }
 Hunk := "@@" [ header ] NEWLINE { HunkLine } [ "*** End of File" NEWLINE ]
 HunkLine := (" " | "-" | "+") text NEWLINE
 
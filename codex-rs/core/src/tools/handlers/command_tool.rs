@@ -0,0 +1,471 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::config::types::CustomToolConfig;
+use crate::exec::ExecParams;
+use crate::exec_env::create_env;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ShellHandler;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+/// Runs a tool declared under `[tools.custom.<name>]` by expanding its
+/// command template and executing it like a `shell` call.
+pub struct CommandToolHandler {
+    tool: CustomToolConfig,
+    /// Parameters declared in the tool's schema. Only these placeholders are
+    /// substituted.
+    parameter_names: BTreeSet<String>,
+}
+
+impl CommandToolHandler {
+    pub fn new(tool: CustomToolConfig) -> Self {
+        let parameter_names = tool
+            .parameters
+            .as_ref()
+            .and_then(|schema| schema.get("properties"))
+            .and_then(Value::as_object)
+            .map(|properties| properties.keys().cloned().collect())
+            .unwrap_or_default();
+        Self {
+            tool,
+            parameter_names,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for CommandToolHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            call_id,
+            tool_name,
+            payload,
+            approved_by_hook,
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{tool_name} handler received unsupported payload"
+                )));
+            }
+        };
+        let arguments: Map<String, Value> = if arguments.trim().is_empty() {
+            Map::new()
+        } else {
+            serde_json::from_str(&arguments).map_err(|err| {
+                FunctionCallError::RespondToModel(format!(
+                    "failed to parse function arguments: {err:?}"
+                ))
+            })?
+        };
+
+        if let Some(schema) = &self.tool.parameters {
+            validate_argument("arguments", schema, &Value::Object(arguments.clone()))
+                .map_err(FunctionCallError::RespondToModel)?;
+        }
+        let command = render_command(
+            &self.tool.command,
+            &arguments,
+            &self.parameter_names,
+            self.tool.allow_leading_dash,
+        )
+        .map_err(FunctionCallError::RespondToModel)?;
+        let exec_params = ExecParams {
+            command,
+            cwd: self
+                .tool
+                .cwd
+                .as_ref()
+                .map_or_else(|| turn.cwd.clone(), |cwd| turn.cwd.join(cwd)),
+            timeout_ms: self.tool.timeout_ms,
+            env: create_env(&turn.shell_environment_policy),
+            with_escalated_permissions: None,
+            justification: None,
+            arg0: None,
+        };
+
+        ShellHandler::run_exec_like(
+            tool_name.as_str(),
+            exec_params,
+            session,
+            turn,
+            tracker,
+            call_id,
+            false,
+            approved_by_hook,
+        )
+        .await
+    }
+}
+
+/// Expand `{name}` placeholders in `template` with the tool arguments.
+///
+/// An element that consists of a single placeholder is dropped when the
+/// argument is absent and expands to one element per item when the argument
+/// is an array. Placeholders embedded in a larger element are replaced with
+/// the argument's text (empty when absent). Braces that do not name a
+/// declared parameter are kept as-is.
+///
+/// Unless `allow_leading_dash` is set, an argument may not put a `-` at the
+/// start of an element, where the command would parse it as an option.
+fn render_command(
+    template: &[String],
+    arguments: &Map<String, Value>,
+    parameter_names: &BTreeSet<String>,
+    allow_leading_dash: bool,
+) -> Result<Vec<String>, String> {
+    let is_parameter = |name: &str| parameter_names.contains(name);
+
+    let mut command = Vec::with_capacity(template.len());
+    for element in template {
+        if let Some(name) = element
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .filter(|name| is_parameter(name))
+        {
+            match arguments.get(name) {
+                None | Some(Value::Null) => {}
+                Some(Value::Array(items)) => {
+                    for item in items {
+                        let text = argument_text(name, item)?;
+                        check_leading_dash(name, &text, allow_leading_dash)?;
+                        command.push(text);
+                    }
+                }
+                Some(value) => {
+                    let text = argument_text(name, value)?;
+                    check_leading_dash(name, &text, allow_leading_dash)?;
+                    command.push(text);
+                }
+            }
+            continue;
+        }
+
+        let mut rendered = String::with_capacity(element.len());
+        let mut rest = element.as_str();
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            match after.find('}') {
+                Some(end) if is_parameter(&after[..end]) => {
+                    let name = &after[..end];
+                    if let Some(value) = arguments.get(name) {
+                        let text = argument_text(name, value)?;
+                        if rendered.is_empty() {
+                            check_leading_dash(name, &text, allow_leading_dash)?;
+                        }
+                        rendered.push_str(&text);
+                    }
+                    rest = &after[end + 1..];
+                }
+                _ => {
+                    rendered.push('{');
+                    rest = after;
+                }
+            }
+        }
+        rendered.push_str(rest);
+        command.push(rendered);
+    }
+
+    if command.is_empty() {
+        return Err("command template expanded to an empty command".to_string());
+    }
+    Ok(command)
+}
+
+fn check_leading_dash(name: &str, text: &str, allow_leading_dash: bool) -> Result<(), String> {
+    if !allow_leading_dash && text.starts_with('-') {
+        return Err(format!(
+            "argument `{name}` must not start with `-`, since the command would read it as an option"
+        ));
+    }
+    Ok(())
+}
+
+/// Checks `value` against the JSON Schema keywords that matter for command
+/// arguments: `type`, `enum`, `const`, `required`, `properties`,
+/// `additionalProperties` and `items`. Other keywords are not enforced.
+fn validate_argument(name: &str, schema: &Value, value: &Value) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        // Boolean schemas are not enforced.
+        return Ok(());
+    };
+
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|ty| has_type(value, ty)) {
+        return Err(format!(
+            "`{name}` must be of type {}, got {value}",
+            types.join(" or ")
+        ));
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        return Err(format!(
+            "`{name}` must be one of {}, got {value}",
+            allowed.join(", ")
+        ));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        return Err(format!("`{name}` must be {expected}, got {value}"));
+    }
+
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for required in schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                if object.get(required).is_none_or(Value::is_null) {
+                    return Err(format!("missing required argument `{required}`"));
+                }
+            }
+            for (key, item) in object {
+                match properties.and_then(|properties| properties.get(key)) {
+                    // Omitted optional arguments may be sent as `null`.
+                    Some(_) if item.is_null() => {}
+                    Some(property) => validate_argument(key, property, item)?,
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            return Err(format!("unexpected argument `{key}`"));
+                        }
+                        Some(additional) => validate_argument(key, additional, item)?,
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for item in items {
+                    validate_argument(name, item_schema, item)?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn argument_text(name: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        Value::Null => Ok(String::new()),
+        Value::Array(_) | Value::Object(_) => Err(format!(
+            "argument `{name}` must be a string, number or boolean here"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn render(
+        template: &[&str],
+        arguments: Value,
+        declared: &[&str],
+        allow_leading_dash: bool,
+    ) -> Result<Vec<String>, String> {
+        let template: Vec<String> = template.iter().map(ToString::to_string).collect();
+        let arguments = arguments.as_object().cloned().unwrap_or_default();
+        let declared = declared.iter().map(ToString::to_string).collect();
+        render_command(&template, &arguments, &declared, allow_leading_dash)
+    }
+
+    #[test]
+    fn substitutes_whole_and_embedded_placeholders() {
+        assert_eq!(
+            render(
+                &["make", "lint-file", "FILE={path}", "{flags}", "{verbose}"],
+                json!({ "path": "src/main.rs", "flags": ["-j", 4] }),
+                &["path", "flags", "verbose"],
+                true,
+            ),
+            Ok(vec![
+                "make".to_string(),
+                "lint-file".to_string(),
+                "FILE=src/main.rs".to_string(),
+                "-j".to_string(),
+                "4".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn leaves_unknown_braces_alone() {
+        assert_eq!(
+            render(
+                &[
+                    "bazel",
+                    "query",
+                    "kind(rule, {target})",
+                    "--output={fmt",
+                    "{other}"
+                ],
+                json!({ "target": "//..." }),
+                &["target"],
+                false,
+            ),
+            Ok(vec![
+                "bazel".to_string(),
+                "query".to_string(),
+                "kind(rule, //...)".to_string(),
+                "--output={fmt".to_string(),
+                "{other}".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_structured_values_inside_an_element() {
+        assert_eq!(
+            render(
+                &["echo", "x={items}"],
+                json!({ "items": [1, 2] }),
+                &["items"],
+                false,
+            ),
+            Err("argument `items` must be a string, number or boolean here".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_values_that_would_become_options() {
+        let err = Err(
+            "argument `path` must not start with `-`, since the command would read it as an option"
+                .to_string(),
+        );
+        assert_eq!(
+            render(
+                &["rm", "{path}"],
+                json!({ "path": "-rf" }),
+                &["path"],
+                false
+            ),
+            err
+        );
+        assert_eq!(
+            render(
+                &["rm", "{path}"],
+                json!({ "path": ["a", "-rf"] }),
+                &["path"],
+                false
+            ),
+            err
+        );
+        assert_eq!(
+            render(
+                &["cat", "{path}.txt"],
+                json!({ "path": "-n" }),
+                &["path"],
+                false
+            ),
+            err
+        );
+        assert_eq!(
+            render(
+                &["echo", "x={path}"],
+                json!({ "path": "-n" }),
+                &["path"],
+                false
+            ),
+            Ok(vec!["echo".to_string(), "x=-n".to_string()])
+        );
+        assert_eq!(
+            render(&["rm", "{path}"], json!({ "path": "-rf" }), &["path"], true),
+            Ok(vec!["rm".to_string(), "-rf".to_string()])
+        );
+    }
+
+    #[test]
+    fn validates_arguments_against_the_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" },
+                "jobs": { "type": "integer" },
+                "mode": { "enum": ["fast", "full"] },
+                "flags": { "type": "array", "items": { "type": "string" } },
+            },
+            "required": ["path"],
+            "additionalProperties": false,
+        });
+        let validate = |arguments: Value| validate_argument("arguments", &schema, &arguments);
+
+        assert_eq!(
+            validate(json!({ "path": "a.rs", "jobs": 4, "mode": "fast", "flags": ["x"] })),
+            Ok(())
+        );
+        assert_eq!(validate(json!({ "path": "a.rs", "jobs": null })), Ok(()));
+        assert_eq!(
+            validate(json!({ "jobs": 4 })),
+            Err("missing required argument `path`".to_string())
+        );
+        assert_eq!(
+            validate(json!({ "path": ["a.rs"] })),
+            Err(r#"`path` must be of type string, got ["a.rs"]"#.to_string())
+        );
+        assert_eq!(
+            validate(json!({ "path": "a.rs", "jobs": 1.5 })),
+            Err("`jobs` must be of type integer, got 1.5".to_string())
+        );
+        assert_eq!(
+            validate(json!({ "path": "a.rs", "mode": "slow" })),
+            Err(r#"`mode` must be one of "fast", "full", got "slow""#.to_string())
+        );
+        assert_eq!(
+            validate(json!({ "path": "a.rs", "flags": [1] })),
+            Err("`flags` must be of type string, got 1".to_string())
+        );
+        assert_eq!(
+            validate(json!({ "path": "a.rs", "extra": true })),
+            Err("unexpected argument `extra`".to_string())
+        );
+    }
+}
@@ -10,6 +10,8 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_readable;
+use crate::tools::handlers::is_readable;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 
@@ -75,6 +77,7 @@ impl ToolHandler for GrepFilesHandler {
         let search_path = turn.resolve_path(args.path.clone());
 
         verify_path_exists(&search_path).await?;
+        ensure_readable(&turn, &search_path)?;
 
         let include = args.include.as_deref().map(str::trim).and_then(|val| {
             if val.is_empty() {
@@ -84,8 +87,20 @@ impl ToolHandler for GrepFilesHandler {
             }
         });
 
-        let search_results =
-            run_rg_search(pattern, include.as_deref(), &search_path, limit, &turn.cwd).await?;
+        // rg itself runs outside the sandbox, so drop matches the policy
+        // would not let a command read.
+        let search_results: Vec<String> = run_rg_search(
+            pattern,
+            include.as_deref(),
+            &search_path,
+            MAX_LIMIT,
+            &turn.cwd,
+        )
+        .await?
+        .into_iter()
+        .filter(|result| is_readable(&turn, Path::new(result)))
+        .take(limit)
+        .collect();
 
         if search_results.is_empty() {
             Ok(ToolOutput::Function {
@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::memory::MemoryStore;
+use crate::memory::format_memory_entry;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct MemoryHandler;
+
+fn default_limit() -> f64 {
+    10.0
+}
+
+#[derive(Deserialize)]
+struct MemoryWriteArgs {
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct MemorySearchArgs {
+    #[serde(default)]
+    query: String,
+    // Models sometimes send `5.0` for an integer; accept any whole number.
+    #[serde(default = "default_limit")]
+    limit: f64,
+}
+
+#[derive(Deserialize)]
+struct MemoryDeleteArgs {
+    ids: Vec<String>,
+}
+
+#[async_trait]
+impl ToolHandler for MemoryHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{tool_name} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let store = MemoryStore::for_project(&turn.client.config().codex_home, &turn.cwd);
+        let content = match tool_name.as_str() {
+            "memory_write" => {
+                let args: MemoryWriteArgs = parse_arguments(&arguments)?;
+                if args.content.trim().is_empty() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "content must not be empty".to_string(),
+                    ));
+                }
+                let entry = store
+                    .write(args.content, args.tags)
+                    .await
+                    .map_err(|err| store_error("save memory", &err))?;
+                format!("Saved memory {}.", entry.id)
+            }
+            "memory_search" => {
+                let args: MemorySearchArgs = parse_arguments(&arguments)?;
+                if args.limit < 1.0 || args.limit.fract() != 0.0 {
+                    return Err(FunctionCallError::RespondToModel(
+                        "limit must be a positive whole number".to_string(),
+                    ));
+                }
+                let entries = store
+                    .search(&args.query, args.limit as usize)
+                    .await
+                    .map_err(|err| store_error("search memories", &err))?;
+                if entries.is_empty() {
+                    "No matching memories.".to_string()
+                } else {
+                    entries
+                        .iter()
+                        .map(|entry| format_memory_entry(entry, None))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
+            "memory_delete" => {
+                let args: MemoryDeleteArgs = parse_arguments(&arguments)?;
+                let removed = store
+                    .delete(&args.ids)
+                    .await
+                    .map_err(|err| store_error("delete memories", &err))?;
+                let missing: Vec<&str> = args
+                    .ids
+                    .iter()
+                    .filter(|id| !removed.iter().any(|entry| &entry.id == *id))
+                    .map(String::as_str)
+                    .collect();
+                if missing.is_empty() {
+                    format!("Deleted {} memories.", removed.len())
+                } else {
+                    format!(
+                        "Deleted {} memories. No memory found for: {}",
+                        removed.len(),
+                        missing.join(", ")
+                    )
+                }
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unknown memory tool: {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+fn parse_arguments<'a, T: Deserialize<'a>>(arguments: &'a str) -> Result<T, FunctionCallError> {
+    serde_json::from_str(arguments).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err:?}"))
+    })
+}
+
+fn store_error(action: &str, err: &std::io::Error) -> FunctionCallError {
+    FunctionCallError::RespondToModel(format!("failed to {action}: {err}"))
+}
@@ -1,8 +1,15 @@
+use std::path::Path;
+
+use crate::codex::TurnContext;
+use crate::function_tool::FunctionCallError;
+
 pub mod apply_patch;
+mod command_tool;
 mod grep_files;
 mod list_dir;
 mod mcp;
 mod mcp_resource;
+mod memory;
 mod plan;
 mod read_file;
 mod shell;
@@ -13,13 +20,93 @@ mod view_image;
 pub use plan::PLAN_TOOL;
 
 pub use apply_patch::ApplyPatchHandler;
+pub use command_tool::CommandToolHandler;
 pub use grep_files::GrepFilesHandler;
 pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
+pub use memory::MemoryHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
 pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
+
+/// Whether the turn's sandbox policy lets a sandboxed command read `path`: it
+/// must not lie under one of the `deny_read` paths and, when `readable_roots`
+/// restricts reads, must lie under one of the readable roots. Symlinks are
+/// followed, as they would be by the sandbox.
+pub(crate) fn is_readable(turn: &TurnContext, path: &Path) -> bool {
+    let policy = &turn.sandbox_policy;
+    let path = turn.cwd.join(path);
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+    if policy.is_read_denied(&path) || policy.is_read_denied(&canonical) {
+        return false;
+    }
+    match policy.get_readable_roots_with_cwd(&turn.cwd) {
+        Some(roots) => roots.iter().any(|root| {
+            let root = root.canonicalize().unwrap_or_else(|_| root.clone());
+            canonical.starts_with(root)
+        }),
+        None => true,
+    }
+}
+
+/// The in-process tools read files directly rather than through the sandbox,
+/// so they check [`is_readable`] before touching `path`.
+pub(crate) fn ensure_readable(turn: &TurnContext, path: &Path) -> Result<(), FunctionCallError> {
+    if is_readable(turn, path) {
+        Ok(())
+    } else {
+        Err(FunctionCallError::RespondToModel(format!(
+            "`{}` is not readable under the sandbox policy",
+            path.display()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::protocol::SandboxPolicy;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn in_process_reads_follow_the_sandbox_policy() -> std::io::Result<()> {
+        let workspace = TempDir::new()?;
+        let outside = TempDir::new()?;
+        let workspace_root = workspace.path().canonicalize()?;
+        let secret = workspace_root.join("secrets/key.pem");
+        std::fs::create_dir(workspace_root.join("secrets"))?;
+        std::fs::write(&secret, "key")?;
+        std::fs::write(workspace_root.join("README.md"), "readme")?;
+        std::fs::write(outside.path().join("notes.txt"), "notes")?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&secret, workspace_root.join("link.pem"))?;
+
+        let (_, mut turn) = make_session_and_context();
+        turn.cwd = workspace_root.clone();
+        turn.sandbox_policy = SandboxPolicy::ReadOnly {
+            readable_roots: vec![PathBuf::from("/usr")],
+            deny_read: vec![workspace_root.join("secrets")],
+        };
+
+        assert!(is_readable(&turn, &workspace_root.join("README.md")));
+        assert!(!is_readable(&turn, &secret));
+        assert!(!is_readable(
+            &turn,
+            &workspace_root.join("secrets/../secrets/key.pem")
+        ));
+        #[cfg(unix)]
+        assert!(!is_readable(&turn, &workspace_root.join("link.pem")));
+        assert!(!is_readable(&turn, &outside.path().join("notes.txt")));
+        assert!(ensure_readable(&turn, &secret).is_err());
+
+        turn.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        assert!(is_readable(&turn, &secret));
+        Ok(())
+    }
+}
@@ -14,6 +14,7 @@ use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
+use crate::tools::handlers::apply_patch::verification_error_message;
 use crate::tools::orchestrator::ToolOrchestrator;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -60,6 +61,7 @@ impl ToolHandler for ShellHandler {
             call_id,
             tool_name,
             payload,
+            approved_by_hook,
         } = invocation;
 
         match payload {
@@ -79,6 +81,7 @@ impl ToolHandler for ShellHandler {
                     tracker,
                     call_id,
                     false,
+                    approved_by_hook,
                 )
                 .await
             }
@@ -92,6 +95,7 @@ impl ToolHandler for ShellHandler {
                     tracker,
                     call_id,
                     true,
+                    approved_by_hook,
                 )
                 .await
             }
@@ -103,7 +107,8 @@ impl ToolHandler for ShellHandler {
 }
 
 impl ShellHandler {
-    async fn run_exec_like(
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn run_exec_like(
         tool_name: &str,
         exec_params: ExecParams,
         session: Arc<crate::codex::Session>,
@@ -111,6 +116,7 @@ impl ShellHandler {
         tracker: crate::tools::context::SharedTurnDiffTracker,
         call_id: String,
         is_user_shell_command: bool,
+        approved_by_hook: bool,
     ) -> Result<ToolOutput, FunctionCallError> {
         // Approval policy guard for explicit escalation in non-OnRequest modes.
         if exec_params.with_escalated_permissions.unwrap_or(false)
@@ -170,6 +176,7 @@ impl ShellHandler {
                             turn: turn.as_ref(),
                             call_id: call_id.clone(),
                             tool_name: tool_name.to_string(),
+                            approved_by_hook,
                         };
                         let out = orchestrator
                             .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
@@ -190,9 +197,9 @@ impl ShellHandler {
                 }
             }
             codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
-                return Err(FunctionCallError::RespondToModel(format!(
-                    "apply_patch verification failed: {parse_error}"
-                )));
+                return Err(FunctionCallError::RespondToModel(
+                    verification_error_message(&parse_error),
+                ));
             }
             codex_apply_patch::MaybeApplyPatchVerified::ShellParseError(error) => {
                 tracing::trace!("Failed to parse shell command, {error:?}");
@@ -227,6 +234,7 @@ impl ShellHandler {
             turn: turn.as_ref(),
             call_id: call_id.clone(),
             tool_name: tool_name.to_string(),
+            approved_by_hook,
         };
         let out = orchestrator
             .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
@@ -81,6 +81,7 @@ impl ToolHandler for UnifiedExecHandler {
             call_id,
             tool_name,
             payload,
+            approved_by_hook,
             ..
         } = invocation;
 
@@ -95,7 +96,12 @@ impl ToolHandler for UnifiedExecHandler {
         };
 
         let manager: &UnifiedExecSessionManager = &session.services.unified_exec_manager;
-        let context = UnifiedExecContext::new(session.clone(), turn.clone(), call_id.clone());
+        let context = UnifiedExecContext::new(
+            session.clone(),
+            turn.clone(),
+            call_id.clone(),
+            approved_by_hook,
+        );
 
         let response = match tool_name.as_str() {
             "exec_command" => {
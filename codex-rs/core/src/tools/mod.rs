@@ -70,9 +70,19 @@ pub fn format_exec_output_str(exec_output: &ExecToolCallOutput) -> String {
             "command timed out after {} milliseconds\n{content}",
             exec_output.duration.as_millis()
         )
+    } else if let Some(reason) = exec_output.termination_reason {
+        format!("{reason}\n{content}")
     } else {
         content.to_string()
     };
+    let body = if exec_output.blocked_network_destinations.is_empty() {
+        body
+    } else {
+        format!(
+            "network access blocked by sandbox allowlist: {}\n{body}",
+            exec_output.blocked_network_destinations.join(", ")
+        )
+    };
 
     // Truncate for model consumption before serialization.
     format_output_for_model_body(&body)
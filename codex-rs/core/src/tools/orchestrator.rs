@@ -2,9 +2,10 @@
 Module: orchestrator
 
 Central place for approvals + sandbox selection + retry semantics. Drives a
-simple sequence for any ToolRuntime: approval → select sandbox → attempt →
-retry without sandbox on denial (no re‑approval thanks to caching).
+simple sequence for any ToolRuntime: exec policy → approval → select sandbox →
+attempt → retry without sandbox on denial (no re‑approval thanks to caching).
 */
+use crate::command_safety::exec_policy::ExecPolicyDecision;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::error::get_error_message_ui;
@@ -48,9 +49,30 @@ impl ToolOrchestrator {
         let otel_user = codex_otel::otel_event_manager::ToolDecisionSource::User;
         let otel_cfg = codex_otel::otel_event_manager::ToolDecisionSource::Config;
 
-        // 1) Approval
-        let needs_initial_approval =
-            tool.wants_initial_approval(req, approval_policy, &turn_ctx.sandbox_policy);
+        // 0) Exec policy: forbidden commands are rejected outright, matched
+        // commands skip the initial approval prompt.
+        let exec_policy_decision = match req.sandbox_retry_data() {
+            Some(metadata) => tool_ctx.session.services.exec_policy.evaluate(
+                &metadata.command,
+                &metadata.cwd,
+                &turn_ctx.sandbox_policy,
+            ),
+            None => ExecPolicyDecision::Unmatched,
+        };
+        if let ExecPolicyDecision::Forbidden { reason } = exec_policy_decision {
+            otel.tool_decision(otel_tn, otel_ci, ReviewDecision::Denied, otel_cfg);
+            return Err(ToolError::Rejected(format!(
+                "command forbidden by exec policy: {reason}"
+            )));
+        }
+
+        // 1) Approval. A `pre_tool_call` hook that answered `allow` stands in
+        // for the initial prompt, but not for a retry without sandbox.
+        let approved_by_exec_policy = exec_policy_decision == ExecPolicyDecision::Allowed
+            && !tool.wants_escalated_first_attempt(req);
+        let needs_initial_approval = !approved_by_exec_policy
+            && !tool_ctx.approved_by_hook
+            && tool.wants_initial_approval(req, approval_policy, &turn_ctx.sandbox_policy);
         let mut already_approved = false;
 
         if needs_initial_approval {
@@ -100,6 +122,7 @@ impl ToolOrchestrator {
             manager: &self.sandbox,
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
+            limits: &turn_ctx.sandbox_limits,
         };
 
         match tool.run(req, &initial_attempt, tool_ctx).await {
@@ -169,6 +192,7 @@ impl ToolOrchestrator {
                     manager: &self.sandbox,
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
+                    limits: &turn_ctx.sandbox_limits,
                 };
 
                 // Second attempt.
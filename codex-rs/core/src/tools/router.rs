@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -5,6 +6,8 @@ use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::hooks::PreToolCallOutcome;
+use crate::hooks::ToolCallInfo;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
@@ -12,10 +15,13 @@ use crate::tools::registry::ConfiguredToolSpec;
 use crate::tools::registry::ToolRegistry;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::build_specs;
+use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::models::LocalShellAction;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::models::ShellToolCallParams;
+use mcp_types::ContentBlock;
+use mcp_types::TextContent;
 
 #[derive(Clone)]
 pub struct ToolCall {
@@ -144,24 +150,97 @@ impl ToolRouter {
         let payload_outputs_custom = matches!(payload, ToolPayload::Custom { .. });
         let failure_call_id = call_id.clone();
 
+        let original_arguments = payload.hook_arguments().into_owned();
+        let pre_hook = session
+            .hooks()
+            .pre_tool_call(
+                session.hook_scope(&turn),
+                ToolCallInfo {
+                    turn_id: &turn.sub_id,
+                    tool_name: &tool_name,
+                    call_id: &call_id,
+                    arguments: &original_arguments,
+                },
+            )
+            .await;
+        let (payload, arguments, approved_by_hook, rewritten) = match pre_hook {
+            PreToolCallOutcome::Continue {
+                arguments: None,
+                approved,
+            } => (payload, original_arguments, approved, false),
+            PreToolCallOutcome::Continue {
+                arguments: Some(arguments),
+                approved,
+            } => match payload.with_hook_arguments(arguments.clone()) {
+                Ok(payload) => (payload, arguments, approved, true),
+                Err(err) => {
+                    return Ok(Self::failure_response(
+                        failure_call_id,
+                        payload_outputs_custom,
+                        FunctionCallError::RespondToModel(format!(
+                            "a hook rewrote the arguments of {tool_name} into an invalid shape: {err}"
+                        )),
+                    ));
+                }
+            },
+            PreToolCallOutcome::Deny { reason } => {
+                return Ok(Self::failure_response(
+                    failure_call_id,
+                    payload_outputs_custom,
+                    FunctionCallError::RespondToModel(format!(
+                        "tool call denied by hook: {reason}"
+                    )),
+                ));
+            }
+        };
+
         let invocation = ToolInvocation {
-            session,
-            turn,
+            session: Arc::clone(&session),
+            turn: Arc::clone(&turn),
             tracker,
-            call_id,
-            tool_name,
+            call_id: call_id.clone(),
+            tool_name: tool_name.clone(),
             payload,
+            approved_by_hook,
         };
 
-        match self.registry.dispatch(invocation).await {
-            Ok(response) => Ok(response),
-            Err(FunctionCallError::Fatal(message)) => Err(FunctionCallError::Fatal(message)),
-            Err(err) => Ok(Self::failure_response(
-                failure_call_id,
-                payload_outputs_custom,
-                err,
-            )),
-        }
+        let response = match self.registry.dispatch(invocation).await {
+            Ok(response) => response,
+            Err(FunctionCallError::Fatal(message)) => {
+                return Err(FunctionCallError::Fatal(message));
+            }
+            Err(err) => Self::failure_response(failure_call_id, payload_outputs_custom, err),
+        };
+
+        let (output, success) = response_output(&response);
+        let context = session
+            .hooks()
+            .post_tool_call(
+                session.hook_scope(&turn),
+                ToolCallInfo {
+                    turn_id: &turn.sub_id,
+                    tool_name: &tool_name,
+                    call_id: &call_id,
+                    arguments: &arguments,
+                },
+                &output,
+                success,
+            )
+            .await;
+        // The recorded call still carries the model's arguments, so tell the
+        // model what actually ran.
+        let response = if rewritten {
+            append_hook_context(
+                response,
+                format!("note: a hook rewrote the arguments of this call to: {arguments}"),
+            )
+        } else {
+            response
+        };
+        Ok(match context {
+            Some(context) => append_hook_context(response, context),
+            None => response,
+        })
     }
 
     fn failure_response(
@@ -187,3 +266,60 @@ impl ToolRouter {
         }
     }
 }
+
+/// The text output and success flag of a tool response, as shown to
+/// `post_tool_call` hooks.
+fn response_output(response: &ResponseInputItem) -> (Cow<'_, str>, Option<bool>) {
+    match response {
+        ResponseInputItem::FunctionCallOutput { output, .. } => {
+            (Cow::Borrowed(output.content.as_str()), output.success)
+        }
+        ResponseInputItem::CustomToolCallOutput { output, .. } => {
+            (Cow::Borrowed(output.as_str()), None)
+        }
+        ResponseInputItem::McpToolCallOutput { result, .. } => match result {
+            Ok(result) => (
+                Cow::Owned(serde_json::to_string(result).unwrap_or_default()),
+                Some(!result.is_error.unwrap_or(false)),
+            ),
+            Err(err) => (Cow::Borrowed(err.as_str()), Some(false)),
+        },
+        ResponseInputItem::Message { .. } => (Cow::Borrowed(""), None),
+    }
+}
+
+fn append_hook_context(response: ResponseInputItem, context: String) -> ResponseInputItem {
+    match response {
+        ResponseInputItem::FunctionCallOutput {
+            call_id,
+            mut output,
+        } => {
+            output.content = format!("{}\n\n{context}", output.content);
+            if let Some(items) = output.content_items.as_mut() {
+                items.push(FunctionCallOutputContentItem::InputText { text: context });
+            }
+            ResponseInputItem::FunctionCallOutput { call_id, output }
+        }
+        ResponseInputItem::CustomToolCallOutput { call_id, output } => {
+            ResponseInputItem::CustomToolCallOutput {
+                call_id,
+                output: format!("{output}\n\n{context}"),
+            }
+        }
+        ResponseInputItem::McpToolCallOutput { call_id, result } => {
+            let result = match result {
+                Ok(mut result) => {
+                    result.content.push(ContentBlock::TextContent(TextContent {
+                        annotations: None,
+                        text: context,
+                        r#type: "text".to_string(),
+                    }));
+                    Ok(result)
+                }
+                Err(err) => Err(format!("{err}\n\n{context}")),
+            };
+            ResponseInputItem::McpToolCallOutput { call_id, result }
+        }
+        message @ ResponseInputItem::Message { .. } => message,
+    }
+}
@@ -157,6 +157,8 @@ pub(crate) struct ToolCtx<'a> {
     pub turn: &'a TurnContext,
     pub call_id: String,
     pub tool_name: String,
+    /// Set when a `pre_tool_call` hook approved the call.
+    pub approved_by_hook: bool,
 }
 
 /// Captures the command metadata needed to re-run a tool request without sandboxing.
@@ -191,6 +193,7 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) manager: &'a SandboxManager,
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
+    pub limits: &'a crate::config::types::SandboxLimits,
 }
 
 impl<'a> SandboxAttempt<'a> {
@@ -204,6 +207,7 @@ impl<'a> SandboxAttempt<'a> {
             self.sandbox,
             self.sandbox_cwd,
             self.codex_linux_sandbox_exe,
+            self.limits,
         )
     }
 }
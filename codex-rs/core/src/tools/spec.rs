@@ -1,5 +1,7 @@
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::types::CustomToolConfig;
+use crate::config::types::CustomTools;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::model_family::ModelFamily;
@@ -28,7 +30,9 @@ pub(crate) struct ToolsConfig {
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
     pub web_search_request: bool,
     pub include_view_image_tool: bool,
+    pub include_memory_tools: bool,
     pub experimental_supported_tools: Vec<String>,
+    pub custom_tools: CustomTools,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -45,6 +49,7 @@ impl ToolsConfig {
         let include_apply_patch_tool = features.enabled(Feature::ApplyPatchFreeform);
         let include_web_search_request = features.enabled(Feature::WebSearchRequest);
         let include_view_image_tool = features.enabled(Feature::ViewImageTool);
+        let include_memory_tools = features.enabled(Feature::Memory);
 
         let shell_type = if features.enabled(Feature::UnifiedExec) {
             ConfigShellToolType::UnifiedExec
@@ -69,9 +74,17 @@ impl ToolsConfig {
             apply_patch_tool_type,
             web_search_request: include_web_search_request,
             include_view_image_tool,
+            include_memory_tools,
             experimental_supported_tools: model_family.experimental_supported_tools.clone(),
+            custom_tools: CustomTools::new(),
         }
     }
+
+    /// Expose the command-backed tools declared under `[tools.custom]`.
+    pub fn with_custom_tools(mut self, custom_tools: CustomTools) -> Self {
+        self.custom_tools = custom_tools;
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -326,6 +339,90 @@ fn create_view_image_tool() -> ToolSpec {
     })
 }
 
+fn create_memory_write_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "content".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "The fact to remember, written so it makes sense without this conversation."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "tags".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String { description: None }),
+            description: Some("Optional keywords that help find the memory later.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "memory_write".to_string(),
+        description: "Saves a memory for this project that is available in future sessions. Use it for durable facts such as build steps, conventions or user preferences, not for details of the current task.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["content".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_memory_search_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Keywords to look for in memory contents and tags. Omit or leave empty to list the most recent memories."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some("Maximum number of memories to return (default 10).".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "memory_search".to_string(),
+        description: "Searches the memories saved for this project in earlier sessions."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_memory_delete_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "ids".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String { description: None }),
+            description: Some("Ids of the memories to delete.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "memory_delete".to_string(),
+        description: "Deletes memories that are wrong or no longer useful.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["ids".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_test_sync_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -768,6 +865,25 @@ pub(crate) fn mcp_tool_to_openai_tool(
     })
 }
 
+pub(crate) fn custom_tool_to_openai_tool(
+    name: String,
+    tool: &CustomToolConfig,
+) -> Result<ResponsesApiTool, serde_json::Error> {
+    let mut parameters = tool
+        .parameters
+        .clone()
+        .unwrap_or_else(|| json!({ "type": "object", "properties": {} }));
+    sanitize_json_schema(&mut parameters);
+    let parameters = serde_json::from_value::<JsonSchema>(parameters)?;
+
+    Ok(ResponsesApiTool {
+        name,
+        description: tool.description.clone(),
+        strict: false,
+        parameters,
+    })
+}
+
 /// Sanitize a JSON Schema (as serde_json::Value) so it can fit our limited
 /// JsonSchema enum. This function:
 /// - Ensures every schema object has a "type". If missing, infers it from
@@ -885,10 +1001,12 @@ pub(crate) fn build_specs(
     mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::CommandToolHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
+    use crate::tools::handlers::MemoryHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::ShellHandler;
@@ -995,6 +1113,32 @@ pub(crate) fn build_specs(
         builder.register_handler("view_image", view_image_handler);
     }
 
+    if config.include_memory_tools {
+        let memory_handler = Arc::new(MemoryHandler);
+        builder.push_spec(create_memory_write_tool());
+        builder.push_spec_with_parallel_support(create_memory_search_tool(), true);
+        builder.push_spec(create_memory_delete_tool());
+        builder.register_handler("memory_write", memory_handler.clone());
+        builder.register_handler("memory_search", memory_handler.clone());
+        builder.register_handler("memory_delete", memory_handler);
+    }
+
+    for (name, tool) in &config.custom_tools {
+        if builder.has_handler(name) {
+            tracing::warn!("custom tool {name:?} conflicts with a built-in tool; skipping");
+            continue;
+        }
+        match custom_tool_to_openai_tool(name.clone(), tool) {
+            Ok(converted_tool) => {
+                builder.push_spec(ToolSpec::Function(converted_tool));
+                builder.register_handler(name, Arc::new(CommandToolHandler::new(tool.clone())));
+            }
+            Err(e) => {
+                tracing::error!("Failed to convert custom tool {name:?} to OpenAI tool: {e:?}");
+            }
+        }
+    }
+
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, mcp_types::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -1293,6 +1437,31 @@ mod tests {
         assert_contains_tool_names(&tools, &subset);
     }
 
+    #[test]
+    fn test_build_specs_memory_tools_follow_feature() {
+        let model_family = find_family_for_model("gpt-5-codex")
+            .expect("gpt-5-codex should be a valid model family");
+        let mut features = Features::with_defaults();
+        let config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec).starts_with("memory_"))
+        );
+
+        features.enable(Feature::Memory);
+        let config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&config, None).build();
+        assert_contains_tool_names(&tools, &["memory_write", "memory_search", "memory_delete"]);
+    }
+
     #[test]
     #[ignore]
     fn test_parallel_support_flags() {
@@ -1570,6 +1739,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_custom_tools_are_registered_as_functions() {
+        let model_family = find_family_for_model("gpt-5-codex")
+            .expect("gpt-5-codex should be a valid model family");
+        let features = Features::with_defaults();
+        let custom_tool = |description: &str| CustomToolConfig {
+            description: description.to_string(),
+            command: vec!["make".to_string(), "lint-file".to_string()],
+            parameters: Some(serde_json::json!({
+                "properties": { "path": { "type": "string" } },
+                "required": ["path"],
+            })),
+            cwd: None,
+            timeout_ms: None,
+            allow_leading_dash: false,
+        };
+        let config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        })
+        .with_custom_tools(CustomTools::from([
+            ("lint_file".to_string(), custom_tool("Lint one file.")),
+            ("shell".to_string(), custom_tool("Shadows a built-in tool.")),
+        ]));
+
+        let (tools, registry) = build_specs(&config, None).build();
+
+        let tool = find_tool(&tools, "lint_file");
+        assert_eq!(
+            tool.spec,
+            ToolSpec::Function(ResponsesApiTool {
+                name: "lint_file".to_string(),
+                parameters: JsonSchema::Object {
+                    properties: BTreeMap::from([(
+                        "path".to_string(),
+                        JsonSchema::String { description: None }
+                    )]),
+                    required: Some(vec!["path".to_string()]),
+                    additional_properties: None,
+                },
+                description: "Lint one file.".to_string(),
+                strict: false,
+            })
+        );
+        assert!(registry.handler("lint_file").is_some());
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| matches!(&tool.spec, ToolSpec::Function(f) if f.description == "Shadows a built-in tool."))
+        );
+    }
+
     #[test]
     fn test_mcp_tool_integer_normalized_to_number() {
         let model_family = find_family_for_model("gpt-5-codex")
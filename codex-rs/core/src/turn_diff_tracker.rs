@@ -545,6 +545,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                new_mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
@@ -620,6 +621,7 @@ index {left_oid}..{ZERO_OID}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: Some(dest.clone()),
+                new_mode: None,
             },
         )]);
         acc.on_patch_begin(&mv_changes);
@@ -660,6 +662,7 @@ index {left_oid}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: Some(dest.clone()),
+                new_mode: None,
             },
         )]);
         acc.on_patch_begin(&mv_changes);
@@ -682,6 +685,7 @@ index {left_oid}..{right_oid}
             FileChange::Update {
                 unified_diff: "".into(),
                 move_path: Some(dest.clone()),
+                new_mode: None,
             },
         )]);
         acc.on_patch_begin(&mv);
@@ -722,6 +726,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                new_mode: None,
             },
         )]);
         acc.on_patch_begin(&update_a);
@@ -802,6 +807,7 @@ index {left_oid_b}..{ZERO_OID}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                new_mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
@@ -868,6 +874,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                new_mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
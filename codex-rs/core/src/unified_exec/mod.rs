@@ -51,14 +51,21 @@ pub(crate) struct UnifiedExecContext {
     pub session: Arc<Session>,
     pub turn: Arc<TurnContext>,
     pub call_id: String,
+    pub approved_by_hook: bool,
 }
 
 impl UnifiedExecContext {
-    pub fn new(session: Arc<Session>, turn: Arc<TurnContext>, call_id: String) -> Self {
+    pub fn new(
+        session: Arc<Session>,
+        turn: Arc<TurnContext>,
+        call_id: String,
+        approved_by_hook: bool,
+    ) -> Self {
         Self {
             session,
             turn,
             call_id,
+            approved_by_hook,
         }
     }
 }
@@ -189,8 +196,12 @@ mod tests {
         cmd: &str,
         yield_time_ms: Option<u64>,
     ) -> Result<UnifiedExecResponse, UnifiedExecError> {
-        let context =
-            UnifiedExecContext::new(Arc::clone(session), Arc::clone(turn), "call".to_string());
+        let context = UnifiedExecContext::new(
+            Arc::clone(session),
+            Arc::clone(turn),
+            "call".to_string(),
+            false,
+        );
 
         session
             .services
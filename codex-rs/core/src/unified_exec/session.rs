@@ -162,6 +162,8 @@ impl UnifiedExecSession {
             aggregated_output: StreamOutput::new(aggregated_text.clone()),
             duration: Duration::ZERO,
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
 
         if is_likely_sandbox_denied(self.sandbox_type(), &exec_output) {
@@ -257,6 +257,8 @@ impl UnifiedExecSessionManager {
             aggregated_output: StreamOutput::new(aggregated_output),
             duration,
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
         let event_ctx = ToolEventCtx::new(
             entry.session_ref.as_ref(),
@@ -285,6 +287,8 @@ impl UnifiedExecSessionManager {
             aggregated_output: StreamOutput::new(aggregated_output),
             duration,
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
         let event_ctx = ToolEventCtx::new(
             context.session.as_ref(),
@@ -341,6 +345,7 @@ impl UnifiedExecSessionManager {
             turn: context.turn.as_ref(),
             call_id: context.call_id.clone(),
             tool_name: "exec_command".to_string(),
+            approved_by_hook: context.approved_by_hook,
         };
         orchestrator
             .run(
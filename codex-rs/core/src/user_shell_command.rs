@@ -75,6 +75,8 @@ mod tests {
             aggregated_output: StreamOutput::new("hi".to_string()),
             duration: Duration::from_secs(1),
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
         let item = user_shell_command_record_item("echo hi", &exec_output);
         let ResponseItem::Message { content, .. } = item else {
@@ -98,6 +100,8 @@ mod tests {
             aggregated_output: StreamOutput::new("combined output wins".to_string()),
             duration: Duration::from_millis(120),
             timed_out: false,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         };
         let record = format_user_shell_command_record("false", &exec_output);
         assert_eq!(
@@ -342,6 +342,38 @@ async fn apply_patch_cli_reports_missing_context() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn apply_patch_cli_reports_closest_match_for_stale_context() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = apply_patch_harness().await?;
+
+    let target = harness.path("greet.rs");
+    fs::write(&target, "fn greet() {\n    println!(\"hello\");\n}\n")?;
+
+    let patch = "*** Begin Patch\n*** Update File: greet.rs\n@@\n fn greet() {\n-    println!(\"helo\");\n+    println!(\"goodbye\");\n }\n*** End Patch";
+    let call_id = "apply-stale-context";
+    mount_apply_patch(&harness, call_id, patch, "ok").await;
+
+    harness.submit("apply stale context patch").await?;
+
+    let out = harness.function_call_stdout(call_id).await;
+
+    assert!(
+        out.contains("(hunk 1)") && out.contains("Closest match starts at line 1"),
+        "expected closest match in output: {out:?}"
+    );
+    assert!(
+        out.contains("    2 |     println!(\"hello\");"),
+        "expected actual file content in output: {out:?}"
+    );
+    assert_eq!(
+        fs::read_to_string(&target)?,
+        "fn greet() {\n    println!(\"hello\");\n}\n"
+    );
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn apply_patch_cli_reports_missing_target_file() -> Result<()> {
     skip_if_no_network!(Ok(()));
@@ -465,6 +497,10 @@ async fn apply_patch_cli_rejects_path_traversal_outside_workspace() -> Result<()
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        allowed_hosts: vec![],
+        allowed_cidrs: vec![],
+        readable_roots: vec![],
+        deny_read: vec![],
     };
     harness
         .submit_with_policy(
@@ -514,6 +550,10 @@ async fn apply_patch_cli_rejects_move_path_traversal_outside_workspace() -> Resu
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        allowed_hosts: vec![],
+        allowed_cidrs: vec![],
+        readable_roots: vec![],
+        deny_read: vec![],
     };
     harness
         .submit_with_policy("attempt move traversal via apply_patch", sandbox_policy)
@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 use std::string::ToString;
 
+use codex_core::config::types::SandboxLimits;
 use codex_core::exec::ExecParams;
 use codex_core::exec::ExecToolCallOutput;
 use codex_core::exec::SandboxType;
@@ -41,7 +42,16 @@ async fn run_test_cmd(tmp: TempDir, cmd: Vec<&str>) -> Result<ExecToolCallOutput
 
     let policy = SandboxPolicy::new_read_only_policy();
 
-    process_exec_tool_call(params, sandbox_type, &policy, tmp.path(), &None, None).await
+    process_exec_tool_call(
+        params,
+        sandbox_type,
+        &policy,
+        tmp.path(),
+        &None,
+        &SandboxLimits::default(),
+        None,
+    )
+    .await
 }
 
 /// Command succeeds with exit code 0 normally
@@ -81,6 +81,9 @@ pub struct Cli {
 pub enum Command {
     /// Resume a previous session by id or pick the most recent with --last.
     Resume(ResumeArgs),
+
+    /// Review code changes and print the findings to stdout.
+    Review(ReviewArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -99,6 +102,75 @@ pub struct ResumeArgs {
     pub prompt: Option<String>,
 }
 
+#[derive(Parser, Debug)]
+#[command(group = clap::ArgGroup::new("target").multiple(false))]
+pub struct ReviewArgs {
+    /// Review uncommitted changes (staged, unstaged and untracked). This is
+    /// the default when no other target is given.
+    #[arg(long = "uncommitted", default_value_t = false, group = "target")]
+    pub uncommitted: bool,
+
+    /// Review the changes the current branch would merge into BRANCH.
+    #[arg(long = "base", value_name = "BRANCH", group = "target")]
+    pub base: Option<String>,
+
+    /// Review a single commit (SHA) or a range of commits (BASE..HEAD).
+    #[arg(long = "commit", value_name = "SHA_OR_RANGE", group = "target")]
+    pub commit: Option<String>,
+
+    /// Review the changes in a `.patch` or `.diff` file.
+    #[arg(long = "patch", value_name = "FILE", group = "target")]
+    pub patch: Option<PathBuf>,
+
+    /// Exit with status 2 when any finding has this priority or a more
+    /// severe one (P0 is the most severe).
+    #[arg(long = "fail-on", value_name = "PRIORITY", value_enum)]
+    pub fail_on: Option<ReviewPriority>,
+
+    /// Format used to print the review to stdout.
+    #[arg(long = "format", value_name = "FORMAT", value_enum, default_value_t = ReviewFormat::Json)]
+    pub format: ReviewFormat,
+
+    /// Additional instructions for the reviewer. If `-` is used, read from stdin.
+    #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
+    pub prompt: Option<String>,
+}
+
+/// Review finding priorities, from most to least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum ReviewPriority {
+    P0,
+    P1,
+    P2,
+    P3,
+}
+
+impl ReviewPriority {
+    /// The `priority` value used for this level in review findings.
+    pub fn as_i32(self) -> i32 {
+        match self {
+            ReviewPriority::P0 => 0,
+            ReviewPriority::P1 => 1,
+            ReviewPriority::P2 => 2,
+            ReviewPriority::P3 => 3,
+        }
+    }
+}
+
+/// Output formats for `codex exec review`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewFormat {
+    /// The review output as JSON.
+    #[default]
+    Json,
+    /// A SARIF 2.1.0 log for code scanning tools.
+    Sarif,
+    /// GitHub Actions workflow commands that annotate the findings.
+    Github,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 pub enum Color {
@@ -5,6 +5,7 @@ use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
+use codex_core::protocol::ElicitationRequestEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -16,6 +17,7 @@ use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::SamplingRequestEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
@@ -185,6 +187,24 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                server_name,
+                message,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} MCP server `{server_name}` asked for input; declined because exec is non-interactive: {message}",
+                    "warning:".style(self.yellow).style(self.bold)
+                );
+            }
+            EventMsg::SamplingRequest(SamplingRequestEvent { server_name, .. }) => {
+                ts_msg!(
+                    self,
+                    "{} MCP server `{server_name}` asked to sample the model; denied because exec is non-interactive",
+                    "warning:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::StreamError(StreamErrorEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
@@ -241,6 +261,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 aggregated_output,
                 duration,
                 exit_code,
+                termination_reason,
+                blocked_network_destinations,
                 ..
             }) => {
                 let duration = format!(" in {}", format_duration(duration));
@@ -256,10 +278,20 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                         ts_msg!(self, "{}", title.style(self.green));
                     }
                     _ => {
-                        let title = format!(" exited {exit_code}{duration}:");
+                        let reason = termination_reason
+                            .map(|reason| format!(" ({reason})"))
+                            .unwrap_or_default();
+                        let title = format!(" exited {exit_code}{reason}{duration}:");
                         ts_msg!(self, "{}", title.style(self.red));
                     }
                 }
+                if !blocked_network_destinations.is_empty() {
+                    let blocked = format!(
+                        "network access blocked by sandbox allowlist: {}",
+                        blocked_network_destinations.join(", ")
+                    );
+                    eprintln!("{}", blocked.style(self.red));
+                }
                 eprintln!("{}", truncated_output.style(self.dimmed));
             }
             EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
@@ -356,6 +388,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                         FileChange::Update {
                             unified_diff,
                             move_path,
+                            new_mode,
                         } => {
                             let header = if let Some(dest) = move_path {
                                 format!(
@@ -368,6 +401,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                                 format!("{} {}", format_file_change(change), path.to_string_lossy())
                             };
                             eprintln!("{}", header.style(self.magenta));
+                            if let Some(mode) = new_mode {
+                                eprintln!("mode -> {mode}");
+                            }
 
                             // Colorize diff lines. We keep file header lines
                             // (--- / +++) without extra coloring so they are
@@ -384,6 +420,14 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                                 }
                             }
                         }
+                        FileChange::Binary { new_size, .. } => {
+                            let header = format!(
+                                "{} {} (binary, {new_size} bytes)",
+                                format_file_change(change),
+                                path.to_string_lossy()
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                        }
                     }
                 }
             }
@@ -517,10 +561,14 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::WebSearchBegin(_)
             | EventMsg::ExecApprovalRequest(_)
             | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::ElicitationCancelled(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::McpListPromptsResponse(_)
+            | EventMsg::GetMcpPromptResponse(_)
+            | EventMsg::McpListChanged(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
             | EventMsg::EnteredReviewMode(_)
@@ -571,6 +619,10 @@ fn format_file_change(change: &FileChange) -> &'static str {
     match change {
         FileChange::Add { .. } => "A",
         FileChange::Delete { .. } => "D",
+        FileChange::Binary { old_size: None, .. } => "A",
+        FileChange::Binary {
+            old_size: Some(_), ..
+        } => "M",
         FileChange::Update {
             move_path: Some(_), ..
         } => "R",
@@ -224,6 +224,7 @@ impl EventProcessorWithJsonOutput {
                 aggregated_output: String::new(),
                 exit_code: None,
                 status: CommandExecutionStatus::InProgress,
+                blocked_network_destinations: Vec::new(),
             }),
         };
 
@@ -333,6 +334,10 @@ impl EventProcessorWithJsonOutput {
             FileChange::Add { .. } => PatchChangeKind::Add,
             FileChange::Delete { .. } => PatchChangeKind::Delete,
             FileChange::Update { .. } => PatchChangeKind::Update,
+            FileChange::Binary { old_size: None, .. } => PatchChangeKind::Add,
+            FileChange::Binary {
+                old_size: Some(_), ..
+            } => PatchChangeKind::Update,
         }
     }
 
@@ -387,6 +392,7 @@ impl EventProcessorWithJsonOutput {
                 aggregated_output: ev.aggregated_output.clone(),
                 exit_code: Some(ev.exit_code),
                 status,
+                blocked_network_destinations: ev.blocked_network_destinations.clone(),
             }),
         };
 
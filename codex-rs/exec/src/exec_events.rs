@@ -153,6 +153,10 @@ pub struct CommandExecutionItem {
     pub aggregated_output: String,
     pub exit_code: Option<i32>,
     pub status: CommandExecutionStatus,
+    /// Destinations the sandbox's network allowlist rejected while the
+    /// command ran.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_network_destinations: Vec<String>,
 }
 
 /// A set of file changes by the agent.
@@ -20,10 +20,16 @@ use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::ReviewRequest;
+use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::review_format::render_github_annotations;
+use codex_core::review_format::render_review_sarif;
 use codex_ollama::DEFAULT_OSS_MODEL;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
@@ -33,6 +39,7 @@ use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use serde_json::Value;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 use supports_color::Stream;
 use tracing::debug;
@@ -42,11 +49,17 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 
 use crate::cli::Command as ExecCommand;
+use crate::cli::ReviewArgs;
+use crate::cli::ReviewFormat;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_conversation_path_by_id_str;
 
+/// Exit status of `codex exec review --fail-on` when a finding meets the
+/// threshold. Distinct from the status used for errors.
+const REVIEW_FINDINGS_EXIT_CODE: i32 = 2;
+
 pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()> {
     if let Err(err) = set_default_originator("codex_exec".to_string()) {
         tracing::warn!(?err, "Failed to set codex exec originator override {err:?}");
@@ -76,41 +89,35 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         // Allow prompt before the subcommand by falling back to the parent-level prompt
         // when the Resume subcommand did not provide its own prompt.
         Some(ExecCommand::Resume(args)) => args.prompt.clone().or(prompt),
+        Some(ExecCommand::Review(args)) => args.prompt.clone().or(prompt),
         None => prompt,
     };
 
-    let prompt = match prompt_arg {
-        Some(p) if p != "-" => p,
-        // Either `-` was passed or no positional arg.
-        maybe_dash => {
-            // When no arg (None) **and** stdin is a TTY, bail out early – unless the
-            // user explicitly forced reading via `-`.
-            let force_stdin = matches!(maybe_dash.as_deref(), Some("-"));
-
-            if std::io::stdin().is_terminal() && !force_stdin {
+    // Reviews print their findings to stdout as a single JSON document, so
+    // their prompt is optional and they cannot share stdout with --json.
+    let review = match &command {
+        Some(ExecCommand::Review(args)) => {
+            if json_mode {
                 eprintln!(
-                    "No prompt provided. Either specify one as an argument or pipe the prompt into stdin."
+                    "--json is not supported with `codex exec review`; the review is printed to stdout as JSON."
                 );
                 std::process::exit(1);
             }
-
-            // Ensure the user knows we are waiting on stdin, as they may
-            // have gotten into this state by mistake. If so, and they are not
-            // writing to stdin, Codex will hang indefinitely, so this should
-            // help them debug in that case.
-            if !force_stdin {
-                eprintln!("Reading prompt from stdin...");
-            }
-            let mut buffer = String::new();
-            if let Err(e) = std::io::stdin().read_to_string(&mut buffer) {
-                eprintln!("Failed to read prompt from stdin: {e}");
-                std::process::exit(1);
-            } else if buffer.trim().is_empty() {
-                eprintln!("No prompt provided via stdin.");
-                std::process::exit(1);
-            }
-            buffer
+            let instructions = prompt_arg
+                .clone()
+                .map(|p| read_prompt(Some(p)))
+                .unwrap_or_default();
+            Some((
+                review_request_from_args(args, instructions, cwd.as_deref()),
+                args.fail_on,
+                args.format,
+            ))
         }
+        _ => None,
+    };
+    let prompt = match &review {
+        Some((review_request, _, _)) => format!("Review {}", review_request.user_facing_hint),
+        None => read_prompt(prompt_arg),
     };
 
     let output_schema = load_output_schema(output_schema_path);
@@ -323,33 +330,74 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         });
     }
 
-    // Package images and prompt into a single user input turn.
-    let mut items: Vec<UserInput> = images
-        .into_iter()
-        .map(|path| UserInput::LocalImage { path })
-        .collect();
-    items.push(UserInput::Text { text: prompt });
-    let initial_prompt_task_id = conversation
-        .submit(Op::UserTurn {
-            items,
-            cwd: default_cwd,
-            approval_policy: default_approval_policy,
-            sandbox_policy: default_sandbox_policy,
-            model: default_model,
-            effort: default_effort,
-            summary: default_summary,
-            final_output_json_schema: output_schema,
-        })
-        .await?;
+    let initial_prompt_task_id = if let Some((review_request, _, _)) = &review {
+        conversation
+            .submit(Op::Review {
+                review_request: review_request.clone(),
+            })
+            .await?
+    } else {
+        // Package images and prompt into a single user input turn.
+        let mut items: Vec<UserInput> = images
+            .into_iter()
+            .map(|path| UserInput::LocalImage { path })
+            .collect();
+        items.push(UserInput::Text { text: prompt });
+        conversation
+            .submit(Op::UserTurn {
+                items,
+                cwd: default_cwd,
+                approval_policy: default_approval_policy,
+                sandbox_policy: default_sandbox_policy,
+                model: default_model,
+                effort: default_effort,
+                summary: default_summary,
+                final_output_json_schema: output_schema,
+            })
+            .await?
+    };
     info!("Sent prompt with event ID: {initial_prompt_task_id}");
 
     // Run the loop until the task is complete.
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let mut review_started = false;
+    let mut review_output = None;
     while let Some(event) = rx.recv().await {
-        if matches!(event.msg, EventMsg::Error(_)) {
-            error_seen = true;
+        match &event.msg {
+            EventMsg::Error(_) => {
+                error_seen = true;
+                // A review that fails before it starts never completes a
+                // task, so nothing else will end the session.
+                if review.is_some() && !review_started {
+                    conversation.submit(Op::Shutdown).await?;
+                }
+            }
+            EventMsg::EnteredReviewMode(_) => review_started = true,
+            EventMsg::ExitedReviewMode(ev) => review_output = ev.review_output.clone(),
+            // Nobody can answer an MCP server's questions in a
+            // non-interactive run, so decline instead of letting it wait.
+            EventMsg::ElicitationRequest(ev) => {
+                conversation
+                    .submit(Op::ResolveElicitation {
+                        server_name: ev.server_name.clone(),
+                        request_id: ev.id.clone(),
+                        decision: ElicitationAction::Decline,
+                        content: None,
+                    })
+                    .await?;
+            }
+            EventMsg::SamplingRequest(ev) => {
+                conversation
+                    .submit(Op::ResolveSamplingRequest {
+                        server_name: ev.server_name.clone(),
+                        request_id: ev.id.clone(),
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await?;
+            }
+            _ => {}
         }
         let shutdown: CodexStatus = event_processor.process_event(event);
         match shutdown {
@@ -367,9 +415,133 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         std::process::exit(1);
     }
 
+    if let Some((_, fail_on, format)) = review {
+        let Some(review_output) = review_output else {
+            eprintln!("The review did not complete.");
+            std::process::exit(1);
+        };
+        // Report paths relative to the repository root, which is where code
+        // scanning tools and GitHub resolve them from.
+        let root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.clone());
+        let rendered = match format {
+            ReviewFormat::Json => serde_json::to_string_pretty(&review_output)?,
+            ReviewFormat::Sarif => {
+                serde_json::to_string_pretty(&render_review_sarif(&review_output, &root))?
+            }
+            ReviewFormat::Github => render_github_annotations(&review_output.findings, &root),
+        };
+        #[allow(clippy::print_stdout)]
+        if !rendered.is_empty() {
+            println!("{rendered}");
+        }
+        if let Some(fail_on) = fail_on
+            && review_output
+                .findings
+                .iter()
+                .any(|finding| finding.priority <= fail_on.as_i32())
+        {
+            std::process::exit(REVIEW_FINDINGS_EXIT_CODE);
+        }
+    }
+
     Ok(())
 }
 
+/// Reads the prompt from `prompt_arg`, or from stdin when it is `None` or `-`.
+fn read_prompt(prompt_arg: Option<String>) -> String {
+    match prompt_arg {
+        Some(p) if p != "-" => p,
+        // Either `-` was passed or no positional arg.
+        maybe_dash => {
+            // When no arg (None) **and** stdin is a TTY, bail out early – unless the
+            // user explicitly forced reading via `-`.
+            let force_stdin = matches!(maybe_dash.as_deref(), Some("-"));
+
+            if std::io::stdin().is_terminal() && !force_stdin {
+                eprintln!(
+                    "No prompt provided. Either specify one as an argument or pipe the prompt into stdin."
+                );
+                std::process::exit(1);
+            }
+
+            // Ensure the user knows we are waiting on stdin, as they may
+            // have gotten into this state by mistake. If so, and they are not
+            // writing to stdin, Codex will hang indefinitely, so this should
+            // help them debug in that case.
+            if !force_stdin {
+                eprintln!("Reading prompt from stdin...");
+            }
+            let mut buffer = String::new();
+            if let Err(e) = std::io::stdin().read_to_string(&mut buffer) {
+                eprintln!("Failed to read prompt from stdin: {e}");
+                std::process::exit(1);
+            } else if buffer.trim().is_empty() {
+                eprintln!("No prompt provided via stdin.");
+                std::process::exit(1);
+            }
+            buffer
+        }
+    }
+}
+
+fn review_request_from_args(
+    args: &ReviewArgs,
+    instructions: String,
+    cwd: Option<&Path>,
+) -> ReviewRequest {
+    let target = if let Some(branch) = &args.base {
+        ReviewTarget::BaseBranch {
+            branch: branch.clone(),
+        }
+    } else if let Some(commit) = &args.commit {
+        match commit.split_once("..") {
+            Some((base, head)) => {
+                // Accept `base...head` too; the reviewer diffs from the merge
+                // base either way.
+                let head = head.trim_start_matches('.');
+                ReviewTarget::CommitRange {
+                    base: base.to_string(),
+                    head: if head.is_empty() { "HEAD" } else { head }.to_string(),
+                }
+            }
+            None => ReviewTarget::Commit {
+                sha: commit.clone(),
+                title: None,
+            },
+        }
+    } else if let Some(path) = &args.patch {
+        ReviewTarget::PatchFile {
+            path: resolve_patch_path(path, cwd),
+        }
+    } else {
+        ReviewTarget::UncommittedChanges
+    };
+    ReviewRequest {
+        prompt: instructions,
+        ..ReviewRequest::for_target(target)
+    }
+}
+
+/// Resolves `--patch` against the `-C` directory, when one is given, so that
+/// it names the same file as it would for commands run in the session.
+fn resolve_patch_path(path: &Path, cwd: Option<&Path>) -> PathBuf {
+    let path = match cwd {
+        Some(cwd) if path.is_relative() => cwd.join(path),
+        _ => path.to_path_buf(),
+    };
+    match path.canonicalize() {
+        Ok(path) if path.is_file() => path,
+        Ok(_) => {
+            eprintln!("Patch file {} is not a file.", path.display());
+            std::process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("Failed to read patch file {}: {err}", path.display());
+            std::process::exit(1);
+        }
+    }
+}
+
 async fn resolve_resume_path(
     config: &Config,
     args: &crate::cli::ResumeArgs,
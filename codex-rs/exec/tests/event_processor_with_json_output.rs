@@ -640,6 +640,7 @@ fn exec_command_end_success_produces_completed_command_item() {
                     aggregated_output: String::new(),
                     exit_code: None,
                     status: CommandExecutionStatus::InProgress,
+                    blocked_network_destinations: Vec::new(),
                 }),
             },
         })]
@@ -656,6 +657,8 @@ fn exec_command_end_success_produces_completed_command_item() {
             exit_code: 0,
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -669,6 +672,7 @@ fn exec_command_end_success_produces_completed_command_item() {
                     aggregated_output: "hi\n".to_string(),
                     exit_code: Some(0),
                     status: CommandExecutionStatus::Completed,
+                    blocked_network_destinations: Vec::new(),
                 }),
             },
         })]
@@ -700,6 +704,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
                     aggregated_output: String::new(),
                     exit_code: None,
                     status: CommandExecutionStatus::InProgress,
+                    blocked_network_destinations: Vec::new(),
                 }),
             },
         })]
@@ -716,6 +721,8 @@ fn exec_command_end_failure_produces_failed_command_item() {
             exit_code: 1,
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -729,6 +736,53 @@ fn exec_command_end_failure_produces_failed_command_item() {
                     aggregated_output: String::new(),
                     exit_code: Some(1),
                     status: CommandExecutionStatus::Failed,
+                    blocked_network_destinations: Vec::new(),
+                }),
+            },
+        })]
+    );
+}
+
+#[test]
+fn exec_command_end_reports_blocked_network_destinations() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let begin = event(
+        "c1",
+        EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+            call_id: "3".to_string(),
+            command: vec!["curl".to_string(), "https://example.com".to_string()],
+            cwd: std::env::current_dir().unwrap(),
+            parsed_cmd: Vec::new(),
+            is_user_shell_command: false,
+        }),
+    );
+    ep.collect_thread_events(&begin);
+
+    let end = event(
+        "c2",
+        EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+            call_id: "3".to_string(),
+            stdout: String::new(),
+            stderr: String::new(),
+            aggregated_output: String::new(),
+            exit_code: 56,
+            duration: Duration::from_millis(2),
+            formatted_output: String::new(),
+            termination_reason: None,
+            blocked_network_destinations: vec!["example.com:443".to_string()],
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&end),
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::CommandExecution(CommandExecutionItem {
+                    command: "curl https://example.com".to_string(),
+                    aggregated_output: String::new(),
+                    exit_code: Some(56),
+                    status: CommandExecutionStatus::Failed,
+                    blocked_network_destinations: vec!["example.com:443".to_string()],
                 }),
             },
         })]
@@ -750,6 +804,8 @@ fn exec_command_end_without_begin_is_ignored() {
             exit_code: 0,
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -779,6 +835,7 @@ fn patch_apply_success_produces_item_completed_patchapply() {
         FileChange::Update {
             unified_diff: "--- c/modified.txt\n+++ c/modified.txt\n@@\n-old\n+new\n".to_string(),
             move_path: Some(PathBuf::from("c/renamed.txt")),
+            new_mode: None,
         },
     );
 
@@ -848,6 +905,7 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
         FileChange::Update {
             unified_diff: "--- file.txt\n+++ file.txt\n@@\n-old\n+new\n".to_string(),
             move_path: None,
+            new_mode: None,
         },
     );
 
@@ -4,5 +4,6 @@ mod auth_env;
 mod originator;
 mod output_schema;
 mod resume;
+mod review;
 mod sandbox;
 mod server_error_exit;
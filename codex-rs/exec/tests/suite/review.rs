@@ -0,0 +1,90 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::Value;
+use wiremock::matchers::any;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_review_prints_findings_and_fails_on_threshold() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    // The patch only exists under the `-C` directory, not in the directory
+    // `codex exec` is started from.
+    let repo = test.cwd_path().join("repo");
+    std::fs::create_dir(&repo)?;
+    std::fs::write(
+        repo.join("fix.patch"),
+        "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n",
+    )?;
+
+    let review_json = serde_json::json!({
+        "findings": [{
+            "title": "Off by one",
+            "body": "The loop skips the last element.",
+            "confidence_score": 0.9,
+            "priority": 1,
+            "code_location": {
+                "absolute_file_path": "/tmp/x",
+                "line_range": {"start": 1, "end": 1}
+            }
+        }],
+        "overall_correctness": "patch is incorrect",
+        "overall_explanation": "One bug.",
+        "overall_confidence_score": 0.8
+    })
+    .to_string();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", &review_json),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once_match(&server, any(), body).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg("repo")
+        .arg("review")
+        .arg("--patch")
+        .arg("fix.patch")
+        .arg("--fail-on")
+        .arg("p1")
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(2));
+    let printed: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(printed["findings"][0]["title"], "Off by one");
+    assert_eq!(printed["findings"][0]["priority"], 1);
+
+    let request = response_mock.single_request();
+    let payload = request.body_json().to_string();
+    assert!(payload.contains("```diff\\n--- a/x\\n+++ b/x"));
+
+    Ok(())
+}
+
+#[test]
+fn exec_review_rejects_a_missing_patch_file() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let output = test
+        .cmd()
+        .arg("--skip-git-repo-check")
+        .arg("review")
+        .arg("--patch")
+        .arg("missing.patch")
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Failed to read patch file missing.patch"),
+        "unexpected stderr: {stderr}"
+    );
+
+    Ok(())
+}
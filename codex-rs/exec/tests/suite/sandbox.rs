@@ -40,6 +40,7 @@ async fn spawn_command_under_sandbox(
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
+    use codex_core::config::types::SandboxLimits;
     use codex_core::landlock::spawn_command_under_linux_sandbox;
     let codex_linux_sandbox_exe = assert_cmd::cargo::cargo_bin("codex-exec");
     spawn_command_under_linux_sandbox(
@@ -48,6 +49,7 @@ async fn spawn_command_under_sandbox(
         command_cwd,
         sandbox_policy,
         sandbox_cwd,
+        &SandboxLimits::default(),
         stdio_policy,
         env,
     )
@@ -72,6 +74,10 @@ async fn python_multiprocessing_lock_works_under_sandbox() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        allowed_hosts: vec![],
+        allowed_cidrs: vec![],
+        readable_roots: vec![],
+        deny_read: vec![],
     };
 
     let python_code = r#"import multiprocessing
@@ -132,6 +138,10 @@ async fn sandbox_distinguishes_command_and_policy_cwds() {
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        allowed_hosts: vec![],
+        allowed_cidrs: vec![],
+        readable_roots: vec![],
+        deny_read: vec![],
     };
 
     // Attempt to write inside the command cwd, which is outside of the sandbox policy cwd.
@@ -269,7 +279,7 @@ fn unix_sock_body() {
 async fn allow_unix_socketpair_recvfrom() {
     run_code_under_sandbox(
         "allow_unix_socketpair_recvfrom",
-        &SandboxPolicy::ReadOnly,
+        &SandboxPolicy::new_read_only_policy(),
         || async { unix_sock_body() },
     )
     .await
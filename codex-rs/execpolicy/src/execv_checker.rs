@@ -11,6 +11,8 @@ use crate::Error::WriteablePathNotInWriteableFolders;
 use crate::ExecCall;
 use crate::MatchedExec;
 use crate::Policy;
+use crate::Redirection;
+use crate::RedirectionKind;
 use crate::Result;
 use crate::ValidExec;
 use path_absolutize::*;
@@ -39,6 +41,11 @@ impl ExecvChecker {
         self.execv_policy.check(exec_call)
     }
 
+    /// Whether the underlying policy has any rule that can forbid a command.
+    pub fn has_forbidden_rules(&self) -> bool {
+        self.execv_policy.has_forbidden_rules()
+    }
+
     /// The caller is responsible for ensuring readable_folders and
     /// writeable_folders are in canonical form.
     pub fn check(
@@ -96,8 +103,36 @@ impl ExecvChecker {
 
         Ok(program)
     }
+
+    /// Checks the target of a shell redirection the same way [`Self::check`]
+    /// checks an `ARG_RFILE` or `ARG_WFILE` argument. The caller is
+    /// responsible for ensuring readable_folders and writeable_folders are in
+    /// canonical form.
+    pub fn check_redirection(
+        &self,
+        redirection: &Redirection,
+        cwd: &Option<OsString>,
+        readable_folders: &[PathBuf],
+        writeable_folders: &[PathBuf],
+    ) -> Result<()> {
+        let file = ensure_absolute_path(&redirection.target, cwd)?;
+        match redirection.kind {
+            RedirectionKind::Read => {
+                check_file_in_folders!(file, readable_folders, ReadablePathNotInReadableFolders);
+            }
+            // Output sent to /dev/null is discarded, so it needs no
+            // writeable folder.
+            RedirectionKind::Write if file == Path::new(DEV_NULL) => {}
+            RedirectionKind::Write => {
+                check_file_in_folders!(file, writeable_folders, WriteablePathNotInWriteableFolders);
+            }
+        }
+        Ok(())
+    }
 }
 
+const DEV_NULL: &str = "/dev/null";
+
 fn ensure_absolute_path(path: &str, cwd: &Option<OsString>) -> Result<PathBuf> {
     let file = PathBuf::from(path);
     let result = if file.is_relative() {
@@ -291,4 +326,41 @@ system_path=[{fake_cp:?}]
         );
         Ok(())
     }
+
+    #[test]
+    fn test_check_redirection() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root_path = temp_dir.path().to_path_buf();
+        let cwd = Some(root_path.clone().into());
+        let checker = setup(&root_path.join("cp"));
+
+        let write = Redirection::write("out.txt");
+        assert_eq!(
+            checker.check_redirection(&write, &cwd, std::slice::from_ref(&root_path), &[]),
+            Err(WriteablePathNotInWriteableFolders {
+                file: root_path.join("out.txt"),
+                folders: vec![]
+            }),
+        );
+        assert_eq!(
+            checker.check_redirection(&write, &cwd, &[], std::slice::from_ref(&root_path)),
+            Ok(()),
+        );
+
+        let read = Redirection::read("in.txt");
+        assert_eq!(
+            checker.check_redirection(&read, &cwd, &[], std::slice::from_ref(&root_path)),
+            Err(ReadablePathNotInReadableFolders {
+                file: root_path.join("in.txt"),
+                folders: vec![]
+            }),
+        );
+
+        // Discarding output never requires a writeable folder.
+        assert_eq!(
+            checker.check_redirection(&Redirection::write("/dev/null"), &cwd, &[], &[]),
+            Ok(()),
+        );
+        Ok(())
+    }
 }
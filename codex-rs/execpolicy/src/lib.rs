@@ -13,6 +13,7 @@ mod opt;
 mod policy;
 mod policy_parser;
 mod program;
+mod redirection;
 mod sed_command;
 mod valid_exec;
 
@@ -31,6 +32,8 @@ pub use program::MatchedExec;
 pub use program::NegativeExamplePassedCheck;
 pub use program::PositiveExampleFailedCheck;
 pub use program::ProgramSpec;
+pub use redirection::Redirection;
+pub use redirection::RedirectionKind;
 pub use sed_command::parse_sed_command;
 pub use valid_exec::MatchedArg;
 pub use valid_exec::MatchedFlag;
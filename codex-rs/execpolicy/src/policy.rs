@@ -85,6 +85,16 @@ impl Policy {
         last_err
     }
 
+    /// Whether any rule in this policy can forbid a command.
+    pub fn has_forbidden_rules(&self) -> bool {
+        !self.forbidden_program_regexes.is_empty()
+            || self.forbidden_substrings_pattern.is_some()
+            || self
+                .programs
+                .flat_iter()
+                .any(|(_program, spec)| spec.is_forbidden())
+    }
+
     pub fn check_each_good_list_individually(&self) -> Vec<PositiveExampleFailedCheck> {
         let mut violations = Vec::new();
         for (_program, spec) in self.programs.flat_iter() {
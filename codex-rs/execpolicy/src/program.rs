@@ -64,6 +64,11 @@ impl ProgramSpec {
             should_not_match,
         }
     }
+
+    /// Whether every invocation matching this spec is forbidden.
+    pub fn is_forbidden(&self) -> bool {
+        self.forbidden.is_some()
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
@@ -133,7 +138,25 @@ impl ProgramSpec {
                         }
                     }
                     None => {
-                        // It could be an --option=value style flag...
+                        // It could be an --option=value style option or a
+                        // bundle of short options such as -al.
+                        if let Some(expanded) = self.expand_option(arg)? {
+                            for ExpandedOption { name, meta, value } in expanded {
+                                match (meta, value) {
+                                    (OptMeta::Flag, _) => {
+                                        matched_flags.push(MatchedFlag { name });
+                                    }
+                                    (OptMeta::Value(arg_type), Some(value)) => {
+                                        matched_opts
+                                            .push(MatchedOpt::new(&name, &value, arg_type)?);
+                                    }
+                                    (OptMeta::Value(arg_type), None) => {
+                                        expecting_option_value = Some((name, arg_type));
+                                    }
+                                }
+                            }
+                            continue;
+                        }
                     }
                 }
 
@@ -194,9 +217,122 @@ impl ProgramSpec {
         }
     }
 
+    /// Expands `--name=value` (when `combined_format` is set) and `-abc`
+    /// (when `option_bundling` is set) into the options they stand for.
+    /// Returns `None` if `arg` is in neither form.
+    fn expand_option(&self, arg: &str) -> Result<Option<Vec<ExpandedOption>>> {
+        if self.combined_format
+            && arg.starts_with("--")
+            && let Some((name, value)) = arg.split_once('=')
+        {
+            return match self.allowed_options.get(name).map(|opt| &opt.meta) {
+                Some(OptMeta::Value(arg_type)) => Ok(Some(vec![ExpandedOption {
+                    name: name.to_string(),
+                    meta: OptMeta::Value(arg_type.clone()),
+                    value: Some(value.to_string()),
+                }])),
+                Some(OptMeta::Flag) => Err(Error::FlagDoesNotTakeValue {
+                    program: self.program.clone(),
+                    option: name.to_string(),
+                    value: value.to_string(),
+                }),
+                None => Ok(None),
+            };
+        }
+
+        if !self.option_bundling || arg.starts_with("--") || arg.chars().count() <= 2 {
+            return Ok(None);
+        }
+
+        // As with getopt(3), every option in the bundle must be a flag except
+        // for the last one, which may take a value: either the rest of the
+        // bundle (`-n5`) or the next argument (`-ln 5`).
+        let mut expanded = Vec::new();
+        for (offset, ch) in arg.char_indices().skip(1) {
+            let name = format!("-{ch}");
+            let Some(opt) = self.allowed_options.get(&name) else {
+                return Err(Error::UnknownOption {
+                    program: self.program.clone(),
+                    option: name,
+                });
+            };
+            match &opt.meta {
+                OptMeta::Flag => expanded.push(ExpandedOption {
+                    name,
+                    meta: OptMeta::Flag,
+                    value: None,
+                }),
+                OptMeta::Value(arg_type) => {
+                    let rest = &arg[offset + ch.len_utf8()..];
+                    expanded.push(ExpandedOption {
+                        name,
+                        meta: OptMeta::Value(arg_type.clone()),
+                        value: (!rest.is_empty()).then(|| rest.to_string()),
+                    });
+                    break;
+                }
+            }
+        }
+        Ok(Some(expanded))
+    }
+
+    /// Rewrites `args` into the most compact spelling this spec accepts:
+    /// consecutive short flags are bundled and `--name value` becomes
+    /// `--name=value`. Used to exercise the expanded forms when verifying
+    /// `should_match` and `should_not_match`.
+    fn compact_args(&self, args: &[String]) -> Vec<String> {
+        let mut compacted = Vec::new();
+        let mut bundle: Option<String> = None;
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match self.allowed_options.get(arg).map(|opt| &opt.meta) {
+                Some(OptMeta::Flag) if self.option_bundling && is_short_option(arg) => {
+                    match bundle.as_mut() {
+                        Some(bundle) => bundle.push_str(&arg[1..]),
+                        None => bundle = Some(arg.clone()),
+                    }
+                    continue;
+                }
+                Some(OptMeta::Value(_)) => {
+                    compacted.extend(bundle.take());
+                    match iter.next() {
+                        Some(value) if self.combined_format && arg.starts_with("--") => {
+                            compacted.push(format!("{arg}={value}"));
+                        }
+                        Some(value) => {
+                            compacted.push(arg.clone());
+                            compacted.push(value.clone());
+                        }
+                        None => compacted.push(arg.clone()),
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            compacted.extend(bundle.take());
+            compacted.push(arg.clone());
+        }
+        compacted.extend(bundle);
+        compacted
+    }
+
+    /// The example as written plus, if different, its compact spelling.
+    fn example_forms(&self, example: &[String]) -> Vec<Vec<String>> {
+        let mut forms = vec![example.to_vec()];
+        let compacted = self.compact_args(example);
+        if compacted != example {
+            forms.push(compacted);
+        }
+        forms
+    }
+
     pub fn verify_should_match_list(&self) -> Vec<PositiveExampleFailedCheck> {
         let mut violations = Vec::new();
-        for good in &self.should_match {
+        for good in self
+            .should_match
+            .iter()
+            .flat_map(|good| self.example_forms(good))
+        {
             let exec_call = ExecCall {
                 program: self.program.clone(),
                 args: good.clone(),
@@ -206,7 +342,7 @@ impl ProgramSpec {
                 Err(error) => {
                     violations.push(PositiveExampleFailedCheck {
                         program: self.program.clone(),
-                        args: good.clone(),
+                        args: good,
                         error,
                     });
                 }
@@ -217,7 +353,11 @@ impl ProgramSpec {
 
     pub fn verify_should_not_match_list(&self) -> Vec<NegativeExamplePassedCheck> {
         let mut violations = Vec::new();
-        for bad in &self.should_not_match {
+        for bad in self
+            .should_not_match
+            .iter()
+            .flat_map(|bad| self.example_forms(bad))
+        {
             let exec_call = ExecCall {
                 program: self.program.clone(),
                 args: bad.clone(),
@@ -225,7 +365,7 @@ impl ProgramSpec {
             if self.check(&exec_call).is_ok() {
                 violations.push(NegativeExamplePassedCheck {
                     program: self.program.clone(),
-                    args: bad.clone(),
+                    args: bad,
                 });
             }
         }
@@ -233,6 +373,19 @@ impl ProgramSpec {
     }
 }
 
+/// A single option recovered from a bundled or `--name=value` argument.
+struct ExpandedOption {
+    name: String,
+    meta: OptMeta,
+    value: Option<String>,
+}
+
+/// Whether `arg` is a single-character option such as `-a`.
+fn is_short_option(arg: &str) -> bool {
+    let mut chars = arg.chars();
+    chars.next() == Some('-') && chars.next().is_some_and(|ch| ch != '-') && chars.next().is_none()
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct PositiveExampleFailedCheck {
     pub program: String,
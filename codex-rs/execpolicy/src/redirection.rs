@@ -0,0 +1,35 @@
+use serde::Serialize;
+
+/// Whether a shell redirection reads from or writes to its target.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum RedirectionKind {
+    /// `< file`
+    Read,
+    /// `> file`, `>> file`, `&> file`, etc.
+    Write,
+}
+
+/// A redirection of one of a command's file descriptors to a file, such as
+/// `> out.txt` in `rg foo > out.txt`. Redirections between file descriptors
+/// (e.g. `2>&1`) do not touch the filesystem and are not represented.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Redirection {
+    pub kind: RedirectionKind,
+    pub target: String,
+}
+
+impl Redirection {
+    pub fn read(target: &str) -> Self {
+        Self {
+            kind: RedirectionKind::Read,
+            target: target.to_string(),
+        }
+    }
+
+    pub fn write(target: &str) -> Self {
+        Self {
+            kind: RedirectionKind::Write,
+            target: target.to_string(),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use codex_execpolicy::ArgType;
+use codex_execpolicy::Error;
+use codex_execpolicy::ExecCall;
+use codex_execpolicy::MatchedArg;
+use codex_execpolicy::MatchedExec;
+use codex_execpolicy::MatchedFlag;
+use codex_execpolicy::MatchedOpt;
+use codex_execpolicy::Policy;
+use codex_execpolicy::Result;
+use codex_execpolicy::ValidExec;
+use codex_execpolicy::get_default_policy;
+
+extern crate codex_execpolicy;
+
+#[expect(clippy::expect_used)]
+fn setup() -> Policy {
+    get_default_policy().expect("failed to load default policy")
+}
+
+#[test]
+fn test_rg_combined_format_option() -> Result<()> {
+    let policy = setup();
+    let rg = ExecCall::new("rg", &["--max-count=5", "init"]);
+    assert_eq!(
+        Ok(MatchedExec::Match {
+            exec: ValidExec {
+                program: "rg".to_string(),
+                opts: vec![MatchedOpt::new(
+                    "--max-count",
+                    "5",
+                    ArgType::PositiveInteger
+                )?],
+                args: vec![MatchedArg::new(1, ArgType::OpaqueNonFile, "init")?],
+                ..Default::default()
+            }
+        }),
+        policy.check(&rg)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rg_bundled_flags_and_option() -> Result<()> {
+    let policy = setup();
+    let rg = ExecCall::new("rg", &["-inm", "3", "init", "src"]);
+    assert_eq!(
+        Ok(MatchedExec::Match {
+            exec: ValidExec {
+                program: "rg".to_string(),
+                flags: vec![MatchedFlag::new("-i"), MatchedFlag::new("-n")],
+                opts: vec![MatchedOpt::new("-m", "3", ArgType::PositiveInteger)?],
+                args: vec![
+                    MatchedArg::new(2, ArgType::OpaqueNonFile, "init")?,
+                    MatchedArg::new(3, ArgType::ReadableFile, "src")?,
+                ],
+                ..Default::default()
+            }
+        }),
+        policy.check(&rg)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rg_flag_with_value_is_rejected() {
+    let policy = setup();
+    let rg = ExecCall::new("rg", &["--files=yes", "."]);
+    assert_eq!(
+        Err(Error::FlagDoesNotTakeValue {
+            program: "rg".to_string(),
+            option: "--files".to_string(),
+            value: "yes".to_string(),
+        }),
+        policy.check(&rg)
+    );
+}
@@ -0,0 +1,237 @@
+//! Per-command cgroup v2 that enforces `max_memory_mb` (`memory.max`) and
+//! `max_processes` (`pids.max`) for the command's whole process tree.
+//!
+//! The helper creates `codex-exec-<pid>` below the cgroup it runs in, or next
+//! to it when that cgroup cannot delegate the needed controllers (a cgroup
+//! that holds processes cannot enable controllers for its children). The
+//! command joins it before exec; once the command exits, `memory.events` and
+//! `pids.events` tell whether a limit stopped it. There is no fallback: if no
+//! suitable cgroup can be created the helper fails instead of running the
+//! command without the limits.
+
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_core::landlock::REPORT_MEMORY_LIMIT;
+use codex_core::landlock::REPORT_PROCESS_LIMIT;
+
+use crate::resource_limits::ResourceLimits;
+
+const CGROUP_MOUNT: &str = "/sys/fs/cgroup";
+const CGROUP_NAME_PREFIX: &str = "codex-exec-";
+
+pub(crate) struct SandboxCgroup {
+    path: PathBuf,
+}
+
+impl SandboxCgroup {
+    /// Creates the cgroup for `limits`, or returns `None` when neither a
+    /// memory nor a process limit is set.
+    pub(crate) fn create(limits: &ResourceLimits) -> io::Result<Option<Self>> {
+        let mut controllers = Vec::new();
+        if limits.max_memory_mb.is_some() {
+            controllers.push("memory");
+        }
+        if limits.max_processes.is_some() {
+            controllers.push("pids");
+        }
+        if controllers.is_empty() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string("/proc/self/cgroup")?;
+        let Some(current) = parse_cgroup_v2_path(&contents) else {
+            return Err(io::Error::other(
+                "sandbox_limits.max_memory_mb and max_processes require cgroup v2",
+            ));
+        };
+        let current = Path::new(CGROUP_MOUNT).join(current.trim_start_matches('/'));
+
+        let mut errors = Vec::new();
+        for parent in current
+            .ancestors()
+            .take(2)
+            .filter(|parent| parent.starts_with(CGROUP_MOUNT))
+        {
+            match Self::create_in(parent, &controllers) {
+                Ok(cgroup) => {
+                    if let Err(e) = cgroup.apply(limits) {
+                        cgroup.remove();
+                        return Err(e);
+                    }
+                    return Ok(Some(cgroup));
+                }
+                Err(e) => errors.push(format!("{}: {e}", parent.display())),
+            }
+        }
+        Err(io::Error::other(format!(
+            "sandbox_limits.max_memory_mb and max_processes need a writable cgroup v2 that can delegate the {} controller(s) ({})",
+            controllers.join(" and "),
+            errors.join("; ")
+        )))
+    }
+
+    fn create_in(parent: &Path, controllers: &[&str]) -> io::Result<Self> {
+        remove_stale_cgroups(parent);
+        let subtree_control = parent.join("cgroup.subtree_control");
+        let enabled = std::fs::read_to_string(&subtree_control)?;
+        for controller in controllers {
+            if !enabled.split_whitespace().any(|c| c == *controller) {
+                std::fs::write(&subtree_control, format!("+{controller}"))?;
+            }
+        }
+        let path = parent.join(format!("{CGROUP_NAME_PREFIX}{}", std::process::id()));
+        std::fs::create_dir(&path)?;
+        Ok(Self { path })
+    }
+
+    fn apply(&self, limits: &ResourceLimits) -> io::Result<()> {
+        if let Some(max_memory_mb) = limits.max_memory_mb {
+            let bytes = max_memory_mb.saturating_mul(1024 * 1024);
+            std::fs::write(self.path.join("memory.max"), bytes.to_string())?;
+            // Without swap accounting the file does not exist and there is
+            // nothing to close off.
+            let swap_max = self.path.join("memory.swap.max");
+            if swap_max.exists() {
+                std::fs::write(swap_max, "0")?;
+            }
+            // Kill the whole tree rather than whichever process the OOM
+            // killer picks, so the command does not limp on half-dead.
+            let _ = std::fs::write(self.path.join("memory.oom.group"), "1");
+        }
+        if let Some(max_processes) = limits.max_processes {
+            std::fs::write(self.path.join("pids.max"), max_processes.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Moves the calling process, and thus everything it later spawns, into
+    /// the cgroup.
+    pub(crate) fn join_current_process(&self) -> io::Result<()> {
+        std::fs::write(self.path.join("cgroup.procs"), "0")
+    }
+
+    /// Reports the limit that stopped the command, if any, on `report` and
+    /// removes the cgroup along with anything the command left running.
+    /// `status` is the command's wait status.
+    pub(crate) fn finish(self, status: libc::c_int, report: Option<&File>) {
+        let succeeded = libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0;
+        if !succeeded
+            && let Some(mut report) = report
+            && let Some(line) = self.exceeded_limit()
+        {
+            let _ = report.write_all(format!("{line}\n").as_bytes());
+        }
+        self.remove();
+    }
+
+    fn exceeded_limit(&self) -> Option<&'static str> {
+        let event_count = |file: &str, key: &str| {
+            std::fs::read_to_string(self.path.join(file))
+                .ok()
+                .and_then(|contents| parse_event_count(&contents, key))
+                .unwrap_or(0)
+        };
+        if event_count("memory.events", "oom_kill") > 0 {
+            Some(REPORT_MEMORY_LIMIT)
+        } else if event_count("pids.events", "max") > 0 {
+            Some(REPORT_PROCESS_LIMIT)
+        } else {
+            None
+        }
+    }
+
+    fn remove(self) {
+        remove_cgroup(&self.path);
+    }
+}
+
+/// Kills whatever is still running in the cgroup and removes it. Best effort.
+fn remove_cgroup(path: &Path) {
+    if std::fs::write(path.join("cgroup.kill"), "1").is_err() {
+        // `cgroup.kill` needs Linux 5.14; signal the members one by one.
+        if let Ok(procs) = std::fs::read_to_string(path.join("cgroup.procs")) {
+            for pid in procs
+                .lines()
+                .filter_map(|pid| pid.parse::<libc::pid_t>().ok())
+            {
+                unsafe { libc::kill(pid, libc::SIGKILL) };
+            }
+        }
+    }
+    // Killed processes leave the cgroup asynchronously.
+    for _ in 0..50 {
+        if std::fs::remove_dir(path).is_ok() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Removes cgroups left behind by helpers that were killed before they could
+/// clean up, e.g. when Codex timed the command out.
+fn remove_stale_cgroups(parent: &Path) {
+    let Ok(entries) = std::fs::read_dir(parent) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(pid) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(CGROUP_NAME_PREFIX))
+        else {
+            continue;
+        };
+        if !Path::new("/proc").join(pid).exists() {
+            remove_cgroup(&entry.path());
+        }
+    }
+}
+
+/// Extracts the cgroup v2 path from the contents of `/proc/self/cgroup`.
+fn parse_cgroup_v2_path(contents: &str) -> Option<&str> {
+    contents.lines().find_map(|line| line.strip_prefix("0::"))
+}
+
+/// Reads the counter for `key` from a flat-keyed file such as
+/// `memory.events`.
+fn parse_event_count(contents: &str, key: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        if name == key {
+            value.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_cgroup_v2_path() {
+        assert_eq!(
+            parse_cgroup_v2_path("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            Some("/user.slice/user-1000.slice/session-2.scope")
+        );
+        assert_eq!(
+            parse_cgroup_v2_path("12:pids:/user.slice\n1:name=systemd:/user.slice\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_event_counts() {
+        let events = "low 0\nhigh 0\nmax 12\noom 1\noom_kill 1\noom_group_kill 0\n";
+        assert_eq!(parse_event_count(events, "oom_kill"), Some(1));
+        assert_eq!(parse_event_count(events, "max"), Some(12));
+        assert_eq!(parse_event_count(events, "missing"), None);
+    }
+}
@@ -7,6 +7,8 @@ use codex_core::error::Result;
 use codex_core::error::SandboxErr;
 use codex_core::protocol::SandboxPolicy;
 
+use crate::mount_namespace::hide_denied_paths;
+
 use landlock::ABI;
 use landlock::Access;
 use landlock::AccessFs;
@@ -27,45 +29,89 @@ use seccompiler::apply_filter;
 
 /// Apply sandbox policies inside this thread so only the child inherits
 /// them, not the entire CLI process.
+///
+/// `in_network_namespace` must only be `true` when the current thread has
+/// already been moved into a private network namespace whose only way out is
+/// the allowlist proxy (see `network_proxy`); sockets are then left usable.
+/// It also means the process is already in its own user namespace.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    in_network_namespace: bool,
 ) -> Result<()> {
     if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
+        install_network_seccomp_filter_on_current_thread(in_network_namespace)?;
     }
 
+    // Only `DangerFullAccess` has full write access, and it never restricts
+    // reads either.
     if !sandbox_policy.has_full_disk_write_access() {
-        let writable_roots = sandbox_policy
+        let writable_roots: Vec<PathBuf> = sandbox_policy
             .get_writable_roots_with_cwd(cwd)
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
-        install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
+        let readable_roots = sandbox_policy
+            .get_readable_roots_with_cwd(cwd)
+            .map(|mut roots| {
+                roots.extend(SYSTEM_READABLE_ROOTS.iter().map(PathBuf::from));
+                roots
+            })
+            .unwrap_or_else(|| vec![PathBuf::from("/")]);
+        let deny_read: Vec<PathBuf> = sandbox_policy
+            .get_deny_read_paths()
+            .iter()
+            .map(|path| canonicalize(path))
+            .collect();
+        // Prefer hiding denied paths behind mounts so that Landlock can keep
+        // granting the roots they live in.
+        let hidden = !deny_read.is_empty() && hide_denied_paths(&deny_read, in_network_namespace)?;
+        let carve_out = if hidden {
+            Vec::new()
+        } else {
+            reject_denied_paths_in_writable_roots(&writable_roots, &deny_read)?;
+            deny_read
+        };
+        install_filesystem_landlock_rules_on_current_thread(
+            readable_roots,
+            writable_roots,
+            &carve_out,
+        )?;
     }
 
-    // TODO(ragona): Add appropriate restrictions if
-    // `sandbox_policy.has_full_disk_read_access()` is `false`.
-
     Ok(())
 }
 
+/// Folders that stay readable when `readable_roots` is set so that shells,
+/// the dynamic loader and common tools keep working.
+const SYSTEM_READABLE_ROOTS: &[&str] = &[
+    "/bin", "/sbin", "/usr", "/lib", "/lib32", "/lib64", "/libx32", "/etc", "/dev", "/proc",
+];
+
 /// Installs Landlock file-system rules on the current thread allowing read
-/// access to the entire file-system while restricting write access to
-/// `/dev/null` and the provided list of `writable_roots`.
+/// access to `readable_roots` while restricting write access to `/dev/null`
+/// and the provided list of `writable_roots`. Nothing under `deny_read`, which
+/// must be canonical, is granted either access.
 ///
 /// # Errors
 /// Returns [`CodexErr::Sandbox`] variants when the ruleset fails to apply.
-fn install_filesystem_landlock_rules_on_current_thread(writable_roots: Vec<PathBuf>) -> Result<()> {
+fn install_filesystem_landlock_rules_on_current_thread(
+    readable_roots: Vec<PathBuf>,
+    writable_roots: Vec<PathBuf>,
+    deny_read: &[PathBuf],
+) -> Result<()> {
     let abi = ABI::V5;
     let access_rw = AccessFs::from_all(abi);
     let access_ro = AccessFs::from_read(abi);
 
+    let readable_roots = exclude_denied_paths(&readable_roots, deny_read);
+    let writable_roots = exclude_denied_paths(&writable_roots, deny_read);
+
     let mut ruleset = Ruleset::default()
         .set_compatibility(CompatLevel::BestEffort)
         .handle_access(access_rw)?
         .create()?
-        .add_rules(landlock::path_beneath_rules(&["/"], access_ro))?
+        .add_rules(landlock::path_beneath_rules(&readable_roots, access_ro))?
         .add_rules(landlock::path_beneath_rules(&["/dev/null"], access_rw))?
         .set_no_new_privs(true);
 
@@ -82,9 +128,96 @@ fn install_filesystem_landlock_rules_on_current_thread(writable_roots: Vec<PathB
     Ok(())
 }
 
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Landlock can only grant access, so a denied path inside a granted root is
+/// carved out by granting every sibling along the way down to it instead of
+/// the root itself. Roots that do not exist are dropped.
+///
+/// This is the fallback for hosts without unprivileged user namespaces. The
+/// folders on the way down are no longer granted themselves, so they cannot
+/// be listed, and entries created in them after the sandbox starts are not
+/// accessible.
+fn exclude_denied_paths(roots: &[PathBuf], deny: &[PathBuf]) -> Vec<PathBuf> {
+    let mut granted = Vec::new();
+    for root in roots {
+        let root = canonicalize(root);
+        if root.exists() {
+            collect_granted_paths(&root, deny, &mut granted);
+        }
+    }
+    granted.sort();
+    granted.dedup();
+    granted
+}
+
+/// With the carve-out fallback, a denied path inside a writable root would
+/// stop the command from creating anything new in that root, which breaks
+/// ordinary commands in the working directory. Refuse such policies instead.
+fn reject_denied_paths_in_writable_roots(
+    writable_roots: &[PathBuf],
+    deny_read: &[PathBuf],
+) -> Result<()> {
+    for root in writable_roots {
+        let root = canonicalize(root);
+        if let Some(denied) = deny_read
+            .iter()
+            .find(|denied| denied.starts_with(&root) && **denied != root)
+        {
+            return Err(CodexErr::UnsupportedOperation(format!(
+                "deny_read path {} is inside writable root {}; this requires unprivileged user namespaces, which are not available",
+                denied.display(),
+                root.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn collect_granted_paths(path: &Path, deny: &[PathBuf], granted: &mut Vec<PathBuf>) {
+    if deny.iter().any(|denied| path.starts_with(denied)) {
+        return;
+    }
+    if !deny.iter().any(|denied| denied.starts_with(path)) {
+        granted.push(path.to_path_buf());
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let child = entry.path();
+        if entry
+            .file_type()
+            .is_ok_and(|file_type| file_type.is_symlink())
+        {
+            // Landlock follows the link, so only grant it when its target does
+            // not overlap a denied path; the target itself is handled when the
+            // walk reaches it.
+            let target = canonicalize(&child);
+            let overlaps = deny
+                .iter()
+                .any(|denied| target.starts_with(denied) || denied.starts_with(&target));
+            if !overlaps && target.exists() {
+                granted.push(child);
+            }
+        } else {
+            collect_granted_paths(&child, deny, granted);
+        }
+    }
+}
+
 /// Installs a seccomp filter that blocks outbound network access except for
-/// AF_UNIX domain sockets.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+/// AF_UNIX domain sockets. With `allow_sockets`, AF_INET/AF_INET6 sockets stay
+/// usable so the command can reach the allowlist proxy inside its network
+/// namespace; every other socket family is still denied because the namespace
+/// does not isolate AF_UNIX sockets such as `/var/run/docker.sock`,
+/// ssh-agent or D-Bus.
+fn install_network_seccomp_filter_on_current_thread(
+    allow_sockets: bool,
+) -> std::result::Result<(), SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
@@ -93,36 +226,58 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         rules.insert(nr, vec![]); // empty rule vec = unconditional match
     };
 
-    deny_syscall(libc::SYS_connect);
-    deny_syscall(libc::SYS_accept);
-    deny_syscall(libc::SYS_accept4);
-    deny_syscall(libc::SYS_bind);
-    deny_syscall(libc::SYS_listen);
-    deny_syscall(libc::SYS_getpeername);
-    deny_syscall(libc::SYS_getsockname);
-    deny_syscall(libc::SYS_shutdown);
-    deny_syscall(libc::SYS_sendto);
-    deny_syscall(libc::SYS_sendmsg);
-    deny_syscall(libc::SYS_sendmmsg);
-    // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
-    // with their socketpair + child processes for sub-proc management
-    // deny_syscall(libc::SYS_recvfrom);
-    deny_syscall(libc::SYS_recvmsg);
-    deny_syscall(libc::SYS_recvmmsg);
-    deny_syscall(libc::SYS_getsockopt);
-    deny_syscall(libc::SYS_setsockopt);
     deny_syscall(libc::SYS_ptrace);
 
-    // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
-    let unix_only_rule = SeccompRule::new(vec![SeccompCondition::new(
-        0, // first argument (domain)
-        SeccompCmpArgLen::Dword,
-        SeccompCmpOp::Ne,
-        libc::AF_UNIX as u64,
-    )?])?;
+    if allow_sockets {
+        // Deny `socket` unless the domain is AF_INET or AF_INET6. Conditions
+        // within a rule are ANDed. `socketpair` stays allowed since it cannot
+        // reach anything outside the process tree.
+        let inet_only_rule = SeccompRule::new(vec![
+            SeccompCondition::new(
+                0, // first argument (domain)
+                SeccompCmpArgLen::Dword,
+                SeccompCmpOp::Ne,
+                libc::AF_INET as u64,
+            )?,
+            SeccompCondition::new(
+                0,
+                SeccompCmpArgLen::Dword,
+                SeccompCmpOp::Ne,
+                libc::AF_INET6 as u64,
+            )?,
+        ])?;
+        rules.insert(libc::SYS_socket, vec![inet_only_rule]);
+    } else {
+        deny_syscall(libc::SYS_connect);
+        deny_syscall(libc::SYS_accept);
+        deny_syscall(libc::SYS_accept4);
+        deny_syscall(libc::SYS_bind);
+        deny_syscall(libc::SYS_listen);
+        deny_syscall(libc::SYS_getpeername);
+        deny_syscall(libc::SYS_getsockname);
+        deny_syscall(libc::SYS_shutdown);
+        deny_syscall(libc::SYS_sendto);
+        deny_syscall(libc::SYS_sendmsg);
+        deny_syscall(libc::SYS_sendmmsg);
+        // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
+        // with their socketpair + child processes for sub-proc management
+        // deny_syscall(libc::SYS_recvfrom);
+        deny_syscall(libc::SYS_recvmsg);
+        deny_syscall(libc::SYS_recvmmsg);
+        deny_syscall(libc::SYS_getsockopt);
+        deny_syscall(libc::SYS_setsockopt);
+
+        // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
+        let unix_only_rule = SeccompRule::new(vec![SeccompCondition::new(
+            0, // first argument (domain)
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_UNIX as u64,
+        )?])?;
 
-    rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
-    rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
+        rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
+        rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
+    }
 
     let filter = SeccompFilter::new(
         rules,
@@ -143,3 +298,65 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn exclude_denied_paths_grants_siblings_of_denied_path() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path().canonicalize().expect("canonical tempdir");
+        for dir in ["project/src", ".ssh", ".config/gh", ".config/other"] {
+            std::fs::create_dir_all(root.join(dir)).expect("create dir");
+        }
+        std::fs::write(root.join("notes.txt"), "notes").expect("write file");
+
+        let granted = exclude_denied_paths(
+            std::slice::from_ref(&root),
+            &[root.join(".ssh"), root.join(".config/gh")],
+        );
+
+        assert_eq!(
+            granted,
+            vec![
+                root.join(".config/other"),
+                root.join("notes.txt"),
+                root.join("project"),
+            ]
+        );
+    }
+
+    #[test]
+    fn exclude_denied_paths_drops_denied_roots() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path().canonicalize().expect("canonical tempdir");
+        let granted = exclude_denied_paths(
+            &[root.join("missing"), root.clone()],
+            std::slice::from_ref(&root),
+        );
+        assert_eq!(granted, Vec::<PathBuf>::new());
+    }
+
+    #[test]
+    fn carve_out_rejects_denied_paths_inside_writable_roots() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path().canonicalize().expect("canonical tempdir");
+        let writable_roots = [root.join("project")];
+
+        assert!(
+            reject_denied_paths_in_writable_roots(&writable_roots, &[root.join("project/.env")])
+                .is_err()
+        );
+        // Denying the root itself or a path elsewhere does not need a carve-out
+        // inside a writable root.
+        assert!(
+            reject_denied_paths_in_writable_roots(
+                &writable_roots,
+                &[root.join("project"), root.join(".ssh")]
+            )
+            .is_ok()
+        );
+    }
+}
@@ -1,7 +1,17 @@
 #[cfg(target_os = "linux")]
+mod cgroup;
+#[cfg(target_os = "linux")]
 mod landlock;
 #[cfg(target_os = "linux")]
 mod linux_run_main;
+#[cfg(target_os = "linux")]
+mod mount_namespace;
+#[cfg(target_os = "linux")]
+mod network_allowlist;
+#[cfg(target_os = "linux")]
+mod network_proxy;
+#[cfg(target_os = "linux")]
+mod resource_limits;
 
 #[cfg(target_os = "linux")]
 pub fn run_main() -> ! {
@@ -1,8 +1,21 @@
 use clap::Parser;
 use std::ffi::CString;
+use std::fs::File;
+use std::os::fd::FromRawFd;
+use std::os::fd::RawFd;
 use std::path::PathBuf;
+use std::sync::Arc;
 
+use codex_core::landlock::SANDBOX_REPORT_FD_ENV_VAR;
+
+use crate::cgroup::SandboxCgroup;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::network_allowlist::NetworkAllowlist;
+use crate::network_proxy::die_with_parent;
+use crate::network_proxy::exit_like;
+use crate::network_proxy::run_with_network_allowlist;
+use crate::network_proxy::wait_for_child;
+use crate::resource_limits::ResourceLimits;
 
 #[derive(Debug, Parser)]
 pub struct LandlockCommand {
@@ -14,6 +27,9 @@ pub struct LandlockCommand {
     #[arg(long = "sandbox-policy")]
     pub sandbox_policy: codex_core::protocol::SandboxPolicy,
 
+    #[command(flatten)]
+    pub resource_limits: ResourceLimits,
+
     /// Full command args to run under landlock.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -23,17 +39,87 @@ pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
         sandbox_policy,
+        resource_limits,
         command,
     } = LandlockCommand::parse();
 
-    if let Err(e) = apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd) {
+    if command.is_empty() {
+        panic!("No command specified to execute.");
+    }
+
+    let report = take_sandbox_report().map(Arc::new);
+    let cgroup = SandboxCgroup::create(&resource_limits)
+        .unwrap_or_else(|e| panic!("error setting up resource limits: {e}"));
+
+    if let Some((allowed_hosts, allowed_cidrs)) = sandbox_policy.network_allowlist() {
+        let allowlist = NetworkAllowlist::parse(allowed_hosts, allowed_cidrs)
+            .unwrap_or_else(|e| panic!("error parsing network allowlist: {e}"));
+        let status = run_with_network_allowlist(allowlist, report.clone(), cgroup.as_ref(), || {
+            apply_resource_limits(&resource_limits);
+            if let Err(e) =
+                apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, true)
+            {
+                panic!("error running landlock: {e:?}");
+            }
+            exec_command(&command)
+        });
+        if let Some(cgroup) = cgroup {
+            cgroup.finish(status, report.as_deref());
+        }
+        exit_like(status);
+    }
+
+    if let Some(cgroup) = cgroup {
+        // Stay outside the cgroup to read its events once the command exits.
+        let parent_pid = unsafe { libc::getpid() };
+        let child_pid = unsafe { libc::fork() };
+        if child_pid < 0 {
+            panic!("fork failed: {}", std::io::Error::last_os_error());
+        }
+        if child_pid > 0 {
+            let status = wait_for_child(child_pid);
+            cgroup.finish(status, report.as_deref());
+            exit_like(status);
+        }
+        drop(report);
+        die_with_parent(parent_pid);
+        if let Err(e) = cgroup.join_current_process() {
+            panic!("failed to move command into its cgroup: {e}");
+        }
+    }
+
+    apply_resource_limits(&resource_limits);
+    if let Err(e) =
+        apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false)
+    {
         panic!("error running landlock: {e:?}");
     }
 
-    if command.is_empty() {
-        panic!("No command specified to execute.");
+    exec_command(&command)
+}
+
+/// Takes the pipe Codex passed in [`SANDBOX_REPORT_FD_ENV_VAR`], if any. The
+/// variable is removed and the descriptor marked close-on-exec, so neither
+/// reaches the sandboxed command.
+fn take_sandbox_report() -> Option<File> {
+    let fd = std::env::var(SANDBOX_REPORT_FD_ENV_VAR).ok();
+    // SAFETY: the helper is still single-threaded here.
+    unsafe { std::env::remove_var(SANDBOX_REPORT_FD_ENV_VAR) };
+    let fd: RawFd = fd?.parse().ok()?;
+    if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+        return None;
     }
+    // SAFETY: Codex handed this descriptor to us and nothing else owns it.
+    Some(unsafe { File::from_raw_fd(fd) })
+}
+
+fn apply_resource_limits(resource_limits: &ResourceLimits) {
+    if let Err(e) = resource_limits.apply_to_current_process() {
+        panic!("error applying resource limits: {e}");
+    }
+}
 
+fn exec_command(command: &[String]) -> ! {
     #[expect(clippy::expect_used)]
     let c_command =
         CString::new(command[0].as_str()).expect("Failed to convert command to CString");
@@ -0,0 +1,131 @@
+//! Hides `deny_read` paths from the sandboxed command by mounting over them
+//! in a private mount namespace. Unlike a Landlock carve-out this leaves the
+//! folders around a denied path fully granted, so the command can still list
+//! them and create new entries next to it.
+
+use std::ffi::CString;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Moves the current process into a private mount namespace, inside a new
+/// user namespace unless `in_user_namespace`, and covers every existing path
+/// in `deny_read` with an empty mount: a read-only tmpfs for directories and a
+/// `nodev` bind of `/dev/null`, which cannot be opened, for files.
+///
+/// Returns `Ok(false)` without changing anything when the kernel does not
+/// allow the namespaces. Must be called while the process is single-threaded.
+pub(crate) fn hide_denied_paths(
+    deny_read: &[PathBuf],
+    in_user_namespace: bool,
+) -> io::Result<bool> {
+    let uid = unsafe { libc::getuid() };
+    let gid = unsafe { libc::getgid() };
+    let flags = if in_user_namespace {
+        libc::CLONE_NEWNS
+    } else {
+        libc::CLONE_NEWUSER | libc::CLONE_NEWNS
+    };
+    if unsafe { libc::unshare(flags) } != 0 {
+        return Ok(false);
+    }
+    if !in_user_namespace {
+        write_id_maps(uid, gid)?;
+    }
+
+    // Keep the mounts below from propagating back to the host.
+    mount(
+        None,
+        Path::new("/"),
+        None,
+        libc::MS_REC | libc::MS_PRIVATE,
+        None,
+    )?;
+    for path in deny_read {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            mount(
+                Some("tmpfs"),
+                path,
+                Some("tmpfs"),
+                libc::MS_RDONLY | libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                Some("mode=000"),
+            )?;
+        } else {
+            mount(Some("/dev/null"), path, None, libc::MS_BIND, None)?;
+            // A remount inside a user namespace must keep the atime flags of
+            // the mount `/dev/null` lives on.
+            let flags = libc::MS_REMOUNT
+                | libc::MS_BIND
+                | libc::MS_RDONLY
+                | libc::MS_NOSUID
+                | libc::MS_NODEV
+                | libc::MS_NOEXEC
+                | atime_flags(Path::new("/dev/null"))?;
+            mount(None, path, None, flags, None)?;
+        }
+    }
+    Ok(true)
+}
+
+/// Maps the current uid and gid into a freshly created user namespace.
+pub(crate) fn write_id_maps(uid: libc::uid_t, gid: libc::gid_t) -> io::Result<()> {
+    let write_proc = |file: &str, contents: String| {
+        std::fs::write(Path::new("/proc/self").join(file), contents).map_err(|e| {
+            io::Error::new(e.kind(), format!("failed to write /proc/self/{file}: {e}"))
+        })
+    };
+    write_proc("setgroups", "deny".to_string())?;
+    write_proc("uid_map", format!("{uid} {uid} 1"))?;
+    write_proc("gid_map", format!("{gid} {gid} 1"))
+}
+
+fn mount(
+    source: Option<&str>,
+    target: &Path,
+    fstype: Option<&str>,
+    flags: libc::c_ulong,
+    data: Option<&str>,
+) -> io::Result<()> {
+    let source = source.map(CString::new).transpose()?;
+    let target = CString::new(target.as_os_str().as_bytes())?;
+    let fstype = fstype.map(CString::new).transpose()?;
+    let data = data.map(CString::new).transpose()?;
+    let as_ptr = |s: &Option<CString>| s.as_ref().map_or(std::ptr::null(), |s| s.as_ptr());
+    let result = unsafe {
+        libc::mount(
+            as_ptr(&source),
+            target.as_ptr(),
+            as_ptr(&fstype),
+            flags,
+            as_ptr(&data).cast(),
+        )
+    };
+    if result != 0 {
+        let err = io::Error::last_os_error();
+        return Err(io::Error::new(
+            err.kind(),
+            format!("failed to mount over {}: {err}", target.to_string_lossy()),
+        ));
+    }
+    Ok(())
+}
+
+fn atime_flags(path: &Path) -> io::Result<libc::c_ulong> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok([
+        (libc::ST_NOATIME, libc::MS_NOATIME),
+        (libc::ST_NODIRATIME, libc::MS_NODIRATIME),
+        (libc::ST_RELATIME, libc::MS_RELATIME),
+    ]
+    .into_iter()
+    .filter(|(st, _)| stat.f_flag & st != 0)
+    .fold(0, |flags, (_, ms)| flags | ms))
+}
@@ -0,0 +1,229 @@
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::net::ToSocketAddrs;
+
+/// The parsed form of `allowed_hosts` and `allowed_cidrs` from
+/// [`codex_core::protocol::SandboxPolicy::WorkspaceWrite`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NetworkAllowlist {
+    hosts: Vec<HostPattern>,
+    cidrs: Vec<Cidr>,
+}
+
+#[derive(Debug)]
+pub(crate) enum ResolveError {
+    /// The destination is not covered by the allowlist.
+    Blocked,
+    /// The host name could not be resolved.
+    Lookup(std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HostPattern {
+    /// `example.com` matches only `example.com`.
+    Exact(String),
+    /// `*.example.com` matches `crates.example.com` but not `example.com`.
+    Subdomain(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl NetworkAllowlist {
+    pub(crate) fn parse(
+        allowed_hosts: &[String],
+        allowed_cidrs: &[String],
+    ) -> Result<Self, String> {
+        let hosts = allowed_hosts
+            .iter()
+            .map(|host| HostPattern::parse(host))
+            .collect::<Result<_, _>>()?;
+        let cidrs = allowed_cidrs
+            .iter()
+            .map(|cidr| Cidr::parse(cidr))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { hosts, cidrs })
+    }
+
+    /// Resolves `host` and returns the addresses the sandboxed command may
+    /// connect to. Hosts listed in `allowed_hosts` may use any address they
+    /// resolve to; any other host is limited to addresses inside
+    /// `allowed_cidrs`.
+    pub(crate) fn resolve(&self, host: &str, port: u16) -> Result<Vec<SocketAddr>, ResolveError> {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let host_allowed = self.hosts.iter().any(|pattern| pattern.matches(host));
+        if !host_allowed && self.cidrs.is_empty() {
+            // Avoid leaking the name to DNS when it can never be allowed.
+            return Err(ResolveError::Blocked);
+        }
+        let addrs: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) => (host, port)
+                .to_socket_addrs()
+                .map_err(ResolveError::Lookup)?
+                .collect(),
+        };
+        if host_allowed {
+            return Ok(addrs);
+        }
+        let allowed: Vec<SocketAddr> = addrs
+            .into_iter()
+            .filter(|addr| self.cidrs.iter().any(|cidr| cidr.contains(addr.ip())))
+            .collect();
+        if allowed.is_empty() {
+            Err(ResolveError::Blocked)
+        } else {
+            Ok(allowed)
+        }
+    }
+}
+
+impl HostPattern {
+    fn parse(host: &str) -> Result<Self, String> {
+        let host = host.trim().trim_end_matches('.').to_ascii_lowercase();
+        if let Some(domain) = host.strip_prefix("*.") {
+            if domain.is_empty() || domain.contains('*') {
+                return Err(format!("invalid allowed_hosts entry: {host}"));
+            }
+            return Ok(Self::Subdomain(domain.to_string()));
+        }
+        if host.is_empty() || host.contains('*') || host.contains('/') {
+            return Err(format!("invalid allowed_hosts entry: {host}"));
+        }
+        Ok(Self::Exact(host))
+    }
+
+    fn matches(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        match self {
+            Self::Exact(expected) => host == *expected,
+            Self::Subdomain(domain) => host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.')),
+        }
+    }
+}
+
+impl Cidr {
+    fn parse(cidr: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid allowed_cidrs entry: {cidr}");
+        let (addr, prefix_len) = match cidr.trim().split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (cidr.trim(), None),
+        };
+        let network: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len.parse::<u8>().map_err(|_| invalid())?,
+            None => max_len,
+        };
+        if prefix_len > max_len {
+            return Err(invalid());
+        }
+        Ok(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                prefix_matches(&network.octets(), &ip.octets(), self.prefix_len)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                prefix_matches(&network.octets(), &ip.octets(), self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches(network: &[u8], ip: &[u8], prefix_len: u8) -> bool {
+    let full_bytes = usize::from(prefix_len / 8);
+    let remaining_bits = prefix_len % 8;
+    if network[..full_bytes] != ip[..full_bytes] {
+        return false;
+    }
+    if remaining_bits == 0 {
+        return true;
+    }
+    let mask = 0xffu8 << (8 - remaining_bits);
+    network[full_bytes] & mask == ip[full_bytes] & mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn allowlist(hosts: &[&str], cidrs: &[&str]) -> NetworkAllowlist {
+        let hosts: Vec<String> = hosts.iter().map(ToString::to_string).collect();
+        let cidrs: Vec<String> = cidrs.iter().map(ToString::to_string).collect();
+        NetworkAllowlist::parse(&hosts, &cidrs).expect("valid allowlist")
+    }
+
+    #[test]
+    fn host_patterns_match_exact_names_and_subdomains() {
+        let exact = HostPattern::parse("Crates.io").expect("valid host");
+        assert!(exact.matches("crates.io"));
+        assert!(exact.matches("CRATES.IO."));
+        assert!(!exact.matches("static.crates.io"));
+
+        let wildcard = HostPattern::parse("*.example.com").expect("valid host");
+        assert!(wildcard.matches("registry.example.com"));
+        assert!(wildcard.matches("a.b.example.com"));
+        assert!(!wildcard.matches("example.com"));
+        assert!(!wildcard.matches("badexample.com"));
+    }
+
+    #[test]
+    fn cidrs_match_addresses_in_range() {
+        let cidr = Cidr::parse("10.1.0.0/16").expect("valid cidr");
+        assert!(cidr.contains("10.1.255.3".parse().expect("ip")));
+        assert!(cidr.contains("::ffff:10.1.0.1".parse().expect("ip")));
+        assert!(!cidr.contains("10.2.0.1".parse().expect("ip")));
+
+        let cidr = Cidr::parse("fd00::/8").expect("valid cidr");
+        assert!(cidr.contains("fd12::1".parse().expect("ip")));
+        assert!(!cidr.contains("fe80::1".parse().expect("ip")));
+
+        let single = Cidr::parse("192.168.1.7").expect("valid cidr");
+        assert!(single.contains("192.168.1.7".parse().expect("ip")));
+        assert!(!single.contains("192.168.1.8".parse().expect("ip")));
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        assert!(NetworkAllowlist::parse(&["*".to_string()], &[]).is_err());
+        assert!(NetworkAllowlist::parse(&[], &["10.0.0.0/33".to_string()]).is_err());
+        assert!(NetworkAllowlist::parse(&[], &["example.com/8".to_string()]).is_err());
+    }
+
+    #[test]
+    fn resolve_filters_by_host_then_cidr() {
+        let allowlist = allowlist(&["localhost"], &["127.0.0.0/8"]);
+        assert!(
+            allowlist
+                .resolve("localhost", 80)
+                .is_ok_and(|addrs| !addrs.is_empty())
+        );
+        assert_eq!(
+            allowlist.resolve("127.0.0.2", 443).expect("allowed"),
+            vec!["127.0.0.2:443".parse().expect("addr")]
+        );
+        assert!(matches!(
+            allowlist.resolve("10.0.0.1", 443),
+            Err(ResolveError::Blocked)
+        ));
+
+        let hosts_only = self::allowlist(&["crates.io"], &[]);
+        assert!(matches!(
+            hosts_only.resolve("127.0.0.1", 443),
+            Err(ResolveError::Blocked)
+        ));
+    }
+}
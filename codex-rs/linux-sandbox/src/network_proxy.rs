@@ -0,0 +1,479 @@
+//! Enforcement of `allowed_hosts`/`allowed_cidrs` for the Linux sandbox.
+//!
+//! The command runs in a fresh user + network namespace whose only interface
+//! is loopback. Before exec, the child binds a listener on `127.0.0.1:<port>`
+//! inside that namespace and hands the socket to the helper process, which
+//! stays outside the namespace and serves an HTTP proxy on it. The proxy
+//! understands `CONNECT host:port` and absolute-form `GET http://host/...`
+//! requests and only dials destinations the [`NetworkAllowlist`] permits.
+//! `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY` point the command at the proxy;
+//! tools that ignore those variables simply have no route out.
+//!
+//! Rejected destinations are explained on stderr for the command's benefit
+//! and, when Codex passed one, reported on the sandbox report pipe, which is
+//! the only report Codex trusts.
+
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::RawFd;
+use std::os::unix::net::UnixStream;
+use std::sync::Arc;
+
+use codex_core::landlock::NETWORK_BLOCKED_PREFIX;
+use codex_core::landlock::REPORT_BLOCKED_NETWORK_PREFIX;
+
+use crate::cgroup::SandboxCgroup;
+use crate::mount_namespace::write_id_maps;
+use crate::network_allowlist::NetworkAllowlist;
+use crate::network_allowlist::ResolveError;
+
+/// Upper bound on the size of a proxy request head.
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+
+const PROXY_ENV_VARS: &[&str] = &[
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+];
+
+/// Runs `exec_child` in a network namespace that can only reach the
+/// destinations permitted by `allowlist` and returns the child's wait status.
+/// Each rejected destination is reported on `report`.
+///
+/// `exec_child` runs in a forked, single-threaded child after it joined
+/// `cgroup` and the namespace and proxy environment variables are set up; it
+/// is expected to apply the remaining sandbox restrictions and `execvp` the
+/// command.
+pub(crate) fn run_with_network_allowlist(
+    allowlist: NetworkAllowlist,
+    report: Option<Arc<File>>,
+    cgroup: Option<&SandboxCgroup>,
+    exec_child: impl FnOnce(),
+) -> libc::c_int {
+    let (parent_channel, child_channel) = UnixStream::pair()
+        .unwrap_or_else(|e| panic!("failed to create socketpair for network proxy: {e}"));
+
+    // Fork while this process is still single-threaded so the child may
+    // safely allocate before it execs.
+    let parent_pid = unsafe { libc::getpid() };
+    let child_pid = unsafe { libc::fork() };
+    if child_pid < 0 {
+        panic!("fork failed: {}", std::io::Error::last_os_error());
+    }
+
+    if child_pid == 0 {
+        drop(parent_channel);
+        drop(report);
+        die_with_parent(parent_pid);
+        if let Some(cgroup) = cgroup
+            && let Err(e) = cgroup.join_current_process()
+        {
+            panic!("failed to move command into its cgroup: {e}");
+        }
+        enter_network_namespace();
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .unwrap_or_else(|e| panic!("failed to bind network proxy listener: {e}"));
+        #[expect(clippy::expect_used)]
+        let port = listener
+            .local_addr()
+            .expect("bound listener has an address")
+            .port();
+        send_fd(&child_channel, listener.as_raw_fd())
+            .unwrap_or_else(|e| panic!("failed to hand network proxy listener to helper: {e}"));
+        drop(listener);
+        drop(child_channel);
+
+        let proxy_url = format!("http://127.0.0.1:{port}");
+        for name in PROXY_ENV_VARS {
+            // SAFETY: the forked child is single-threaded.
+            unsafe { std::env::set_var(name, &proxy_url) };
+        }
+        for name in ["NO_PROXY", "no_proxy"] {
+            // SAFETY: the forked child is single-threaded.
+            unsafe { std::env::remove_var(name) };
+        }
+        exec_child();
+        // `exec_child` only returns if it failed to exec.
+        unsafe { libc::_exit(1) };
+    }
+
+    drop(child_channel);
+    // If the child fails before handing over the listener, the channel is
+    // simply closed and there is nothing to serve.
+    if let Ok(fd) = recv_fd(&parent_channel) {
+        // SAFETY: `fd` was just received via SCM_RIGHTS and is owned by us.
+        let listener = unsafe { TcpListener::from_raw_fd(fd) };
+        std::thread::spawn(move || serve_proxy(listener, allowlist, report));
+    }
+    drop(parent_channel);
+    wait_for_child(child_pid)
+}
+
+/// Ensures the current process is killed if `parent_pid` exits first, e.g.
+/// when Codex kills the helper after a timeout.
+pub(crate) fn die_with_parent(parent_pid: libc::pid_t) {
+    unsafe {
+        libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+        if libc::getppid() != parent_pid {
+            libc::_exit(1);
+        }
+    }
+}
+
+/// Moves the current process into a new user and network namespace, keeping
+/// its uid/gid, and brings up the loopback interface.
+fn enter_network_namespace() {
+    let uid = unsafe { libc::getuid() };
+    let gid = unsafe { libc::getgid() };
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } != 0 {
+        panic!(
+            "failed to create network namespace for allowed_hosts/allowed_cidrs: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+
+    if let Err(e) = write_id_maps(uid, gid) {
+        panic!("{e}");
+    }
+
+    bring_up_loopback();
+}
+
+fn bring_up_loopback() {
+    unsafe {
+        let fd = libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0);
+        if fd < 0 {
+            panic!(
+                "failed to open socket for loopback setup: {}",
+                std::io::Error::last_os_error()
+            );
+        }
+        let mut ifr: libc::ifreq = std::mem::zeroed();
+        for (dst, src) in ifr.ifr_name.iter_mut().zip(b"lo\0") {
+            *dst = *src as libc::c_char;
+        }
+        let result = if libc::ioctl(fd, libc::SIOCGIFFLAGS, &mut ifr) == 0 {
+            ifr.ifr_ifru.ifru_flags |= libc::IFF_UP as libc::c_short;
+            libc::ioctl(fd, libc::SIOCSIFFLAGS, &ifr)
+        } else {
+            -1
+        };
+        let err = std::io::Error::last_os_error();
+        libc::close(fd);
+        if result != 0 {
+            panic!("failed to bring up loopback interface: {err}");
+        }
+    }
+}
+
+/// Sends `fd` over `channel` as SCM_RIGHTS ancillary data.
+fn send_fd(channel: &UnixStream, fd: RawFd) -> std::io::Result<()> {
+    let mut byte = [0u8; 1];
+    let mut iov = libc::iovec {
+        iov_base: byte.as_mut_ptr().cast(),
+        iov_len: byte.len(),
+    };
+    let space = unsafe { libc::CMSG_SPACE(std::mem::size_of::<RawFd>() as u32) } as usize;
+    let mut control = vec![0u8; space];
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = space as _;
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(std::mem::size_of::<RawFd>() as u32) as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(cmsg).cast::<RawFd>(), fd);
+        if libc::sendmsg(channel.as_raw_fd(), &msg, 0) < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Receives a file descriptor sent with [`send_fd`].
+fn recv_fd(channel: &UnixStream) -> std::io::Result<RawFd> {
+    let mut byte = [0u8; 1];
+    let mut iov = libc::iovec {
+        iov_base: byte.as_mut_ptr().cast(),
+        iov_len: byte.len(),
+    };
+    let space = unsafe { libc::CMSG_SPACE(std::mem::size_of::<RawFd>() as u32) } as usize;
+    let mut control = vec![0u8; space];
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = space as _;
+    unsafe {
+        if libc::recvmsg(channel.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC) <= 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        if cmsg.is_null()
+            || (*cmsg).cmsg_level != libc::SOL_SOCKET
+            || (*cmsg).cmsg_type != libc::SCM_RIGHTS
+        {
+            return Err(std::io::Error::other("no file descriptor received"));
+        }
+        Ok(std::ptr::read_unaligned(
+            libc::CMSG_DATA(cmsg).cast::<RawFd>(),
+        ))
+    }
+}
+
+fn serve_proxy(listener: TcpListener, allowlist: NetworkAllowlist, report: Option<Arc<File>>) {
+    for stream in listener.incoming().flatten() {
+        let allowlist = allowlist.clone();
+        let report = report.clone();
+        std::thread::spawn(move || {
+            let _ = handle_proxy_connection(stream, &allowlist, report.as_deref());
+        });
+    }
+}
+
+/// A parsed proxy request head.
+#[derive(Debug, PartialEq, Eq)]
+struct ProxyRequest {
+    host: String,
+    port: u16,
+    /// `CONNECT` tunnels get a `200` response; plain HTTP requests are
+    /// replayed to the upstream server as-is.
+    is_connect: bool,
+}
+
+fn handle_proxy_connection(
+    client: TcpStream,
+    allowlist: &NetworkAllowlist,
+    report: Option<&File>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(client.try_clone()?);
+    let mut client_write = client;
+    let head = read_request_head(&mut reader)?;
+    let Some(request) = parse_request_head(&head) else {
+        client_write.write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n")?;
+        return Ok(());
+    };
+
+    let destination = format!("{}:{}", request.host, request.port);
+    let addrs = match allowlist.resolve(&request.host, request.port) {
+        Ok(addrs) => addrs,
+        Err(ResolveError::Lookup(e)) => {
+            write!(
+                client_write,
+                "HTTP/1.1 502 Bad Gateway\r\nConnection: close\r\n\r\nfailed to resolve {destination}: {e}\n"
+            )?;
+            return Ok(());
+        }
+        Err(ResolveError::Blocked) => {
+            if let Some(mut report) = report {
+                // A single short write to a pipe is atomic, so lines from
+                // concurrent connections do not interleave.
+                let _ = report.write_all(
+                    format!("{REPORT_BLOCKED_NETWORK_PREFIX}{destination}\n").as_bytes(),
+                );
+            }
+            let message = format!(
+                "{NETWORK_BLOCKED_PREFIX}{destination} (not in allowed_hosts or allowed_cidrs)"
+            );
+            #[expect(clippy::print_stderr)]
+            {
+                eprintln!("{message}");
+            }
+            write!(
+                client_write,
+                "HTTP/1.1 403 Forbidden\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{message}\n",
+                message.len() + 1
+            )?;
+            return Ok(());
+        }
+    };
+
+    let mut upstream = match TcpStream::connect(addrs.as_slice()) {
+        Ok(upstream) => upstream,
+        Err(e) => {
+            write!(
+                client_write,
+                "HTTP/1.1 502 Bad Gateway\r\nConnection: close\r\n\r\nfailed to connect to {destination}: {e}\n"
+            )?;
+            return Ok(());
+        }
+    };
+
+    if request.is_connect {
+        client_write.write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")?;
+    } else {
+        upstream.write_all(&head)?;
+    }
+    // Anything the client sent after the head is still in the reader's buffer.
+    upstream.write_all(reader.buffer())?;
+
+    let mut upstream_read = upstream.try_clone()?;
+    let forward = std::thread::spawn(move || {
+        let _ = std::io::copy(&mut reader.into_inner(), &mut upstream);
+        let _ = upstream.shutdown(Shutdown::Write);
+    });
+    let _ = std::io::copy(&mut upstream_read, &mut client_write);
+    let _ = client_write.shutdown(Shutdown::Write);
+    let _ = forward.join();
+    Ok(())
+}
+
+fn read_request_head(reader: &mut impl BufRead) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::new();
+    loop {
+        let read = reader
+            .by_ref()
+            .take((MAX_REQUEST_HEAD_BYTES - head.len()) as u64)
+            .read_until(b'\n', &mut head)?;
+        if read == 0 || head.ends_with(b"\r\n\r\n") || head.ends_with(b"\n\n") {
+            return Ok(head);
+        }
+        if head.len() >= MAX_REQUEST_HEAD_BYTES {
+            return Ok(head);
+        }
+    }
+}
+
+fn parse_request_head(head: &[u8]) -> Option<ProxyRequest> {
+    let head = std::str::from_utf8(head).ok()?;
+    let request_line = head.lines().next()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let (host, port) = split_host_port(target, None)?;
+        return Some(ProxyRequest {
+            host,
+            port,
+            is_connect: true,
+        });
+    }
+
+    let rest = target.strip_prefix("http://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit('@').next()?;
+    let (host, port) = split_host_port(authority, Some(80))?;
+    Some(ProxyRequest {
+        host,
+        port,
+        is_connect: false,
+    })
+}
+
+fn split_host_port(authority: &str, default_port: Option<u16>) -> Option<(String, u16)> {
+    let (host, port) = if let Some(rest) = authority.strip_prefix('[') {
+        let (host, after) = rest.split_once(']')?;
+        (host, after.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => default_port?,
+    };
+    if host.is_empty() {
+        return None;
+    }
+    Some((host.to_string(), port))
+}
+
+pub(crate) fn wait_for_child(pid: libc::pid_t) -> libc::c_int {
+    let mut status: libc::c_int = 0;
+    loop {
+        let result = unsafe { libc::waitpid(pid, &mut status, 0) };
+        if result == pid {
+            return status;
+        }
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            panic!("waitpid failed: {err}");
+        }
+    }
+}
+
+/// Exits with the same status as the child so callers cannot tell the
+/// difference between the helper and the command it ran.
+pub(crate) fn exit_like(status: libc::c_int) -> ! {
+    if libc::WIFSIGNALED(status) {
+        let signal = libc::WTERMSIG(status);
+        unsafe {
+            libc::signal(signal, libc::SIG_DFL);
+            libc::kill(libc::getpid(), signal);
+        }
+        std::process::exit(128 + signal);
+    }
+    std::process::exit(libc::WEXITSTATUS(status));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_connect_requests() {
+        assert_eq!(
+            parse_request_head(b"CONNECT crates.io:443 HTTP/1.1\r\nHost: crates.io:443\r\n\r\n"),
+            Some(ProxyRequest {
+                host: "crates.io".to_string(),
+                port: 443,
+                is_connect: true,
+            })
+        );
+        assert_eq!(
+            parse_request_head(b"CONNECT [::1]:8443 HTTP/1.1\r\n\r\n"),
+            Some(ProxyRequest {
+                host: "::1".to_string(),
+                port: 8443,
+                is_connect: true,
+            })
+        );
+        assert_eq!(
+            parse_request_head(b"CONNECT crates.io HTTP/1.1\r\n\r\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_absolute_form_requests() {
+        assert_eq!(
+            parse_request_head(b"GET http://user@registry.internal:8080/api?q=1 HTTP/1.1\r\n\r\n"),
+            Some(ProxyRequest {
+                host: "registry.internal".to_string(),
+                port: 8080,
+                is_connect: false,
+            })
+        );
+        assert_eq!(
+            parse_request_head(b"GET http://example.com HTTP/1.1\r\n\r\n"),
+            Some(ProxyRequest {
+                host: "example.com".to_string(),
+                port: 80,
+                is_connect: false,
+            })
+        );
+        // Origin-form requests carry no destination and cannot be proxied.
+        assert_eq!(
+            parse_request_head(b"GET /index.html HTTP/1.1\r\n\r\n"),
+            None
+        );
+    }
+}
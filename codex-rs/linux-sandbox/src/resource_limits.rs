@@ -0,0 +1,62 @@
+use std::io;
+
+/// The `[sandbox_limits]` the helper enforces. The CPU limit is an rlimit
+/// set before the command is exec'd, so every process the command spawns
+/// inherits it; memory and process limits apply to the command's whole tree
+/// through a [`crate::cgroup::SandboxCgroup`].
+#[derive(Debug, Clone, Copy, Default, clap::Args)]
+pub(crate) struct ResourceLimits {
+    /// Memory limit for the whole process tree (cgroup `memory.max`), in MiB.
+    #[arg(long = "max-memory-mb")]
+    pub max_memory_mb: Option<u64>,
+
+    /// Per-process CPU time limit (`RLIMIT_CPU`), in seconds.
+    #[arg(long = "max-cpu-seconds")]
+    pub max_cpu_seconds: Option<u64>,
+
+    /// Number of processes and threads the command's tree may have running
+    /// at once (cgroup `pids.max`).
+    #[arg(long = "max-processes")]
+    pub max_processes: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub(crate) fn apply_to_current_process(&self) -> io::Result<()> {
+        if let Some(max_cpu_seconds) = self.max_cpu_seconds {
+            // The soft limit delivers SIGXCPU, which is how the caller tells a
+            // CPU limit apart from other failures; the hard limit is a
+            // SIGKILL backstop for processes that handle SIGXCPU.
+            set_rlimit(
+                libc::RLIMIT_CPU,
+                max_cpu_seconds,
+                max_cpu_seconds.saturating_add(1),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_env = "gnu")]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(not(target_env = "gnu"))]
+type RlimitResource = libc::c_int;
+
+fn set_rlimit(resource: RlimitResource, soft: u64, hard: u64) -> io::Result<()> {
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    if unsafe { libc::getrlimit(resource, &mut current) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // Never try to raise the hard limit: that fails for unprivileged users.
+    let hard = (hard as libc::rlim_t).min(current.rlim_max);
+    let limit = libc::rlimit {
+        rlim_cur: (soft as libc::rlim_t).min(hard),
+        rlim_max: hard,
+    };
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
@@ -1,4 +1,5 @@
 #![cfg(target_os = "linux")]
+use codex_core::config::types::SandboxLimits;
 use codex_core::config::types::ShellEnvironmentPolicy;
 use codex_core::error::CodexErr;
 use codex_core::error::SandboxErr;
@@ -6,6 +7,7 @@ use codex_core::exec::ExecParams;
 use codex_core::exec::SandboxType;
 use codex_core::exec::process_exec_tool_call;
 use codex_core::exec_env::create_env;
+use codex_core::protocol::ExecTerminationReason;
 use codex_core::protocol::SandboxPolicy;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -55,6 +57,10 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
         // writing to in the sandbox.
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        allowed_hosts: vec![],
+        allowed_cidrs: vec![],
+        readable_roots: vec![],
+        deny_read: vec![],
     };
     let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
     let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
@@ -64,6 +70,7 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
         &sandbox_policy,
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
+        &SandboxLimits::default(),
         None,
     )
     .await
@@ -130,6 +137,137 @@ async fn test_timeout() {
     run_cmd(&["sleep", "2"], &[], 50).await;
 }
 
+#[expect(clippy::expect_used)]
+async fn run_cat_with_read_restrictions(
+    path: &std::path::Path,
+    readable_roots: Vec<PathBuf>,
+    deny_read: Vec<PathBuf>,
+) -> codex_core::error::Result<codex_core::exec::ExecToolCallOutput> {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let sandbox_cwd = cwd.clone();
+    let params = ExecParams {
+        command: vec!["cat".to_string(), path.to_string_lossy().to_string()],
+        cwd,
+        timeout_ms: Some(LONG_TIMEOUT_MS),
+        env: create_env_from_core_vars(),
+        with_escalated_permissions: None,
+        justification: None,
+        arg0: None,
+    };
+    let sandbox_policy = SandboxPolicy::ReadOnly {
+        readable_roots,
+        deny_read,
+    };
+    let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
+    let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
+    process_exec_tool_call(
+        params,
+        SandboxType::LinuxSeccomp,
+        &sandbox_policy,
+        sandbox_cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        &SandboxLimits::default(),
+        None,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn sandbox_denies_reads_under_deny_read() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let secret = tmpdir.path().join("secret");
+    let public = tmpdir.path().join("public");
+    std::fs::write(&secret, "secret\n").unwrap();
+    std::fs::write(&public, "public\n").unwrap();
+
+    let output = run_cat_with_read_restrictions(&public, vec![], vec![secret.clone()])
+        .await
+        .unwrap();
+    assert_eq!(output.stdout.text, "public\n");
+
+    let result = run_cat_with_read_restrictions(&secret, vec![], vec![secret.clone()]).await;
+    assert!(
+        matches!(result, Err(CodexErr::Sandbox(SandboxErr::Denied { .. }))),
+        "expected sandbox denied error, got: {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn sandbox_allows_new_files_next_to_denied_path() {
+    if !user_namespaces_available() {
+        return;
+    }
+    let tmpdir = tempfile::tempdir().unwrap();
+    let root = tmpdir.path().canonicalize().unwrap();
+    let secret = root.join(".env");
+    std::fs::write(&secret, "secret\n").unwrap();
+    let script = format!(
+        "echo new > {root}/new.txt && cat {root}/new.txt && ls {root} && ! cat {root}/.env",
+        root = root.display()
+    );
+
+    let params = ExecParams {
+        command: vec!["sh".to_string(), "-c".to_string(), script],
+        cwd: root.clone(),
+        timeout_ms: Some(LONG_TIMEOUT_MS),
+        env: create_env_from_core_vars(),
+        with_escalated_permissions: None,
+        justification: None,
+        arg0: None,
+    };
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+        allowed_hosts: vec![],
+        allowed_cidrs: vec![],
+        readable_roots: vec![],
+        deny_read: vec![secret],
+    };
+    let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
+    let output = process_exec_tool_call(
+        params,
+        SandboxType::LinuxSeccomp,
+        &sandbox_policy,
+        root.as_path(),
+        &Some(PathBuf::from(sandbox_program)),
+        &SandboxLimits::default(),
+        None,
+    )
+    .await
+    .expect("command should run");
+
+    assert_eq!(output.exit_code, 0, "stderr: {}", output.stderr.text);
+    assert_eq!(output.stdout.text, "new\nnew.txt\n");
+    assert_eq!(
+        std::fs::read_to_string(root.join("new.txt")).unwrap(),
+        "new\n"
+    );
+}
+
+#[tokio::test]
+async fn sandbox_limits_reads_to_readable_roots() {
+    let readable = tempfile::tempdir().unwrap();
+    let unreadable = tempfile::tempdir().unwrap();
+    let allowed = readable.path().join("allowed");
+    let other = unreadable.path().join("other");
+    std::fs::write(&allowed, "allowed\n").unwrap();
+    std::fs::write(&other, "other\n").unwrap();
+
+    let readable_roots = vec![readable.path().to_path_buf()];
+    let output = run_cat_with_read_restrictions(&allowed, readable_roots.clone(), vec![])
+        .await
+        .unwrap();
+    assert_eq!(output.stdout.text, "allowed\n");
+
+    let result = run_cat_with_read_restrictions(&other, readable_roots, vec![]).await;
+    assert!(
+        matches!(result, Err(CodexErr::Sandbox(SandboxErr::Denied { .. }))),
+        "expected sandbox denied error, got: {result:?}"
+    );
+}
+
 /// Helper that runs `cmd` under the Linux sandbox and asserts that the command
 /// does NOT succeed (i.e. returns a non‑zero exit code) **unless** the binary
 /// is missing in which case we silently treat it as an accepted skip so the
@@ -159,6 +297,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         &sandbox_policy,
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
+        &SandboxLimits::default(),
         None,
     )
     .await;
@@ -236,3 +375,295 @@ async fn sandbox_blocks_dev_tcp_redirection() {
     // all images ship bash, so we guard against 127 as well.
     assert_network_blocked(&["bash", "-c", "echo hi > /dev/tcp/127.0.0.1/80"]).await;
 }
+
+/// Serves a single canned HTTP response on an ephemeral loopback port.
+#[expect(clippy::expect_used)]
+fn spawn_http_server() -> u16 {
+    use std::io::Read;
+    use std::io::Write;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind test server");
+    let port = listener.local_addr().expect("local addr").port();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\nhello\n",
+            );
+        }
+    });
+    port
+}
+
+async fn run_curl_with_allowed_cidrs(
+    port: u16,
+    allowed_cidrs: &[&str],
+) -> codex_core::error::Result<codex_core::exec::ExecToolCallOutput> {
+    run_with_allowed_cidrs(
+        vec![
+            "curl".to_string(),
+            "-sSf".to_string(),
+            format!("http://127.0.0.1:{port}/"),
+        ],
+        allowed_cidrs,
+    )
+    .await
+}
+
+#[expect(clippy::expect_used)]
+async fn run_with_allowed_cidrs(
+    command: Vec<String>,
+    allowed_cidrs: &[&str],
+) -> codex_core::error::Result<codex_core::exec::ExecToolCallOutput> {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let sandbox_cwd = cwd.clone();
+    let params = ExecParams {
+        command,
+        cwd,
+        timeout_ms: Some(NETWORK_TIMEOUT_MS),
+        env: create_env_from_core_vars(),
+        with_escalated_permissions: None,
+        justification: None,
+        arg0: None,
+    };
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+        allowed_hosts: vec![],
+        allowed_cidrs: allowed_cidrs.iter().map(ToString::to_string).collect(),
+        readable_roots: vec![],
+        deny_read: vec![],
+    };
+    let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
+    let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
+    process_exec_tool_call(
+        params,
+        SandboxType::LinuxSeccomp,
+        &sandbox_policy,
+        sandbox_cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        &SandboxLimits::default(),
+        None,
+    )
+    .await
+}
+
+/// Unprivileged user namespaces are required to enforce a network allowlist;
+/// skip the allowlist tests on hosts that disable them.
+fn user_namespaces_available() -> bool {
+    std::process::Command::new("unshare")
+        .args(["-Urn", "true"])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[tokio::test]
+async fn sandbox_allows_allowlisted_cidr() {
+    if !user_namespaces_available() {
+        return;
+    }
+    let port = spawn_http_server();
+    let output = run_curl_with_allowed_cidrs(port, &["127.0.0.1/32"])
+        .await
+        .expect("allowlisted request should succeed");
+    assert_eq!(output.exit_code, 0, "stderr: {}", output.stderr.text);
+    assert_eq!(output.stdout.text, "hello\n");
+}
+
+#[tokio::test]
+async fn sandbox_reports_blocked_destination() {
+    if !user_namespaces_available() {
+        return;
+    }
+    let port = spawn_http_server();
+    let result = run_curl_with_allowed_cidrs(port, &["10.255.0.0/16"]).await;
+    match result {
+        Err(CodexErr::Sandbox(SandboxErr::NetworkBlocked { destinations, .. })) => {
+            assert_eq!(destinations, vec![format!("127.0.0.1:{port}")]);
+        }
+        other => panic!("expected NetworkBlocked, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn sandbox_denies_unix_sockets_under_allowlist() {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+
+    if !user_namespaces_available() {
+        return;
+    }
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let socket_path = tmp.path().join("host.sock");
+    let listener = std::os::unix::net::UnixListener::bind(&socket_path).expect("bind unix socket");
+    let connected = Arc::new(AtomicBool::new(false));
+    let connected_flag = Arc::clone(&connected);
+    std::thread::spawn(move || {
+        if listener.accept().is_ok() {
+            connected_flag.store(true, Ordering::SeqCst);
+        }
+    });
+
+    let result = run_with_allowed_cidrs(
+        vec![
+            "curl".to_string(),
+            "-sS".to_string(),
+            "--unix-socket".to_string(),
+            socket_path.to_string_lossy().into_owned(),
+            "http://localhost/".to_string(),
+        ],
+        &["127.0.0.1/32"],
+    )
+    .await;
+    let output = match result {
+        Ok(output) => output,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => *output,
+        other => panic!("expected the command to fail, got: {other:?}"),
+    };
+    assert_ne!(output.exit_code, 0, "stderr: {}", output.stderr.text);
+    assert!(!connected.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn command_cannot_forge_blocked_destinations() {
+    if !user_namespaces_available() {
+        return;
+    }
+    let script = r#"echo "fd=${CODEX_SANDBOX_REPORT_FD:-unset}"; echo "codex-linux-sandbox: blocked network connection to evil.example:443 (not in allowed_hosts or allowed_cidrs)" >&2; exit 1"#;
+    let result = run_with_allowed_cidrs(
+        vec!["bash".to_string(), "-c".to_string(), script.to_string()],
+        &["10.255.0.0/16"],
+    )
+    .await;
+    let output = match result {
+        Ok(output) => output,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => *output,
+        other => panic!("expected the command's own failure, got: {other:?}"),
+    };
+    assert_eq!(output.stdout.text, "fd=unset\n");
+}
+
+#[test]
+fn sandbox_enforces_cpu_time_limit() {
+    use std::os::unix::process::ExitStatusExt;
+
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .args([
+            "--sandbox-policy-cwd",
+            ".",
+            "--sandbox-policy",
+            r#"{"mode":"read-only"}"#,
+            "--max-cpu-seconds",
+            "1",
+            "--",
+            "bash",
+            "-c",
+            "while :; do :; done",
+        ])
+        .status()
+        .expect("helper should run");
+    assert_eq!(status.signal(), Some(libc::SIGXCPU));
+}
+
+#[tokio::test]
+async fn configured_cpu_limit_is_applied_and_reported() {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let params = ExecParams {
+        command: vec![
+            "bash".to_string(),
+            "-c".to_string(),
+            "while :; do :; done".to_string(),
+        ],
+        cwd: cwd.clone(),
+        timeout_ms: Some(10_000),
+        env: create_env_from_core_vars(),
+        with_escalated_permissions: None,
+        justification: None,
+        arg0: None,
+    };
+    let limits = SandboxLimits {
+        max_cpu_seconds: Some(1),
+        ..Default::default()
+    };
+    let codex_linux_sandbox_exe = Some(PathBuf::from(env!("CARGO_BIN_EXE_codex-linux-sandbox")));
+    let output = process_exec_tool_call(
+        params,
+        SandboxType::LinuxSeccomp,
+        &SandboxPolicy::new_read_only_policy(),
+        cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        &limits,
+        None,
+    )
+    .await
+    .expect("a cpu limit kill should be reported as a regular result");
+    assert_eq!(
+        output.termination_reason,
+        Some(ExecTerminationReason::CpuTimeLimit)
+    );
+}
+
+/// Memory and process limits need a cgroup v2 the helper can create children
+/// in; skip the cgroup tests on hosts that do not delegate one.
+fn cgroup_limits_available() -> bool {
+    std::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .args([
+            "--sandbox-policy-cwd",
+            ".",
+            "--sandbox-policy",
+            r#"{"mode":"read-only"}"#,
+            "--max-memory-mb",
+            "64",
+            "--",
+            "true",
+        ])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[tokio::test]
+async fn configured_memory_limit_is_applied_and_reported() {
+    if !cgroup_limits_available() {
+        return;
+    }
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let params = ExecParams {
+        // `tail` buffers the single, never-ending line in memory.
+        command: vec![
+            "bash".to_string(),
+            "-c".to_string(),
+            "head -c 536870912 /dev/zero | tail > /dev/null".to_string(),
+        ],
+        cwd: cwd.clone(),
+        timeout_ms: Some(10_000),
+        env: create_env_from_core_vars(),
+        with_escalated_permissions: None,
+        justification: None,
+        arg0: None,
+    };
+    let limits = SandboxLimits {
+        max_memory_mb: Some(64),
+        ..Default::default()
+    };
+    let codex_linux_sandbox_exe = Some(PathBuf::from(env!("CARGO_BIN_EXE_codex-linux-sandbox")));
+    let output = process_exec_tool_call(
+        params,
+        SandboxType::LinuxSeccomp,
+        &SandboxPolicy::new_read_only_policy(),
+        cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        &limits,
+        None,
+    )
+    .await
+    .expect("a memory limit kill should be reported as a regular result");
+    assert_eq!(
+        output.termination_reason,
+        Some(ExecTerminationReason::MemoryLimit)
+    );
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use codex_core::CodexConversation;
+use codex_core::protocol::ElicitationAction;
+use codex_core::protocol::ElicitationRequestEvent;
+use codex_core::protocol::Op;
+use mcp_types::ElicitRequest;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::ElicitResult;
+use mcp_types::JSONRPCErrorError;
+use mcp_types::ModelContextProtocolRequest;
+use mcp_types::RequestId;
+use serde::Serialize;
+use tracing::error;
+
+use crate::codex_tool_runner::INVALID_PARAMS_ERROR_CODE;
+use crate::outgoing_message::OutgoingMessageSender;
+
+/// Elicitation request from an MCP server used by the Codex session,
+/// forwarded as-is to our own client.
+#[derive(Debug, Serialize)]
+pub struct McpServerElicitRequestParams {
+    pub message: String,
+    #[serde(rename = "requestedSchema")]
+    pub requested_schema: ElicitRequestParamsRequestedSchema,
+    pub codex_elicitation: String,
+    pub codex_mcp_tool_call_id: String,
+    pub codex_event_id: String,
+    pub codex_mcp_server_name: String,
+    pub codex_mcp_request_id: RequestId,
+}
+
+pub(crate) async fn handle_elicitation_request(
+    event: ElicitationRequestEvent,
+    outgoing: Arc<OutgoingMessageSender>,
+    codex: Arc<CodexConversation>,
+    request_id: RequestId,
+    tool_call_id: String,
+    event_id: String,
+) {
+    let ElicitationRequestEvent {
+        server_name,
+        id,
+        message,
+        requested_schema,
+    } = event;
+
+    let params = McpServerElicitRequestParams {
+        message,
+        requested_schema,
+        codex_elicitation: "mcp-server-elicitation".to_string(),
+        codex_mcp_tool_call_id: tool_call_id,
+        codex_event_id: event_id,
+        codex_mcp_server_name: server_name.clone(),
+        codex_mcp_request_id: id.clone(),
+    };
+    let params_json = match serde_json::to_value(&params) {
+        Ok(value) => value,
+        Err(err) => {
+            let message = format!("Failed to serialize McpServerElicitRequestParams: {err}");
+            error!("{message}");
+
+            outgoing
+                .send_error(
+                    request_id,
+                    JSONRPCErrorError {
+                        code: INVALID_PARAMS_ERROR_CODE,
+                        message,
+                        data: None,
+                    },
+                )
+                .await;
+
+            return;
+        }
+    };
+
+    let on_response = outgoing
+        .send_request(ElicitRequest::METHOD, Some(params_json))
+        .await;
+
+    // Listen for the response on a separate task so we don't block the main agent loop.
+    tokio::spawn(async move {
+        on_elicitation_response(server_name, id, on_response, codex).await;
+    });
+}
+
+async fn on_elicitation_response(
+    server_name: String,
+    mcp_request_id: RequestId,
+    receiver: tokio::sync::oneshot::Receiver<mcp_types::Result>,
+    codex: Arc<CodexConversation>,
+) {
+    let response = match receiver.await {
+        Ok(value) => serde_json::from_value::<ElicitResult>(value).unwrap_or_else(|err| {
+            error!("failed to deserialize ElicitResult: {err}");
+            ElicitResult {
+                action: "decline".to_string(),
+                content: None,
+            }
+        }),
+        Err(err) => {
+            error!("request failed: {err:?}");
+            ElicitResult {
+                action: "cancel".to_string(),
+                content: None,
+            }
+        }
+    };
+
+    let decision = match response.action.as_str() {
+        "accept" => ElicitationAction::Accept,
+        "decline" => ElicitationAction::Decline,
+        _ => ElicitationAction::Cancel,
+    };
+    if let Err(err) = codex
+        .submit(Op::ResolveElicitation {
+            server_name,
+            request_id: mcp_request_id,
+            decision,
+            content: response.content,
+        })
+        .await
+    {
+        error!("failed to submit ResolveElicitation: {err}");
+    }
+}
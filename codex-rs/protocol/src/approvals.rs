@@ -3,6 +3,9 @@ use std::path::PathBuf;
 
 use crate::parse_command::ParsedCommand;
 use crate::protocol::FileChange;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::RequestId;
+use mcp_types::SamplingMessage;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -61,3 +64,53 @@ pub struct ApplyPatchApprovalRequestEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
 }
+
+/// An MCP server asked the user for input via `elicitation/create`.
+/// Answer with `Op::ResolveElicitation`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ElicitationRequestEvent {
+    pub server_name: String,
+    /// The server's JSON-RPC id for the request.
+    pub id: RequestId,
+    pub message: String,
+    /// Flat object schema of primitive fields the server wants filled in.
+    /// Empty `properties` means the server only wants a confirmation.
+    pub requested_schema: ElicitRequestParamsRequestedSchema,
+}
+
+/// An MCP server withdrew an [`ElicitationRequestEvent`] before it was
+/// answered. Clients should close the form; it can no longer be answered.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ElicitationCancelledEvent {
+    pub server_name: String,
+    /// The server's JSON-RPC id for the request.
+    pub id: RequestId,
+}
+
+/// An MCP server asked to run a completion via `sampling/createMessage`.
+/// Answer with `Op::ResolveSamplingRequest`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SamplingRequestEvent {
+    pub server_name: String,
+    /// The server's JSON-RPC id for the request.
+    pub id: RequestId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    pub messages: Vec<SamplingMessage>,
+    /// Upper bound on completion tokens requested by the server.
+    pub max_tokens: i64,
+    /// Tokens left in the server's sampling budget for this session.
+    pub remaining_tokens: u64,
+}
+
+/// The user's answer to an [`ElicitationRequestEvent`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum ElicitationAction {
+    /// Submit the form; the answer carries its `content`.
+    Accept,
+    /// Explicitly refuse the request.
+    Decline,
+    /// Dismiss the request without choosing.
+    Cancel,
+}
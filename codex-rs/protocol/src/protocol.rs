@@ -23,6 +23,8 @@ use crate::parse_command::ParsedCommand;
 use crate::plan_tool::UpdatePlanArgs;
 use crate::user_input::UserInput;
 use mcp_types::CallToolResult;
+use mcp_types::GetPromptResult as McpGetPromptResult;
+use mcp_types::Prompt as McpPrompt;
 use mcp_types::Resource as McpResource;
 use mcp_types::ResourceTemplate as McpResourceTemplate;
 use mcp_types::Tool as McpTool;
@@ -35,7 +37,11 @@ use strum_macros::Display;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::ElicitationAction;
+pub use crate::approvals::ElicitationCancelledEvent;
+pub use crate::approvals::ElicitationRequestEvent;
 pub use crate::approvals::ExecApprovalRequestEvent;
+pub use crate::approvals::SamplingRequestEvent;
 pub use crate::approvals::SandboxCommandAssessment;
 pub use crate::approvals::SandboxRiskLevel;
 
@@ -153,6 +159,30 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Answer an MCP server's elicitation request.
+    ResolveElicitation {
+        /// Name of the MCP server that made the request.
+        server_name: String,
+        /// The server's id for the request.
+        request_id: mcp_types::RequestId,
+        decision: ElicitationAction,
+        /// Form values matching the requested schema. Only sent with
+        /// `ElicitationAction::Accept`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content: Option<Value>,
+    },
+
+    /// Approve or deny an MCP server's request to sample the model.
+    ResolveSamplingRequest {
+        /// Name of the MCP server that made the request.
+        server_name: String,
+        /// The server's id for the request.
+        request_id: mcp_types::RequestId,
+        /// `ApprovedForSession` approves all further requests from this
+        /// server until its token budget runs out.
+        decision: ReviewDecision,
+    },
+
     /// Append an entry to the persistent cross-session message history.
     ///
     /// Note the entry is not guaranteed to be logged if the user has
@@ -172,6 +202,18 @@ pub enum Op {
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
+    /// Request the prompts offered by the configured MCP servers.
+    /// Reply is delivered via `EventMsg::McpListPromptsResponse`.
+    ListMcpPrompts,
+
+    /// Fetch a single MCP prompt with the given arguments.
+    /// Reply is delivered via `EventMsg::GetMcpPromptResponse`.
+    GetMcpPrompt {
+        server: String,
+        name: String,
+        arguments: HashMap<String, String>,
+    },
+
     /// Request the agent to summarize the current conversation context.
     /// The agent will use its existing context (either conversation history or previous response id)
     /// to generate a summary which will be returned as an AgentMessage event.
@@ -247,9 +289,22 @@ pub enum SandboxPolicy {
     #[serde(rename = "danger-full-access")]
     DangerFullAccess,
 
-    /// Read-only access to the entire file-system.
+    /// Read-only access to the entire file-system, unless narrowed by
+    /// `readable_roots` or `deny_read`.
     #[serde(rename = "read-only")]
-    ReadOnly,
+    ReadOnly {
+        /// When non-empty, reads are limited to these folders (plus the cwd,
+        /// the writable roots and the system folders needed to run programs)
+        /// instead of the entire file-system. Only enforced by the Linux
+        /// sandbox.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        readable_roots: Vec<PathBuf>,
+
+        /// Files and folders that must not be readable from within the
+        /// sandbox, even when they live under a readable or writable root.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_read: Vec<PathBuf>,
+    },
 
     /// Same as `ReadOnly` but additionally grants write access to the current
     /// working directory ("workspace").
@@ -275,6 +330,30 @@ pub enum SandboxPolicy {
         /// writable roots on UNIX. Defaults to `false`.
         #[serde(default)]
         exclude_slash_tmp: bool,
+
+        /// Hosts that outbound connections may reach even though
+        /// `network_access` is `false`. Entries are exact host names or
+        /// `*.example.com` wildcards. Only enforced by the Linux sandbox.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allowed_hosts: Vec<String>,
+
+        /// CIDR ranges (e.g. `10.0.0.0/8`) that outbound connections may reach
+        /// even though `network_access` is `false`. Only enforced by the Linux
+        /// sandbox.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allowed_cidrs: Vec<String>,
+
+        /// When non-empty, reads are limited to these folders (plus the cwd,
+        /// the writable roots and the system folders needed to run programs)
+        /// instead of the entire file-system. Only enforced by the Linux
+        /// sandbox.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        readable_roots: Vec<PathBuf>,
+
+        /// Files and folders that must not be readable from within the
+        /// sandbox, even when they live under a readable or writable root.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_read: Vec<PathBuf>,
     },
 }
 
@@ -320,7 +399,10 @@ impl FromStr for SandboxPolicy {
 impl SandboxPolicy {
     /// Returns a policy with read-only disk access and no network.
     pub fn new_read_only_policy() -> Self {
-        SandboxPolicy::ReadOnly
+        SandboxPolicy::ReadOnly {
+            readable_roots: vec![],
+            deny_read: vec![],
+        }
     }
 
     /// Returns a policy that can read the entire disk, but can only write to
@@ -332,18 +414,73 @@ impl SandboxPolicy {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            allowed_hosts: vec![],
+            allowed_cidrs: vec![],
+            readable_roots: vec![],
+            deny_read: vec![],
         }
     }
 
-    /// Always returns `true`; restricting read access is not supported.
+    /// Returns `false` when `readable_roots` or `deny_read` narrow what the
+    /// sandboxed command may read.
     pub fn has_full_disk_read_access(&self) -> bool {
-        true
+        match self {
+            SandboxPolicy::DangerFullAccess => true,
+            SandboxPolicy::ReadOnly {
+                readable_roots,
+                deny_read,
+            }
+            | SandboxPolicy::WorkspaceWrite {
+                readable_roots,
+                deny_read,
+                ..
+            } => readable_roots.is_empty() && deny_read.is_empty(),
+        }
+    }
+
+    /// Returns the folders reads are limited to, or `None` when the whole
+    /// file-system is readable (before `deny_read` is applied). Writable
+    /// roots are always readable.
+    pub fn get_readable_roots_with_cwd(&self, cwd: &Path) -> Option<Vec<PathBuf>> {
+        let readable_roots = match self {
+            SandboxPolicy::DangerFullAccess => return None,
+            SandboxPolicy::ReadOnly { readable_roots, .. }
+            | SandboxPolicy::WorkspaceWrite { readable_roots, .. } => readable_roots,
+        };
+        if readable_roots.is_empty() {
+            return None;
+        }
+        let mut roots = readable_roots.clone();
+        roots.push(cwd.to_path_buf());
+        roots.extend(
+            self.get_writable_roots_with_cwd(cwd)
+                .into_iter()
+                .map(|writable_root| writable_root.root),
+        );
+        Some(roots)
+    }
+
+    /// Returns the paths that must not be readable from within the sandbox.
+    pub fn get_deny_read_paths(&self) -> &[PathBuf] {
+        match self {
+            SandboxPolicy::DangerFullAccess => &[],
+            SandboxPolicy::ReadOnly { deny_read, .. }
+            | SandboxPolicy::WorkspaceWrite { deny_read, .. } => deny_read,
+        }
+    }
+
+    /// Returns `true` when `path`, which must be absolute and free of `..`
+    /// components, is one of the `deny_read` paths or lies below one.
+    pub fn is_read_denied(&self, path: &Path) -> bool {
+        self.get_deny_read_paths()
+            .iter()
+            .any(|denied| path.starts_with(denied))
     }
 
     pub fn has_full_disk_write_access(&self) -> bool {
         match self {
             SandboxPolicy::DangerFullAccess => true,
-            SandboxPolicy::ReadOnly => false,
+            SandboxPolicy::ReadOnly { .. } => false,
             SandboxPolicy::WorkspaceWrite { .. } => false,
         }
     }
@@ -351,23 +488,44 @@ impl SandboxPolicy {
     pub fn has_full_network_access(&self) -> bool {
         match self {
             SandboxPolicy::DangerFullAccess => true,
-            SandboxPolicy::ReadOnly => false,
+            SandboxPolicy::ReadOnly { .. } => false,
             SandboxPolicy::WorkspaceWrite { network_access, .. } => *network_access,
         }
     }
 
+    /// Returns the hosts and CIDR ranges that outbound connections may reach
+    /// when the policy otherwise blocks network access, or `None` if there is
+    /// no such allowlist.
+    pub fn network_allowlist(&self) -> Option<(&[String], &[String])> {
+        match self {
+            SandboxPolicy::WorkspaceWrite {
+                network_access: false,
+                allowed_hosts,
+                allowed_cidrs,
+                ..
+            } if !allowed_hosts.is_empty() || !allowed_cidrs.is_empty() => {
+                Some((allowed_hosts, allowed_cidrs))
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the list of writable roots (tailored to the current working
     /// directory) together with subpaths that should remain read‑only under
     /// each writable root.
     pub fn get_writable_roots_with_cwd(&self, cwd: &Path) -> Vec<WritableRoot> {
         match self {
             SandboxPolicy::DangerFullAccess => Vec::new(),
-            SandboxPolicy::ReadOnly => Vec::new(),
+            SandboxPolicy::ReadOnly { .. } => Vec::new(),
             SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
                 network_access: _,
+                allowed_hosts: _,
+                allowed_cidrs: _,
+                readable_roots: _,
+                deny_read: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<PathBuf> = writable_roots.clone();
@@ -500,6 +658,15 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// An MCP server is asking the user for input.
+    ElicitationRequest(ElicitationRequestEvent),
+
+    /// An MCP server withdrew an elicitation request before it was answered.
+    ElicitationCancelled(ElicitationCancelledEvent),
+
+    /// An MCP server wants to run a completion through Codex's model.
+    SamplingRequest(SamplingRequestEvent),
+
     /// Notification advising the user that something they are using has been
     /// deprecated and should be phased out.
     DeprecationNotice(DeprecationNoticeEvent),
@@ -532,6 +699,15 @@ pub enum EventMsg {
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// List of prompts offered by MCP servers.
+    McpListPromptsResponse(McpListPromptsResponseEvent),
+
+    /// Response to `Op::GetMcpPrompt`.
+    GetMcpPromptResponse(GetMcpPromptResponseEvent),
+
+    /// An MCP server changed the tools, resources or prompts it offers.
+    McpListChanged(McpListChangedEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     TurnAborted(TurnAbortedEvent),
@@ -1155,8 +1331,63 @@ pub struct GitInfo {
 /// Review request sent to the review session.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 pub struct ReviewRequest {
+    /// Instructions for the reviewer. When `target` is set, the review prompt
+    /// is derived from the target and this holds optional extra instructions.
     pub prompt: String,
     pub user_facing_hint: String,
+    /// What to review.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub target: Option<ReviewTarget>,
+}
+
+impl ReviewRequest {
+    /// Builds a request that reviews `target` with no extra instructions.
+    pub fn for_target(target: ReviewTarget) -> Self {
+        Self {
+            prompt: String::new(),
+            user_facing_hint: target.user_facing_hint(),
+            target: Some(target),
+        }
+    }
+}
+
+/// The changes a review should look at.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReviewTarget {
+    /// Staged, unstaged and untracked changes in the working tree.
+    UncommittedChanges,
+    /// The changes the current branch would merge into `branch`.
+    BaseBranch { branch: String },
+    /// The changes introduced by a single commit.
+    Commit {
+        sha: String,
+        /// Commit subject, shown to the reviewer for context.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        title: Option<String>,
+    },
+    /// The commits reachable from `head` but not from `base`, as in
+    /// `git log base..head`.
+    CommitRange { base: String, head: String },
+    /// A `.patch` or `.diff` file, resolved against the session's cwd.
+    PatchFile { path: PathBuf },
+}
+
+impl ReviewTarget {
+    /// Short description of the target shown when the review starts.
+    pub fn user_facing_hint(&self) -> String {
+        match self {
+            ReviewTarget::UncommittedChanges => "current changes".to_string(),
+            ReviewTarget::BaseBranch { branch } => format!("changes against '{branch}'"),
+            ReviewTarget::Commit { sha, .. } => {
+                format!("commit {}", sha.chars().take(7).collect::<String>())
+            }
+            ReviewTarget::CommitRange { base, head } => format!("commits {base}..{head}"),
+            ReviewTarget::PatchFile { path } => format!("patch {}", path.display()),
+        }
+    }
 }
 
 /// Structured review result produced by a child review session.
@@ -1236,6 +1467,38 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
     /// Formatted output from the command, as seen by the model.
     pub formatted_output: String,
+    /// Set when Codex terminated the command for exceeding one of the
+    /// configured `[sandbox_limits]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub termination_reason: Option<ExecTerminationReason>,
+    /// Destinations the sandbox's network allowlist rejected while the
+    /// command ran.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_network_destinations: Vec<String>,
+}
+
+/// Why a command was terminated before it exited on its own.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecTerminationReason {
+    CpuTimeLimit,
+    OutputLimit,
+    MemoryLimit,
+    /// The command failed after it tried to run more processes or threads
+    /// than allowed; it was not killed, but its `fork` or `clone` failed.
+    ProcessLimit,
+}
+
+impl fmt::Display for ExecTerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecTerminationReason::CpuTimeLimit => write!(f, "killed: cpu time limit"),
+            ExecTerminationReason::OutputLimit => write!(f, "killed: output limit"),
+            ExecTerminationReason::MemoryLimit => write!(f, "killed: memory limit"),
+            ExecTerminationReason::ProcessLimit => write!(f, "failed: process limit reached"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1338,6 +1601,19 @@ pub struct GetHistoryEntryResponseEvent {
     /// The entry at the requested offset, if available and parseable.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub entry: Option<HistoryEntry>,
+    /// Set when `log_id` no longer identifies the history file because it was
+    /// replaced, e.g. by `codex sessions prune`. Offsets into the old file do
+    /// not apply to the new one; clients should switch to this log instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<HistoryLogMetadata>,
+}
+
+/// Identifier and entry count of the message history file, as reported in
+/// [`SessionConfiguredEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct HistoryLogMetadata {
+    pub log_id: u64,
+    pub entry_count: usize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1380,6 +1656,41 @@ pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
+/// Response payload for `Op::ListMcpPrompts`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpListPromptsResponseEvent {
+    /// Known prompts grouped by server name.
+    pub prompts: HashMap<String, Vec<McpPrompt>>,
+}
+
+/// Response payload for `Op::GetMcpPrompt`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct GetMcpPromptResponseEvent {
+    pub server: String,
+    pub name: String,
+    /// The rendered prompt, or an error message if the server could not
+    /// produce it.
+    pub result: Result<McpGetPromptResult, String>,
+}
+
+/// Sent when an MCP server reports a `notifications/*/list_changed`. For
+/// tools, the new list has already been fetched and is used from the next
+/// model request on.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpListChangedEvent {
+    pub server_name: String,
+    pub kind: McpListChangedKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum McpListChangedKind {
+    Tools,
+    Resources,
+    Prompts,
+}
+
 #[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct SessionConfiguredEvent {
     /// Name left as session_id instead of conversation_id for backwards compatibility.
@@ -1442,9 +1753,39 @@ pub enum FileChange {
     Update {
         unified_diff: String,
         move_path: Option<PathBuf>,
+        /// Permissions the file will have afterwards, when the patch changes
+        /// them. `unified_diff` is empty when only the mode changes.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        new_mode: Option<FileMode>,
+    },
+    /// A file created or replaced with binary contents, which are not shown.
+    Binary {
+        /// Size in bytes of the contents being replaced, or `None` when the
+        /// file is created.
+        old_size: Option<u64>,
+        new_size: u64,
     },
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum FileMode {
+    /// `100644`
+    Regular,
+    /// `100755`
+    Executable,
+}
+
+impl fmt::Display for FileMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileMode::Regular => f.write_str("100644"),
+            FileMode::Executable => f.write_str("100755"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct Chunk {
     /// 1-based line index of the first line in the original file
@@ -1475,6 +1816,32 @@ mod tests {
     use serde_json::json;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn read_only_policy_round_trips_read_restrictions() -> Result<()> {
+        let unrestricted: SandboxPolicy = serde_json::from_value(json!({"mode": "read-only"}))?;
+        assert_eq!(unrestricted, SandboxPolicy::new_read_only_policy());
+        assert!(unrestricted.has_full_disk_read_access());
+        assert_eq!(
+            serde_json::to_value(&unrestricted)?,
+            json!({"mode": "read-only"})
+        );
+
+        let restricted = SandboxPolicy::ReadOnly {
+            readable_roots: vec![PathBuf::from("/srv/data")],
+            deny_read: vec![PathBuf::from("/srv/data/secrets")],
+        };
+        assert!(!restricted.has_full_disk_read_access());
+        assert_eq!(
+            restricted.get_readable_roots_with_cwd(Path::new("/work")),
+            Some(vec![PathBuf::from("/srv/data"), PathBuf::from("/work")])
+        );
+        assert_eq!(
+            serde_json::from_value::<SandboxPolicy>(serde_json::to_value(&restricted)?)?,
+            restricted
+        );
+        Ok(())
+    }
+
     #[test]
     fn item_started_event_from_web_search_emits_begin_event() {
         let event = ItemStartedEvent {
@@ -7,11 +7,18 @@ use rmcp::ServiceExt;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::CallToolRequestParam;
 use rmcp::model::CallToolResult;
+use rmcp::model::GetPromptRequestParam;
+use rmcp::model::GetPromptResult;
 use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParam;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
 use rmcp::model::RawResource;
 use rmcp::model::RawResourceTemplate;
 use rmcp::model::ReadResourceRequestParam;
@@ -24,13 +31,15 @@ use rmcp::model::ServerInfo;
 use rmcp::model::Tool;
 use serde::Deserialize;
 use serde_json::json;
+use tokio::sync::RwLock;
 use tokio::task;
 
 #[derive(Clone)]
 struct TestToolServer {
-    tools: Arc<Vec<Tool>>,
+    tools: Arc<RwLock<Vec<Tool>>>,
     resources: Arc<Vec<Resource>>,
     resource_templates: Arc<Vec<ResourceTemplate>>,
+    prompts: Arc<Vec<Prompt>>,
 }
 
 const MEMO_URI: &str = "memo://codex/example-note";
@@ -40,13 +49,20 @@ pub fn stdio() -> (tokio::io::Stdin, tokio::io::Stdout) {
 }
 impl TestToolServer {
     fn new() -> Self {
-        let tools = vec![Self::echo_tool(), Self::image_tool()];
+        let tools = vec![
+            Self::echo_tool(),
+            Self::image_tool(),
+            Self::add_tool_tool(),
+            Self::roots_tool(),
+        ];
         let resources = vec![Self::memo_resource()];
         let resource_templates = vec![Self::memo_template()];
+        let prompts = vec![Self::summarize_prompt()];
         Self {
-            tools: Arc::new(tools),
+            tools: Arc::new(RwLock::new(tools)),
             resources: Arc::new(resources),
             resource_templates: Arc::new(resource_templates),
+            prompts: Arc::new(prompts),
         }
     }
 
@@ -86,6 +102,56 @@ impl TestToolServer {
         )
     }
 
+    fn add_tool_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("add_tool tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("add_tool"),
+            Cow::Borrowed(
+                "Register the `extra` tool and notify the client that the tool list changed.",
+            ),
+            Arc::new(schema),
+        )
+    }
+
+    fn roots_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("roots tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("roots"),
+            Cow::Borrowed("Return the roots reported by the client's `roots/list`."),
+            Arc::new(schema),
+        )
+    }
+
+    fn extra_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("extra tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("extra"),
+            Cow::Borrowed("A tool that only exists after `add_tool` was called."),
+            Arc::new(schema),
+        )
+    }
+
     fn memo_resource() -> Resource {
         let raw = RawResource {
             uri: MEMO_URI.to_string(),
@@ -112,6 +178,19 @@ impl TestToolServer {
         ResourceTemplate::new(raw, None)
     }
 
+    fn summarize_prompt() -> Prompt {
+        Prompt::new(
+            "summarize",
+            Some("Summarize a memo."),
+            Some(vec![PromptArgument {
+                name: "slug".to_string(),
+                title: None,
+                description: Some("Memo to summarize.".to_string()),
+                required: Some(true),
+            }]),
+        )
+    }
+
     fn memo_text() -> &'static str {
         MEMO_CONTENT
     }
@@ -131,6 +210,7 @@ impl ServerHandler for TestToolServer {
                 .enable_tools()
                 .enable_tool_list_changed()
                 .enable_resources()
+                .enable_prompts()
                 .build(),
             ..ServerInfo::default()
         }
@@ -144,7 +224,7 @@ impl ServerHandler for TestToolServer {
         let tools = self.tools.clone();
         async move {
             Ok(ListToolsResult {
-                tools: (*tools).clone(),
+                tools: tools.read().await.clone(),
                 next_cursor: None,
             })
         }
@@ -197,12 +277,85 @@ impl ServerHandler for TestToolServer {
         }
     }
 
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            prompts: (*self.prompts).clone(),
+            next_cursor: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        if name != "summarize" {
+            return Err(McpError::invalid_params(
+                format!("unknown prompt: {name}"),
+                None,
+            ));
+        }
+        let slug = arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("slug"))
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| McpError::invalid_params("missing slug argument", None))?;
+
+        Ok(GetPromptResult {
+            description: Some("Summarize a memo.".to_string()),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                format!("Summarize memo://codex/{slug} in one sentence."),
+            )],
+        })
+    }
+
     async fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         match request.name.as_ref() {
+            "add_tool" => {
+                {
+                    let mut tools = self.tools.write().await;
+                    if !tools.iter().any(|tool| tool.name == "extra") {
+                        tools.push(Self::extra_tool());
+                    }
+                }
+                context
+                    .peer
+                    .notify_tool_list_changed()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    "added the extra tool",
+                )]))
+            }
+            "roots" => {
+                let result = context
+                    .peer
+                    .list_roots()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let roots = serde_json::to_value(result.roots)
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+
+                Ok(CallToolResult {
+                    content: Vec::new(),
+                    structured_content: Some(json!({ "roots": roots })),
+                    is_error: Some(false),
+                    meta: None,
+                })
+            }
+            "extra" => Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                "extra tool called",
+            )])),
             "echo" => {
                 let args: EchoArgs = match request.arguments {
                     Some(arguments) => serde_json::from_value(serde_json::Value::Object(
@@ -16,4 +16,10 @@ pub use oauth::delete_oauth_tokens;
 pub(crate) use oauth::load_oauth_tokens;
 pub use oauth::save_oauth_tokens;
 pub use perform_oauth_login::perform_oauth_login;
+pub use rmcp_client::CancelElicitation;
+pub use rmcp_client::ClientCallbacks;
+pub use rmcp_client::ListRoots;
+pub use rmcp_client::OnListChanged;
 pub use rmcp_client::RmcpClient;
+pub use rmcp_client::SendCreateMessage;
+pub use rmcp_client::SendElicitation;
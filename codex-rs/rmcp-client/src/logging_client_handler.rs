@@ -1,47 +1,116 @@
+use codex_protocol::protocol::McpListChangedKind;
 use rmcp::ClientHandler;
 use rmcp::RoleClient;
 use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientInfo;
 use rmcp::model::CreateElicitationRequestParam;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestParam;
+use rmcp::model::CreateMessageResult;
 use rmcp::model::ElicitationAction;
+use rmcp::model::ListRootsResult;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::ProgressNotificationParam;
 use rmcp::model::ResourceUpdatedNotificationParam;
 use rmcp::service::NotificationContext;
 use rmcp::service::RequestContext;
+use std::fmt;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-#[derive(Debug, Clone)]
+use crate::rmcp_client::ClientCallbacks;
+use crate::utils::convert_to_mcp;
+use crate::utils::convert_to_rmcp;
+
+#[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
+    callbacks: ClientCallbacks,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo) -> Self {
-        Self { client_info }
+    pub(crate) fn new(client_info: ClientInfo, callbacks: ClientCallbacks) -> Self {
+        Self {
+            client_info,
+            callbacks,
+        }
+    }
+}
+
+impl fmt::Debug for LoggingClientHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoggingClientHandler")
+            .field("client_info", &self.client_info)
+            .finish_non_exhaustive()
     }
 }
 
 impl ClientHandler for LoggingClientHandler {
-    // TODO (CODEX-3571): support elicitations.
     async fn create_elicitation(
         &self,
         request: CreateElicitationRequestParam,
-        _context: RequestContext<RoleClient>,
+        context: RequestContext<RoleClient>,
     ) -> Result<CreateElicitationResult, rmcp::ErrorData> {
-        info!(
-            "MCP server requested elicitation ({}). Elicitations are not supported yet. Declining.",
-            request.message
-        );
-        Ok(CreateElicitationResult {
-            action: ElicitationAction::Decline,
-            content: None,
-        })
+        info!("MCP server requested elicitation ({})", request.message);
+        let (id, params) = match (convert_to_mcp(context.id), convert_to_mcp(request)) {
+            (Ok(id), Ok(params)) => (id, params),
+            (Err(err), _) | (_, Err(err)) => {
+                return Err(rmcp::ErrorData::invalid_params(err.to_string(), None));
+            }
+        };
+
+        let response = tokio::select! {
+            response = (self.callbacks.send_elicitation)(id.clone(), params) => response,
+            () = context.ct.cancelled() => {
+                info!("MCP server cancelled its elicitation request");
+                (self.callbacks.cancel_elicitation)(id);
+                return Ok(CreateElicitationResult {
+                    action: ElicitationAction::Cancel,
+                    content: None,
+                });
+            }
+        };
+        response
+            .and_then(convert_to_rmcp)
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+        context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, rmcp::ErrorData> {
+        info!("MCP server requested sampling");
+        let (id, params) = match (convert_to_mcp(context.id), convert_to_mcp(params)) {
+            (Ok(id), Ok(params)) => (id, params),
+            (Err(err), _) | (_, Err(err)) => {
+                return Err(rmcp::ErrorData::invalid_params(err.to_string(), None));
+            }
+        };
+
+        let response = tokio::select! {
+            response = (self.callbacks.send_create_message)(id, params) => response,
+            () = context.ct.cancelled() => {
+                info!("MCP server cancelled its sampling request");
+                return Err(rmcp::ErrorData::internal_error("request cancelled", None));
+            }
+        };
+        response
+            .and_then(convert_to_rmcp)
+            .map_err(|err| rmcp::ErrorData::internal_error(format!("{err:#}"), None))
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<ListRootsResult, rmcp::ErrorData> {
+        let roots = mcp_types::ListRootsResult {
+            roots: (self.callbacks.list_roots)(),
+        };
+        convert_to_rmcp(roots).map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
     }
 
     async fn on_cancelled(
@@ -76,14 +145,17 @@ impl ClientHandler for LoggingClientHandler {
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server resource list changed");
+        (self.callbacks.on_list_changed)(McpListChangedKind::Resources);
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        (self.callbacks.on_list_changed)(McpListChangedKind::Tools);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server prompt list changed");
+        (self.callbacks.on_list_changed)(McpListChangedKind::Prompts);
     }
 
     fn get_info(&self) -> ClientInfo {
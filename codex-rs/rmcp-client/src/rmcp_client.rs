@@ -8,11 +8,21 @@ use std::time::Duration;
 
 use anyhow::Result;
 use anyhow::anyhow;
+use codex_protocol::protocol::McpListChangedKind;
 use futures::FutureExt;
+use futures::future::BoxFuture;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::ElicitRequestParams;
+use mcp_types::ElicitResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::InitializeRequestParams;
 use mcp_types::InitializeResult;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListPromptsResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
@@ -21,8 +31,11 @@ use mcp_types::ListToolsRequestParams;
 use mcp_types::ListToolsResult;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
+use mcp_types::RequestId;
+use mcp_types::Root;
 use reqwest::header::HeaderMap;
 use rmcp::model::CallToolRequestParam;
+use rmcp::model::GetPromptRequestParam;
 use rmcp::model::InitializeRequestParam;
 use rmcp::model::PaginatedRequestParam;
 use rmcp::model::ReadResourceRequestParam;
@@ -55,6 +68,68 @@ use crate::utils::convert_to_rmcp;
 use crate::utils::create_env_for_mcp_server;
 use crate::utils::run_with_timeout;
 
+/// Answers an `elicitation/create` request from the server, typically by
+/// asking the user. The future resolves once the request has been answered.
+pub type SendElicitation = Arc<
+    dyn Fn(RequestId, ElicitRequestParams) -> BoxFuture<'static, Result<ElicitResult>>
+        + Send
+        + Sync,
+>;
+
+/// Answers a `sampling/createMessage` request from the server. An error is
+/// reported back to the server as the request's error.
+pub type SendCreateMessage = Arc<
+    dyn Fn(RequestId, CreateMessageRequestParams) -> BoxFuture<'static, Result<CreateMessageResult>>
+        + Send
+        + Sync,
+>;
+
+/// Called when the server cancels an elicitation request before it was
+/// answered. Runs on the connection's event loop, so it must not block.
+pub type CancelElicitation = Arc<dyn Fn(RequestId) + Send + Sync>;
+
+/// Called when the server sends a `notifications/*/list_changed`
+/// notification. Runs on the connection's event loop, so it must not block.
+pub type OnListChanged = Arc<dyn Fn(McpListChangedKind) + Send + Sync>;
+
+/// Answers a `roots/list` request from the server with the roots it may
+/// operate on. Runs on the connection's event loop, so it must not block.
+pub type ListRoots = Arc<dyn Fn() -> Vec<Root> + Send + Sync>;
+
+/// Answers the requests and notifications the server sends for the lifetime
+/// of a connection. The default declines elicitations, refuses sampling,
+/// ignores list changes and advertises no roots.
+#[derive(Clone)]
+pub struct ClientCallbacks {
+    pub send_elicitation: SendElicitation,
+    pub cancel_elicitation: CancelElicitation,
+    pub send_create_message: SendCreateMessage,
+    pub on_list_changed: OnListChanged,
+    pub list_roots: ListRoots,
+}
+
+impl Default for ClientCallbacks {
+    fn default() -> Self {
+        Self {
+            send_elicitation: Arc::new(|_, _| {
+                async {
+                    Ok(ElicitResult {
+                        action: "decline".to_string(),
+                        content: None,
+                    })
+                }
+                .boxed()
+            }),
+            cancel_elicitation: Arc::new(|_| {}),
+            send_create_message: Arc::new(|_, _| {
+                async { Err(anyhow!("sampling is not supported")) }.boxed()
+            }),
+            on_list_changed: Arc::new(|_| {}),
+            list_roots: Arc::new(Vec::new),
+        }
+    }
+}
+
 enum PendingTransport {
     ChildProcess(TokioChildProcess),
     StreamableHttp {
@@ -188,13 +263,17 @@ impl RmcpClient {
 
     /// Perform the initialization handshake with the MCP server.
     /// https://modelcontextprotocol.io/specification/2025-06-18/basic/lifecycle#initialization
+    ///
+    /// `callbacks` answer the requests the server makes for the lifetime of
+    /// the connection.
     pub async fn initialize(
         &self,
         params: InitializeRequestParams,
         timeout: Option<Duration>,
+        callbacks: ClientCallbacks,
     ) -> Result<InitializeResult> {
         let rmcp_params: InitializeRequestParam = convert_to_rmcp(params.clone())?;
-        let client_handler = LoggingClientHandler::new(rmcp_params);
+        let client_handler = LoggingClientHandler::new(rmcp_params, callbacks);
 
         let (transport, oauth_persistor) = {
             let mut guard = self.state.lock().await;
@@ -319,6 +398,37 @@ impl RmcpClient {
         Ok(converted)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<ListPromptsRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        let service = self.service().await?;
+        let rmcp_params = params
+            .map(convert_to_rmcp::<_, PaginatedRequestParam>)
+            .transpose()?;
+
+        let fut = service.list_prompts(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/list").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        let service = self.service().await?;
+        let rmcp_params: GetPromptRequestParam = convert_to_rmcp(params)?;
+        let fut = service.get_prompt(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/get").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
@@ -335,6 +445,13 @@ impl RmcpClient {
         Ok(converted)
     }
 
+    /// Tell the server that the result of `roots/list` has changed.
+    pub async fn notify_roots_list_changed(&self) -> Result<()> {
+        let service = self.service().await?;
+        service.notify_roots_list_changed().await?;
+        Ok(())
+    }
+
     async fn service(&self) -> Result<Arc<RunningService<RoleClient, LoggingClientHandler>>> {
         let guard = self.state.lock().await;
         match &*guard {
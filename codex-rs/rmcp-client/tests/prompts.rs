@@ -0,0 +1,103 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ClientCallbacks;
+use codex_rmcp_client::RmcpClient;
+use escargot::CargoBuild;
+use mcp_types::ClientCapabilities;
+use mcp_types::ContentBlock;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::Implementation;
+use mcp_types::InitializeRequestParams;
+use mcp_types::Prompt;
+use mcp_types::PromptArgument;
+use mcp_types::Role;
+use mcp_types::TextContent;
+use serde_json::json;
+
+fn stdio_server_bin() -> anyhow::Result<PathBuf> {
+    let build = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?;
+    Ok(build.path().to_path_buf())
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: None,
+            sampling: None,
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp prompt test".into()),
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_string(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_can_list_and_get_prompts() -> anyhow::Result<()> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            ClientCallbacks::default(),
+        )
+        .await?;
+
+    let list = client
+        .list_prompts(None, Some(Duration::from_secs(5)))
+        .await?;
+    assert_eq!(
+        list.prompts,
+        vec![Prompt {
+            arguments: Some(vec![PromptArgument {
+                description: Some("Memo to summarize.".to_string()),
+                name: "slug".to_string(),
+                required: Some(true),
+                title: None,
+            }]),
+            description: Some("Summarize a memo.".to_string()),
+            name: "summarize".to_string(),
+            title: None,
+        }]
+    );
+
+    let prompt = client
+        .get_prompt(
+            GetPromptRequestParams {
+                arguments: Some(json!({ "slug": "example-note" })),
+                name: "summarize".to_string(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    assert_eq!(prompt.messages.len(), 1);
+    assert_eq!(prompt.messages[0].role, Role::User);
+    assert_eq!(
+        prompt.messages[0].content,
+        ContentBlock::TextContent(TextContent {
+            annotations: None,
+            text: "Summarize memo://codex/example-note in one sentence.".to_string(),
+            r#type: "text".to_string(),
+        })
+    );
+
+    Ok(())
+}
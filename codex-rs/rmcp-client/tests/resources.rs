@@ -2,6 +2,7 @@ use std::ffi::OsString;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use codex_rmcp_client::ClientCallbacks;
 use codex_rmcp_client::RmcpClient;
 use escargot::CargoBuild;
 use mcp_types::ClientCapabilities;
@@ -55,7 +56,11 @@ async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
     .await?;
 
     client
-        .initialize(init_params(), Some(Duration::from_secs(5)))
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            ClientCallbacks::default(),
+        )
         .await?;
 
     let list = client
@@ -0,0 +1,102 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use codex_rmcp_client::ClientCallbacks;
+use codex_rmcp_client::ListRoots;
+use codex_rmcp_client::RmcpClient;
+use escargot::CargoBuild;
+use mcp_types::ClientCapabilities;
+use mcp_types::ClientCapabilitiesRoots;
+use mcp_types::Implementation;
+use mcp_types::InitializeRequestParams;
+use mcp_types::Root;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn stdio_server_bin() -> anyhow::Result<PathBuf> {
+    let build = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?;
+    Ok(build.path().to_path_buf())
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: Some(ClientCapabilitiesRoots {
+                list_changed: Some(true),
+            }),
+            sampling: None,
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp roots test".into()),
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_string(),
+    }
+}
+
+fn root(uri: &str) -> Root {
+    Root {
+        name: None,
+        uri: uri.to_string(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_answers_roots_list() -> anyhow::Result<()> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+
+    let roots = Arc::new(Mutex::new(vec![root("file:///workspace/one")]));
+    let list_roots: ListRoots = {
+        let roots = Arc::clone(&roots);
+        Arc::new(move || roots.lock().unwrap().clone())
+    };
+
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            ClientCallbacks {
+                list_roots,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let result = client
+        .call_tool("roots".to_string(), None, Some(Duration::from_secs(5)))
+        .await?;
+    assert_eq!(
+        result.structured_content,
+        Some(json!({ "roots": [{ "uri": "file:///workspace/one" }] }))
+    );
+
+    *roots.lock().unwrap() = vec![root("file:///workspace/two")];
+    client.notify_roots_list_changed().await?;
+
+    let result = client
+        .call_tool("roots".to_string(), None, Some(Duration::from_secs(5)))
+        .await?;
+    assert_eq!(
+        result.structured_content,
+        Some(json!({ "roots": [{ "uri": "file:///workspace/two" }] }))
+    );
+
+    Ok(())
+}
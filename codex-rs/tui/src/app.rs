@@ -2,6 +2,7 @@ use crate::app_backtrack::BacktrackState;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::ApprovalRequest;
+use crate::bottom_pane::sampling_message_lines;
 use crate::chatwidget::ChatWidget;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
@@ -181,7 +182,7 @@ impl App {
                 && matches!(
                     app.config.sandbox_policy,
                     codex_core::protocol::SandboxPolicy::WorkspaceWrite { .. }
-                        | codex_core::protocol::SandboxPolicy::ReadOnly
+                        | codex_core::protocol::SandboxPolicy::ReadOnly { .. }
                 )
                 && !app
                     .config
@@ -462,7 +463,7 @@ impl App {
                 let policy_is_workspace_write_or_ro = matches!(
                     policy,
                     codex_core::protocol::SandboxPolicy::WorkspaceWrite { .. }
-                        | codex_core::protocol::SandboxPolicy::ReadOnly
+                        | codex_core::protocol::SandboxPolicy::ReadOnly { .. }
                 );
 
                 self.chat_widget.set_sandbox_policy(policy);
@@ -556,6 +557,15 @@ impl App {
             AppEvent::OpenReviewCommitPicker(cwd) => {
                 self.chat_widget.show_review_commit_picker(&cwd).await;
             }
+            AppEvent::OpenMemoryPicker => {
+                self.chat_widget.show_memory_picker().await;
+            }
+            AppEvent::ConfirmDeleteMemory(entry) => {
+                self.chat_widget.show_memory_delete_confirmation(entry);
+            }
+            AppEvent::DeleteMemory(id) => {
+                self.chat_widget.delete_memory(id).await;
+            }
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
@@ -577,6 +587,13 @@ impl App {
                         "E X E C".to_string(),
                     ));
                 }
+                ApprovalRequest::Sampling(ev) => {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(Overlay::new_static_with_lines(
+                        sampling_message_lines(&ev.messages),
+                        "S A M P L I N G".to_string(),
+                    ));
+                }
             },
         }
         Ok(true)
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use codex_common::approval_presets::ApprovalPreset;
 use codex_common::model_presets::ModelPreset;
+use codex_core::memory::MemoryEntry;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_file_search::FileMatch;
@@ -136,6 +137,15 @@ pub(crate) enum AppEvent {
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// Open the `/memory` picker listing the project's saved memories.
+    OpenMemoryPicker,
+
+    /// Ask the user to confirm deleting a memory picked from `/memory`.
+    ConfirmDeleteMemory(MemoryEntry),
+
+    /// Delete the saved memory with the given id.
+    DeleteMemory(String),
+
     /// Open the approval popup.
     FullScreenApprovalRequest(ApprovalRequest),
 
@@ -19,12 +19,16 @@ use crate::render::renderable::Renderable;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SamplingRequestEvent;
 use codex_core::protocol::SandboxCommandAssessment;
 use codex_core::protocol::SandboxRiskLevel;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
+use mcp_types::Role;
+use mcp_types::SamplingMessage;
+use mcp_types::SamplingMessageContent;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -48,6 +52,7 @@ pub(crate) enum ApprovalRequest {
         cwd: PathBuf,
         changes: HashMap<PathBuf, FileChange>,
     },
+    Sampling(SamplingRequestEvent),
 }
 
 /// Modal overlay asking the user to approve or deny one or more requests.
@@ -105,6 +110,10 @@ impl ApprovalOverlay {
                 patch_options(),
                 "Would you like to make the following edits?".to_string(),
             ),
+            ApprovalVariant::Sampling { server_name, .. } => (
+                sampling_options(),
+                format!("Allow MCP server {server_name} to sample the model?"),
+            ),
         };
 
         let header = Box::new(ColumnRenderable::with([
@@ -156,6 +165,15 @@ impl ApprovalOverlay {
                 (ApprovalVariant::ApplyPatch { id, .. }, decision) => {
                     self.handle_patch_decision(id, decision);
                 }
+                (
+                    ApprovalVariant::Sampling {
+                        server_name,
+                        request_id,
+                    },
+                    decision,
+                ) => {
+                    self.handle_sampling_decision(server_name, request_id, decision);
+                }
             }
         }
 
@@ -179,6 +197,20 @@ impl ApprovalOverlay {
         }));
     }
 
+    fn handle_sampling_decision(
+        &self,
+        server_name: &str,
+        request_id: &mcp_types::RequestId,
+        decision: ReviewDecision,
+    ) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::ResolveSamplingRequest {
+                server_name: server_name.to_string(),
+                request_id: request_id.clone(),
+                decision,
+            }));
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -244,6 +276,12 @@ impl BottomPaneView for ApprovalOverlay {
                 ApprovalVariant::ApplyPatch { id, .. } => {
                     self.handle_patch_decision(id, ReviewDecision::Abort);
                 }
+                ApprovalVariant::Sampling {
+                    server_name,
+                    request_id,
+                } => {
+                    self.handle_sampling_decision(server_name, request_id, ReviewDecision::Abort);
+                }
             }
         }
         self.queue.clear();
@@ -336,10 +374,64 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
+            ApprovalRequest::Sampling(ev) => {
+                let mut header: Vec<Line<'static>> = Vec::new();
+                if let Some(system_prompt) = ev.system_prompt.filter(|p| !p.is_empty()) {
+                    header.push(Line::from(vec![
+                        "System prompt: ".into(),
+                        system_prompt.italic(),
+                    ]));
+                }
+                // Only the latest message fits here; ctrl+a shows the full conversation.
+                if let Some(last) = ev.messages.last() {
+                    header.extend(sampling_message_lines(std::slice::from_ref(last)));
+                }
+                header.push(Line::from(""));
+                header.push(
+                    format!(
+                        "{} message(s), up to {} tokens; {} tokens left in budget",
+                        ev.messages.len(),
+                        ev.max_tokens,
+                        ev.remaining_tokens
+                    )
+                    .dim()
+                    .into(),
+                );
+                Self {
+                    variant: ApprovalVariant::Sampling {
+                        server_name: ev.server_name,
+                        request_id: ev.id,
+                    },
+                    header: Box::new(Paragraph::new(header).wrap(Wrap { trim: false })),
+                }
+            }
         }
     }
 }
 
+/// Renders the messages an MCP server wants to send to the model.
+pub(crate) fn sampling_message_lines(messages: &[SamplingMessage]) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for message in messages {
+        let role = match message.role {
+            Role::User => "user: ".cyan().bold(),
+            Role::Assistant => "assistant: ".magenta().bold(),
+        };
+        let body = match &message.content {
+            SamplingMessageContent::TextContent(text) => text.text.clone(),
+            SamplingMessageContent::ImageContent(image) => format!("<image {}>", image.mime_type),
+            SamplingMessageContent::AudioContent(audio) => format!("<audio {}>", audio.mime_type),
+        };
+        let mut body_lines = body.lines();
+        lines.push(Line::from(vec![
+            role,
+            body_lines.next().unwrap_or_default().to_string().into(),
+        ]));
+        lines.extend(body_lines.map(|line| Line::from(line.to_string())));
+    }
+    lines
+}
+
 fn render_risk_lines(risk: &SandboxCommandAssessment) -> Vec<Line<'static>> {
     let level_span = match risk.risk_level {
         SandboxRiskLevel::Low => "LOW".green().bold(),
@@ -364,8 +456,17 @@ fn render_risk_lines(risk: &SandboxCommandAssessment) -> Vec<Line<'static>> {
 
 #[derive(Clone)]
 enum ApprovalVariant {
-    Exec { id: String, command: Vec<String> },
-    ApplyPatch { id: String },
+    Exec {
+        id: String,
+        command: Vec<String>,
+    },
+    ApplyPatch {
+        id: String,
+    },
+    Sampling {
+        server_name: String,
+        request_id: mcp_types::RequestId,
+    },
 }
 
 #[derive(Clone)]
@@ -424,6 +525,29 @@ fn patch_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn sampling_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Yes, send it".to_string(),
+            decision: ReviewDecision::Approved,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "Yes, and don't ask again for this server".to_string(),
+            decision: ReviewDecision::ApprovedForSession,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+        },
+        ApprovalOption {
+            label: "No, decline the request".to_string(),
+            decision: ReviewDecision::Denied,
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -469,6 +593,45 @@ mod tests {
         assert!(saw_op, "expected approval decision to emit an op");
     }
 
+    #[test]
+    fn sampling_decision_resolves_request() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let request = ApprovalRequest::Sampling(SamplingRequestEvent {
+            server_name: "docs".to_string(),
+            id: mcp_types::RequestId::Integer(7),
+            system_prompt: None,
+            messages: vec![SamplingMessage {
+                role: Role::User,
+                content: SamplingMessageContent::TextContent(mcp_types::TextContent {
+                    annotations: None,
+                    text: "summarize".to_string(),
+                    r#type: "text".to_string(),
+                }),
+            }],
+            max_tokens: 100,
+            remaining_tokens: 1000,
+        });
+        let mut view = ApprovalOverlay::new(request, tx);
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ResolveSamplingRequest {
+                server_name,
+                request_id,
+                decision: d,
+            }) = ev
+            {
+                assert_eq!(server_name, "docs");
+                assert_eq!(request_id, mcp_types::RequestId::Integer(7));
+                decision = Some(d);
+            }
+        }
+        assert_eq!(decision, Some(ReviewDecision::Denied));
+        assert!(view.is_complete());
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -1,6 +1,7 @@
 use crate::bottom_pane::ApprovalRequest;
 use crate::render::renderable::Renderable;
 use crossterm::event::KeyEvent;
+use mcp_types::RequestId;
 
 use super::CancellationEvent;
 
@@ -26,6 +27,12 @@ pub(crate) trait BottomPaneView: Renderable {
         false
     }
 
+    /// Close the view if it is the form for the given elicitation request.
+    /// Return `true` if it was.
+    fn dismiss_elicitation(&mut self, _server_name: &str, _id: &RequestId) -> bool {
+        false
+    }
+
     /// Try to handle approval request; return the original value if not
     /// consumed.
     fn try_consume_approval_request(
@@ -29,8 +29,11 @@ use super::footer::toggle_shortcut_mode;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::McpPromptCommand;
+use crate::bottom_pane::prompt_args::McpPromptInvocation;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::parse_mcp_prompt_invocation;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
@@ -69,6 +72,8 @@ const LARGE_PASTE_CHAR_THRESHOLD: usize = 1000;
 pub enum InputResult {
     Submitted(String),
     Command(SlashCommand),
+    /// Fetch an MCP prompt and send it as the user's message.
+    McpPrompt(McpPromptInvocation),
     None,
 }
 
@@ -109,6 +114,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<i64>,
@@ -152,6 +158,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: Vec::new(),
             footer_mode: FooterMode::ShortcutSummary,
             footer_hint_override: None,
             context_window_percent: None,
@@ -200,6 +207,10 @@ impl ChatComposer {
         self.history.set_metadata(log_id, entry_count);
     }
 
+    pub(crate) fn on_history_log_replaced(&mut self, log_id: u64, entry_count: usize) {
+        self.history.on_log_replaced(log_id, entry_count);
+    }
+
     /// Integrate an asynchronous response to an on-demand history lookup. If
     /// the entry is present and the offset matches the current cursor we
     /// immediately populate the textarea.
@@ -475,6 +486,13 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let (text, cursor) = prompt.command_with_arg_placeholders();
+                                self.textarea.set_text(&text);
+                                cursor_target = Some(cursor);
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -528,6 +546,21 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                // Prompts without arguments are sent right away;
+                                // otherwise let the user fill in the arguments.
+                                if prompt.argument_names().is_empty() {
+                                    let invocation = prompt.invocation(Default::default());
+                                    self.textarea.set_text("");
+                                    return (InputResult::McpPrompt(invocation), true);
+                                }
+                                let (text, cursor) = prompt.command_with_arg_placeholders();
+                                self.textarea.set_text(&text);
+                                self.textarea.set_cursor(cursor);
+                            }
+                            return (InputResult::None, true);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -1011,7 +1044,11 @@ impl ChatComposer {
                                     .iter()
                                     .any(|prompt| prompt.name == prompt_name)
                             })
-                            .unwrap_or(false);
+                            .unwrap_or(false)
+                            || self
+                                .mcp_prompts
+                                .iter()
+                                .any(|prompt| prompt.command() == name);
                         if !is_builtin && !is_known_prompt {
                             let message = format!(
                                 r#"Unrecognized command '/{name}'. Type "/" for a list of supported commands."#
@@ -1039,6 +1076,22 @@ impl ChatComposer {
                 };
                 if let Some(expanded) = expanded_prompt {
                     text = expanded;
+                } else {
+                    match parse_mcp_prompt_invocation(&text, &self.mcp_prompts) {
+                        Ok(Some(invocation)) => {
+                            self.history.record_local_submission(&text);
+                            return (InputResult::McpPrompt(invocation), true);
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                                history_cell::new_error_event(err.user_message()),
+                            )));
+                            self.textarea.set_text(&original_input);
+                            self.textarea.set_cursor(original_input.len());
+                            return (InputResult::None, true);
+                        }
+                    }
                 }
                 if text.is_empty() && !has_attachments {
                     return (InputResult::None, true);
@@ -1454,6 +1507,7 @@ impl ChatComposer {
             _ => {
                 if is_editing_slash_command_name {
                     let mut command_popup = CommandPopup::new(self.custom_prompts.clone());
+                    command_popup.set_mcp_prompts(self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -1468,6 +1522,13 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(prompts);
+        }
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self) {
@@ -2997,6 +3058,54 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    #[test]
+    fn mcp_prompt_submission_returns_invocation() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.set_mcp_prompts(vec![McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: mcp_types::Prompt {
+                arguments: Some(vec![mcp_types::PromptArgument {
+                    description: None,
+                    name: "url".to_string(),
+                    required: Some(true),
+                    title: None,
+                }]),
+                description: None,
+                name: "summarize".to_string(),
+                title: None,
+            },
+        }]);
+
+        composer
+            .textarea
+            .set_text("/docs:summarize url=https://example.com");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            InputResult::McpPrompt(McpPromptInvocation {
+                server: "docs".to_string(),
+                name: "summarize".to_string(),
+                arguments: std::collections::HashMap::from([(
+                    "url".to_string(),
+                    "https://example.com".to_string()
+                )]),
+            }),
+            result
+        );
+        assert!(composer.textarea.is_empty());
+    }
+
     #[test]
     fn custom_prompt_submission_accepts_quoted_values() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
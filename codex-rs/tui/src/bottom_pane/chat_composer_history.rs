@@ -52,6 +52,18 @@ impl ChatComposerHistory {
         self.last_history_text = None;
     }
 
+    /// Switch to a history file that replaced the one from [`Self::set_metadata`],
+    /// e.g. after `codex sessions prune` rewrote it. Entries fetched from the
+    /// old file are dropped since their offsets no longer apply; submissions
+    /// from this session are kept.
+    pub fn on_log_replaced(&mut self, log_id: u64, entry_count: usize) {
+        self.history_log_id = Some(log_id);
+        self.history_entry_count = entry_count;
+        self.fetched_history.clear();
+        self.history_cursor = None;
+        self.last_history_text = None;
+    }
+
     /// Record a message submitted by the user in the current session so it can
     /// be recalled later.
     pub fn record_local_submission(&mut self, text: &str) {
@@ -278,6 +290,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn replaced_log_drops_fetched_entries_and_keeps_local_ones() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+
+        let mut history = ChatComposerHistory::new();
+        history.set_metadata(1, 3);
+        history.fetched_history.insert(2, "stale".into());
+        history.record_local_submission("mine");
+
+        history.on_log_replaced(2, 5);
+
+        assert_eq!(Some("mine".into()), history.navigate_up(&tx));
+        assert!(history.navigate_up(&tx).is_none());
+        let event = rx.try_recv().expect("expected a lookup in the new log");
+        let AppEvent::CodexOp(request) = event else {
+            panic!("unexpected event variant");
+        };
+        assert_eq!(
+            Op::GetHistoryEntryRequest {
+                log_id: 2,
+                offset: 4
+            },
+            request
+        );
+        // Responses for the old log are ignored.
+        assert_eq!(None, history.on_entry_response(1, 4, Some("old".into())));
+    }
+
     #[test]
     fn reset_navigation_resets_cursor() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -0,0 +1,474 @@
+use codex_core::protocol::ElicitationAction;
+use codex_core::protocol::ElicitationRequestEvent;
+use codex_core::protocol::Op;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use mcp_types::RequestId;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+use crate::wrapping::RtOptions;
+use crate::wrapping::word_wrap_lines;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+
+#[derive(Debug, PartialEq)]
+enum FieldKind {
+    Text,
+    Number,
+    Integer,
+    Boolean,
+    Choice(Vec<String>),
+}
+
+/// One top-level property of the requested schema.
+#[derive(Debug)]
+struct FormField {
+    name: String,
+    label: String,
+    description: Option<String>,
+    kind: FieldKind,
+    required: bool,
+    text: String,
+    checked: bool,
+    choice: usize,
+}
+
+impl FormField {
+    fn from_schema(name: &str, schema: &Value, required: bool) -> Self {
+        let kind = match schema.get("enum").and_then(Value::as_array) {
+            Some(values) => FieldKind::Choice(
+                values
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            None => match schema.get("type").and_then(Value::as_str) {
+                Some("number") => FieldKind::Number,
+                Some("integer") => FieldKind::Integer,
+                Some("boolean") => FieldKind::Boolean,
+                _ => FieldKind::Text,
+            },
+        };
+        let default = schema.get("default");
+        let text = match default {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => String::new(),
+        };
+        let choice = match (&kind, default.and_then(Value::as_str)) {
+            (FieldKind::Choice(values), Some(default)) => {
+                values.iter().position(|v| v == default).unwrap_or(0)
+            }
+            _ => 0,
+        };
+        Self {
+            name: name.to_string(),
+            label: schema
+                .get("title")
+                .and_then(Value::as_str)
+                .unwrap_or(name)
+                .to_string(),
+            description: schema
+                .get("description")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            kind,
+            required,
+            text,
+            checked: default.and_then(Value::as_bool).unwrap_or(false),
+            choice,
+        }
+    }
+
+    /// Value to send back, `None` when an optional field was left empty.
+    fn value(&self) -> Result<Option<Value>, String> {
+        let text = self.text.trim();
+        if matches!(
+            self.kind,
+            FieldKind::Text | FieldKind::Number | FieldKind::Integer
+        ) && text.is_empty()
+        {
+            return if self.required {
+                Err(format!("{} is required", self.label))
+            } else {
+                Ok(None)
+            };
+        }
+        let value = match &self.kind {
+            FieldKind::Text => Value::String(text.to_string()),
+            FieldKind::Number => text
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| format!("{} must be a number", self.label))?,
+            FieldKind::Integer => text
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| format!("{} must be a whole number", self.label))?,
+            FieldKind::Boolean => Value::Bool(self.checked),
+            FieldKind::Choice(values) => match values.get(self.choice) {
+                Some(value) => Value::String(value.clone()),
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(value))
+    }
+
+    fn display_value(&self) -> String {
+        match &self.kind {
+            FieldKind::Text | FieldKind::Number | FieldKind::Integer => self.text.clone(),
+            FieldKind::Boolean => if self.checked { "[x]" } else { "[ ]" }.to_string(),
+            FieldKind::Choice(values) => values
+                .get(self.choice)
+                .map(|value| format!("‹ {value} ›"))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn handle_key(&mut self, key_event: KeyEvent) {
+        match (&self.kind, key_event.code) {
+            (FieldKind::Boolean, KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right) => {
+                self.checked = !self.checked;
+            }
+            (FieldKind::Choice(values), KeyCode::Right | KeyCode::Char(' ')) => {
+                if !values.is_empty() {
+                    self.choice = (self.choice + 1) % values.len();
+                }
+            }
+            (FieldKind::Choice(values), KeyCode::Left) => {
+                if !values.is_empty() {
+                    self.choice = (self.choice + values.len() - 1) % values.len();
+                }
+            }
+            (FieldKind::Text | FieldKind::Number | FieldKind::Integer, KeyCode::Backspace) => {
+                self.text.pop();
+            }
+            (FieldKind::Text | FieldKind::Number | FieldKind::Integer, KeyCode::Char(c))
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.text.push(c);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Form built from the schema of an MCP elicitation request.
+pub(crate) struct ElicitationView {
+    server_name: String,
+    request_id: RequestId,
+    message: String,
+    fields: Vec<FormField>,
+    selected: usize,
+    error: Option<String>,
+    app_event_tx: AppEventSender,
+    complete: bool,
+}
+
+impl ElicitationView {
+    pub(crate) fn new(request: ElicitationRequestEvent, app_event_tx: AppEventSender) -> Self {
+        let ElicitationRequestEvent {
+            server_name,
+            id,
+            message,
+            requested_schema,
+        } = request;
+        let required = requested_schema.required.unwrap_or_default();
+        let fields = requested_schema
+            .properties
+            .as_object()
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, schema)| {
+                        FormField::from_schema(name, schema, required.contains(name))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            server_name,
+            request_id: id,
+            message,
+            fields,
+            selected: 0,
+            error: None,
+            app_event_tx,
+            complete: false,
+        }
+    }
+
+    fn submit(&mut self) {
+        let mut content = Map::new();
+        for field in &self.fields {
+            match field.value() {
+                Ok(Some(value)) => {
+                    content.insert(field.name.clone(), value);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    self.error = Some(err);
+                    return;
+                }
+            }
+        }
+        self.resolve(ElicitationAction::Accept, Some(Value::Object(content)));
+    }
+
+    fn resolve(&mut self, decision: ElicitationAction, content: Option<Value>) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::ResolveElicitation {
+                server_name: self.server_name.clone(),
+                request_id: self.request_id.clone(),
+                decision,
+                content,
+            }));
+        self.complete = true;
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let len = self.fields.len();
+        if len == 0 {
+            return;
+        }
+        self.selected = if forward {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + len - 1) % len
+        };
+    }
+
+    fn as_renderable(&self, width: u16) -> Box<dyn Renderable> {
+        let width = width.max(4) as usize;
+        let mut lines: Vec<Line<'static>> = vec![Line::from(vec![
+            "MCP server ".into(),
+            self.server_name.clone().bold(),
+            " is asking for input".into(),
+        ])];
+        lines.extend(word_wrap_lines(
+            self.message
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+            RtOptions::new(width)
+                .initial_indent(Line::from("  "))
+                .subsequent_indent(Line::from("  ")),
+        ));
+        lines.push(Line::from(""));
+
+        for (idx, field) in self.fields.iter().enumerate() {
+            let selected = idx == self.selected;
+            let marker: Span<'static> = if selected { "› ".cyan() } else { "  ".into() };
+            let mut label: Span<'static> = field.label.clone().into();
+            if selected {
+                label = label.cyan().bold();
+            }
+            let mut spans = vec![marker, label];
+            if field.required {
+                spans.push("*".red());
+            }
+            spans.push(": ".into());
+            spans.push(field.display_value().into());
+            lines.push(Line::from(spans));
+            if selected && let Some(description) = &field.description {
+                lines.extend(word_wrap_lines(
+                    description
+                        .lines()
+                        .map(|line| Line::from(line.to_string()).dim()),
+                    RtOptions::new(width)
+                        .initial_indent(Line::from("    "))
+                        .subsequent_indent(Line::from("    ")),
+                ));
+            }
+        }
+
+        if let Some(error) = &self.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(error.clone().red()));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from(vec![
+                key_hint::plain(KeyCode::Tab).into(),
+                " next field  ".into(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " submit  ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " decline".into(),
+            ])
+            .dim(),
+        );
+
+        Paragraph::new(lines).into()
+    }
+}
+
+impl BottomPaneView for ElicitationView {
+    fn dismiss_elicitation(&mut self, server_name: &str, id: &RequestId) -> bool {
+        let matches = self.server_name == server_name && &self.request_id == id;
+        if matches {
+            self.complete = true;
+        }
+        matches
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc => self.resolve(ElicitationAction::Decline, None),
+            KeyCode::Enter => self.submit(),
+            KeyCode::Tab | KeyCode::Down => self.move_selection(true),
+            KeyCode::BackTab | KeyCode::Up => self.move_selection(false),
+            _ => {
+                if let Some(field) = self.fields.get_mut(self.selected) {
+                    field.handle_key(key_event);
+                    self.error = None;
+                }
+            }
+        }
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.resolve(ElicitationAction::Cancel, None);
+        CancellationEvent::Handled
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        match self.fields.get_mut(self.selected) {
+            Some(field)
+                if matches!(
+                    field.kind,
+                    FieldKind::Text | FieldKind::Number | FieldKind::Integer
+                ) =>
+            {
+                field.text.push_str(&pasted);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Renderable for ElicitationView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        self.as_renderable(area.width).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.as_renderable(width).desired_height(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::ElicitRequestParamsRequestedSchema;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn request() -> ElicitationRequestEvent {
+        ElicitationRequestEvent {
+            server_name: "deploy".to_string(),
+            id: RequestId::Integer(1),
+            message: "Where should we deploy?".to_string(),
+            requested_schema: ElicitRequestParamsRequestedSchema {
+                r#type: "object".to_string(),
+                properties: json!({
+                    "environment": { "type": "string", "enum": ["staging", "production"] },
+                    "replicas": { "type": "integer" },
+                }),
+                required: Some(vec!["replicas".to_string()]),
+            },
+        }
+    }
+
+    fn type_text(view: &mut ElicitationView, text: &str) {
+        for c in text.chars() {
+            view.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn submit_sends_form_content() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = ElicitationView::new(request(), AppEventSender::new(tx));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        type_text(&mut view, "3");
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(view.is_complete());
+        let Ok(AppEvent::CodexOp(Op::ResolveElicitation {
+            decision, content, ..
+        })) = rx.try_recv()
+        else {
+            panic!("expected ResolveElicitation op");
+        };
+        assert_eq!(decision, ElicitationAction::Accept);
+        assert_eq!(
+            content,
+            Some(json!({ "environment": "production", "replicas": 3 }))
+        );
+    }
+
+    #[test]
+    fn withdrawn_request_closes_the_form_without_answering() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = ElicitationView::new(request(), AppEventSender::new(tx));
+
+        assert!(!view.dismiss_elicitation("deploy", &RequestId::Integer(2)));
+        assert!(!view.dismiss_elicitation("other", &RequestId::Integer(1)));
+        assert!(!view.is_complete());
+
+        assert!(view.dismiss_elicitation("deploy", &RequestId::Integer(1)));
+        assert!(view.is_complete());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn missing_required_field_keeps_the_form_open() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = ElicitationView::new(request(), AppEventSender::new(tx));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!view.is_complete());
+        assert_eq!(view.error.as_deref(), Some("replicas is required"));
+        assert!(rx.try_recv().is_err());
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(view.is_complete());
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::CodexOp(Op::ResolveElicitation {
+                decision: ElicitationAction::Decline,
+                content: None,
+                ..
+            }))
+        ));
+    }
+}
@@ -11,6 +11,7 @@ use bottom_pane_view::BottomPaneView;
 use codex_file_search::FileMatch;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use mcp_types::RequestId;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use std::time::Duration;
@@ -18,16 +19,20 @@ use std::time::Duration;
 mod approval_overlay;
 pub(crate) use approval_overlay::ApprovalOverlay;
 pub(crate) use approval_overlay::ApprovalRequest;
+pub(crate) use approval_overlay::sampling_message_lines;
 mod bottom_pane_view;
 mod chat_composer;
 mod chat_composer_history;
 mod command_popup;
 pub mod custom_prompt_view;
+mod elicitation_view;
+pub(crate) use elicitation_view::ElicitationView;
 mod file_search_popup;
 mod footer;
 mod list_selection_view;
 mod prompt_args;
 pub(crate) use list_selection_view::SelectionViewParams;
+pub(crate) use prompt_args::McpPromptCommand;
 mod feedback_view;
 pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
@@ -351,6 +356,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Update MCP server prompts available for the slash popup.
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.composer.set_mcp_prompts(prompts);
+        self.request_redraw();
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
@@ -370,6 +381,22 @@ impl BottomPane {
         self.push_view(view);
     }
 
+    /// Closes the form for an elicitation request the server has withdrawn.
+    pub(crate) fn dismiss_elicitation(&mut self, server_name: &str, id: &RequestId) {
+        let Some(idx) = self
+            .view_stack
+            .iter_mut()
+            .position(|view| view.dismiss_elicitation(server_name, id))
+        else {
+            return;
+        };
+        self.view_stack.remove(idx);
+        if self.view_stack.is_empty() {
+            self.on_active_view_complete();
+        }
+        self.request_redraw();
+    }
+
     /// Called when the agent requests user approval.
     pub fn push_approval_request(&mut self, request: ApprovalRequest) {
         let request = if let Some(view) = self.view_stack.last_mut() {
@@ -429,6 +456,11 @@ impl BottomPane {
         self.composer.is_in_paste_burst()
     }
 
+    /// Switch to a history file that replaced the one reported at startup.
+    pub(crate) fn on_history_log_replaced(&mut self, log_id: u64, entry_count: usize) {
+        self.composer.on_history_log_replaced(log_id, entry_count);
+    }
+
     pub(crate) fn on_history_entry_response(
         &mut self,
         log_id: u64,
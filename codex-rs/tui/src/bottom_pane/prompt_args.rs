@@ -1,6 +1,7 @@
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use lazy_static::lazy_static;
+use mcp_types::Prompt as McpPrompt;
 use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
@@ -296,10 +297,101 @@ pub fn expand_numeric_placeholders(content: &str, args: &[String]) -> String {
     out
 }
 
+/// An MCP prompt offered as the `/server:prompt` slash command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct McpPromptCommand {
+    pub server: String,
+    pub prompt: McpPrompt,
+}
+
+impl McpPromptCommand {
+    /// Command token after '/', e.g. `docs:summarize`.
+    pub fn command(&self) -> String {
+        format!("{}:{}", self.server, self.prompt.name)
+    }
+
+    pub fn argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .map(|argument| argument.name.clone())
+            .collect()
+    }
+
+    fn required_argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .filter(|argument| argument.required.unwrap_or(false))
+            .map(|argument| argument.name.clone())
+            .collect()
+    }
+
+    pub fn invocation(&self, arguments: HashMap<String, String>) -> McpPromptInvocation {
+        McpPromptInvocation {
+            server: self.server.clone(),
+            name: self.prompt.name.clone(),
+            arguments,
+        }
+    }
+
+    /// Constructs the command text with an empty `key=""` pair for every
+    /// argument. Returns the text and the cursor position.
+    pub fn command_with_arg_placeholders(&self) -> (String, usize) {
+        command_with_arg_placeholders(&self.command(), &self.argument_names())
+    }
+}
+
+/// A request to fetch an MCP prompt with the arguments the user entered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct McpPromptInvocation {
+    pub server: String,
+    pub name: String,
+    pub arguments: HashMap<String, String>,
+}
+
+/// Parses a message of the form `/server:prompt key=value …` for a known MCP prompt.
+///
+/// Returns `Ok(None)` when the text does not name one of `mcp_prompts`, and an
+/// error when the arguments cannot be parsed or a required one is missing.
+pub fn parse_mcp_prompt_invocation(
+    text: &str,
+    mcp_prompts: &[McpPromptCommand],
+) -> Result<Option<McpPromptInvocation>, PromptExpansionError> {
+    let Some((name, rest)) = parse_slash_name(text) else {
+        return Ok(None);
+    };
+    let Some(prompt) = mcp_prompts.iter().find(|p| p.command() == name) else {
+        return Ok(None);
+    };
+    let inputs = parse_prompt_inputs(rest).map_err(|error| PromptExpansionError::Args {
+        command: format!("/{name}"),
+        error,
+    })?;
+    let missing: Vec<String> = prompt
+        .required_argument_names()
+        .into_iter()
+        .filter(|k| inputs.get(k).is_none_or(String::is_empty))
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: format!("/{name}"),
+            missing,
+        });
+    }
+    Ok(Some(prompt.invocation(inputs)))
+}
+
 /// Constructs a command text for a custom prompt with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
 pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
-    let mut text = format!("/{PROMPTS_CMD_PREFIX}:{name}");
+    command_with_arg_placeholders(&format!("{PROMPTS_CMD_PREFIX}:{name}"), args)
+}
+
+fn command_with_arg_placeholders(command: &str, args: &[String]) -> (String, usize) {
+    let mut text = format!("/{command}");
     let mut cursor: usize = text.len();
     for (i, arg) in args.iter().enumerate() {
         text.push_str(format!(" {arg}=\"\"").as_str());
@@ -403,4 +495,69 @@ mod tests {
         let out = expand_custom_prompt("/prompts:my-prompt", &prompts).unwrap();
         assert_eq!(out, Some("literal $$USER".to_string()));
     }
+
+    fn summarize_prompt() -> McpPromptCommand {
+        McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: McpPrompt {
+                arguments: Some(vec![
+                    mcp_types::PromptArgument {
+                        description: None,
+                        name: "url".to_string(),
+                        required: Some(true),
+                        title: None,
+                    },
+                    mcp_types::PromptArgument {
+                        description: None,
+                        name: "style".to_string(),
+                        required: None,
+                        title: None,
+                    },
+                ]),
+                description: None,
+                name: "summarize".to_string(),
+                title: None,
+            },
+        }
+    }
+
+    #[test]
+    fn parse_mcp_prompt_invocation_collects_arguments() {
+        let prompts = vec![summarize_prompt()];
+
+        let invocation =
+            parse_mcp_prompt_invocation("/docs:summarize url=\"https://a.b/c d\"", &prompts)
+                .unwrap()
+                .expect("invocation");
+        assert_eq!(
+            invocation,
+            McpPromptInvocation {
+                server: "docs".to_string(),
+                name: "summarize".to_string(),
+                arguments: HashMap::from([("url".to_string(), "https://a.b/c d".to_string())]),
+            }
+        );
+
+        assert!(
+            parse_mcp_prompt_invocation("/docs:other", &prompts)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parse_mcp_prompt_invocation_requires_required_arguments() {
+        let prompts = vec![summarize_prompt()];
+        let err = parse_mcp_prompt_invocation("/docs:summarize style=short", &prompts)
+            .unwrap_err()
+            .user_message();
+        assert!(err.contains("url"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn mcp_prompt_placeholders_cover_every_argument() {
+        let (text, cursor) = summarize_prompt().command_with_arg_placeholders();
+        assert_eq!(text, "/docs:summarize url=\"\" style=\"\"");
+        assert_eq!(cursor, "/docs:summarize url=\"".len());
+    }
 }
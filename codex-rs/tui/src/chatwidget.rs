@@ -5,8 +5,12 @@ use std::sync::Arc;
 
 use codex_core::config::Config;
 use codex_core::config::types::Notifications;
+use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
+use codex_core::memory::MemoryEntry;
+use codex_core::memory::MemoryStore;
+use codex_core::memory::format_memory_entry;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
@@ -17,6 +21,8 @@ use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
+use codex_core::protocol::ElicitationCancelledEvent;
+use codex_core::protocol::ElicitationRequestEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -24,7 +30,11 @@ use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExitedReviewModeEvent;
+use codex_core::protocol::GetMcpPromptResponseEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::McpListChangedEvent;
+use codex_core::protocol::McpListChangedKind;
+use codex_core::protocol::McpListPromptsResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
@@ -32,6 +42,8 @@ use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::ReviewRequest;
+use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SamplingRequestEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TokenUsage;
@@ -46,12 +58,17 @@ use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_protocol::ConversationId;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::user_input::UserInput;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
+use mcp_types::ContentBlock;
+use mcp_types::EmbeddedResourceResource;
+use mcp_types::GetPromptResult;
+use mcp_types::Role;
 use rand::Rng;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -69,7 +86,9 @@ use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::ElicitationView;
 use crate::bottom_pane::InputResult;
+use crate::bottom_pane::McpPromptCommand;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
@@ -134,6 +153,7 @@ struct RunningCommand {
 const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
 const NUDGE_MODEL_SLUG: &str = "gpt-5-codex-mini";
 const RATE_LIMIT_SWITCH_PROMPT_THRESHOLD: f64 = 90.0;
+const MEMORY_PICKER_MAX_CHARS: usize = 80;
 
 #[derive(Default)]
 struct RateLimitWarningState {
@@ -352,8 +372,9 @@ impl ChatWidget {
         if let Some(messages) = initial_messages {
             self.replay_initial_messages(messages);
         }
-        // Ask codex-core to enumerate custom prompts for this session.
+        // Ask codex-core to enumerate custom and MCP prompts for this session.
         self.submit_op(Op::ListCustomPrompts);
+        self.submit_op(Op::ListMcpPrompts);
         if let Some(user_message) = self.initial_user_message.take() {
             self.submit_user_message(user_message);
         }
@@ -630,6 +651,36 @@ impl ChatWidget {
         );
     }
 
+    fn on_elicitation_request(&mut self, ev: ElicitationRequestEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(
+            |q| q.push_elicitation(ev),
+            |s| s.handle_elicitation_request_now(ev2),
+        );
+    }
+
+    fn on_elicitation_cancelled(&mut self, ev: ElicitationCancelledEvent) {
+        if !self.interrupts.remove_elicitation(&ev.server_name, &ev.id) {
+            self.bottom_pane
+                .dismiss_elicitation(&ev.server_name, &ev.id);
+        }
+        self.add_info_message(
+            format!(
+                "MCP server `{}` withdrew its request for input",
+                ev.server_name
+            ),
+            None,
+        );
+    }
+
+    fn on_sampling_request(&mut self, ev: SamplingRequestEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(
+            |q| q.push_sampling(ev),
+            |s| s.handle_sampling_request_now(ev2),
+        );
+    }
+
     fn on_exec_command_begin(&mut self, ev: ExecCommandBeginEvent) {
         self.flush_answer_stream_with_separator();
         let ev2 = ev.clone();
@@ -702,7 +753,13 @@ impl ChatWidget {
             offset,
             log_id,
             entry,
+            replaced_by,
         } = event;
+        if let Some(current) = replaced_by {
+            self.bottom_pane
+                .on_history_log_replaced(current.log_id, current.entry_count);
+            return;
+        }
         self.bottom_pane
             .on_history_entry_response(log_id, offset, entry.map(|e| e.text));
     }
@@ -920,6 +977,26 @@ impl ChatWidget {
         });
     }
 
+    pub(crate) fn handle_elicitation_request_now(&mut self, ev: ElicitationRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        self.notify(Notification::ElicitationRequested {
+            server_name: ev.server_name.clone(),
+        });
+        let view = ElicitationView::new(ev, self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
+    pub(crate) fn handle_sampling_request_now(&mut self, ev: SamplingRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        self.notify(Notification::SamplingRequested {
+            server_name: ev.server_name.clone(),
+        });
+        self.bottom_pane
+            .push_approval_request(ApprovalRequest::Sampling(ev));
+        self.request_redraw();
+    }
+
     pub(crate) fn handle_exec_begin_now(&mut self, ev: ExecCommandBeginEvent) {
         // Ensure the status indicator is visible while the command runs.
         self.running_commands.insert(
@@ -1194,6 +1271,13 @@ impl ChatWidget {
                     InputResult::Command(cmd) => {
                         self.dispatch_command(cmd);
                     }
+                    InputResult::McpPrompt(invocation) => {
+                        self.submit_op(Op::GetMcpPrompt {
+                            server: invocation.server,
+                            name: invocation.name,
+                            arguments: invocation.arguments,
+                        });
+                    }
                     InputResult::None => {}
                 }
             }
@@ -1302,6 +1386,9 @@ impl ChatWidget {
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
+            SlashCommand::Memory => {
+                self.app_event_tx.send(AppEvent::OpenMemoryPicker);
+            }
             SlashCommand::Rollout => {
                 if let Some(path) = self.rollout_path() {
                     self.add_info_message(
@@ -1341,6 +1428,7 @@ impl ChatWidget {
                                 FileChange::Update {
                                     unified_diff: "+test\n-test2".to_string(),
                                     move_path: None,
+                                    new_mode: None,
                                 },
                             ),
                         ]),
@@ -1539,6 +1627,9 @@ impl ChatWidget {
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
             }
+            EventMsg::ElicitationRequest(ev) => self.on_elicitation_request(ev),
+            EventMsg::ElicitationCancelled(ev) => self.on_elicitation_cancelled(ev),
+            EventMsg::SamplingRequest(ev) => self.on_sampling_request(ev),
             EventMsg::ExecCommandBegin(ev) => self.on_exec_command_begin(ev),
             EventMsg::ExecCommandOutputDelta(delta) => self.on_exec_command_output_delta(delta),
             EventMsg::PatchApplyBegin(ev) => self.on_patch_apply_begin(ev),
@@ -1552,6 +1643,9 @@ impl ChatWidget {
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::McpListPromptsResponse(ev) => self.on_list_mcp_prompts(ev),
+            EventMsg::GetMcpPromptResponse(ev) => self.on_get_mcp_prompt_response(ev),
+            EventMsg::McpListChanged(ev) => self.on_mcp_list_changed(ev),
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
@@ -2274,7 +2368,7 @@ impl ChatWidget {
         let mut header_children: Vec<Box<dyn Renderable>> = Vec::new();
         let mode_label = match self.config.sandbox_policy {
             SandboxPolicy::WorkspaceWrite { .. } => "Auto mode",
-            SandboxPolicy::ReadOnly => "Read-Only mode",
+            SandboxPolicy::ReadOnly { .. } => "Read-Only mode",
             _ => "Auto mode",
         };
         let title_line = Line::from("Unprotected directories found").bold();
@@ -2543,6 +2637,67 @@ impl ChatWidget {
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
 
+    fn on_list_mcp_prompts(&mut self, ev: McpListPromptsResponseEvent) {
+        let mut prompts_by_server = ev.prompts;
+        // `/prompts:` is reserved for custom prompts.
+        if prompts_by_server
+            .remove(PROMPTS_CMD_PREFIX)
+            .is_some_and(|prompts| !prompts.is_empty())
+        {
+            self.add_error_message(format!(
+                "Prompts from MCP server `{PROMPTS_CMD_PREFIX}` are unavailable: /{PROMPTS_CMD_PREFIX}: is reserved for custom prompts. Rename the server to use them."
+            ));
+        }
+        let prompts: Vec<McpPromptCommand> = prompts_by_server
+            .into_iter()
+            .flat_map(|(server, prompts)| {
+                prompts.into_iter().map(move |prompt| McpPromptCommand {
+                    server: server.clone(),
+                    prompt,
+                })
+            })
+            .collect();
+        debug!("received {} MCP prompts", prompts.len());
+        self.bottom_pane.set_mcp_prompts(prompts);
+    }
+
+    fn on_mcp_list_changed(&mut self, ev: McpListChangedEvent) {
+        debug!(
+            "MCP server '{}' changed its {:?} list",
+            ev.server_name, ev.kind
+        );
+        // Tools are refreshed by codex-core; only the prompt popup is cached here.
+        if ev.kind == McpListChangedKind::Prompts {
+            self.submit_op(Op::ListMcpPrompts);
+        }
+    }
+
+    fn on_get_mcp_prompt_response(&mut self, ev: GetMcpPromptResponseEvent) {
+        let GetMcpPromptResponseEvent {
+            server,
+            name,
+            result,
+        } = ev;
+        match result {
+            Ok(result) => match mcp_prompt_text(&result) {
+                Ok(text) if text.is_empty() => {
+                    self.add_error_message(format!("MCP prompt /{server}:{name} has no text"));
+                }
+                Ok(text) => self.queue_user_message(text.into()),
+                Err(err) => {
+                    self.add_error_message(format!(
+                        "Cannot use MCP prompt /{server}:{name}: {err}"
+                    ));
+                }
+            },
+            Err(err) => {
+                self.add_error_message(format!(
+                    "Failed to load MCP prompt /{server}:{name}: {err}"
+                ));
+            }
+        }
+    }
+
     pub(crate) fn open_review_popup(&mut self) {
         let mut items: Vec<SelectionItem> = Vec::new();
 
@@ -2561,16 +2716,11 @@ impl ChatWidget {
 
         items.push(SelectionItem {
             name: "Review uncommitted changes".to_string(),
-            actions: vec![Box::new(
-                move |tx: &AppEventSender| {
-                    tx.send(AppEvent::CodexOp(Op::Review {
-                        review_request: ReviewRequest {
-                            prompt: "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.".to_string(),
-                            user_facing_hint: "current changes".to_string(),
-                        },
-                    }));
-                },
-            )],
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest::for_target(ReviewTarget::UncommittedChanges),
+                }));
+            })],
             dismiss_on_select: true,
             ..Default::default()
         });
@@ -2618,12 +2768,9 @@ impl ChatWidget {
                 name: format!("{current_branch} -> {branch}"),
                 actions: vec![Box::new(move |tx3: &AppEventSender| {
                     tx3.send(AppEvent::CodexOp(Op::Review {
-                        review_request: ReviewRequest {
-                            prompt: format!(
-                                "Review the code changes against the base branch '{branch}'. Start by finding the merge diff between the current branch and {branch}'s upstream e.g. (`git merge-base HEAD \"$(git rev-parse --abbrev-ref \"{branch}@{{upstream}}\")\"`), then run `git diff` against that SHA to see what changes we would merge into the {branch} branch. Provide prioritized, actionable findings."
-                            ),
-                            user_facing_hint: format!("changes against '{branch}'"),
-                        },
+                        review_request: ReviewRequest::for_target(ReviewTarget::BaseBranch {
+                            branch: branch.clone(),
+                        }),
                     }));
                 })],
                 dismiss_on_select: true,
@@ -2642,6 +2789,91 @@ impl ChatWidget {
         });
     }
 
+    pub(crate) async fn show_memory_picker(&mut self) {
+        let store = MemoryStore::for_project(&self.config.codex_home, &self.config.cwd);
+        let entries = match store.list().await {
+            Ok(entries) => entries,
+            Err(err) => {
+                self.add_error_message(format!("Failed to read memories: {err}"));
+                return;
+            }
+        };
+        if entries.is_empty() {
+            let hint = (!self.config.features.enabled(Feature::Memory))
+                .then(|| "Enable `features.memory` to let Codex save memories.".to_string());
+            self.add_info_message("No memories saved for this project yet.".to_string(), hint);
+            return;
+        }
+
+        let items: Vec<SelectionItem> = entries
+            .into_iter()
+            .rev()
+            .map(|entry| SelectionItem {
+                name: format_memory_entry(&entry, Some(MEMORY_PICKER_MAX_CHARS)),
+                description: Some(format!("Saved {}", entry.created_at.date_naive())),
+                search_value: Some(entry.content.clone()),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::ConfirmDeleteMemory(entry.clone()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Memories".to_string()),
+            subtitle: Some("Select a memory to delete it.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search memories".to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn show_memory_delete_confirmation(&mut self, entry: MemoryEntry) {
+        let id = entry.id.clone();
+        let items = vec![
+            SelectionItem {
+                name: "Yes, delete it".to_string(),
+                description: Some("Later sessions will no longer see this memory".to_string()),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::DeleteMemory(id.clone()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Cancel".to_string(),
+                description: Some("Go back to the list of memories".to_string()),
+                actions: vec![Box::new(|tx: &AppEventSender| {
+                    tx.send(AppEvent::OpenMemoryPicker);
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Delete this memory?".to_string()),
+            subtitle: Some(format_memory_entry(&entry, Some(MEMORY_PICKER_MAX_CHARS))),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) async fn delete_memory(&mut self, id: String) {
+        let store = MemoryStore::for_project(&self.config.codex_home, &self.config.cwd);
+        match store.delete(std::slice::from_ref(&id)).await {
+            Ok(removed) if !removed.is_empty() => {
+                self.add_info_message(format!("Deleted memory {id}."), None);
+            }
+            Ok(_) => self.add_error_message(format!("No memory found with id {id}.")),
+            Err(err) => self.add_error_message(format!("Failed to delete memory {id}: {err}")),
+        }
+    }
+
     pub(crate) async fn show_review_commit_picker(&mut self, cwd: &Path) {
         let commits = codex_core::git_info::recent_commits(cwd, 100).await;
 
@@ -2649,21 +2881,16 @@ impl ChatWidget {
         for entry in commits {
             let subject = entry.subject.clone();
             let sha = entry.sha.clone();
-            let short = sha.chars().take(7).collect::<String>();
             let search_val = format!("{subject} {sha}");
 
             items.push(SelectionItem {
                 name: subject.clone(),
                 actions: vec![Box::new(move |tx3: &AppEventSender| {
-                    let hint = format!("commit {short}");
-                    let prompt = format!(
-                        "Review the code changes introduced by commit {sha} (\"{subject}\"). Provide prioritized, actionable findings."
-                    );
                     tx3.send(AppEvent::CodexOp(Op::Review {
-                        review_request: ReviewRequest {
-                            prompt,
-                            user_facing_hint: hint,
-                        },
+                        review_request: ReviewRequest::for_target(ReviewTarget::Commit {
+                            sha: sha.clone(),
+                            title: Some(subject.clone()),
+                        }),
                     }));
                 })],
                 dismiss_on_select: true,
@@ -2697,6 +2924,7 @@ impl ChatWidget {
                     review_request: ReviewRequest {
                         prompt: trimmed.clone(),
                         user_facing_hint: trimmed,
+                        target: None,
                     },
                 }));
             }),
@@ -2763,6 +2991,8 @@ enum Notification {
     AgentTurnComplete { response: String },
     ExecApprovalRequested { command: String },
     EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
+    ElicitationRequested { server_name: String },
+    SamplingRequested { server_name: String },
 }
 
 impl Notification {
@@ -2786,6 +3016,12 @@ impl Notification {
                     }
                 )
             }
+            Notification::ElicitationRequested { server_name } => {
+                format!("MCP server {server_name} is asking for input")
+            }
+            Notification::SamplingRequested { server_name } => {
+                format!("MCP server {server_name} wants to sample the model")
+            }
         }
     }
 
@@ -2793,7 +3029,9 @@ impl Notification {
         match self {
             Notification::AgentTurnComplete { .. } => "agent-turn-complete",
             Notification::ExecApprovalRequested { .. }
-            | Notification::EditApprovalRequested { .. } => "approval-requested",
+            | Notification::EditApprovalRequested { .. }
+            | Notification::ElicitationRequested { .. }
+            | Notification::SamplingRequested { .. } => "approval-requested",
         }
     }
 
@@ -2862,6 +3100,35 @@ fn extract_first_bold(s: &str) -> Option<String> {
     None
 }
 
+// Flatten the text of an MCP prompt's messages into a single user message.
+// Non-text content (images, audio, resource links) is skipped. Prompts that
+// script assistant turns are rejected: they cannot be sent as the user's words.
+fn mcp_prompt_text(result: &GetPromptResult) -> Result<String, String> {
+    if result
+        .messages
+        .iter()
+        .any(|message| message.role == Role::Assistant)
+    {
+        return Err("it includes assistant messages, which Codex cannot submit".to_string());
+    }
+    Ok(result
+        .messages
+        .iter()
+        .filter_map(|message| match &message.content {
+            ContentBlock::TextContent(text) => Some(text.text.as_str()),
+            ContentBlock::EmbeddedResource(embedded) => match &embedded.resource {
+                EmbeddedResourceResource::TextResourceContents(contents) => {
+                    Some(contents.text.as_str())
+                }
+                EmbeddedResourceResource::BlobResourceContents(_) => None,
+            },
+            _ => None,
+        })
+        .filter(|text| !text.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
+
 #[cfg(test)]
 pub(crate) fn show_review_commit_picker_with_entries(
     chat: &mut ChatWidget,
@@ -2871,21 +3138,16 @@ pub(crate) fn show_review_commit_picker_with_entries(
     for entry in entries {
         let subject = entry.subject.clone();
         let sha = entry.sha.clone();
-        let short = sha.chars().take(7).collect::<String>();
         let search_val = format!("{subject} {sha}");
 
         items.push(SelectionItem {
             name: subject.clone(),
             actions: vec![Box::new(move |tx3: &AppEventSender| {
-                let hint = format!("commit {short}");
-                let prompt = format!(
-                    "Review the code changes introduced by commit {sha} (\"{subject}\"). Provide prioritized, actionable findings."
-                );
                 tx3.send(AppEvent::CodexOp(Op::Review {
-                    review_request: ReviewRequest {
-                        prompt,
-                        user_facing_hint: hint,
-                    },
+                    review_request: ReviewRequest::for_target(ReviewTarget::Commit {
+                        sha: sha.clone(),
+                        title: Some(subject.clone()),
+                    }),
                 }));
             })],
             dismiss_on_select: true,
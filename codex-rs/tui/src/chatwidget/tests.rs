@@ -177,6 +177,7 @@ fn entered_review_mode_uses_request_hint() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             prompt: "Review the latest changes".to_string(),
             user_facing_hint: "feature branch".to_string(),
+            target: None,
         }),
     });
 
@@ -196,6 +197,7 @@ fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             prompt: "Review the current changes".to_string(),
             user_facing_hint: "current changes".to_string(),
+            target: None,
         }),
     });
 
@@ -662,6 +664,8 @@ fn end_exec(chat: &mut ChatWidget, call_id: &str, stdout: &str, stderr: &str, ex
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         }),
     });
 }
@@ -2796,6 +2800,8 @@ fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            termination_reason: None,
+            blocked_network_destinations: Vec::new(),
         }),
     });
     chat.handle_codex_event(Event {
@@ -2951,3 +2957,90 @@ fn chatwidget_tall() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+fn prompt_message(role: Role, text: &str) -> mcp_types::PromptMessage {
+    mcp_types::PromptMessage {
+        content: ContentBlock::TextContent(mcp_types::TextContent {
+            annotations: None,
+            text: text.to_string(),
+            r#type: "text".to_string(),
+        }),
+        role,
+    }
+}
+
+#[test]
+fn mcp_prompt_with_assistant_messages_is_rejected() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual();
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::GetMcpPromptResponse(GetMcpPromptResponseEvent {
+            server: "docs".to_string(),
+            name: "few_shot".to_string(),
+            result: Ok(GetPromptResult {
+                description: None,
+                messages: vec![
+                    prompt_message(Role::User, "What is 2 + 2?"),
+                    prompt_message(Role::Assistant, "4"),
+                ],
+            }),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    let rendered = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("Cannot use MCP prompt /docs:few_shot"),
+        "expected a rejection, got {rendered:?}"
+    );
+    assert!(
+        !std::iter::from_fn(|| op_rx.try_recv().ok()).any(|op| matches!(op, Op::UserInput { .. })),
+        "the prompt must not be submitted"
+    );
+}
+
+#[test]
+fn mcp_prompt_user_messages_are_joined() {
+    let result = GetPromptResult {
+        description: None,
+        messages: vec![
+            prompt_message(Role::User, "first"),
+            prompt_message(Role::User, "  "),
+            prompt_message(Role::User, "second"),
+        ],
+    };
+
+    assert_eq!(mcp_prompt_text(&result), Ok("first\n\nsecond".to_string()));
+}
+
+#[test]
+fn mcp_server_named_prompts_is_reserved() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+    let prompt = mcp_types::Prompt {
+        arguments: None,
+        description: None,
+        name: "summarize".to_string(),
+        title: None,
+    };
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::McpListPromptsResponse(McpListPromptsResponseEvent {
+            prompts: HashMap::from([(PROMPTS_CMD_PREFIX.to_string(), vec![prompt])]),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    let rendered = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("reserved for custom prompts"),
+        "expected a warning about the reserved name, got {rendered:?}"
+    );
+}
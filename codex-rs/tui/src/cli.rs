@@ -1,7 +1,11 @@
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
 use clap::Parser;
 use clap::ValueHint;
 use codex_common::ApprovalModeCliArg;
 use codex_common::CliConfigOverrides;
+use codex_core::ConversationSearch;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -28,6 +32,11 @@ pub struct Cli {
     #[clap(skip)]
     pub resume_session_id: Option<String>,
 
+    /// Internal: filters for the sessions considered by `codex resume`. Set by
+    /// the top-level `codex resume --find ...` wrapper.
+    #[clap(skip)]
+    pub resume_search: ConversationSearch,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -80,3 +89,58 @@ pub struct Cli {
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
+
+/// Filters for the sessions listed by `codex resume`.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct ResumeSearchArgs {
+    /// Only list sessions whose messages, commands or touched files contain
+    /// every word in QUERY.
+    #[arg(long = "find", value_name = "QUERY")]
+    pub query: Option<String>,
+
+    /// Only list sessions started in DIR or one of its subdirectories.
+    #[arg(long = "find-cwd", value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub cwd: Option<PathBuf>,
+
+    /// Only list sessions started on the Git branch BRANCH.
+    #[arg(long = "find-branch", value_name = "BRANCH")]
+    pub git_branch: Option<String>,
+
+    /// Only list sessions that used MODEL.
+    #[arg(long = "find-model", value_name = "MODEL")]
+    pub model: Option<String>,
+
+    /// Only list sessions created on or after DATE (YYYY-MM-DD or RFC 3339).
+    #[arg(long = "since", value_name = "DATE", value_parser = parse_search_date)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only list sessions created before DATE (YYYY-MM-DD or RFC 3339).
+    #[arg(long = "until", value_name = "DATE", value_parser = parse_search_date)]
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl ResumeSearchArgs {
+    pub fn into_search(self) -> ConversationSearch {
+        ConversationSearch {
+            query: self.query.filter(|query| !query.trim().is_empty()),
+            cwd: self.cwd.map(|cwd| dunce::canonicalize(&cwd).unwrap_or(cwd)),
+            git_branch: self.git_branch,
+            model: self.model,
+            created_after: self.since,
+            created_before: self.until,
+        }
+    }
+}
+
+/// Parses a date given as `YYYY-MM-DD` (midnight UTC) or as an RFC 3339
+/// timestamp.
+fn parse_search_date(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        && let Some(midnight) = date.and_hms_opt(0, 0, 0)
+    {
+        return Ok(midnight.and_utc());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|ts| ts.with_timezone(&Utc))
+        .map_err(|_| format!("invalid date `{value}`; expected YYYY-MM-DD or RFC 3339"))
+}
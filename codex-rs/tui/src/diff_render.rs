@@ -103,6 +103,7 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
             FileChange::Add { content } => (content.lines().count(), 0),
             FileChange::Delete { content } => (0, content.lines().count()),
             FileChange::Update { unified_diff, .. } => calculate_add_remove_from_diff(unified_diff),
+            FileChange::Binary { .. } => (0, 0),
         };
         let move_path = match change {
             FileChange::Update {
@@ -153,7 +154,7 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     let mut header_spans: Vec<RtSpan<'static>> = vec!["• ".dim()];
     if let [row] = &rows[..] {
         let verb = match &row.change {
-            FileChange::Add { .. } => "Added",
+            FileChange::Add { .. } | FileChange::Binary { old_size: None, .. } => "Added",
             FileChange::Delete { .. } => "Deleted",
             _ => "Edited",
         };
@@ -219,7 +220,21 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                 ));
             }
         }
-        FileChange::Update { unified_diff, .. } => {
+        FileChange::Binary { old_size, new_size } => {
+            let summary = match old_size {
+                Some(old_size) => format!("Binary file ({old_size} → {new_size} bytes)"),
+                None => format!("Binary file ({new_size} bytes)"),
+            };
+            out.push(RtLine::from(summary.dim()));
+        }
+        FileChange::Update {
+            unified_diff,
+            new_mode,
+            ..
+        } => {
+            if let Some(mode) = new_mode {
+                out.push(RtLine::from(format!("mode → {mode}").dim()));
+            }
             if let Ok(patch) = diffy::Patch::from_str(unified_diff) {
                 let mut max_line_number = 0;
                 for h in patch.hunks() {
@@ -419,6 +434,7 @@ fn style_del() -> Style {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_core::protocol::FileMode;
     use insta::assert_snapshot;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
@@ -484,6 +500,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                new_mode: None,
             },
         );
 
@@ -504,6 +521,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: Some(PathBuf::from("new_name.rs")),
+                new_mode: None,
             },
         );
 
@@ -524,6 +542,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch_a,
                 move_path: None,
+                new_mode: None,
             },
         );
 
@@ -590,6 +609,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                new_mode: None,
             },
         );
 
@@ -613,6 +633,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                new_mode: None,
             },
         );
 
@@ -640,6 +661,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                new_mode: None,
             },
         );
 
@@ -647,6 +669,29 @@ mod tests {
         snapshot_lines_text("apply_update_block_line_numbers_three_digits_text", &lines);
     }
 
+    #[test]
+    fn ui_snapshot_apply_binary_and_mode_changes_text() {
+        let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
+        changes.insert(
+            PathBuf::from("logo.png"),
+            FileChange::Binary {
+                old_size: Some(2048),
+                new_size: 4096,
+            },
+        );
+        changes.insert(
+            PathBuf::from("run.sh"),
+            FileChange::Update {
+                unified_diff: String::new(),
+                move_path: None,
+                new_mode: Some(FileMode::Executable),
+            },
+        );
+
+        let lines = diff_summary_for_tests(&changes);
+        snapshot_lines_text("apply_binary_and_mode_changes_text", &lines);
+    }
+
     #[test]
     fn ui_snapshot_apply_update_block_relativizes_path() {
         let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
@@ -663,6 +708,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: Some(abs_new),
+                new_mode: None,
             },
         );
 
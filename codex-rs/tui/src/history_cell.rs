@@ -1526,6 +1526,7 @@ mod tests {
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            sampling: None,
         };
         config.mcp_servers.insert("docs".to_string(), stdio_config);
 
@@ -1545,6 +1546,7 @@ mod tests {
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            sampling: None,
         };
         config.mcp_servers.insert("http".to_string(), http_config);
 
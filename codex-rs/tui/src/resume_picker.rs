@@ -2,10 +2,12 @@ use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::DateTime;
 use chrono::Utc;
 use codex_core::ConversationItem;
+use codex_core::ConversationSearch;
 use codex_core::ConversationsPage;
 use codex_core::Cursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
@@ -24,6 +26,7 @@ use ratatui::text::Span;
 use tokio::sync::mpsc;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_util::sync::CancellationToken;
 use unicode_width::UnicodeWidthStr;
 
 use crate::key_hint;
@@ -35,6 +38,8 @@ use codex_protocol::models::ResponseItem;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+/// How long typing must pause before a full-text query scans the transcripts.
+const FULL_TEXT_DEBOUNCE: Duration = Duration::from_millis(300);
 
 #[derive(Debug, Clone)]
 pub enum ResumeSelection {
@@ -50,6 +55,12 @@ struct PageLoadRequest {
     request_token: usize,
     search_token: Option<usize>,
     default_provider: String,
+    search: ConversationSearch,
+    /// Wait this long before loading, so that a newer request can cancel
+    /// this one first.
+    delay: Duration,
+    /// Cancelled when the listing restarts, abandoning the scan.
+    cancel: CancellationToken,
 }
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
@@ -65,10 +76,14 @@ enum BackgroundEvent {
 /// Interactive session picker that lists recorded rollout files with simple
 /// search and pagination. Shows the first user input as the preview, relative
 /// time (e.g., "5 seconds ago"), and the absolute path.
+///
+/// Only sessions matching `search` are listed. When it has a query, the
+/// picker starts in full-text mode with that query typed in.
 pub async fn run_resume_picker(
     tui: &mut Tui,
     codex_home: &Path,
     default_provider: &str,
+    search: &ConversationSearch,
 ) -> Result<ResumeSelection> {
     let alt = AltScreenGuard::enter(tui);
     let (bg_tx, bg_rx) = mpsc::unbounded_channel();
@@ -80,15 +95,23 @@ pub async fn run_resume_picker(
         let tx = loader_tx.clone();
         tokio::spawn(async move {
             let provider_filter = vec![request.default_provider.clone()];
-            let page = RolloutRecorder::list_conversations(
-                &request.codex_home,
-                PAGE_SIZE,
-                request.cursor.as_ref(),
-                INTERACTIVE_SESSION_SOURCES,
-                Some(provider_filter.as_slice()),
-                request.default_provider.as_str(),
-            )
-            .await;
+            let load = async {
+                tokio::time::sleep(request.delay).await;
+                RolloutRecorder::search_conversations(
+                    &request.codex_home,
+                    PAGE_SIZE,
+                    request.cursor.as_ref(),
+                    INTERACTIVE_SESSION_SOURCES,
+                    Some(provider_filter.as_slice()),
+                    request.default_provider.as_str(),
+                    &request.search,
+                )
+                .await
+            };
+            let page = tokio::select! {
+                page = load => page,
+                () = request.cancel.cancelled() => return,
+            };
             let _ = tx.send(BackgroundEvent::PageLoaded {
                 request_token: request.request_token,
                 search_token: request.search_token,
@@ -103,6 +126,7 @@ pub async fn run_resume_picker(
         page_loader,
         default_provider.clone(),
     );
+    state.set_search(search.clone());
     state.load_initial_page().await?;
     state.request_frame();
 
@@ -177,6 +201,13 @@ struct PickerState {
     page_loader: PageLoader,
     view_rows: Option<usize>,
     default_provider: String,
+    /// Filters applied to every page request, apart from the query.
+    search: ConversationSearch,
+    /// When set, `query` is matched against the full session transcripts by
+    /// the backend instead of against the loaded previews.
+    full_text: bool,
+    /// Shared by the page requests of the current listing.
+    load_cancel: CancellationToken,
 }
 
 struct PaginationState {
@@ -264,7 +295,66 @@ impl PickerState {
             page_loader,
             view_rows: None,
             default_provider,
+            search: ConversationSearch::default(),
+            full_text: false,
+            load_cancel: CancellationToken::new(),
+        }
+    }
+
+    /// Sets the filters for every page request. A query in `search` switches
+    /// the picker to full-text mode with that query typed in.
+    fn set_search(&mut self, mut search: ConversationSearch) {
+        if let Some(query) = search.query.take() {
+            self.query = query;
+            self.full_text = true;
+        }
+        self.search = search;
+    }
+
+    /// The filters sent to the backend for the current state.
+    fn backend_search(&self) -> ConversationSearch {
+        let mut search = self.search.clone();
+        if self.full_text && !self.query.trim().is_empty() {
+            search.query = Some(self.query.clone());
         }
+        search
+    }
+
+    fn toggle_full_text(&mut self) {
+        self.full_text = !self.full_text;
+        self.restart_listing(Duration::ZERO);
+    }
+
+    /// Drops the loaded rows, cancels any page still loading and requests the
+    /// first page again with the current filters after `delay`.
+    fn restart_listing(&mut self, delay: Duration) {
+        self.load_cancel.cancel();
+        self.load_cancel = CancellationToken::new();
+        self.all_rows.clear();
+        self.filtered_rows.clear();
+        self.seen_paths.clear();
+        self.selected = 0;
+        self.scroll_top = 0;
+        self.search_state = SearchState::Idle;
+        self.reset_pagination();
+
+        let request_token = self.allocate_request_token();
+        self.pagination.loading = LoadingState::Pending(PendingLoad {
+            request_token,
+            search_token: None,
+        });
+        self.request_frame();
+
+        (self.page_loader)(PageLoadRequest {
+            codex_home: self.codex_home.clone(),
+            cursor: None,
+            request_token,
+            search_token: None,
+            default_provider: self.default_provider.clone(),
+            search: self.backend_search(),
+            delay,
+            cancel: self.load_cancel.clone(),
+        });
     }
 
     fn request_frame(&self) {
@@ -286,6 +376,7 @@ impl PickerState {
                     return Ok(Some(ResumeSelection::Resume(row.path.clone())));
                 }
             }
+            KeyCode::Tab => self.toggle_full_text(),
             KeyCode::Up => {
                 if self.selected > 0 {
                     self.selected -= 1;
@@ -343,13 +434,14 @@ impl PickerState {
 
     async fn load_initial_page(&mut self) -> Result<()> {
         let provider_filter = vec![self.default_provider.clone()];
-        let page = RolloutRecorder::list_conversations(
+        let page = RolloutRecorder::search_conversations(
             &self.codex_home,
             PAGE_SIZE,
             None,
             INTERACTIVE_SESSION_SOURCES,
             Some(provider_filter.as_slice()),
             self.default_provider.as_str(),
+            &self.backend_search(),
         )
         .await?;
         self.reset_pagination();
@@ -418,7 +510,8 @@ impl PickerState {
     }
 
     fn apply_filter(&mut self) {
-        if self.query.is_empty() {
+        // In full-text mode the backend has already filtered the rows.
+        if self.query.is_empty() || self.full_text {
             self.filtered_rows = self.all_rows.clone();
         } else {
             let q = self.query.to_lowercase();
@@ -444,6 +537,12 @@ impl PickerState {
             return;
         }
         self.query = new_query;
+        if self.full_text {
+            // Every keystroke would otherwise start a scan of every
+            // transcript; wait for typing to pause instead.
+            self.restart_listing(FULL_TEXT_DEBOUNCE);
+            return;
+        }
         self.selected = 0;
         self.apply_filter();
         if self.query.is_empty() {
@@ -574,6 +673,9 @@ impl PickerState {
             request_token,
             search_token,
             default_provider: self.default_provider.clone(),
+            search: self.backend_search(),
+            delay: Duration::ZERO,
+            cancel: self.load_cancel.clone(),
         });
     }
 
@@ -663,10 +765,11 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
         );
 
         // Search line
-        let q = if state.query.is_empty() {
-            "Type to search".dim().to_string()
-        } else {
-            format!("Search: {}", state.query)
+        let q = match (state.query.is_empty(), state.full_text) {
+            (true, false) => "Type to search".dim().to_string(),
+            (true, true) => "Type to search full text".dim().to_string(),
+            (false, false) => format!("Search: {}", state.query),
+            (false, true) => format!("Search (full text): {}", state.query),
         };
         frame.render_widget_ref(Line::from(q), search);
 
@@ -684,6 +787,13 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
             key_hint::plain(KeyCode::Esc).into(),
             " to start new ".dim(),
             "    ".dim(),
+            key_hint::plain(KeyCode::Tab).into(),
+            if state.full_text {
+                " to search previews ".dim()
+            } else {
+                " to search full text ".dim()
+            },
+            "    ".dim(),
             key_hint::ctrl(KeyCode::Char('c')).into(),
             " to quit ".dim(),
             "    ".dim(),
@@ -784,7 +894,8 @@ fn render_list(
 fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     if !state.query.is_empty() {
         if state.search_state.is_active()
-            || (state.pagination.loading.is_pending() && state.pagination.next_cursor.is_some())
+            || (state.pagination.loading.is_pending()
+                && (state.full_text || state.pagination.next_cursor.is_some()))
         {
             return vec!["Searching…".italic().dim()].into();
         }
@@ -806,6 +917,10 @@ fn render_empty_state_line(state: &PickerState) -> Line<'static> {
         return vec!["Loading older sessions…".italic().dim()].into();
     }
 
+    if state.search != ConversationSearch::default() {
+        return vec!["No sessions match the filters".italic().dim()].into();
+    }
+
     vec!["No sessions yet".italic().dim()].into()
 }
 
@@ -1432,4 +1547,126 @@ mod tests {
         assert!(!state.search_state.is_active());
         assert!(state.pagination.reached_scan_cap);
     }
+
+    #[test]
+    fn full_text_query_reloads_from_backend() {
+        let recorded_requests: Arc<Mutex<Vec<PageLoadRequest>>> = Arc::new(Mutex::new(Vec::new()));
+        let request_sink = recorded_requests.clone();
+        let loader: PageLoader = Arc::new(move |req: PageLoadRequest| {
+            request_sink.lock().unwrap().push(req);
+        });
+
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+        );
+        state.set_search(ConversationSearch {
+            query: Some("migration".to_string()),
+            git_branch: Some("main".to_string()),
+            ..Default::default()
+        });
+        assert!(state.full_text);
+        assert_eq!(state.query, "migration");
+        assert_eq!(
+            state.backend_search(),
+            ConversationSearch {
+                query: Some("migration".to_string()),
+                git_branch: Some("main".to_string()),
+                ..Default::default()
+            }
+        );
+
+        state.reset_pagination();
+        state.ingest_page(page(
+            vec![make_item(
+                "/tmp/old.jsonl",
+                "2025-01-01T00:00:00Z",
+                "unrelated preview",
+            )],
+            None,
+            1,
+            false,
+        ));
+        // The backend already matched the transcript, so the row is kept even
+        // though its preview does not contain the query.
+        assert_eq!(state.filtered_rows.len(), 1);
+
+        state.set_query("schema".to_string());
+        let request = {
+            let guard = recorded_requests.lock().unwrap();
+            assert_eq!(guard.len(), 1);
+            guard[0].clone()
+        };
+        assert!(request.cursor.is_none());
+        assert_eq!(request.search.query.as_deref(), Some("schema"));
+        assert_eq!(request.search.git_branch.as_deref(), Some("main"));
+        assert!(state.all_rows.is_empty());
+
+        state
+            .handle_background_event(BackgroundEvent::PageLoaded {
+                request_token: request.request_token,
+                search_token: request.search_token,
+                page: Ok(page(
+                    vec![make_item(
+                        "/tmp/match.jsonl",
+                        "2025-01-02T00:00:00Z",
+                        "fix the db",
+                    )],
+                    None,
+                    4,
+                    false,
+                )),
+            })
+            .unwrap();
+        assert_eq!(state.filtered_rows.len(), 1);
+        assert_eq!(
+            state.filtered_rows[0].path,
+            PathBuf::from("/tmp/match.jsonl")
+        );
+
+        // Leaving full-text mode lists every session matching the base filters.
+        recorded_requests.lock().unwrap().clear();
+        state.toggle_full_text();
+        let request = recorded_requests.lock().unwrap()[0].clone();
+        assert!(!state.full_text);
+        assert_eq!(request.search.query, None);
+        assert_eq!(request.search.git_branch.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn full_text_typing_waits_and_cancels_the_previous_scan() {
+        let recorded_requests: Arc<Mutex<Vec<PageLoadRequest>>> = Arc::new(Mutex::new(Vec::new()));
+        let request_sink = recorded_requests.clone();
+        let loader: PageLoader = Arc::new(move |req: PageLoadRequest| {
+            request_sink.lock().unwrap().push(req);
+        });
+
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+        );
+        state.set_search(ConversationSearch {
+            query: Some("mig".to_string()),
+            ..Default::default()
+        });
+
+        state.set_query("migr".to_string());
+        state.set_query("migra".to_string());
+        let requests = recorded_requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].cancel.is_cancelled());
+        assert!(!requests[1].cancel.is_cancelled());
+        assert_eq!(requests[1].delay, FULL_TEXT_DEBOUNCE);
+        assert_eq!(requests[1].search.query.as_deref(), Some("migra"));
+
+        // Switching modes reloads right away.
+        state.toggle_full_text();
+        let requests = recorded_requests.lock().unwrap().clone();
+        assert!(requests[1].cancel.is_cancelled());
+        assert_eq!(requests[2].delay, Duration::ZERO);
+    }
 }
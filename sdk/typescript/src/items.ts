@@ -17,6 +17,8 @@ export type CommandExecutionItem = {
   exit_code?: number;
   /** Current status of the command execution. */
   status: CommandExecutionStatus;
+  /** Destinations the sandbox's network allowlist rejected while the command ran. */
+  blocked_network_destinations?: string[];
 };
 
 /** Indicates the type of the file change. */